@@ -1,9 +1,8 @@
-use crate::types::wallet::WalletData;
-use crate::utils::constants;
+use crate::api::ApiProvider;
+use crate::config::ConfigManager;
 use anyhow::Result;
 use clap::Parser;
 use colored::Colorize;
-use std::fs;
 
 #[derive(Parser, Debug)]
 pub struct SetApiKeyCommand {
@@ -13,18 +12,16 @@ pub struct SetApiKeyCommand {
 }
 
 impl SetApiKeyCommand {
+    /// Saves the key in `config.json` for the default network
     pub async fn execute(&self) -> Result<()> {
-        let wallet_file = constants::wallet_file_path();
-        let mut wallet_data = if wallet_file.exists() {
-            let data = fs::read_to_string(&wallet_file)?;
-            serde_json::from_str::<WalletData>(&data)?
-        } else {
-            WalletData::new()
-        };
-
-        wallet_data.api_key = Some(self.api_key.clone());
-        fs::write(&wallet_file, serde_json::to_string_pretty(&wallet_data)?)?;
-        println!("{}: API key set successfully", "Success".green().bold());
+        let message = ConfigManager::new()?.update(|config| {
+            Ok(config.set_api_key(
+                ApiProvider::Alchemy,
+                self.api_key.clone(),
+                Some("Alchemy".to_string()),
+            ))
+        })?;
+        println!("{}: {}", "Success".green().bold(), message);
         Ok(())
     }
 }
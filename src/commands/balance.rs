@@ -1,8 +1,15 @@
-use crate::config::ConfigManager;
+use crate::commands::tokens::{self, TokenRegistry};
+use crate::config::{ConfigManager, ReadBlockTag};
+use crate::types::network::Network;
 use crate::types::wallet::WalletData;
+use crate::utils::address::is_contract;
 use crate::utils::constants;
+use crate::utils::eth::EthClient;
 use crate::utils::helper::Helper;
+use crate::utils::multicall;
+use crate::utils::pending::pending_outflow;
 use crate::utils::table::TableBuilder;
+use crate::utils::units::format_amount;
 use anyhow::{Result, anyhow};
 use clap::Parser;
 use alloy::primitives::Address;
@@ -15,9 +22,22 @@ pub struct BalanceCommand {
     #[arg(long)]
     pub address: Option<String>,
 
-    /// Optional Token to get Balance for
-    #[arg(long)]
+    /// Optional token to get the balance for: a symbol from the token registry
+    /// (e.g. RBTC, RIF, DoC) or a contract address
+    #[arg(long, conflicts_with = "all")]
     pub token: Option<String>,
+
+    /// Show RBTC and every registry token for the network in one table
+    #[arg(long, visible_alias = "all-tokens")]
+    pub all: bool,
+
+    /// With --all, also list tokens whose balance is zero
+    #[arg(long, requires = "all")]
+    pub show_zero: bool,
+
+    /// Read at this block (latest, safe or finalized) instead of the configured tag
+    #[arg(long)]
+    pub block_tag: Option<ReadBlockTag>,
 }
 
 impl BalanceCommand {
@@ -27,6 +47,10 @@ impl BalanceCommand {
         let network = config.default_network.to_string().to_lowercase();
 
         let (_config, eth_client) = Helper::init_eth_client(&network).await?;
+        let eth_client = match self.block_tag {
+            Some(tag) => eth_client.with_read_tag(tag),
+            None => eth_client,
+        };
 
         // Get address - use default wallet if none provided
         let address = if let Some(addr) = &self.address {
@@ -48,11 +72,23 @@ impl BalanceCommand {
             default_wallet.address
         };
 
-        let (balance, token_name) = if let Some(token) = &self.token {
+        if self.all {
+            return show_portfolio(
+                &eth_client,
+                config.default_network,
+                &network,
+                address,
+                self.show_zero,
+            )
+            .await;
+        }
+
+        let token = self.token.as_deref().map(|token| resolve_token(token, &network)).transpose()?;
+        let (balance, decimals, token_name, token_address) = if let Some(token) = &token {
             // Check if it's the RBTC zero address
             if token == "0x0000000000000000000000000000000000000000" {
                 let balance = eth_client.get_balance(&address, &None).await?;
-                (balance, "RBTC".to_string())
+                (balance, 18, "RBTC".to_string(), None)
             } else {
                 let token_address = Address::from_str(token)
                     .map_err(|_| anyhow!("Invalid token address format: {}", token))?;
@@ -60,36 +96,135 @@ impl BalanceCommand {
                     .get_balance(&address, &Some(token_address))
                     .await?;
 
-                // Try to get token info, but don't fail if we can't
-                let token_name = match eth_client.get_token_info(token_address).await {
-                    Ok((_, symbol)) => symbol,
-                    Err(_) => format!("Token (0x{})", &token[2..10]),
-                };
+                // Without the real decimals the balance would be misread, so don't guess
+                let (decimals, token_name) = tokens::token_info(&eth_client, token_address)
+                    .await
+                    .map_err(|e| anyhow!("Failed to read token decimals: {}", e))?;
 
-                (balance, token_name)
+                (balance, decimals, token_name, Some(token_address))
             }
         } else {
             // Native RBTC balance
             let balance = eth_client.get_balance(&address, &None).await?;
-            (balance, "RBTC".to_string())
+            (balance, 18, "RBTC".to_string(), None)
+        };
+
+        // Subtract what recently sent, not yet mined transactions will spend
+        let pending = match eth_client.pending_transactions(address).await {
+            Ok(pending) => pending,
+            Err(e) => {
+                eprintln!("⚠️  Could not check pending transactions: {}", e);
+                Vec::new()
+            }
         };
+        let available = balance.saturating_sub(pending_outflow(&pending, token_address));
 
-        // Format the balance with appropriate decimals
-        // All tokens including RBTC use 18 decimals
-        let decimals = 18;
-        let balance_str = alloy::primitives::utils::format_units(balance, decimals)
-            .map_err(|e| anyhow!("Failed to format balance: {}", e))?;
+        // Format the balance with the token's own decimals (18 for RBTC)
+        let balance_str = format_amount(balance, decimals);
 
         let mut table = TableBuilder::new();
-        table.add_header(&["Address", "Network", "Token", "Balance"]);
+        table.add_header(&["Address", "Network", "Token", "Confirmed", "Available"]);
         table.add_row(&[
             &Helper::format_address(&address),
             &config.default_network.to_string(),
             &token_name,
             &balance_str,
+            &format_amount(available, decimals),
         ]);
 
         table.print();
+        match is_contract(eth_client.provider(), address).await {
+            Ok(true) => println!("Account type: contract"),
+            Ok(false) => println!("Account type: externally owned account"),
+            Err(e) => eprintln!("⚠️  Could not check the account type: {}", e),
+        }
+        if !pending.is_empty() {
+            println!(
+                "{} pending transaction(s) not yet mined; Available excludes their amounts and maximum fees",
+                pending.len()
+            );
+            for tx in &pending {
+                println!("  nonce {}: {:?}", tx.nonce, tx.hash);
+            }
+        }
         Ok(())
     }
 }
+
+/// Prints RBTC and every registry token for `network`, read in a single batch.
+/// Tokens holding nothing are left out unless `show_zero`; tokens whose balance
+/// can't be read are skipped and named below the table.
+async fn show_portfolio(
+    eth_client: &EthClient,
+    network: Network,
+    network_name: &str,
+    address: Address,
+    show_zero: bool,
+) -> Result<()> {
+    let registry = TokenRegistry::load().unwrap_or_default();
+    let tokens: Vec<(String, Address, u8)> = registry
+        .list_tokens(Some(network_name))
+        .into_iter()
+        .filter_map(|(symbol, info)| {
+            Address::from_str(&info.address)
+                .ok()
+                .map(|token| (symbol, token, info.decimals))
+        })
+        .collect();
+    let token_addresses: Vec<Address> = tokens.iter().map(|(_, token, _)| *token).collect();
+
+    let (rbtc, balances) = tokio::join!(
+        eth_client.get_balance(&address, &None),
+        multicall::token_balances(eth_client.provider(), network, address, &token_addresses)
+    );
+    let rbtc = rbtc?;
+
+    let mut table = TableBuilder::new();
+    table.add_header(&["Token", "Balance"]);
+    table.add_row(&["RBTC", &format_amount(rbtc, 18)]);
+    let mut hidden = 0;
+    let mut unreadable = Vec::new();
+    for ((symbol, _, decimals), balance) in tokens.iter().zip(balances) {
+        match balance {
+            Some(balance) if balance.is_zero() && !show_zero => hidden += 1,
+            Some(balance) => {
+                table.add_row(&[symbol, &format_amount(balance, *decimals)]);
+            }
+            None => unreadable.push(symbol.as_str()),
+        }
+    }
+
+    println!("Address: {}", Helper::format_address(&address));
+    println!("Network: {}", network);
+    table.print();
+    if hidden > 0 {
+        println!("{} token(s) with a zero balance hidden; pass --show-zero to list them", hidden);
+    }
+    if !unreadable.is_empty() {
+        println!("⚠️  Skipped, balance could not be read: {}", unreadable.join(", "));
+    }
+    Ok(())
+}
+
+/// Turns a token symbol into its registry address on `network`; addresses pass through
+fn resolve_token(token: &str, network: &str) -> Result<String> {
+    let token = token.trim();
+    if token.starts_with("0x") {
+        return Ok(token.to_string());
+    }
+    if token.eq_ignore_ascii_case("RBTC") {
+        return Ok("0x0000000000000000000000000000000000000000".to_string());
+    }
+    let registry =
+        TokenRegistry::load().map_err(|e| anyhow!("Failed to load token registry: {}", e))?;
+    registry
+        .find_by_symbol(network, token)
+        .map(|info| info.address.clone())
+        .ok_or_else(|| {
+            anyhow!(
+                "Unknown token '{}' on {}; add it with 'token-add' or pass its address",
+                token,
+                network
+            )
+        })
+}
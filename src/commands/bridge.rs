@@ -0,0 +1,204 @@
+use crate::config::ConfigManager;
+use crate::utils::bridge::{
+    BridgeClient, FederationInfo, PegoutStage, contains_hash, decode_redeem_script,
+    format_satoshis, format_script_hex, pegout_rejection_reason,
+};
+use crate::utils::helper::Helper;
+use crate::utils::table::TableBuilder;
+use alloy::primitives::B256;
+use alloy::providers::Provider;
+use anyhow::{Result, anyhow};
+use clap::Parser;
+use colored::Colorize;
+use std::str::FromStr;
+
+#[derive(Parser, Debug)]
+pub struct BridgeCommand {
+    #[command(subcommand)]
+    pub action: BridgeAction,
+}
+
+#[derive(Parser, Debug)]
+pub enum BridgeAction {
+    /// Show the active and retiring powpeg federations and the active redeem script
+    Federation,
+    /// Show where a peg-out (RBTC to BTC) request is in the release pipeline
+    PegoutStatus {
+        /// Hash of the transaction that sent RBTC to the Bridge
+        #[arg(long)]
+        tx_hash: String,
+    },
+}
+
+impl BridgeCommand {
+    pub async fn execute(&self) -> Result<()> {
+        match &self.action {
+            BridgeAction::Federation => self.show_federation().await,
+            BridgeAction::PegoutStatus { tx_hash } => self.show_pegout_status(tx_hash).await,
+        }
+    }
+
+    async fn show_pegout_status(&self, tx_hash: &str) -> Result<()> {
+        let tx_hash =
+            B256::from_str(tx_hash.trim()).map_err(|_| anyhow!("Invalid transaction hash"))?;
+        let config = ConfigManager::new()?.load()?;
+        let network = config.default_network.to_string().to_lowercase();
+        let (_config, eth_client) = Helper::init_eth_client(&network).await?;
+
+        let bridge = BridgeClient::new(&eth_client)?;
+        let stage = bridge.get_pegout_stage(tx_hash).await?;
+        let queue = bridge.get_pegout_queue().await?;
+        let current_block = eth_client.provider().get_block_number().await?;
+
+        println!("\n{}", "Peg-out Status".bold());
+        match &stage {
+            PegoutStage::Rejected {
+                amount_sats,
+                reason,
+            } => {
+                println!(
+                    "{}: Rejected by the Bridge, {} ({} refunded)",
+                    "Stage".red().bold(),
+                    pegout_rejection_reason(*reason),
+                    format_satoshis(*amount_sats as i64)
+                );
+                return Ok(());
+            }
+            PegoutStage::Queued {
+                amount_sats,
+                btc_destination,
+            } => {
+                println!(
+                    "{}: 1/3 Queued, waiting for the next peg-out batch",
+                    "Stage".yellow().bold()
+                );
+                println!("Amount: {}", format_satoshis(*amount_sats as i64));
+                println!("BTC destination: {}", btc_destination);
+                if queue.next_creation_block >= 0 {
+                    let remaining = (queue.next_creation_block as u64).saturating_sub(current_block);
+                    println!(
+                        "Next batch at RSK block {} (~{} blocks from now)",
+                        queue.next_creation_block, remaining
+                    );
+                }
+            }
+            PegoutStage::AwaitingSignatures {
+                btc_tx_hash,
+                batch_rsk_tx,
+            } => {
+                println!(
+                    "{}: 2/3 Batched, waiting for federation signatures",
+                    "Stage".yellow().bold()
+                );
+                println!("Batch RSK tx: {}", batch_rsk_tx);
+                println!("BTC tx: {}", btc_tx_hash);
+                if contains_hash(&queue.release_client_state, *batch_rsk_tx) {
+                    println!("The federation release client is tracking this batch");
+                }
+            }
+            PegoutStage::Released {
+                btc_tx_hash,
+                batch_rsk_tx,
+            } => {
+                println!(
+                    "{}: 3/3 Released to the Bitcoin network",
+                    "Stage".green().bold()
+                );
+                println!("Batch RSK tx: {}", batch_rsk_tx);
+                println!("BTC tx: {}", btc_tx_hash);
+            }
+        }
+
+        let mut table = TableBuilder::new();
+        table.add_header(&["Peg-out Queue", ""]);
+        table.add_row(&["Queued Requests", &queue.queued_count.to_string()]);
+        table.add_row(&[
+            "Estimated Fees (next batch)",
+            &format_satoshis(queue.estimated_fees_sats),
+        ]);
+        table.add_row(&["Next Batch Block", &queue.next_creation_block.to_string()]);
+        table.add_row(&["Current Block", &current_block.to_string()]);
+        table.print();
+
+        Ok(())
+    }
+
+    async fn show_federation(&self) -> Result<()> {
+        let config = ConfigManager::new()?.load()?;
+        let network = config.default_network.to_string().to_lowercase();
+        let (_config, eth_client) = Helper::init_eth_client(&network).await?;
+
+        let bridge = BridgeClient::new(&eth_client)?;
+        let status = bridge.get_federation_status().await?;
+
+        let mut table = TableBuilder::new();
+        table.add_header(&["", "Active", "Retiring"]);
+        let retiring = status.retiring.as_ref();
+        let cell = |f: fn(&FederationInfo) -> String| retiring.map(f).unwrap_or("-".to_string());
+        table.add_row(&[
+            "Address",
+            &status.active.address,
+            &cell(|f| f.address.clone()),
+        ]);
+        table.add_row(&[
+            "Signers",
+            &format!("{} of {}", status.active.threshold, status.active.size),
+            &cell(|f| format!("{} of {}", f.threshold, f.size)),
+        ]);
+        table.add_row(&[
+            "Created At Block",
+            &status.active.creation_block.to_string(),
+            &cell(|f| f.creation_block.to_string()),
+        ]);
+        table.add_row(&[
+            "Created At",
+            &format_creation_time(status.active.creation_time),
+            &cell(|f| format_creation_time(f.creation_time)),
+        ]);
+        table.print();
+
+        if status.migration_in_progress() {
+            println!(
+                "{}: Powpeg migration in progress, funds are moving from the retiring federation",
+                "Info".yellow().bold()
+            );
+        } else {
+            println!(
+                "{}: No powpeg migration in progress",
+                "Info".green().bold()
+            );
+        }
+
+        if let Some(size) = status.pending_size {
+            println!(
+                "{}: A pending federation of {} members is awaiting commitment",
+                "Info".yellow().bold(),
+                size
+            );
+        }
+
+        println!("\n{}", "Active Powpeg Redeem Script".bold());
+        for line in format_script_hex(&status.redeem_script, 64) {
+            println!("  {}", line);
+        }
+        match decode_redeem_script(&status.redeem_script) {
+            Some(info) => println!(
+                "  Multisig: {} of {} ({} public keys, {} bytes)",
+                info.threshold,
+                info.total,
+                info.public_keys,
+                status.redeem_script.len()
+            ),
+            None => println!("  {}", "Could not decode multisig parameters".dimmed()),
+        }
+
+        Ok(())
+    }
+}
+
+/// The Bridge reports creation time in milliseconds since the epoch
+fn format_creation_time(millis: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(millis)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
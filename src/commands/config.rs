@@ -27,8 +27,27 @@ pub enum ConfigSubcommand {
     /// Run the setup wizard
     Setup,
     
+    /// Delete all wallet data and configuration
+    Reset {
+        /// Only list what would be removed
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Skip the backup made before deleting
+        #[arg(long)]
+        no_backup: bool,
+    },
+
     /// Run diagnostics
-    Doctor,
+    Doctor {
+        /// List every file the wallet reads or writes instead
+        #[arg(long)]
+        files: bool,
+    },
+
+    /// Back up, then rewrite the data files through their current format,
+    /// dropping unknown fields and moving legacy API keys into the config
+    Compact,
 }
 
 impl ConfigCommand {
@@ -42,10 +61,19 @@ impl ConfigCommand {
                 crate::config::run_setup_wizard()?;
                 Ok(())
             }
-            ConfigSubcommand::Doctor => {
-                crate::config::run_doctor()?;
+            ConfigSubcommand::Reset { dry_run, no_backup } => {
+                reset(&config_manager, *dry_run, *no_backup)?;
                 Ok(())
             }
+            ConfigSubcommand::Doctor { files } => {
+                if *files {
+                    crate::config::run_file_audit()?;
+                } else {
+                    crate::config::run_doctor()?;
+                }
+                Ok(())
+            }
+            ConfigSubcommand::Compact => crate::config::run_compact(),
         }
     }
 
@@ -73,7 +101,42 @@ impl ConfigCommand {
                 .map(|_| "********".to_string())
                 .unwrap_or_else(|| style("Not set").dim().to_string())
         );
+        println!(
+            "  Alchemy rate limit: {} requests/s",
+            config.alchemy_requests_per_second
+        );
         
+        println!("\n{}", style("🧾 Transactions").bold());
+        println!("  Transaction type: {}", config.tx_type);
+        println!("  Safe confirmation depth: {}", config.safe_confirmations);
+        println!("  Read block tag: {}", config.read_block_tag);
+        println!(
+            "  Node request attempts: {} ({} ms backoff)",
+            config.rpc_attempts, config.rpc_retry_delay_ms
+        );
+        for path in &config.extra_token_files {
+            println!("  Extra token file: {}", path.display());
+        }
+
+        for (network, sponsor) in &config.sponsor {
+            println!(
+                "  Gas sponsor ({}): {} via forwarder {}",
+                network, sponsor.relayer_url, sponsor.forwarder
+            );
+        }
+
+        if let Some(view) = &config.history_view {
+            println!("\n{}", style("📜 History").bold());
+            println!(
+                "  Default view: {}, sorted by {} ({}), {} per page{}",
+                view.network,
+                view.sort_by,
+                view.sort_order,
+                view.limit,
+                if view.detailed { ", detailed" } else { "" }
+            );
+        }
+
         if let Some(wallet) = &config.default_wallet {
             println!("\n{}", style("💼 Wallet").bold());
             println!("  Default wallet: {}", wallet);
@@ -86,11 +149,88 @@ impl ConfigCommand {
     }
 
     async fn set_config(&self, config_manager: &ConfigManager, key: &str, value: &str) -> Result<()> {
-        let mut config = config_manager.load()?;
-        
+        set_value(config_manager, key, value)
+    }
+}
+
+/// Keys accepted by `config set`, with a short description, in display order
+pub const SETTINGS: &[(&str, &str)] = &[
+    ("default-network", "Network used by default"),
+    ("tx-type", "Transaction type: auto, legacy or eip1559"),
+    ("confirmations", "Confirmations before a transaction counts as settled"),
+    ("read-block-tag", "Block balances and nonces are read at: latest, safe or finalized"),
+    ("rpc-attempts", "Attempts for node requests that fail in transit"),
+    ("rpc-retry-delay", "Milliseconds before the first retry; doubled each time"),
+    ("display-decimals", "Maximum decimals shown for amounts (0-18)"),
+    ("group-thousands", "Group thousands with separators: true or false"),
+    ("default-token", "Token pre-selected on the default network (empty for RBTC)"),
+    ("alchemy-rps", "Alchemy requests per second"),
+    ("extra-token-files", "Comma-separated token files merged into the registry"),
+    ("health-block-time", "Expected seconds between blocks on the default network"),
+    ("health-samples", "Blocks the health check averages over"),
+    ("health-retries", "Retries for each failed health check request"),
+    ("health-endpoints", "Comma-separated RPC endpoints to compare against"),
+    ("relayer-url", "Gas sponsor relayer on the default network"),
+    ("forwarder-address", "Gas sponsor forwarder contract on the default network"),
+    ("low-balance-threshold", "Warn below this RBTC balance on the default network (empty to disable)"),
+    ("large-transfer-threshold", "Mainnet sends above this RBTC amount need typing MAINNET (empty to disable)"),
+    ("address-reuse-warning", "Warn when a wallet address receives repeatedly: true or false"),
+    ("default-wallet", "Default wallet name"),
+];
+
+/// Current value of a `config set` key, as it would be typed
+pub fn current_value(config: &Config, key: &str) -> String {
+    let network = config.default_network;
+    let health = config.health_check_for(network);
+    let sponsor = config
+        .sponsor
+        .get(&network.to_string().to_lowercase())
+        .cloned()
+        .unwrap_or_default();
+    match key {
+        "default-network" => network.to_string(),
+        "tx-type" => config.tx_type.to_string(),
+        "confirmations" => config.safe_confirmations.to_string(),
+        "read-block-tag" => config.read_block_tag.to_string(),
+        "rpc-attempts" => config.rpc_attempts.to_string(),
+        "rpc-retry-delay" => config.rpc_retry_delay_ms.to_string(),
+        "display-decimals" => config.display_decimals.to_string(),
+        "group-thousands" => config.group_thousands.to_string(),
+        "default-token" => config
+            .default_token(&network.to_string())
+            .unwrap_or_default()
+            .to_string(),
+        "alchemy-rps" => config.alchemy_requests_per_second.to_string(),
+        "extra-token-files" => config
+            .extra_token_files
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(","),
+        "health-block-time" => health.expected_block_secs.to_string(),
+        "health-samples" => health.samples.to_string(),
+        "health-retries" => health.retries.to_string(),
+        "health-endpoints" => health.endpoints.join(","),
+        "relayer-url" => sponsor.relayer_url,
+        "forwarder-address" => sponsor.forwarder,
+        "low-balance-threshold" => config
+            .low_balance_threshold
+            .get(&network.to_string().to_lowercase())
+            .cloned()
+            .unwrap_or_default(),
+        "large-transfer-threshold" => config.large_transfer_threshold.clone(),
+        "address-reuse-warning" => config.address_reuse_warning.to_string(),
+        "default-wallet" => config.default_wallet.clone().unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+/// Validates and saves one configuration value
+pub fn set_value(config_manager: &ConfigManager, key: &str, value: &str) -> Result<()> {
+    config_manager.update(|config| {
         match key.to_lowercase().as_str() {
             "default-network" => {
-                let network = value.parse()?;
+                let network: Network = value.parse()?;
                 config.default_network = network;
                 println!("Set default network to: {}", network);
             }
@@ -106,10 +246,332 @@ impl ConfigCommand {
                 config.default_wallet = Some(value.to_string());
                 println!("Set default wallet to: {}", value);
             }
+            "tx-type" => {
+                config.tx_type = value.parse()?;
+                println!("Set transaction type to: {}", config.tx_type);
+            }
+            "alchemy-rps" => {
+                let rps: u32 = value
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Requests per second must be a positive integer"))?;
+                if rps == 0 {
+                    anyhow::bail!("Requests per second must be a positive integer");
+                }
+                config.alchemy_requests_per_second = rps;
+                println!("Set Alchemy rate limit to {} requests/s", rps);
+            }
+            "extra-token-files" => {
+                config.extra_token_files = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|path| !path.is_empty())
+                    .map(std::path::PathBuf::from)
+                    .collect();
+                println!("Merging {} extra token file(s)", config.extra_token_files.len());
+            }
+            "health-block-time" | "health-samples" | "health-retries"
+            | "health-endpoints" => {
+                let network = config.default_network;
+                let mut settings = config.health_check_for(network);
+                let number = || {
+                    value
+                        .parse::<u64>()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| anyhow::anyhow!("{} must be a positive integer", key))
+                };
+                match key.to_lowercase().as_str() {
+                    "health-block-time" => settings.expected_block_secs = number()?,
+                    "health-samples" => settings.samples = number()?,
+                    "health-retries" => {
+                        settings.retries = value
+                            .parse()
+                            .map_err(|_| anyhow::anyhow!("Retries must be a whole number"))?
+                    }
+                    _ => {
+                        settings.endpoints = value
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|url| !url.is_empty())
+                            .map(str::to_string)
+                            .collect()
+                    }
+                }
+                config
+                    .health_check
+                    .insert(network.to_string().to_lowercase(), settings);
+                println!("Updated the health check for {}", network);
+            }
+            "relayer-url" | "forwarder-address" => {
+                let network = config.default_network;
+                let settings = config
+                    .sponsor
+                    .entry(network.to_string().to_lowercase())
+                    .or_default();
+                if key.eq_ignore_ascii_case("relayer-url") {
+                    value
+                        .parse::<reqwest::Url>()
+                        .map_err(|e| anyhow::anyhow!("Invalid relayer URL: {}", e))?;
+                    settings.relayer_url = value.to_string();
+                } else {
+                    value
+                        .parse::<alloy::primitives::Address>()
+                        .map_err(|_| anyhow::anyhow!("Invalid forwarder address: {}", value))?;
+                    settings.forwarder = value.to_string();
+                }
+                println!("Updated the gas sponsor for {}", network);
+            }
+            "low-balance-threshold" => {
+                let network = config.default_network.to_string().to_lowercase();
+                let amount = value.trim();
+                if amount.is_empty() {
+                    config.low_balance_threshold.remove(&network);
+                    println!("Low-balance warning disabled for {}", network);
+                } else {
+                    crate::utils::units::to_wei(amount, crate::utils::units::Denomination::Rbtc)?;
+                    config
+                        .low_balance_threshold
+                        .insert(network.clone(), amount.to_string());
+                    println!("Warning when the balance on {} drops below {} RBTC", network, amount);
+                }
+            }
+            "address-reuse-warning" => {
+                config.address_reuse_warning = value
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Use true or false"))?;
+                println!("Address reuse warning: {}", config.address_reuse_warning);
+            }
+            "large-transfer-threshold" => {
+                let amount = value.trim();
+                if amount.is_empty() {
+                    println!("Large mainnet transfers no longer need an acknowledgement");
+                } else {
+                    crate::utils::units::to_wei(amount, crate::utils::units::Denomination::Rbtc)?;
+                    println!(
+                        "Mainnet transfers above {} RBTC must be acknowledged by typing MAINNET",
+                        amount
+                    );
+                }
+                config.large_transfer_threshold = amount.to_string();
+            }
+            "rpc-attempts" => {
+                let attempts: u32 = value
+                    .parse()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| anyhow::anyhow!("Attempts must be a positive integer"))?;
+                config.rpc_attempts = attempts;
+                println!("Node requests are tried up to {} times", attempts);
+            }
+            "rpc-retry-delay" => {
+                config.rpc_retry_delay_ms = value
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Delay must be a whole number of milliseconds"))?;
+                println!(
+                    "Retries start after {} ms and double each time",
+                    config.rpc_retry_delay_ms
+                );
+            }
+            "read-block-tag" => {
+                config.read_block_tag = value.parse()?;
+                println!(
+                    "Balances and nonces are read at the '{}' block",
+                    config.read_block_tag
+                );
+            }
+            "display-decimals" => {
+                let decimals: u8 = value
+                    .parse()
+                    .ok()
+                    .filter(|d| *d <= 18)
+                    .ok_or_else(|| anyhow::anyhow!("Decimals must be between 0 and 18"))?;
+                config.display_decimals = decimals;
+                crate::utils::units::forget_display_settings();
+                println!("Amounts show at most {} decimals", decimals);
+            }
+            "group-thousands" => {
+                config.group_thousands = value
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Use true or false"))?;
+                crate::utils::units::forget_display_settings();
+                println!("Thousands separators: {}", config.group_thousands);
+            }
+            "default-token" => {
+                let network = config.default_network.to_string().to_lowercase();
+                let symbol = value.trim().to_uppercase();
+                if symbol.is_empty() || symbol == "RBTC" {
+                    config.default_tokens.remove(&network);
+                    println!("Default token cleared for {}", network);
+                } else {
+                    let registry = crate::commands::tokens::TokenRegistry::load()
+                        .map_err(|e| anyhow::anyhow!("Failed to load token registry: {}", e))?;
+                    if registry.find_by_symbol(&network, &symbol).is_none() {
+                        anyhow::bail!("Token {} is not in the {} registry", symbol, network);
+                    }
+                    println!("Default token for {} set to {}", network, symbol);
+                    config.default_tokens.insert(network, symbol);
+                }
+            }
+            "confirmations" => {
+                let depth: u64 = value
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Confirmations must be a positive integer"))?;
+                if depth == 0 {
+                    anyhow::bail!("Confirmations must be a positive integer");
+                }
+                config.safe_confirmations = depth;
+                println!("Transactions count as settled after {} confirmations", depth);
+            }
             _ => anyhow::bail!("Unknown configuration key: {}", key),
         }
-        
-        config_manager.save(&config)?;
         Ok(())
+    })
+}
+
+/// Lists everything a reset removes and, unless `dry_run`, deletes it after
+/// confirmation, backing it up first unless `no_backup`. Returns whether anything was deleted.
+pub fn reset(config_manager: &ConfigManager, dry_run: bool, no_backup: bool) -> Result<bool> {
+    let targets = config_manager.reset_targets()?;
+    if targets.is_empty() {
+        println!("Nothing to remove");
+        return Ok(false);
+    }
+
+    println!("\n{}", style("The reset removes:").bold());
+    for path in &targets {
+        println!("  {}", path.display());
+    }
+    if dry_run {
+        println!("\nDry run: nothing was deleted");
+        return Ok(false);
     }
-}
\ No newline at end of file
+
+    let warning = if no_backup {
+        "⚠️  WARNING: This will delete ALL wallet data and cannot be undone! Continue?"
+    } else {
+        "⚠️  This will delete ALL wallet data (a backup is made first). Continue?"
+    };
+    if !dialoguer::Confirm::new()
+        .with_prompt(warning)
+        .default(false)
+        .interact()?
+    {
+        println!("\nOperation cancelled. No data was deleted.");
+        return Ok(false);
+    }
+
+    let backup = if no_backup {
+        None
+    } else {
+        Some(config_manager.backup_all()?)
+    };
+    config_manager.clear_cache()?;
+
+    println!("\n✅ Removed {} item(s); all wallet data has been cleared.", targets.len());
+    if let Some(backup) = backup {
+        println!("Backup: {}", backup.display());
+    }
+    println!("Restart the wallet to set it up again.");
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// A `config set` key, values it takes paired with how they read back, and values
+    /// it rejects
+    type Case = (&'static str, &'static [(&'static str, &'static str)], &'static [&'static str]);
+
+    /// One case for every key in `SETTINGS`
+    const CASES: &[Case] = &[
+        ("default-network", &[("testnet", "Testnet"), ("MAINNET", "Mainnet")], &["moon"]),
+        ("tx-type", &[("legacy", "legacy"), ("EIP-1559", "eip1559")], &["fast"]),
+        ("confirmations", &[("12", "12")], &["0"]),
+        ("read-block-tag", &[("finalized", "finalized")], &["pending"]),
+        ("rpc-attempts", &[("5", "5")], &["0"]),
+        ("rpc-retry-delay", &[("250", "250"), ("0", "0")], &["soon"]),
+        ("display-decimals", &[("4", "4"), ("0", "0")], &["19"]),
+        ("group-thousands", &[("false", "false")], &["maybe"]),
+        // Naming a token reads the user's registry, so only clearing it is covered
+        ("default-token", &[("", ""), ("rbtc", "")], &[]),
+        ("alchemy-rps", &[("25", "25")], &["0", "fast"]),
+        (
+            "extra-token-files",
+            &[(" team.json, ,shared.json", "team.json,shared.json"), ("", "")],
+            &[],
+        ),
+        ("health-block-time", &[("30", "30")], &["0"]),
+        ("health-samples", &[("5", "5")], &["0"]),
+        ("health-retries", &[("0", "0")], &["-1"]),
+        (
+            "health-endpoints",
+            &[("https://a.example, https://b.example", "https://a.example,https://b.example")],
+            &[],
+        ),
+        (
+            "relayer-url",
+            &[("https://relay.example/api", "https://relay.example/api")],
+            &["not a url"],
+        ),
+        (
+            "forwarder-address",
+            &[(
+                "0x742d35Cc6634C0532925a3b844Bc454e4438f44e",
+                "0x742d35Cc6634C0532925a3b844Bc454e4438f44e",
+            )],
+            &["0x1234"],
+        ),
+        ("low-balance-threshold", &[("0.01", "0.01"), ("", "")], &["lots", "-1"]),
+        ("large-transfer-threshold", &[("2.5", "2.5"), (" ", "")], &["lots", "-1"]),
+        ("address-reuse-warning", &[("false", "false"), ("true", "true")], &["yes"]),
+        ("default-wallet", &[("savings", "savings")], &[]),
+    ];
+
+    /// Sets `key` through `config set` on a scratch config file and reads it back
+    fn set_and_read(key: &str, value: &str) -> Result<String> {
+        let dir = TempDir::new()?;
+        let config_manager = ConfigManager::at(dir.path().join("config.json"));
+        set_value(&config_manager, key, value)?;
+        Ok(current_value(&config_manager.load()?, key))
+    }
+
+    #[test]
+    fn test_every_setting_round_trips() {
+        for (key, _) in SETTINGS {
+            assert!(CASES.iter().any(|(case, ..)| case == key), "{} has no test case", key);
+        }
+        for (key, accepted, rejected) in CASES {
+            for (value, read_back) in accepted.iter() {
+                assert_eq!(set_and_read(key, value).unwrap(), *read_back, "{} {:?}", key, value);
+            }
+            for value in rejected.iter() {
+                assert!(set_and_read(key, value).is_err(), "{} took {:?}", key, value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_listed_key_has_a_value() {
+        let config = Config::default();
+        for (key, _) in SETTINGS {
+            if !matches!(*key, "default-token" | "relayer-url" | "forwarder-address"
+                | "low-balance-threshold" | "extra-token-files" | "health-endpoints"
+                | "default-wallet")
+            {
+                assert!(!current_value(&config, key).is_empty(), "{} has no value", key);
+            }
+        }
+    }
+
+    #[test]
+    fn test_compact_is_reachable_from_the_command_line() {
+        use clap::Parser;
+        let command = crate::commands::Commands::try_parse_from(["rsk", "config", "compact"]).unwrap();
+        assert!(matches!(
+            command,
+            crate::commands::Commands::Config(ConfigCommand { command: ConfigSubcommand::Compact })
+        ));
+    }
+}
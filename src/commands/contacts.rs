@@ -1,10 +1,17 @@
 use anyhow::Result;
 use clap::Parser;
 use colored::Colorize;
-use alloy::primitives::Address;
-use std::str::FromStr;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
 
+use crate::config::ConfigManager;
 use crate::types::contacts::Contact;
+use crate::types::network::Network;
+use crate::types::wallet::WalletData;
+use crate::utils::address::parse_address;
+use crate::utils::constants;
+use crate::utils::contacts_csv::{contacts_from_csv, contacts_to_csv};
+use crate::utils::qr::decode_qr_image;
 use crate::utils::table::TableBuilder;
 
 #[derive(Parser, Debug)]
@@ -72,6 +79,146 @@ pub enum ContactsAction {
         /// File path to load contacts from
         file: Option<String>,
     },
+    /// Export contacts as CSV (name,address,notes,tags; tags separated by ';')
+    ExportCsv {
+        /// CSV file to write
+        file: PathBuf,
+    },
+    /// Import contacts from CSV with name,address,notes,tags columns
+    ImportCsv {
+        /// CSV file to read
+        file: PathBuf,
+    },
+    /// Undo the last add/update/remove/load
+    Undo,
+    /// Import contacts from a scanned address-book QR payload
+    ImportQr {
+        /// The QR payload text (contacts JSON)
+        #[arg(long, conflicts_with = "image", required_unless_present = "image")]
+        payload: Option<String>,
+        /// Image file containing the QR code
+        #[arg(long)]
+        image: Option<PathBuf>,
+    },
+}
+
+/// Contact entry as shared between devices; extra fields from a full export are ignored
+#[derive(Debug, Deserialize)]
+struct SharedContact {
+    name: String,
+    address: String,
+    #[serde(default)]
+    notes: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// Accepted address-book payloads: a bare list or `{ "contacts": [...] }`
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ContactsPayload {
+    List(Vec<SharedContact>),
+    Wrapped { contacts: Vec<SharedContact> },
+}
+
+/// Parses and validates an address-book payload into contacts for `network`
+fn parse_contacts_payload(payload: &str, network: Network) -> Result<Vec<Contact>> {
+    let payload: ContactsPayload = serde_json::from_str(payload.trim()).map_err(|_| {
+        anyhow::anyhow!("Not an address-book payload: expected a JSON list of contacts")
+    })?;
+    let entries = match payload {
+        ContactsPayload::List(entries) | ContactsPayload::Wrapped { contacts: entries } => entries,
+    };
+
+    entries
+        .into_iter()
+        .map(|entry| {
+            if entry.name.trim().is_empty() {
+                anyhow::bail!("Contact with address {} has no name", entry.address);
+            }
+            let address = parse_address(&entry.address, network).map_err(|e| {
+                anyhow::anyhow!("Invalid address for contact '{}': {}", entry.name, e)
+            })?;
+            Ok(Contact::new(entry.name, address, entry.notes, entry.tags))
+        })
+        .collect()
+}
+
+/// Merges `incoming` by address, which every import and sync goes through: a saved
+/// contact takes the name, notes and tags of an incoming copy edited more recently,
+/// keeping its own transaction stats; new addresses are added unless their name is
+/// taken. Returns (added, updated, skipped).
+fn merge_contacts(existing: &mut Vec<Contact>, incoming: Vec<Contact>) -> (usize, usize, usize) {
+    let (mut added, mut updated, mut skipped) = (0, 0, 0);
+    for contact in incoming {
+        match existing.iter().position(|c| c.address == contact.address) {
+            Some(i) if contact.last_edited() > existing[i].last_edited() => {
+                let saved = &mut existing[i];
+                saved.updated_at = Some(contact.last_edited());
+                saved.name = contact.name;
+                saved.notes = contact.notes;
+                saved.tags = contact.tags;
+                updated += 1;
+            }
+            Some(_) => skipped += 1,
+            None if existing.iter().any(|c| c.name.eq_ignore_ascii_case(&contact.name)) => {
+                skipped += 1
+            }
+            None => {
+                existing.push(contact);
+                added += 1;
+            }
+        }
+    }
+    (added, updated, skipped)
+}
+
+/// Collapses contacts sharing an address into the most recently edited one, kept
+/// where the first of them was; returns how many were removed
+fn dedupe_by_address(contacts: &mut Vec<Contact>) -> usize {
+    let before = contacts.len();
+    let mut kept: Vec<Contact> = Vec::with_capacity(before);
+    for contact in contacts.drain(..) {
+        match kept.iter_mut().find(|c| c.address == contact.address) {
+            Some(saved) => {
+                if contact.last_edited() > saved.last_edited() {
+                    *saved = contact;
+                }
+            }
+            None => kept.push(contact),
+        }
+    }
+    *contacts = kept;
+    before - contacts.len()
+}
+
+/// Merges `incoming` into the address book by address, the newest edit winning;
+/// returns how many contacts were added or updated
+pub fn import_contacts(incoming: Vec<Contact>) -> Result<usize> {
+    let store = ContactsCommand {
+        action: ContactsAction::List,
+    };
+    let mut contacts = store.load_contacts()?;
+    let (added, updated, _) = merge_contacts(&mut contacts, incoming);
+    if added + updated > 0 {
+        store.save_contacts(&contacts)?;
+    }
+    Ok(added + updated)
+}
+
+/// Moves contacts that older versions kept in the wallet file into `contacts.json`;
+/// returns how many were added or updated there. The wallet file is only emptied
+/// once the address book was saved.
+pub fn migrate_wallet_contacts() -> Result<usize> {
+    let wallet_file = constants::wallet_file_path();
+    let has_legacy_contacts = std::fs::read_to_string(&wallet_file)
+        .ok()
+        .and_then(|data| serde_json::from_str::<WalletData>(&data).ok())
+        .is_some_and(|data| !data.contacts.is_empty());
+    if !has_legacy_contacts {
+        return Ok(0);
+    }
+    WalletData::update(|data| import_contacts(std::mem::take(&mut data.contacts)))
 }
 
 impl ContactsCommand {
@@ -108,6 +255,13 @@ impl ContactsCommand {
             ContactsAction::Search { query } => self.search_contacts(query).await?,
             ContactsAction::Load { file } => self.load_contacts_from_file(file).await?,
             ContactsAction::Save { file } => self.save_contacts_to_file(file).await?,
+            ContactsAction::ExportCsv { file } => self.export_csv(file)?,
+            ContactsAction::ImportCsv { file } => self.import_csv(file)?,
+            ContactsAction::Undo => self.undo_last_change().await?,
+            ContactsAction::ImportQr { payload, image } => {
+                self.import_from_qr(payload.as_deref(), image.as_deref())
+                    .await?
+            }
         }
         Ok(())
     }
@@ -119,12 +273,20 @@ impl ContactsCommand {
         notes: Option<String>,
         tags: Vec<String>,
     ) -> Result<()> {
-        let address = Address::from_str(address)?;
+        let network = ConfigManager::new()?.load()?.default_network;
+        let address = parse_address(address, network)?;
 
         let contact = Contact::new(name.to_string(), address, notes, tags);
         contact.validate()?;
 
         let mut contacts = self.load_contacts()?;
+        if let Some(existing) = contacts.iter().find(|c| c.address == contact.address) {
+            anyhow::bail!(
+                "Address 0x{:x} is already saved as '{}'",
+                contact.address,
+                existing.name
+            );
+        }
         contacts.push(contact);
         self.save_contacts(&contacts)?;
 
@@ -201,7 +363,8 @@ impl ContactsCommand {
             contact.name = name;
         }
         if let Some(address) = address {
-            contact.address = address.parse()?;
+            let network = ConfigManager::new()?.load()?.default_network;
+            contact.address = parse_address(&address, network)?;
         }
         if let Some(notes) = notes {
             contact.notes = Some(notes);
@@ -209,7 +372,12 @@ impl ContactsCommand {
         if let Some(tags) = tags {
             contact.tags = tags;
         }
+        contact.updated_at = Some(chrono::Local::now());
+        let address = contact.address;
 
+        if contacts.iter().filter(|c| c.address == address).count() > 1 {
+            anyhow::bail!("Address 0x{:x} is already saved as another contact", address);
+        }
         self.save_contacts(&contacts)?;
 
         println!("{}: Contact updated successfully", "Success".green().bold());
@@ -276,11 +444,19 @@ impl ContactsCommand {
         Ok(())
     }
 
+    fn contacts_path() -> std::path::PathBuf {
+        constants::contacts_path()
+    }
+
+    fn backup_path() -> std::path::PathBuf {
+        Self::contacts_path().with_extension("json.bak")
+    }
+
+    /// Reads the address book. This and [`Self::save_contacts`] are the only way
+    /// contacts are read and written; `WalletData.contacts` is legacy and emptied
+    /// by [`migrate_wallet_contacts`] at startup.
     pub fn load_contacts(&self) -> Result<Vec<Contact>> {
-        let contacts_path = dirs::data_local_dir()
-            .ok_or_else(|| anyhow::anyhow!("Failed to get data directory"))?
-            .join("rootstock-wallet")
-            .join("contacts.json");
+        let contacts_path = Self::contacts_path();
 
         if !contacts_path.exists() {
             return Ok(Vec::new());
@@ -291,30 +467,68 @@ impl ContactsCommand {
             .map_err(|e| anyhow::anyhow!("Failed to parse contacts: {}", e))
     }
 
-    // pub fn save_contacts(&self, contacts: &[Contact]) -> Result<()> {
-    //     let contacts_path = dirs::data_local_dir()
-    //         .ok_or_else(|| anyhow::anyhow!("Failed to get data directory"))?
-    //         .join("rootstock-wallet")
-    //         .join("contacts.json");
-
-    //     std::fs::create_dir_all(contacts_path.parent().unwrap())?;
-    //     let content = serde_json::to_string_pretty(contacts)?;
-    //     std::fs::write(contacts_path, content)?;
-    //     Ok(())
-    // }
     pub fn save_contacts(&self, contacts: &[Contact]) -> Result<()> {
-        let contacts_dir = dirs::data_local_dir()
-            .ok_or_else(|| anyhow::anyhow!("Failed to get data directory"))?
-            .join("rootstock-wallet");
+        let contacts_path = Self::contacts_path();
+        if let Some(contacts_dir) = contacts_path.parent() {
+            std::fs::create_dir_all(contacts_dir)?;
+        }
 
-        std::fs::create_dir_all(&contacts_dir)?;
+        // Keep a single-level snapshot of the previous state for `contacts undo`
+        let backup_path = Self::backup_path();
+        if contacts_path.exists() {
+            std::fs::copy(&contacts_path, &backup_path)?;
+        } else {
+            std::fs::write(&backup_path, "[]")?;
+        }
 
-        let contacts_path = contacts_dir.join("contacts.json");
         let content = serde_json::to_string_pretty(contacts)?;
         std::fs::write(contacts_path, content)?;
         Ok(())
     }
 
+    pub async fn undo_last_change(&self) -> Result<()> {
+        let backup_path = Self::backup_path();
+        if !backup_path.exists() {
+            println!("{}: Nothing to undo", "Info".yellow().bold());
+            return Ok(());
+        }
+
+        let previous: Vec<Contact> =
+            serde_json::from_str(&std::fs::read_to_string(&backup_path)?)
+                .map_err(|e| anyhow::anyhow!("Failed to parse contacts backup: {}", e))?;
+        let current = self.load_contacts().unwrap_or_default();
+
+        std::fs::copy(&backup_path, Self::contacts_path())?;
+        std::fs::remove_file(&backup_path)?;
+
+        let same = |a: &Contact, b: &Contact| {
+            a.name == b.name && a.address == b.address && a.notes == b.notes && a.tags == b.tags
+        };
+        let restored: Vec<&str> = previous
+            .iter()
+            .filter(|p| !current.iter().any(|c| same(p, c)))
+            .map(|c| c.name.as_str())
+            .collect();
+        let dropped: Vec<&str> = current
+            .iter()
+            .filter(|c| !previous.iter().any(|p| same(p, c)))
+            .map(|c| c.name.as_str())
+            .collect();
+
+        println!(
+            "{}: Restored previous contacts ({} contacts)",
+            "Success".green().bold(),
+            previous.len()
+        );
+        if !restored.is_empty() {
+            println!("  Restored: {}", restored.join(", "));
+        }
+        if !dropped.is_empty() {
+            println!("  Reverted: {}", dropped.join(", "));
+        }
+        Ok(())
+    }
+
     pub async fn save_contacts_to_file(&self, file: &Option<String>) -> Result<()> {
         let contacts = self.load_contacts()?;
 
@@ -349,16 +563,153 @@ impl ContactsCommand {
         let content = std::fs::read_to_string(&file_path)?;
         let contacts: Vec<Contact> = serde_json::from_str(&content)?;
 
-        // Merge with existing contacts (optional - you might want to replace instead)
+        // Merge by address, keeping whichever copy was edited last
         let mut existing_contacts = self.load_contacts().unwrap_or_default();
-        existing_contacts.extend(contacts);
+        let collapsed = dedupe_by_address(&mut existing_contacts);
+        let (added, updated, skipped) = merge_contacts(&mut existing_contacts, contacts);
         self.save_contacts(&existing_contacts)?;
 
         println!(
-            "{}: Contacts loaded from {}",
+            "{}: Loaded {} contact(s) from {} ({} updated, {} duplicate(s) skipped)",
             "Success".green().bold(),
-            file_path.display()
+            added,
+            file_path.display(),
+            updated,
+            skipped
+        );
+        if collapsed > 0 {
+            println!("Removed {} duplicate address(es) from the saved contacts", collapsed);
+        }
+        Ok(())
+    }
+
+    pub fn export_csv(&self, file: &Path) -> Result<()> {
+        let contacts = self.load_contacts()?;
+        std::fs::write(file, contacts_to_csv(&contacts)?)?;
+        println!(
+            "{}: Exported {} contact(s) to {}",
+            "Success".green().bold(),
+            contacts.len(),
+            file.display()
+        );
+        Ok(())
+    }
+
+    /// Merges the valid rows of a CSV address book into the saved contacts and lists
+    /// the rows that failed validation
+    pub fn import_csv(&self, file: &Path) -> Result<()> {
+        let content = std::fs::read_to_string(file)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", file.display(), e))?;
+        let network = ConfigManager::new()?.load()?.default_network;
+        let (incoming, errors) = contacts_from_csv(&content, network)?;
+        let total = incoming.len() + errors.len();
+
+        let mut contacts = self.load_contacts().unwrap_or_default();
+        let (added, updated, skipped) = merge_contacts(&mut contacts, incoming);
+        if added + updated > 0 {
+            self.save_contacts(&contacts)?;
+        }
+
+        println!(
+            "{}: Imported {} of {} contact(s) ({} updated, {} duplicate(s) skipped, {} invalid)",
+            "Success".green().bold(),
+            added,
+            total,
+            updated,
+            skipped,
+            errors.len()
+        );
+        for error in &errors {
+            println!("  {} {}", "✗".red(), error);
+        }
+        Ok(())
+    }
+
+    pub async fn import_from_qr(
+        &self,
+        payload: Option<&str>,
+        image: Option<&std::path::Path>,
+    ) -> Result<()> {
+        let payload = match (payload, image) {
+            (Some(payload), _) => payload.to_string(),
+            (None, Some(image)) => decode_qr_image(image)?,
+            (None, None) => anyhow::bail!("Provide either a QR payload or an image"),
+        };
+
+        let network = ConfigManager::new()?.load()?.default_network;
+        let incoming = parse_contacts_payload(&payload, network)?;
+        let total = incoming.len();
+
+        let mut contacts = self.load_contacts().unwrap_or_default();
+        let (added, updated, skipped) = merge_contacts(&mut contacts, incoming);
+        if added + updated > 0 {
+            self.save_contacts(&contacts)?;
+        }
+
+        println!(
+            "{}: Imported {} of {} contact(s) ({} updated, {} duplicate(s) skipped)",
+            "Success".green().bold(),
+            added,
+            total,
+            updated,
+            skipped
         );
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{Address, B256};
+    use chrono::Duration;
+
+    fn contact(name: &str, byte: u8, edited_minutes_ago: i64) -> Contact {
+        let mut contact = Contact::new(name.to_string(), Address::repeat_byte(byte), None, Vec::new());
+        contact.updated_at = Some(chrono::Local::now() - Duration::minutes(edited_minutes_ago));
+        contact
+    }
+
+    #[test]
+    fn test_loading_the_same_contacts_twice_adds_nothing() {
+        let file = vec![contact("alice", 1, 10), contact("bob", 2, 10)];
+        let mut saved = Vec::new();
+        assert_eq!(merge_contacts(&mut saved, file.clone()), (2, 0, 0));
+        assert_eq!(merge_contacts(&mut saved, file), (0, 0, 2));
+        assert_eq!(saved.len(), 2);
+    }
+
+    #[test]
+    fn test_newer_copy_of_an_address_wins() {
+        let mut saved = vec![contact("alice", 1, 10)];
+        let (added, updated, skipped) = merge_contacts(
+            &mut saved,
+            vec![contact("alice (treasury)", 1, 1), contact("old alice", 1, 60)],
+        );
+        assert_eq!((added, updated, skipped), (0, 1, 1));
+        assert_eq!(saved.len(), 1);
+        assert_eq!(saved[0].name, "alice (treasury)");
+    }
+
+    #[test]
+    fn test_newer_copy_keeps_local_transaction_stats() {
+        let mut saved = vec![contact("alice", 1, 10)];
+        saved[0].recent_transactions.push(B256::repeat_byte(7));
+        merge_contacts(&mut saved, vec![contact("alice (treasury)", 1, 1)]);
+        assert_eq!(saved[0].name, "alice (treasury)");
+        assert_eq!(saved[0].recent_transactions, vec![B256::repeat_byte(7)]);
+    }
+
+    #[test]
+    fn test_dedupe_keeps_most_recently_edited() {
+        let mut contacts = vec![
+            contact("alice", 1, 30),
+            contact("bob", 2, 30),
+            contact("alice renamed", 1, 5),
+            contact("alice stale", 1, 90),
+        ];
+        assert_eq!(dedupe_by_address(&mut contacts), 2);
+        let names: Vec<&str> = contacts.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["alice renamed", "bob"]);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::utils::hash::{decode_hex_input, digest_matches, keccak256_hex};
+use anyhow::{Result, anyhow};
+use clap::Parser;
+use colored::Colorize;
+use std::fs;
+use std::path::Path;
+
+/// Compute (and optionally verify) the keccak256 digest of text, hex data or a file
+#[derive(Parser, Debug)]
+pub struct HashCommand {
+    /// Text or file path to hash; an existing path is hashed as a file
+    #[arg(long)]
+    pub keccak256: String,
+
+    /// Treat the input as hex bytes (e.g. calldata) instead of text
+    #[arg(long, conflicts_with = "text")]
+    pub hex: bool,
+
+    /// Always hash the input as text, even if a file with that name exists
+    #[arg(long)]
+    pub text: bool,
+
+    /// Expected digest; fails if the computed one differs
+    #[arg(long)]
+    pub verify: Option<String>,
+}
+
+impl HashCommand {
+    pub async fn execute(&self) -> Result<()> {
+        let input = &self.keccak256;
+        let (data, source) = if self.hex {
+            (decode_hex_input(input)?, "hex data")
+        } else if !self.text && Path::new(input).is_file() {
+            let data = fs::read(input).map_err(|e| anyhow!("Failed to read {}: {}", input, e))?;
+            (data, "file")
+        } else {
+            (input.as_bytes().to_vec(), "text")
+        };
+
+        let digest = keccak256_hex(&data);
+        println!("keccak256 of {} ({} bytes): {}", source, data.len(), digest.green());
+        println!("Selector (first 4 bytes): {}", &digest[..10]);
+
+        if let Some(expected) = &self.verify {
+            if digest_matches(&digest, expected) {
+                println!("{}", "✓ Digest matches".green().bold());
+            } else {
+                return Err(anyhow!("Digest mismatch: expected {}", expected.trim()));
+            }
+        }
+        Ok(())
+    }
+}
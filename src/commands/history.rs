@@ -1,13 +1,27 @@
-use crate::types::transaction::{RskTransaction, TransactionStatus};
+use crate::api::{ApiKey, ApiProvider};
+use crate::commands::contacts::{ContactsAction, ContactsCommand};
+use crate::config::ConfigManager;
+use crate::types::contacts::Contact;
+use crate::types::network::Network;
+use crate::types::transaction::{RskTransaction, TransactionStatus, TxType};
+use crate::types::tx_label::TxLabels;
 use crate::types::wallet::WalletData;
 use crate::utils::alchemy::AlchemyClient;
-use crate::utils::{constants, table::TableBuilder};
+use crate::utils::eth::EthClient;
+use crate::utils::helper::Config as HelperConfig;
+use crate::utils::token_metadata::TokenMetadata;
+use crate::utils::{
+    address::parse_address, constants, file_lock, receive_log, table::TableBuilder,
+    units::format_amount,
+};
 use anyhow::Result;
 use chrono::TimeZone;
 use clap::Parser;
 use colored::Colorize;
 use console::style;
-use alloy::primitives::Address;
+use alloy::primitives::{Address, B256};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::str::FromStr;
 
@@ -18,8 +32,8 @@ pub struct HistoryCommand {
     #[arg(short, long)]
     pub address: Option<String>,
 
-    /// Contact name to check transaction history for
-    #[arg(short, long)]
+    /// Contact name to check transaction history for (case-insensitive)
+    #[arg(short, long, conflicts_with = "address")]
     pub contact: Option<String>,
 
     /// Number of transactions to show
@@ -58,6 +72,10 @@ pub struct HistoryCommand {
     #[arg(long)]
     pub export_csv: Option<String>,
 
+    /// Show only one type: native, transfer, approval, call or creation
+    #[arg(long = "type")]
+    pub tx_type: Option<TxType>,
+
     /// Show only incoming transactions
     #[arg(short, long)]
     pub incoming: bool,
@@ -73,14 +91,59 @@ pub struct HistoryCommand {
     /// Network to query (mainnet | testnet). Defaults to mainnet.
     #[arg(long, default_value = "mainnet")]
     pub network: String,
+
+    /// Only show transfers (incoming and outgoing) since the last such check
+    #[arg(long, visible_alias = "new")]
+    pub since_last_check: bool,
+
+    /// Show only the transfers directly between two addresses, with their net flow
+    #[arg(long, num_args = 2, value_names = ["A", "B"])]
+    pub between: Option<Vec<String>>,
+}
+
+/// Highest block already shown by `history --since-last-check`, keyed by "network:address"
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HistoryState {
+    last_seen_block: HashMap<String, u64>,
+}
+
+impl HistoryState {
+    fn load() -> Self {
+        fs::read_to_string(constants::history_state_path())
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    /// Stores the block for one address, keeping entries written by other instances
+    fn record(key: String, block: u64) -> Result<()> {
+        file_lock::update_json(&constants::history_state_path(), |state: &mut HistoryState| {
+            state.last_seen_block.insert(key, block);
+            Ok(())
+        })
+    }
+}
+
+fn block_number(transfer: &serde_json::Value) -> Option<u64> {
+    transfer["blockNum"]
+        .as_str()
+        .and_then(|b| u64::from_str_radix(b.trim_start_matches("0x"), 16).ok())
+}
+
+/// Transfers fetched in both directions, oldest first, without the duplicates a
+/// self-transfer produces, cut to `limit`
+fn merge_transfers(mut transfers: Vec<serde_json::Value>, limit: u32) -> Vec<serde_json::Value> {
+    let mut seen = std::collections::HashSet::new();
+    transfers.retain(|t| seen.insert(t["hash"].to_string()));
+    transfers.sort_by_key(|t| block_number(t).unwrap_or(0));
+    transfers.truncate(limit as usize);
+    transfers
 }
 
 impl HistoryCommand {
     pub async fn execute(&self) -> Result<()> {
         // 1. Load config and resolve API key
-        // let config = Config::load()?;
         let wallet_file = constants::wallet_file_path();
-        let mut stored_api_key: Option<String> = None;
 
         // If export is requested, ensure we have a filename
         if let Some(filename) = &self.export_csv
@@ -89,22 +152,32 @@ impl HistoryCommand {
             return Err(anyhow::anyhow!("Export filename must end with .csv"));
         }
 
-        // Try to load API key from wallet file
-        if wallet_file.exists() {
-            let data = fs::read_to_string(&wallet_file)?;
-            if let Ok(mut val) = serde_json::from_str::<serde_json::Value>(&data) {
-                if let Some(api_key) = val["alchemyApiKey"].as_str() {
-                    stored_api_key = Some(api_key.to_string());
-                }
-
-                // Persist CLI key if supplied and not yet saved
-                if stored_api_key.is_none() && self.api_key.is_some() {
-                    val["alchemyApiKey"] = serde_json::Value::String(self.api_key.clone().unwrap());
-                    fs::write(&wallet_file, serde_json::to_string_pretty(&val)?)?;
-                    stored_api_key = self.api_key.clone();
-                    println!("{}", "Saved Alchemy API key ✅".green());
-                }
-            }
+        let network = self.network.to_lowercase();
+        let is_testnet = network == "testnet";
+        if network != "mainnet" && !is_testnet {
+            anyhow::bail!("Invalid network: use 'mainnet' or 'testnet'");
+        }
+
+        let config_manager = ConfigManager::new()?;
+        let config = config_manager.load()?;
+        let stored_api_key = config
+            .get_api_key_for(&ApiProvider::Alchemy, &network)
+            .map(str::to_string);
+
+        // Persist CLI key if supplied and not yet saved
+        if stored_api_key.is_none()
+            && let Some(key) = &self.api_key
+        {
+            config_manager.update(|config| {
+                config.api.keys.push(ApiKey {
+                    key: key.clone(),
+                    network: network.clone(),
+                    provider: ApiProvider::Alchemy,
+                    name: Some("Alchemy".to_string()),
+                });
+                Ok(())
+            })?;
+            println!("{}", "Saved Alchemy API key ✅".green());
         }
 
         let final_api_key = self
@@ -114,9 +187,24 @@ impl HistoryCommand {
             .or(std::env::var("ALCHEMY_API_KEY").ok())
             .ok_or_else(|| anyhow::anyhow!("Alchemy API key missing – supply --api-key once"))?;
 
-        let is_testnet = self.network.to_lowercase() == "testnet";
-        if self.network.to_lowercase() != "mainnet" && !is_testnet {
-            anyhow::bail!("Invalid network: use 'mainnet' or 'testnet'");
+        if let Some(pair) = &self.between {
+            let network = if is_testnet {
+                Network::Testnet
+            } else {
+                Network::Mainnet
+            };
+            let parse = |input: &String| {
+                parse_address(input.trim(), network)
+                    .map_err(|e| anyhow::anyhow!("Invalid address {}: {}", input, e))
+            };
+            let (a, b) = (parse(&pair[0])?, parse(&pair[1])?);
+            let alchemy_client = AlchemyClient::new(final_api_key, is_testnet);
+            let eth_client = EthClient::new(&HelperConfig {
+                network: network.get_config(),
+                wallet: Default::default(),
+            })
+            .await?;
+            return show_history_between(&alchemy_client, &eth_client, a, b, self.limit).await;
         }
 
         // 2. Get address to query
@@ -124,15 +212,9 @@ impl HistoryCommand {
             Address::from_str(addr).map_err(|_| {
                 anyhow::anyhow!("Invalid address format. Expected 0x-prefixed hex string")
             })?
-        }
-        //  else if let Some(contact_name) = &self.contact {
-        //     // Handle contact name resolution
-        //     let contacts = Contact::load_all()?;
-        //     let contact = contacts.iter().find(|c| &c.name == contact_name)
-        //         .ok_or_else(|| anyhow::anyhow!("Contact '{}' not found", contact_name))?;
-        //     contact.address
-        // }
-        else {
+        } else if let Some(contact_name) = &self.contact {
+            resolve_contact(contact_name)?
+        } else {
             // Get current wallet address
             if !wallet_file.exists() {
                 anyhow::bail!("No wallets found. Create or import a wallet first.");
@@ -149,22 +231,81 @@ impl HistoryCommand {
 
         // 3. Initialize Alchemy client and fetch transfers
         let alchemy_client = AlchemyClient::new(final_api_key, is_testnet);
-        let response = alchemy_client
-            .get_asset_transfers(
-                &format!("{:#x}", address),
-                self.limit,
-                self.from.as_deref(),
-                self.to.as_deref(),
-            )
-            .await?;
+        let address_str = format!("{:#x}", address);
+        let state_key = format!("{}:{}", network, address_str);
+        let state = HistoryState::load();
 
-        // 4. Process transactions
-        let transfers = response["result"]["transfers"]
-            .as_array()
-            .ok_or_else(|| anyhow::anyhow!("Invalid response format from Alchemy"))?;
+        if self.incoming && self.outgoing {
+            anyhow::bail!("Cannot use both --incoming and --outgoing at the same time");
+        }
+
+        // --since-last-check resumes right after the last block shown
+        let (from_block, to_block) = if self.since_last_check {
+            let from_block = state
+                .last_seen_block
+                .get(&state_key)
+                .map(|block| format!("0x{:x}", block + 1));
+            (from_block, None)
+        } else {
+            (self.from.clone(), self.to.clone())
+        };
+
+        // Alchemy filters on one side of a transfer per request, so sent and
+        // received transfers are fetched separately and merged
+        let mut pages = Vec::new();
+        if !self.incoming {
+            pages.push(
+                alchemy_client
+                    .get_asset_transfers(
+                        &address_str,
+                        self.limit,
+                        from_block.as_deref(),
+                        to_block.as_deref(),
+                    )
+                    .await?,
+            );
+        }
+        if !self.outgoing {
+            pages.push(
+                alchemy_client
+                    .get_incoming_asset_transfers(
+                        &address_str,
+                        self.limit,
+                        from_block.as_deref(),
+                        to_block.as_deref(),
+                    )
+                    .await?,
+            );
+        }
+        let mut transfers = Vec::new();
+        for response in &pages {
+            transfers.extend(
+                response["result"]["transfers"]
+                    .as_array()
+                    .ok_or_else(|| anyhow::anyhow!("Invalid response format from Alchemy"))?
+                    .iter()
+                    .cloned(),
+            );
+        }
+        let transfers = merge_transfers(transfers, self.limit);
+
+        if self.since_last_check {
+            let newest = transfers.iter().filter_map(block_number).max();
+            if let Some(newest) = newest {
+                HistoryState::record(state_key, newest)?;
+            } else {
+                println!("{}", "✓ No new transactions since your last check.".green());
+                return Ok(());
+            }
+        }
 
+        if config.address_reuse_warning {
+            track_receives(&alchemy_client, &network, address, self.limit).await;
+        }
+
+        // 4. Process transactions
         let mut txs = Vec::new();
-        for transfer in transfers {
+        for transfer in &transfers {
             // Convert Alchemy transfer to RskTransaction
             let tx =
                 RskTransaction::from_alchemy_transfer(transfer, &address, &alchemy_client).await?;
@@ -172,14 +313,14 @@ impl HistoryCommand {
         }
 
         // 5. Apply filters
-        if self.incoming && self.outgoing {
-            anyhow::bail!("Cannot use both --incoming and --outgoing at the same time");
-        }
         if self.incoming {
             txs.retain(|tx| tx.to == Some(address));
         } else if self.outgoing {
             txs.retain(|tx| tx.from == address);
         }
+        if let Some(tx_type) = self.tx_type {
+            txs.retain(|tx| tx.tx_type() == tx_type);
+        }
 
         // 6. Handle empty result
         if txs.is_empty() {
@@ -193,9 +334,14 @@ impl HistoryCommand {
             ("timestamp", _) => txs.sort_by_key(|t| std::cmp::Reverse(t.timestamp)),
             ("value", "asc") => txs.sort_by_key(|t| t.value),
             ("value", _) => txs.sort_by_key(|t| std::cmp::Reverse(t.value)),
+            ("gas", "asc") => txs.sort_by_key(|t| t.gas),
+            ("gas", _) => txs.sort_by_key(|t| std::cmp::Reverse(t.gas)),
             _ => {}
         }
 
+        // Labels are matched by hash, so they follow the transactions across re-fetches
+        let labels = TxLabels::load();
+
         // 8. Export to CSV if requested
         if let Some(filename) = &self.export_csv {
             let mut wtr = csv::Writer::from_path(filename)?;
@@ -212,11 +358,17 @@ impl HistoryCommand {
                 "Gas Used",
                 "Status",
                 "Block Number",
+                "Type",
+                "Category",
+                "Memo",
             ])?;
 
             // Write transactions
             for tx in &txs {
-                let record = tx.to_csv_record();
+                let mut record = tx.to_csv_record();
+                let label = labels.get(&tx.hash).cloned().unwrap_or_default();
+                record.push_field(&label.category);
+                record.push_field(&label.memo);
                 wtr.write_record(&record)?;
             }
 
@@ -237,7 +389,10 @@ impl HistoryCommand {
                 "TX Hash",
                 "From",
                 "To",
+                "Type",
+                "Value",
                 "Status",
+                "Label",
                 "Timestamp",
                 "Block",
                 "Gas Used",
@@ -269,13 +424,16 @@ impl HistoryCommand {
                         .as_ref()
                         .map(|a| format!("0x{}", &a.to_string()[2..]))
                         .unwrap_or_else(|| "-".into()),
+                    &tx.tx_type().to_string(),
+                    &value_text(tx),
                     &status_disp.to_string(),
+                    &label_text(&labels, tx),
                     &ts.format("%Y-%m-%d %H:%M:%S").to_string(),
                     // &tx.block_number.to_string(),
                 ]);
             }
         } else {
-            table.add_header(&["TX Hash", "From", "To", "Status"]);
+            table.add_header(&["TX Hash", "From", "To", "Type", "Value", "Status", "Label"]);
 
             for tx in &txs {
                 let status_disp = match tx.status {
@@ -292,7 +450,10 @@ impl HistoryCommand {
                         .as_ref()
                         .map(|a| format!("0x{}", &a.to_string()[2..6]))
                         .unwrap_or_else(|| "-".into()),
+                    &tx.tx_type().to_string(),
+                    &value_text(tx),
                     &status_disp.to_string(),
+                    &label_text(&labels, tx),
                 ]);
             }
         }
@@ -301,3 +462,184 @@ impl HistoryCommand {
         Ok(())
     }
 }
+
+/// Amount of `tx` at its own decimals; token amounts of unknown decimals stay in base units
+fn value_text(tx: &RskTransaction) -> String {
+    match (tx.token_address, tx.decimals) {
+        (_, Some(decimals)) => format_amount(tx.value, decimals),
+        (None, None) => format_amount(tx.value, 18),
+        (Some(_), None) => format!("{} (base units)", tx.value),
+    }
+}
+
+fn label_text(labels: &TxLabels, tx: &RskTransaction) -> String {
+    labels
+        .get(&tx.hash)
+        .map(|label| label.summary())
+        .unwrap_or_else(|| "-".into())
+}
+
+fn short_address(address: Address) -> String {
+    let hex = format!("{:#x}", address);
+    format!("{}…{}", &hex[..6], &hex[hex.len() - 4..])
+}
+
+/// Address of the saved contact called `name`, ignoring case
+pub fn resolve_contact(name: &str) -> Result<Address> {
+    let contacts = ContactsCommand {
+        action: ContactsAction::List,
+    }
+    .load_contacts()?;
+    contacts
+        .iter()
+        .find(|contact| contact.name.eq_ignore_ascii_case(name.trim()))
+        .map(|contact| contact.address)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Contact '{}' not found; see `contacts list` for saved names",
+                name.trim()
+            )
+        })
+}
+
+/// Timeline of the transfers sent directly between `a` and `b` (up to `limit`
+/// each way), oldest first, followed by the RBTC net flow from `a`'s side
+pub async fn show_history_between(
+    alchemy_client: &AlchemyClient,
+    eth_client: &EthClient,
+    a: Address,
+    b: Address,
+    limit: u32,
+) -> Result<()> {
+    let mut txs = Vec::new();
+    for (from, to) in [(a, b), (b, a)] {
+        let response = alchemy_client
+            .get_transfers_between(&format!("{:#x}", from), &format!("{:#x}", to), limit)
+            .await?;
+        let transfers = response["result"]["transfers"]
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Invalid response format from Alchemy"))?;
+        for transfer in transfers {
+            txs.push(RskTransaction::from_alchemy_transfer(transfer, &a, alchemy_client).await?);
+        }
+    }
+
+    let (a_label, b_label) = (short_address(a), short_address(b));
+    if txs.is_empty() {
+        println!(
+            "{}",
+            format!("⚠️  No transactions between {} and {}.", a_label, b_label).yellow()
+        );
+        return Ok(());
+    }
+    txs.sort_by_key(|tx| tx.timestamp);
+
+    // Amounts are in base units; token rows use the decimals Alchemy reports, then
+    // the token's metadata, and stay in base units when neither is known
+    let mut tokens: HashMap<Address, TokenMetadata> = HashMap::new();
+    for token in txs.iter().filter_map(|tx| tx.token_address) {
+        if !tokens.contains_key(&token)
+            && let Ok(metadata) = eth_client.token_metadata(token).await
+        {
+            tokens.insert(token, metadata);
+        }
+    }
+
+    let mut table = TableBuilder::new();
+    table.add_header(&["Date", "Direction", "Value", "Asset", "TX Hash"]);
+    for tx in &txs {
+        let ts = chrono::Local
+            .timestamp_opt(
+                tx.timestamp
+                    .duration_since(std::time::UNIX_EPOCH)?
+                    .as_secs() as i64,
+                0,
+            )
+            .unwrap();
+        let direction = if tx.from == a {
+            format!("{} → {}", a_label, b_label)
+        } else {
+            format!("{} → {}", b_label, a_label)
+        };
+        let (value, asset) = match tx.token_address {
+            None => (value_text(tx), "RBTC".to_string()),
+            Some(token) => {
+                let metadata = tokens.get(&token);
+                let value = match tx.decimals.or(metadata.map(|m| m.decimals)) {
+                    Some(decimals) => format_amount(tx.value, decimals),
+                    None => format!("{} (base units)", tx.value),
+                };
+                let asset = metadata
+                    .map(|m| m.symbol.clone())
+                    .unwrap_or_else(|| short_address(token));
+                (value, asset)
+            }
+        };
+        table.add_row(&[
+            &ts.format("%Y-%m-%d %H:%M").to_string(),
+            &direction,
+            &value,
+            &asset,
+            &format!("0x{}", &tx.hash.to_string()[2..10]),
+        ]);
+    }
+    table.print();
+
+    // Token amounts use their own decimals, so the net flow covers RBTC only
+    let rbtc: Vec<RskTransaction> = txs
+        .iter()
+        .filter(|tx| tx.token_address.is_none())
+        .cloned()
+        .collect();
+    let (sent, received) =
+        Contact::new(a_label.clone(), a, None, Vec::new()).get_volume_between(b, &rbtc);
+    println!("\n{} → {}: {} RBTC", a_label, b_label, format_amount(sent, 18));
+    println!("{} → {}: {} RBTC", b_label, a_label, format_amount(received, 18));
+    let net = if received >= sent {
+        format!("+{} RBTC", format_amount(received - sent, 18)).green()
+    } else {
+        format!("-{} RBTC", format_amount(sent - received, 18)).red()
+    };
+    println!("Net flow for {}: {}", a_label, net);
+    Ok(())
+}
+
+/// Records the payments `address` received in the receive log and warns once it
+/// has received several; failures only print a warning
+async fn track_receives(
+    alchemy_client: &AlchemyClient,
+    network: &str,
+    address: Address,
+    limit: u32,
+) {
+    let address_str = format!("{:#x}", address);
+    let response = match alchemy_client
+        .get_incoming_asset_transfers(&address_str, limit, None, None)
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("⚠️  Could not check received payments: {}", e);
+            return;
+        }
+    };
+    // Self-transfers are not payments from someone else
+    let hashes: Vec<B256> = response["result"]["transfers"]
+        .as_array()
+        .map(|transfers| {
+            transfers
+                .iter()
+                .filter(|t| t["from"].as_str() != Some(address_str.as_str()))
+                .filter_map(|t| t["hash"].as_str().and_then(|h| B256::from_str(h).ok()))
+                .collect()
+        })
+        .unwrap_or_default();
+    match receive_log::record(network, &address, &hashes) {
+        Ok(count) => {
+            if let Some(warning) = receive_log::reuse_warning(&address, count) {
+                println!("{}", format!("🔒 {}", warning).yellow());
+            }
+        }
+        Err(e) => eprintln!("⚠️  Could not update the received payments log: {}", e),
+    }
+}
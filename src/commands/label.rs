@@ -0,0 +1,51 @@
+use crate::types::tx_label::{TxLabel, TxLabels};
+use alloy::primitives::B256;
+use anyhow::{Result, anyhow};
+use clap::Parser;
+use std::str::FromStr;
+
+/// Attach a bookkeeping category and memo to a transaction
+#[derive(Parser, Debug)]
+pub struct LabelCommand {
+    /// Transaction hash to label
+    #[arg(long)]
+    pub tx_hash: String,
+
+    /// Category, e.g. "income" or "fees"
+    #[arg(long, default_value = "")]
+    pub category: String,
+
+    /// Free-form memo
+    #[arg(long, default_value = "")]
+    pub memo: String,
+
+    /// Remove the transaction's label
+    #[arg(long, conflicts_with_all = ["category", "memo"])]
+    pub remove: bool,
+}
+
+impl LabelCommand {
+    pub async fn execute(&self) -> Result<()> {
+        let hash = B256::from_str(self.tx_hash.trim())
+            .map_err(|_| anyhow!("Invalid transaction hash: {}", self.tx_hash))?;
+        let label = TxLabel {
+            category: self.category.trim().to_string(),
+            memo: self.memo.trim().to_string(),
+        };
+
+        if self.remove || (label.category.is_empty() && label.memo.is_empty()) {
+            let existed = TxLabels::load().get(&hash).is_some();
+            TxLabels::set(&hash, TxLabel::default())?;
+            if existed {
+                println!("Removed the label of 0x{:x}", hash);
+            } else {
+                println!("0x{:x} has no label", hash);
+            }
+            return Ok(());
+        }
+
+        TxLabels::set(&hash, label.clone())?;
+        println!("Labelled 0x{:x}: {}", hash, label.summary());
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use crate::config::ConfigManager;
+use crate::utils::activity::{self, ActivityDates};
+use crate::utils::address::{is_contract, parse_address};
+use crate::utils::alchemy::AlchemyClient;
+use crate::utils::eth::EthClient;
+use crate::utils::helper::Config as HelperConfig;
+use crate::utils::table::TableBuilder;
+use crate::utils::units::format_amount;
+use alloy::providers::Provider;
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use clap::Parser;
+
+/// Look up an address before transacting with it: balance, type and how long it has been active
+#[derive(Parser, Debug)]
+pub struct LookupCommand {
+    /// Address to look up (an rsk:/rsktest: prefix is accepted)
+    #[arg(long)]
+    pub address: String,
+
+    /// Fetch the first and last activity dates again instead of using the cache
+    #[arg(long)]
+    pub refresh: bool,
+}
+
+impl LookupCommand {
+    pub async fn execute(&self) -> Result<()> {
+        let config = ConfigManager::new()?.load()?;
+        let network = config.default_network;
+        let address = parse_address(&self.address, network)?;
+        let network_name = network.to_string().to_lowercase();
+
+        let eth_client = EthClient::new(&HelperConfig {
+            network: network.get_config(),
+            wallet: Default::default(),
+        })
+        .await?;
+        let balance = eth_client.get_balance(&address, &None).await?;
+        let sent = eth_client
+            .provider()
+            .get_transaction_count(address)
+            .await
+            .map_err(|e| anyhow!("Failed to get nonce: {}", e))?;
+        let account_type = match is_contract(eth_client.provider(), address).await {
+            Ok(true) => "Contract",
+            Ok(false) => "Externally owned account",
+            Err(_) => "Unknown",
+        };
+
+        let dates = match activity::cached(&network_name, &address).filter(|_| !self.refresh) {
+            Some(dates) => Some(dates),
+            None => match config.get_alchemy_key() {
+                Some(key) => {
+                    let client = AlchemyClient::new(key.to_string(), network.chain_id() == 31);
+                    let address_str = format!("0x{:x}", address);
+                    let edges = match client.edge_transfer_time(&address_str, false).await {
+                        Ok(first) => client
+                            .edge_transfer_time(&address_str, true)
+                            .await
+                            .map(|last| (first, last)),
+                        Err(e) => Err(e),
+                    };
+                    match edges {
+                        Ok((first, last)) => {
+                            let dates = ActivityDates::new(first, last);
+                            if let Err(e) = activity::store(&network_name, &address, &dates) {
+                                eprintln!("⚠️  Could not cache the activity dates: {}", e);
+                            }
+                            Some(dates)
+                        }
+                        Err(e) => {
+                            eprintln!("⚠️  Could not fetch the activity dates: {}", e);
+                            None
+                        }
+                    }
+                }
+                None => None,
+            },
+        };
+
+        let mut table = TableBuilder::new();
+        table.add_header(&["Field", "Value"]);
+        table.add_row(&["Address", &format!("0x{:x}", address)]);
+        table.add_row(&["Network", &network.to_string()]);
+        table.add_row(&["Type", account_type]);
+        table.add_row(&["Balance", &format!("{} RBTC", format_amount(balance, 18))]);
+        table.add_row(&["Sent transactions", &sent.to_string()]);
+        match &dates {
+            Some(dates) => {
+                let first_seen = dates.first_seen.as_deref().unwrap_or("No transfers");
+                let last_seen = dates.last_seen.as_deref().unwrap_or("No transfers");
+                table.add_row(&["First seen", first_seen]);
+                table.add_row(&["Last seen", last_seen]);
+                if let Some(first) = dates
+                    .first_seen
+                    .as_deref()
+                    .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                {
+                    let age = activity::describe_age(first.with_timezone(&Utc), Utc::now());
+                    table.add_row(&["Active for", &age]);
+                }
+            }
+            None if config.get_alchemy_key().is_some() => {
+                table.add_row(&["First / last seen", "Unavailable"]);
+            }
+            None => {
+                table.add_row(&["First / last seen", "Needs an Alchemy API key"]);
+            }
+        }
+        table.print();
+        Ok(())
+    }
+}
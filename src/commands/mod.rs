@@ -1,11 +1,20 @@
 pub mod api;
 pub mod balance;
+pub mod bridge;
+pub mod config;
 pub mod contacts;
+pub mod hash;
 pub mod history;
+pub mod label;
+pub mod lookup;
+pub mod networks;
+pub mod raw_tx;
 pub mod root;
+pub mod rpc;
 pub mod tokens;
 pub mod transfer;
 pub mod tx;
 pub mod wallet;
+pub mod watch;
 
 pub use root::Commands;
@@ -0,0 +1,51 @@
+use crate::config::ConfigManager;
+use crate::types::network::Network;
+use crate::utils::{helper::Helper, table::TableBuilder};
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+
+/// List every network with the endpoint the wallet would actually use for it
+#[derive(Parser, Debug)]
+pub struct NetworksCommand {}
+
+impl NetworksCommand {
+    pub async fn execute(&self) -> Result<()> {
+        let config = ConfigManager::new()?.load()?;
+        // Mask every configured key, not just the active network's
+        let keys: Vec<Option<&str>> = config
+            .api
+            .keys
+            .iter()
+            .map(|k| Some(k.key.as_str()))
+            .chain([
+                config.alchemy_mainnet_key.as_deref(),
+                config.alchemy_testnet_key.as_deref(),
+            ])
+            .collect();
+
+        let mut table = TableBuilder::new();
+        table.add_header(&["", "Network", "Name", "Chain ID", "RPC Endpoint", "Source", "Explorer"]);
+        for network in Network::ALL {
+            let (rpc_url, source) = config.rpc_url_for(network);
+            let explorer = network.get_config().explorer_url;
+            table.add_row(&[
+                if network == config.default_network { "✓" } else { "" },
+                &network.to_string(),
+                network.cli_name(),
+                &network.chain_id().to_string(),
+                &Helper::mask_url(&rpc_url, &keys),
+                source,
+                if explorer.is_empty() { "-" } else { &explorer },
+            ]);
+        }
+        table.print();
+
+        println!(
+            "{} {} (change it with 'config set default-network <name>')",
+            "Active network:".bold(),
+            config.default_network
+        );
+        Ok(())
+    }
+}
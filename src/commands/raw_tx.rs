@@ -0,0 +1,202 @@
+use crate::config::ConfigManager;
+use crate::utils::eth::EthClient;
+use crate::utils::calldata::{decode_erc20_transfer, decode_with_abi, parse_abi};
+use crate::utils::helper::Config as HelperConfig;
+use crate::utils::network_guard;
+use crate::utils::qr::{
+    TX_FRAME_CHARS, decode_qr_image, generate_qr_code, join_frames, generate_qr_png, split_frames,
+};
+use crate::utils::signed_tx::decode_signed_transaction;
+use crate::utils::units::{Denomination, format_amount, from_wei};
+use alloy::primitives::{Bytes, U256, hex, keccak256};
+use alloy::providers::Provider;
+use anyhow::{Result, anyhow};
+use clap::{Parser, Subcommand};
+use colored::Colorize;
+use std::path::PathBuf;
+
+/// Move an already signed transaction between an offline and an online device
+#[derive(Parser, Debug)]
+pub struct RawTxCommand {
+    #[command(subcommand)]
+    pub action: RawTxAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RawTxAction {
+    /// Show a signed transaction as QR codes to scan with the online device
+    ExportQr {
+        /// Signed transaction as 0x-prefixed hex
+        #[arg(long, conflicts_with = "file", required_unless_present = "file")]
+        raw: Option<String>,
+        /// File holding the signed transaction hex
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Also save each frame as frame-<n>.png in this directory
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Broadcast a signed transaction, typed or scanned from QR images
+    Broadcast {
+        /// Signed transaction as 0x-prefixed hex
+        #[arg(long, conflicts_with = "qr", required_unless_present = "qr")]
+        raw: Option<String>,
+        /// Images of the QR frames, in any order
+        #[arg(long, num_args = 1..)]
+        qr: Vec<PathBuf>,
+    },
+}
+
+impl RawTxCommand {
+    pub async fn execute(&self) -> Result<()> {
+        match &self.action {
+            RawTxAction::ExportQr { raw, file, output } => {
+                let raw = match (raw, file) {
+                    (Some(raw), _) => raw.clone(),
+                    (None, Some(file)) => std::fs::read_to_string(file)
+                        .map_err(|e| anyhow!("Failed to read {}: {}", file.display(), e))?,
+                    (None, None) => return Err(anyhow!("Pass --raw or --file")),
+                };
+                export_qr(&raw, output.as_ref())
+            }
+            RawTxAction::Broadcast { raw, qr } => {
+                let raw = match raw {
+                    Some(raw) => raw.clone(),
+                    None => {
+                        let frames = qr
+                            .iter()
+                            .map(|path| decode_qr_image(path))
+                            .collect::<Result<Vec<_>>>()?;
+                        join_frames(&frames)?
+                    }
+                };
+                broadcast(&raw).await
+            }
+        }
+    }
+}
+
+/// Checks that `raw` is hex and returns its bytes
+fn parse_raw(raw: &str) -> Result<Bytes> {
+    let raw = raw.trim();
+    let bytes = hex::decode(raw).map_err(|e| anyhow!("Signed transaction is not hex: {}", e))?;
+    if bytes.is_empty() {
+        return Err(anyhow!("Signed transaction is empty"));
+    }
+    Ok(bytes.into())
+}
+
+/// Prints the frames one at a time, waiting for Enter between them
+pub fn export_qr(raw: &str, output: Option<&PathBuf>) -> Result<()> {
+    let bytes = parse_raw(raw)?;
+    let frames = split_frames(&format!("0x{}", hex::encode(&bytes)), TX_FRAME_CHARS);
+    println!("Transaction hash: 0x{:x}", keccak256(&bytes));
+
+    if let Some(dir) = output {
+        std::fs::create_dir_all(dir)?;
+        for (i, frame) in frames.iter().enumerate() {
+            generate_qr_png(frame, &dir.join(format!("frame-{}.png", i + 1)))?;
+        }
+        println!("Saved {} frame(s) to {}", frames.len(), dir.display());
+    }
+
+    for (i, frame) in frames.iter().enumerate() {
+        println!("\nFrame {}/{}", i + 1, frames.len());
+        println!("{}", generate_qr_code(frame)?);
+        if i + 1 < frames.len() {
+            println!("Press Enter for the next frame");
+            std::io::stdin().read_line(&mut String::new())?;
+        }
+    }
+    Ok(())
+}
+
+/// Prints the fields of a signed transaction; `abi` is the contents of an ABI
+/// file used to decode the calldata (ERC20 transfers are decoded without one)
+pub fn decode(raw: &str, abi: Option<&str>) -> Result<()> {
+    let tx = decode_signed_transaction(&parse_raw(raw)?)?;
+    let gwei = |wei: u128| from_wei(U256::from(wei), Denomination::Gwei);
+
+    println!("Type: {}", tx.kind);
+    println!("Hash: 0x{:x}", tx.hash);
+    println!("From: {} (recovered)", tx.from.to_checksum(None));
+    match tx.to {
+        Some(to) => println!("To: {}", to.to_checksum(None)),
+        None => println!("To: {}", "contract deployment".yellow()),
+    }
+    println!("Value: {} RBTC", format_amount(tx.value, 18));
+    println!("Nonce: {}", tx.nonce);
+    println!("Gas limit: {}", tx.gas_limit);
+    if let Some(gas_price) = tx.gas_price {
+        println!("Gas price: {} gwei", gwei(gas_price));
+    }
+    if let Some(max_fee) = tx.max_fee_per_gas {
+        println!("Max fee: {} gwei", gwei(max_fee));
+    }
+    if let Some(priority_fee) = tx.max_priority_fee_per_gas {
+        println!("Max priority fee: {} gwei", gwei(priority_fee));
+    }
+    match tx.chain_id {
+        Some(chain_id) => println!("Chain id: {}", chain_id),
+        None => println!("Chain id: {}", "none (replayable on any chain)".yellow()),
+    }
+
+    if tx.input.is_empty() {
+        println!("Calldata: none");
+        return Ok(());
+    }
+    println!("Calldata: 0x{}", hex::encode(&tx.input));
+    let decoded = match abi {
+        Some(abi) => decode_with_abi(&parse_abi(abi)?, &tx.input),
+        None => decode_erc20_transfer(&tx.input),
+    };
+    match decoded {
+        Ok(call) => {
+            println!("Function: {}", call.signature.cyan());
+            for param in &call.params {
+                println!("    {} ({}): {}", param.name, param.kind, param.value);
+            }
+        }
+        Err(e) if abi.is_some() => println!("Function: {}", e.to_string().red()),
+        Err(_) => println!("Function: unknown (pass --abi to decode)"),
+    }
+    Ok(())
+}
+
+/// Sends a signed transaction to the default network after confirmation
+pub async fn broadcast(raw: &str) -> Result<()> {
+    let bytes = parse_raw(raw)?;
+    let config = ConfigManager::new()?.load()?;
+    let network = config.default_network;
+
+    println!("{}", network_guard::banner(network));
+    println!("Transaction hash: 0x{:x} ({} bytes)", keccak256(&bytes), bytes.len());
+    if let Err(e) = decode(raw, None) {
+        println!("{}: {}", "Warning".yellow().bold(), e);
+    }
+    if !dialoguer::Confirm::new()
+        .with_prompt("Broadcast this signed transaction?")
+        .default(false)
+        .interact()?
+    {
+        println!("Broadcast cancelled");
+        return Ok(());
+    }
+
+    let eth_client = EthClient::new(&HelperConfig {
+        network: network.get_config(),
+        wallet: Default::default(),
+    })
+    .await?;
+    let pending = eth_client
+        .provider()
+        .send_raw_transaction(&bytes)
+        .await
+        .map_err(|e| anyhow!("Failed to broadcast the transaction: {}", e))?;
+    println!(
+        "{}: Transaction broadcast: 0x{:x}",
+        "Success".green().bold(),
+        pending.tx_hash()
+    );
+    Ok(())
+}
@@ -1,6 +1,18 @@
 use crate::commands::api::SetApiKeyCommand;
+use crate::commands::bridge::BridgeCommand;
+use crate::commands::config::ConfigCommand;
 use crate::commands::contacts::ContactsCommand;
-use crate::commands::tokens::{TokenAddCommand, TokenListCommand, TokenRemoveCommand};
+use crate::commands::hash::HashCommand;
+use crate::commands::label::LabelCommand;
+use crate::commands::lookup::LookupCommand;
+use crate::commands::networks::NetworksCommand;
+use crate::commands::raw_tx::RawTxCommand;
+use crate::commands::rpc::RpcCommand;
+use crate::commands::watch::WatchCommand;
+use crate::commands::tx::TxCommand;
+use crate::commands::tokens::{
+    TokenAddCommand, TokenListCommand, TokenRefreshCommand, TokenRemoveCommand,
+};
 use crate::commands::wallet::WalletCommand;
 use clap::Parser;
 
@@ -10,6 +22,8 @@ pub enum Commands {
     Wallet(WalletCommand),
     /// Manage contacts
     Contacts(ContactsCommand),
+    /// Show, change or reset the wallet configuration
+    Config(ConfigCommand),
     /// Show transaction history
     History {
         #[arg(short, long, default_value = "10")]
@@ -20,6 +34,9 @@ pub enum Commands {
         token: Option<String>,
         #[arg(short, long)]
         status: Option<String>,
+        /// Only show one type: native, transfer, approval, call or creation
+        #[arg(long = "type")]
+        tx_type: Option<crate::types::transaction::TxType>,
         #[arg(short, long)]
         incoming: bool,
         #[arg(short, long)]
@@ -29,6 +46,12 @@ pub enum Commands {
         api_key: Option<String>,
         #[arg(long, default_value = "mainnet")]
         network: String,
+        /// Only show transfers since the last such check
+        #[arg(long, visible_alias = "new")]
+        since_last_check: bool,
+        /// Only show transfers directly between two addresses
+        #[arg(long, num_args = 2, value_names = ["A", "B"])]
+        between: Option<Vec<String>>,
     },
     /// Check balance of an address
     Balance {
@@ -41,6 +64,12 @@ pub enum Commands {
         /// Address to check balance for (optional if using default wallet)
         #[arg(long)]
         address: Option<String>,
+        /// Block to read at: latest, safe or finalized (defaults to the config)
+        #[arg(long)]
+        block_tag: Option<crate::config::ReadBlockTag>,
+        /// Show every registry token for the network in one table
+        #[arg(long, conflicts_with = "token")]
+        all: bool,
     },
     /// Transfer RBTC or tokens
     Transfer {
@@ -48,13 +77,19 @@ pub enum Commands {
         #[arg(long, required = true)]
         address: String,
         /// Amount to send (in RBTC or token units)
-        #[arg(long, required = true)]
-        value: f64,
+        #[arg(long, required_unless_present = "wei", conflicts_with = "wei")]
+        value: Option<String>,
+        /// Exact amount in wei / token base units (no decimal conversion)
+        #[arg(long)]
+        wei: Option<String>,
         /// Token address (for ERC20 transfers)
         #[arg(long)]
         token: Option<String>,
         #[arg(short, long, default_value = "mainnet")]
         network: String,
+        /// Sign with this wallet instead of the active one
+        #[arg(long)]
+        from: Option<String>,
     },
 
     SetApiKey(SetApiKeyCommand),
@@ -67,4 +102,34 @@ pub enum Commands {
 
     /// List tokens in the registry
     TokenList(TokenListCommand),
+
+    /// Forget cached token metadata so it is read from the chain again
+    TokenRefresh(TokenRefreshCommand),
+
+    /// Query the RSK Bridge (powpeg federation status)
+    Bridge(BridgeCommand),
+
+    /// Send a raw JSON-RPC call to the active network's node
+    Rpc(RpcCommand),
+
+    /// Watch an address for balance changes
+    Watch(WatchCommand),
+
+    /// Attach a category and memo to a transaction for bookkeeping
+    Label(LabelCommand),
+
+    /// Compute or verify a keccak256 digest
+    Hash(HashCommand),
+
+    /// List all networks with the RPC endpoint, chain id and explorer each would use
+    Networks(NetworksCommand),
+
+    /// Show an address's balance, type and first and last activity dates
+    Lookup(LookupCommand),
+
+    /// Check a transaction's status, or decode a signed one
+    Tx(TxCommand),
+
+    /// Export a signed transaction as QR codes, or broadcast one scanned from them
+    RawTx(RawTxCommand),
 }
@@ -0,0 +1,73 @@
+use crate::config::ConfigManager;
+use crate::utils::helper::Helper;
+use anyhow::{Result, anyhow};
+use clap::Parser;
+use colored::Colorize;
+use serde_json::{Value, json};
+
+/// Sends an arbitrary JSON-RPC request to the active network's endpoint
+#[derive(Parser, Debug)]
+pub struct RpcCommand {
+    /// JSON-RPC method, e.g. eth_getLogs
+    #[arg(long)]
+    pub method: String,
+
+    /// Parameters as a JSON array (or object)
+    #[arg(long, default_value = "[]")]
+    pub params: String,
+
+    /// Network to query instead of the configured default (mainnet | testnet | regtest)
+    #[arg(long)]
+    pub network: Option<String>,
+}
+
+impl RpcCommand {
+    pub async fn execute(&self) -> Result<()> {
+        let params: Value = serde_json::from_str(&self.params)
+            .map_err(|e| anyhow!("--params is not valid JSON: {}", e))?;
+        if !params.is_array() && !params.is_object() {
+            return Err(anyhow!("--params must be a JSON array or object"));
+        }
+
+        let config = ConfigManager::new()?.load()?;
+        let network = match &self.network {
+            Some(name) => name.parse()?,
+            None => config.default_network,
+        };
+        let keys = [config.get_rsk_rpc_key(), config.get_alchemy_key()];
+        let rpc_url = network.get_rpc_url_with_key(keys[0], keys[1]);
+
+        println!(
+            "{} {} → {}",
+            "POST".dimmed(),
+            Helper::mask_url(&rpc_url, &keys),
+            self.method.bold()
+        );
+
+        let response = reqwest::Client::new()
+            .post(&rpc_url)
+            .json(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": self.method,
+                "params": params,
+            }))
+            .send()
+            .await
+            .map_err(|e| anyhow!("Request failed: {}", Helper::mask_url(&e.to_string(), &keys)))?;
+
+        let status = response.status();
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Node returned {} with a non-JSON body: {}", status, e))?;
+
+        if let Some(error) = body.get("error") {
+            println!("{}", "Error".red().bold());
+            println!("{}", serde_json::to_string_pretty(error)?);
+            return Err(anyhow!("{} failed", self.method));
+        }
+        println!("{}", serde_json::to_string_pretty(&body["result"])?);
+        Ok(())
+    }
+}
@@ -1,9 +1,16 @@
+use crate::config::ConfigManager;
+use crate::utils::eth::EthClient;
+use crate::utils::token_metadata::{self, TokenMetadata};
+use crate::utils::{constants, file_lock};
+use alloy::primitives::Address;
+use anyhow::Context;
 use clap::Parser;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 #[derive(Parser, Debug)]
 pub struct TokenAddCommand {
@@ -42,43 +49,240 @@ pub struct TokenListCommand {
     pub network: Option<String>,
 }
 
+#[derive(Parser, Debug)]
+pub struct TokenRefreshCommand {
+    /// Only forget the metadata of this network's tokens (mainnet/testnet)
+    #[arg(short, long)]
+    pub network: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TokenInfo {
     pub address: String,
     pub decimals: u8,
 }
 
+/// Where older versions kept the registry, relative to the working directory
+const LEGACY_PATH: &str = "tokens.json";
+
+/// Writing `tokens.json` failed; the file on disk still holds the previous list
+#[derive(Debug, thiserror::Error)]
+#[error("Couldn't save tokens: {0:#}")]
+pub struct SaveTokensError(anyhow::Error);
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct TokenRegistry {
+    #[serde(default)]
     pub mainnet: HashMap<String, TokenInfo>,
+    #[serde(default)]
     pub testnet: HashMap<String, TokenInfo>,
 }
 
 impl TokenRegistry {
+    /// `tokens.json` in the config directory. A `tokens.json` in the working directory,
+    /// where older versions kept it, is imported the first time.
+    pub fn path() -> PathBuf {
+        let path = constants::token_registry_path();
+        Self::import_legacy(&path, Path::new(LEGACY_PATH));
+        path
+    }
+
     pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        let path = "tokens.json";
-        if !Path::new(path).exists() {
+        let mut registry = Self::load_from(&Self::path())?;
+        registry.merge_extra_files();
+        Ok(registry)
+    }
+
+    /// Reads the registry at `path`, creating an empty one when it is missing
+    fn load_from(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        if !path.exists() {
             // Create a new empty registry if file doesn't exist
             let registry = TokenRegistry {
                 mainnet: HashMap::new(),
                 testnet: HashMap::new(),
             };
             let json = serde_json::to_string_pretty(&json!(&registry))?;
-            fs::write(path, json)?;
+            Self::write(path, &json)?;
             return Ok(registry);
         }
 
         let content = fs::read_to_string(path)?;
-        let registry: TokenRegistry = serde_json::from_str(&content)?;
+        match serde_json::from_str::<TokenRegistry>(&content) {
+            Ok(registry) => Ok(registry),
+            Err(e) => Self::recover(path, &content, &e.to_string()),
+        }
+    }
+
+    /// Copies `legacy` to `path` when only the legacy file exists. The legacy file
+    /// is left in place; returns whether anything was imported.
+    fn import_legacy(path: &Path, legacy: &Path) -> bool {
+        if path.exists() || !legacy.is_file() {
+            return false;
+        }
+        let imported = file_lock::with_lock(path, || {
+            if path.exists() {
+                return Ok(false);
+            }
+            let content = fs::read_to_string(legacy)?;
+            file_lock::write_atomic(path, &content)?;
+            Ok(true)
+        });
+        match imported {
+            Ok(true) => {
+                eprintln!(
+                    "ℹ️  Moved your token list from {} to {}; the old file can be deleted",
+                    legacy.display(),
+                    path.display()
+                );
+                true
+            }
+            Ok(false) => false,
+            Err(e) => {
+                eprintln!("⚠️  Warning: Could not import {}: {}", legacy.display(), e);
+                false
+            }
+        }
+    }
+
+    /// Backs up a registry that fails to parse and keeps every entry that still
+    /// parses, so one bad token doesn't cost the user the whole list
+    fn recover(
+        path: &Path,
+        content: &str,
+        error: &str,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let backup = path.with_extension(format!(
+            "json.corrupt-{}",
+            chrono::Local::now().format("%Y%m%d%H%M%S")
+        ));
+        fs::copy(path, &backup)?;
+        eprintln!(
+            "⚠️  Warning: {} could not be read ({}); a copy was saved to {}",
+            path.display(),
+            error,
+            backup.display()
+        );
+
+        let mut registry = TokenRegistry::default();
+        match serde_json::from_str::<serde_json::Value>(content) {
+            Ok(value) => {
+                for (network, tokens) in [
+                    ("mainnet", &mut registry.mainnet),
+                    ("testnet", &mut registry.testnet),
+                ] {
+                    let Some(entries) = value[network].as_object() else {
+                        continue;
+                    };
+                    for (symbol, entry) in entries {
+                        match serde_json::from_value::<TokenInfo>(entry.clone()) {
+                            Ok(info) => {
+                                tokens.insert(symbol.clone(), info);
+                            }
+                            Err(e) => eprintln!(
+                                "⚠️  Warning: Dropped {} token {}: {}",
+                                network, symbol, e
+                            ),
+                        }
+                    }
+                }
+            }
+            Err(_) => eprintln!(
+                "⚠️  Warning: No tokens could be recovered; restore them from the backup"
+            ),
+        }
+
+        let recovered = registry.mainnet.len() + registry.testnet.len();
+        eprintln!("Recovered {} token(s)", recovered);
+        let json = serde_json::to_string_pretty(&registry)?;
+        Self::write(path, &json)?;
         Ok(registry)
     }
 
+    /// Adds the tokens of the `extra_token_files` listed in the config. Entries already
+    /// present (by symbol or address) win; unreadable files and invalid entries are skipped.
+    fn merge_extra_files(&mut self) {
+        let Ok(config) = ConfigManager::new().and_then(|manager| manager.load()) else {
+            return;
+        };
+        for path in &config.extra_token_files {
+            let extra = fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|content| {
+                    serde_json::from_str::<TokenRegistry>(&content).map_err(|e| e.to_string())
+                });
+            match extra {
+                Ok(extra) => {
+                    self.merge_network("mainnet", extra.mainnet, path);
+                    self.merge_network("testnet", extra.testnet, path);
+                }
+                Err(e) => eprintln!(
+                    "⚠️  Warning: Skipping token file {}: {}",
+                    path.display(),
+                    e
+                ),
+            }
+        }
+    }
+
+    fn merge_network(&mut self, network: &str, tokens: HashMap<String, TokenInfo>, source: &Path) {
+        let mut tokens: Vec<_> = tokens.into_iter().collect();
+        tokens.sort_by(|a, b| a.0.cmp(&b.0));
+        for (symbol, info) in tokens {
+            if Address::from_str(&info.address).is_err() {
+                eprintln!(
+                    "⚠️  Warning: Skipping {} in {}: invalid address {}",
+                    symbol,
+                    source.display(),
+                    info.address
+                );
+                continue;
+            }
+            // add_token rejects duplicate symbols and addresses, which keeps the registry's entry
+            let _ = self.add_token(network, &symbol, &info.address, info.decimals);
+        }
+    }
+
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
         let json = serde_json::to_string_pretty(&self)?;
-        fs::write("tokens.json", json)?;
+        Self::write(&Self::path(), &json)?;
         Ok(())
     }
 
+    /// Replaces `path` atomically under its lock, retrying transient failures
+    fn write(path: &Path, json: &str) -> Result<(), SaveTokensError> {
+        file_lock::with_lock(path, || file_lock::write_atomic_retrying(path, json))
+            .map_err(SaveTokensError)
+    }
+
+    /// Applies `f` to `tokens.json` under an exclusive lock, re-reading it first so
+    /// tokens added by another running instance are kept. Nothing is written if `f`
+    /// fails; a failed write is reported as a [`SaveTokensError`].
+    pub fn update<R>(
+        f: impl FnOnce(&mut TokenRegistry) -> anyhow::Result<R>,
+    ) -> Result<R, Box<dyn std::error::Error>> {
+        Self::update_at(&Self::path(), f)
+    }
+
+    fn update_at<R>(
+        path: &Path,
+        f: impl FnOnce(&mut TokenRegistry) -> anyhow::Result<R>,
+    ) -> Result<R, Box<dyn std::error::Error>> {
+        Ok(file_lock::with_lock(path, || {
+            let mut registry = if path.exists() {
+                let content = fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                serde_json::from_str(&content)
+                    .with_context(|| format!("Failed to parse {}", path.display()))?
+            } else {
+                TokenRegistry::default()
+            };
+            let result = f(&mut registry)?;
+            file_lock::write_atomic_retrying(path, &serde_json::to_string_pretty(&registry)?)
+                .map_err(|e| anyhow::Error::new(SaveTokensError(e)))?;
+            Ok(result)
+        })?)
+    }
+
     pub fn add_token(
         &mut self,
         network: &str,
@@ -123,6 +327,31 @@ impl TokenRegistry {
         Ok(())
     }
 
+    /// Looks up a token by symbol (case-insensitive) on `network`
+    pub fn find_by_symbol(&self, network: &str, symbol: &str) -> Option<&TokenInfo> {
+        let tokens = match network.to_lowercase().as_str() {
+            "mainnet" => &self.mainnet,
+            "testnet" => &self.testnet,
+            _ => return None,
+        };
+        tokens.get(&symbol.to_uppercase())
+    }
+
+    /// Looks up a token by contract address on `network`, returning its symbol too
+    pub fn find_by_address(&self, network: &str, address: &Address) -> Option<(&str, &TokenInfo)> {
+        let tokens = match token_metadata::network_key(network).as_str() {
+            "mainnet" => &self.mainnet,
+            "testnet" => &self.testnet,
+            _ => return None,
+        };
+        tokens
+            .iter()
+            .find(|(_, info)| {
+                Address::from_str(&info.address).is_ok_and(|token| token == *address)
+            })
+            .map(|(symbol, info)| (symbol.as_str(), info))
+    }
+
     pub fn remove_token(&mut self, network: &str, symbol: &str) -> Result<(), &'static str> {
         match network.to_lowercase().as_str() {
             "mainnet" => {
@@ -196,19 +425,21 @@ pub fn add_token(
     address: &str,
     decimals: u8,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let mut registry = TokenRegistry::load()?;
-    if let Err(e) = registry.add_token(network, symbol, address, decimals) {
-        return Err(e.into());
-    }
-    registry.save()?;
+    TokenRegistry::update(|registry| {
+        registry
+            .add_token(network, symbol, address, decimals)
+            .map_err(anyhow::Error::msg)
+    })?;
     println!("Added token {} to {} network", symbol, network);
     Ok(())
 }
 
 pub fn remove_token(network: &str, symbol: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let mut registry = TokenRegistry::load()?;
-    registry.remove_token(network, symbol)?;
-    registry.save()?;
+    TokenRegistry::update(|registry| {
+        registry
+            .remove_token(network, symbol)
+            .map_err(anyhow::Error::msg)
+    })?;
     println!("Removed token {} from {} network", symbol, network);
     Ok(())
 }
@@ -228,3 +459,99 @@ pub fn list_tokens(
 
     Ok(tokens)
 }
+
+/// Decimals and symbol of `token`: taken from the registry when it lists the token,
+/// otherwise read from the chain and added to the registry so later commands skip
+/// the lookup
+pub async fn token_info(eth_client: &EthClient, token: Address) -> anyhow::Result<(u8, String)> {
+    let network = token_metadata::network_key(eth_client.network_name());
+    if let Ok(registry) = TokenRegistry::load()
+        && let Some((symbol, info)) = registry.find_by_address(&network, &token)
+    {
+        return Ok((info.decimals, symbol.to_string()));
+    }
+    let metadata = eth_client.token_metadata(token).await?;
+    register_token(&network, token, &metadata);
+    Ok((metadata.decimals, metadata.symbol))
+}
+
+/// Adds a token read from the chain to the registry. Tokens whose symbol is already
+/// taken stay out of it; their metadata is still cached in `token_metadata.json`.
+fn register_token(network: &str, token: Address, metadata: &TokenMetadata) {
+    if !matches!(network, "mainnet" | "testnet") {
+        return;
+    }
+    let added = TokenRegistry::update(|registry| {
+        Ok(registry
+            .add_token(network, &metadata.symbol, &format!("{:#x}", token), metadata.decimals)
+            .is_ok())
+    });
+    if let Err(e) = added {
+        eprintln!("⚠️  Could not add {} to the token registry: {}", metadata.symbol, e);
+    }
+}
+
+/// Forgets cached token symbols, decimals and names so they are read from the chain again
+/// on next use; returns how many tokens were refreshed
+pub fn refresh_metadata(network: Option<&str>) -> Result<usize, Box<dyn std::error::Error>> {
+    Ok(token_metadata::clear(network)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RIF: &str = "0x2acc95758f8b5f583470ba265eb685a8f45fc9d5";
+
+    #[test]
+    fn test_registry_round_trips_in_config_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tokens.json");
+        assert!(TokenRegistry::load_from(&path).unwrap().mainnet.is_empty());
+        assert!(path.exists());
+
+        TokenRegistry::update_at(&path, |registry| {
+            registry
+                .add_token("mainnet", "rif", RIF, 18)
+                .map_err(anyhow::Error::msg)
+        })
+        .unwrap();
+        let registry = TokenRegistry::load_from(&path).unwrap();
+        assert_eq!(registry.mainnet["RIF"].address, RIF);
+        assert_eq!(registry.mainnet["RIF"].decimals, 18);
+    }
+
+    #[test]
+    fn test_find_by_address_ignores_case_and_other_networks() {
+        let mut registry = TokenRegistry::default();
+        registry.add_token("mainnet", "rif", RIF, 18).unwrap();
+        let rif = Address::from_str(RIF).unwrap();
+
+        let (symbol, info) = registry.find_by_address("RSK Mainnet", &rif).unwrap();
+        assert_eq!(symbol, "RIF");
+        assert_eq!(info.decimals, 18);
+        assert!(registry.find_by_address("testnet", &rif).is_none());
+        assert!(registry.find_by_address("mainnet", &Address::ZERO).is_none());
+    }
+
+    #[test]
+    fn test_legacy_registry_is_imported_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let (path, legacy) = (
+            dir.path().join("config").join("tokens.json"),
+            dir.path().join("tokens.json"),
+        );
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let mut old = TokenRegistry::default();
+        old.add_token("testnet", "TRIF", RIF, 18).unwrap();
+        fs::write(&legacy, serde_json::to_string(&old).unwrap()).unwrap();
+
+        assert!(TokenRegistry::import_legacy(&path, &legacy));
+        assert!(TokenRegistry::load_from(&path).unwrap().testnet.contains_key("TRIF"));
+        assert!(legacy.exists());
+
+        fs::write(&legacy, r#"{"mainnet":{},"testnet":{}}"#).unwrap();
+        assert!(!TokenRegistry::import_legacy(&path, &legacy));
+        assert!(TokenRegistry::load_from(&path).unwrap().testnet.contains_key("TRIF"));
+    }
+}
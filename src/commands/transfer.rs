@@ -1,8 +1,18 @@
 use crate::config::ConfigManager;
+use crate::commands::tokens;
+use crate::types::network::Network;
+use crate::types::tx_label::TxLabels;
 use crate::types::wallet::WalletData;
+use crate::utils::access_list::AccessListSource;
+use crate::utils::address::{other_network_hint, parse_address};
 use crate::utils::constants;
 use crate::utils::eth::EthClient;
 use crate::utils::helper::Config as HelperConfig;
+use crate::utils::network_guard;
+use crate::utils::retry::{self, RetryPolicy};
+use crate::utils::units::{
+    Denomination, format_amount, from_wei, parse_amount, parse_base_units, shortfall, to_wei,
+};
 use anyhow::{Result, anyhow};
 use clap::Parser;
 use colored::Colorize;
@@ -11,6 +21,7 @@ use alloy::signers::local::PrivateKeySigner;
 use rpassword::prompt_password;
 use std::fs;
 use std::str::FromStr;
+use std::time::Duration;
 
 /// Result of a transfer operation
 #[derive(Debug)]
@@ -33,12 +44,85 @@ pub struct TransferCommand {
     pub address: String,
 
     /// Amount to send (in tokens or RBTC)
-    #[arg(long, required = true)]
-    pub value: f64,
+    #[arg(long, required_unless_present = "wei", conflicts_with = "wei")]
+    pub value: Option<String>,
+
+    /// Exact amount in wei / token base units (no decimal conversion)
+    #[arg(long)]
+    pub wei: Option<String>,
 
     /// Token address (for ERC20 transfers)
     #[arg(long)]
     pub token: Option<String>,
+
+    /// Gas price to send at, in gwei, instead of the node's current price
+    #[arg(long, conflicts_with_all = ["max_gas_price", "sponsored"])]
+    pub gas_price: Option<String>,
+
+    /// Highest gas price to send at, in gwei
+    #[arg(long)]
+    pub max_gas_price: Option<String>,
+
+    /// Wait for the gas price to drop to --max-gas-price instead of failing
+    #[arg(long, requires = "max_gas_price")]
+    pub wait_for_gas: bool,
+
+    /// How long --wait-for-gas waits before giving up, in minutes
+    #[arg(long, default_value_t = 60)]
+    pub gas_timeout: u64,
+
+    /// Have the configured relayer pay the gas (token transfers only)
+    #[arg(long)]
+    pub sponsored: bool,
+
+    /// Sign with this wallet instead of the active one (the active wallet is not changed)
+    #[arg(long)]
+    pub from: Option<String>,
+
+    /// EIP-2930 access list: 'auto' (eth_createAccessList) or a JSON file
+    #[arg(long, conflicts_with = "sponsored")]
+    pub access_list: Option<AccessListSource>,
+
+    /// Note on the purpose of the payment, kept locally with the transaction's label
+    #[arg(long)]
+    pub note: Option<String>,
+
+    /// Nonce to send with instead of the next free one (e.g. to replace a stuck transfer)
+    #[arg(long, conflicts_with = "sponsored")]
+    pub nonce: Option<u64>,
+
+    /// Blocks (including its own) the transfer must be buried under before it is reported
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    pub confirmations: u64,
+
+    /// How long to wait for --confirmations, in minutes
+    #[arg(long, default_value_t = 10)]
+    pub confirmation_timeout: u64,
+
+    /// Build, price and estimate the transfer without broadcasting it
+    #[arg(long, conflicts_with = "sponsored")]
+    pub dry_run: bool,
+
+    /// Send a mainnet transfer above the large-transfer threshold without asking
+    #[arg(long, visible_alias = "yes")]
+    pub confirm_mainnet: bool,
+}
+
+/// Refuses a mainnet RBTC transfer above the large-transfer threshold unless it was
+/// acknowledged with --confirm-mainnet; there is nobody to type MAINNET headless
+fn check_mainnet_confirmation(
+    network: Network,
+    rbtc_amount: U256,
+    threshold: Option<U256>,
+    confirmed: bool,
+) -> Result<()> {
+    if confirmed || !network_guard::requires_acknowledgement(network, rbtc_amount, threshold) {
+        return Ok(());
+    }
+    Err(anyhow!(
+        "This sends {} RBTC on mainnet, above the large-transfer threshold; pass --confirm-mainnet to send it",
+        format_amount(rbtc_amount, 18)
+    ))
 }
 
 impl TransferCommand {
@@ -53,21 +137,34 @@ impl TransferCommand {
         }
         let data = fs::read_to_string(&wallet_file)?;
         let wallet_data: WalletData = serde_json::from_str(&data)?;
-        let default_wallet = wallet_data.get_current_wallet().ok_or_else(|| {
-            anyhow!(
-                "No default wallet selected. Please use 'wallet switch' to select a default wallet."
-            )
-        })?;
+        let default_wallet = match &self.from {
+            Some(name) => wallet_data
+                .get_wallet_by_name(name)
+                .ok_or_else(|| anyhow!("Wallet '{}' not found", name))?,
+            None => wallet_data.get_current_wallet().ok_or_else(|| {
+                anyhow!(
+                    "No default wallet selected. Please use 'wallet switch' to select a default wallet."
+                )
+            })?,
+        };
+
+        // Get the network from config
+        let config = ConfigManager::new()?.load()?;
+
+        // Parse recipient address (an EIP-3770 prefix must match the active network);
+        // a mistyped one is rejected before the password is asked for
+        let to = parse_address(&self.address, config.default_network)
+            .map_err(|e| anyhow!("Invalid recipient address {}: {}", &self.address, e))?;
 
         // Prompt for password and decrypt private key
-        let password = prompt_password("Enter password for the default wallet: ")?;
+        let password = match &self.from {
+            Some(name) => prompt_password(format!("Enter password for wallet '{}': ", name))?,
+            None => prompt_password("Enter password for the default wallet: ")?,
+        };
         let private_key = default_wallet.decrypt_private_key(&password)?;
         let _local_wallet = PrivateKeySigner::from_str(&private_key)
             .map_err(|e| anyhow!("Failed to create PrivateKeySigner: {}", e))?;
 
-        // Get the network from config
-        let config = ConfigManager::new()?.load()?;
-
         // Create a new helper config with the private key
         let client_config = HelperConfig {
             network: config.default_network.get_config(),
@@ -78,94 +175,267 @@ impl TransferCommand {
             },
         };
 
-        let eth_client = EthClient::new(&client_config, None).await?;
-
-        // Parse recipient address
-        let to = Address::from_str(&self.address)
-            .map_err(|_| anyhow!("Invalid recipient address: {}", &self.address))?;
+        let eth_client = EthClient::new(&client_config).await?;
+        let eth_client = match &self.access_list {
+            Some(source) => eth_client.with_access_list(source.clone()),
+            None => eth_client,
+        };
+        let eth_client = match &self.gas_price {
+            Some(gas_price) => eth_client.with_gas_price(
+                to_wei(gas_price, Denomination::Gwei)?
+                    .try_into()
+                    .map_err(|_| anyhow!("Gas price '{}' is too large", gas_price))?,
+            ),
+            None => eth_client,
+        };
+        let eth_client = match self.nonce {
+            Some(nonce) => eth_client.with_nonce(nonce),
+            None => eth_client,
+        };
 
         // Parse optional token address
-        let (token_address, token_symbol) = if let Some(token_addr) = &self.token {
+        let (token_address, token_symbol, decimals) = if let Some(token_addr) = &self.token {
             // Handle RBTC case (zero address or None)
             if token_addr == "0x0000000000000000000000000000000000000000" || token_addr.is_empty() {
-                (None, Some("RBTC".to_string()))
+                (None, Some("RBTC".to_string()), 18)
             } else {
                 // Parse token address
                 let addr = Address::from_str(token_addr)
                     .map_err(|_| anyhow!("Invalid token address: {}", token_addr))?;
 
-                // Try to get token info, but don't fail if we can't
-                let symbol = match eth_client.get_token_info(addr).await {
-                    Ok((_, sym)) => sym,
-                    Err(_) => format!("Token (0x{})", &token_addr[2..10]),
+                // Decimals are required to convert the amount; the symbol is best-effort
+                // With --wei the amount is already in base units, so it is shown as such
+                let (decimals, symbol) = match tokens::token_info(&eth_client, addr).await {
+                    Ok(info) => info,
+                    Err(_) if self.wei.is_some() => {
+                        (0, format!("base units of token 0x{}", &token_addr[2..10]))
+                    }
+                    Err(e) => return Err(anyhow!("Failed to read token decimals: {}", e)),
                 };
 
-                (Some(addr), Some(symbol))
+                (Some(addr), Some(symbol), decimals)
             }
         } else {
             // Native RBTC transfer
-            (None, Some("RBTC".to_string()))
+            (None, Some("RBTC".to_string()), 18)
         };
 
-        // Parse amount (convert f64 to wei or token units)
-        // Both RBTC and tokens use 18 decimals
-        let decimals = 18;
-        let amount = alloy::primitives::utils::parse_units(&self.value.to_string(), decimals)
-            .map_err(|e| anyhow!("Invalid amount: {}", e))?;
+        // Parse amount straight from the decimal string so no precision is lost
+        let amount: U256 = match (&self.wei, &self.value) {
+            (Some(wei), _) => parse_base_units(wei)?,
+            (None, Some(value)) => parse_amount(value, decimals)?,
+            (None, None) => return Err(anyhow!("Either --value or --wei is required")),
+        };
 
-        // Send transaction
-        let tx_hash = eth_client
-            .send_transaction(to, amount.into(), token_address)
+        if let Some(hint) = other_network_hint(&config, to).await {
+            eprintln!("⚠️  {}", hint);
+        }
+
+        let banner = network_guard::banner(config.default_network);
+        if config.default_network.is_mainnet() {
+            eprintln!("{}", banner.red().bold());
+        } else {
+            eprintln!("{}", banner.cyan().bold());
+        }
+        if !self.dry_run {
+            let rbtc_amount = if token_address.is_none() { amount } else { U256::ZERO };
+            check_mainnet_confirmation(
+                config.default_network,
+                rbtc_amount,
+                config.large_transfer_threshold_wei(),
+                self.confirm_mainnet,
+            )?;
+        }
+
+        // Compare with the exact balance so the user sees the shortfall instead of a revert
+        let balance = eth_client
+            .get_balance(&default_wallet.address(), &token_address)
             .await?;
+        let symbol = token_symbol.as_deref().unwrap_or("RBTC");
+        if let Some(message) = shortfall(balance, amount, decimals, symbol) {
+            return Err(anyhow!(
+                "Insufficient {} balance: {}. Lower --value (or --wei) to at most the balance",
+                symbol,
+                message
+            ));
+        }
+
+        // Hold the transfer until the gas price is acceptable, then send at that price
+        // with the fee capped at the maximum
+        let eth_client = match &self.max_gas_price {
+            Some(max_gas_price) => {
+                let target: u128 = to_wei(max_gas_price, Denomination::Gwei)?
+                    .try_into()
+                    .map_err(|_| anyhow!("Gas price '{}' is too large", max_gas_price))?;
+                let price = if self.wait_for_gas {
+                    eth_client
+                        .wait_for_gas_price(target, Duration::from_secs(self.gas_timeout * 60))
+                        .await?
+                } else {
+                    let current = eth_client.gas_price().await?;
+                    if current > target {
+                        return Err(anyhow!(
+                            "Gas price {} gwei is above the {} gwei maximum; retry later or use --wait-for-gas",
+                            from_wei(U256::from(current), Denomination::Gwei),
+                            max_gas_price.trim()
+                        ));
+                    }
+                    current
+                };
+                eth_client.with_gas_price(price).with_max_gas_price(target)
+            }
+            None => eth_client,
+        };
+
+        // Wallets without RBTC for gas can have a relayer sponsor token transfers
+        let sponsor = config.sponsor_for(config.default_network);
+        let sponsored = match (&sponsor, token_address) {
+            (Some(_), Some(_)) if self.sponsored => true,
+            (Some(_), Some(_)) if !self.dry_run => {
+                eth_client
+                    .get_balance(&default_wallet.address(), &None)
+                    .await?
+                    .is_zero()
+                    && dialoguer::Confirm::new()
+                        .with_prompt("This wallet has no RBTC for gas. Send through the gas sponsor?")
+                        .default(true)
+                        .interact()?
+            }
+            (None, _) if self.sponsored => {
+                return Err(anyhow!(
+                    "No gas sponsor configured; set 'relayer-url' and 'forwarder-address' with config set"
+                ));
+            }
+            (_, None) if self.sponsored => {
+                return Err(anyhow!("Only token transfers can be sponsored"));
+            }
+            _ => false,
+        };
+
+        if self.dry_run {
+            let prepared = eth_client
+                .prepare_transaction(to, amount, token_address)
+                .await?;
+            println!("{}", "Dry run: nothing will be broadcast".yellow().bold());
+            println!("From:      {}", prepared.from);
+            println!("To:        {}", to);
+            println!(
+                "Amount:    {} {}",
+                format_amount(amount, decimals),
+                token_symbol.as_deref().unwrap_or("RBTC")
+            );
+            if let Some(token) = token_address {
+                println!("Token:     {}", token);
+            }
+            println!("Nonce:     {}", prepared.nonce);
+            println!("Gas limit: {}", prepared.gas_limit);
+            println!(
+                "Gas price: {} gwei",
+                from_wei(U256::from(prepared.gas_price), Denomination::Gwei)
+            );
+            println!(
+                "Max fee:   {} RBTC",
+                from_wei(prepared.max_fee, Denomination::Rbtc)
+            );
+
+            return Ok(TransferResult {
+                tx_hash: B256::ZERO,
+                from: prepared.from,
+                to,
+                value: amount,
+                gas_used: U256::from(prepared.gas_limit),
+                gas_price: U256::from(prepared.gas_price),
+                status: U64::from(0), // never sent, so still pending
+                token_address,
+                token_symbol,
+            });
+        }
+
+        // Send transaction
+        let (tx_hash, nonce) = match (sponsor, token_address) {
+            (Some(sponsor), Some(token)) if sponsored => {
+                let tx_hash = eth_client
+                    .send_sponsored_transfer(&sponsor, to, amount, token)
+                    .await?;
+                (tx_hash, None)
+            }
+            _ => {
+                let prepared = eth_client
+                    .prepare_transaction(to, amount, token_address)
+                    .await?;
+                let nonce = prepared.nonce;
+                (eth_client.send_prepared(prepared).await?, Some(nonce))
+            }
+        };
 
         println!(
-            "{}: Transaction sent: 0x{:x} for {} {}",
+            "{}: Transaction sent: 0x{:x} for {} {}{}",
             "Success".green().bold(),
             tx_hash,
-            self.value,
-            token_symbol.clone().unwrap_or("RBTC".to_string())
+            format_amount(amount, decimals),
+            token_symbol.clone().unwrap_or("RBTC".to_string()),
+            nonce.map(|n| format!(" (nonce {})", n)).unwrap_or_default()
         );
+        if let Some(note) = self.note.as_deref().filter(|n| !n.trim().is_empty()) {
+            match TxLabels::add_note(&tx_hash, note) {
+                Ok(()) => println!("Note: {}", note.trim()),
+                Err(e) => eprintln!("⚠️  Could not save the note: {}", e),
+            }
+        }
 
         println!(
             "\n{}: Transaction submitted. Waiting for confirmation... (This may take a moment)",
             "Info".blue().bold()
         );
 
-        // Try to get receipt with retries
-        let mut retries = 5;
-        let receipt = loop {
-            match eth_client.get_transaction_receipt(tx_hash).await {
-                Ok(receipt) => break receipt,
-                Err(_e) if retries > 0 => {
-                    retries -= 1;
-                    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-                }
-                Err(_e) => {
-                    println!(
-                        "\n{}: Could not get transaction receipt. The transaction has been submitted but is still pending.",
-                        "Warning".yellow().bold()
-                    );
-                    println!(
-                        "You can check the status later with: wallet tx --tx-hash 0x{:x}",
-                        tx_hash
-                    );
-
-                    // Return with minimal receipt info since we couldn't get the full receipt
-                    return Ok(TransferResult {
-                        tx_hash,
-                        from: default_wallet.address(),
-                        to,
-                        value: amount.into(),
-                        gas_used: U256::ZERO,
-                        gas_price: U256::ZERO,
-                        status: U64::from(0), // 0 indicates unknown/pending status
-                        token_address,
-                        token_symbol,
-                    });
-                }
+        // Poll for the receipt, backing off from 1s (about 30s in total)
+        let polling = RetryPolicy::new(6, Duration::from_secs(1));
+        let receipt = match retry::retry_if(
+            &polling,
+            || eth_client.get_transaction_receipt(tx_hash),
+            |_| true,
+        )
+        .await
+        {
+            Ok(receipt) => receipt,
+            Err(_e) => {
+                println!(
+                    "\n{}: Could not get transaction receipt. The transaction has been submitted but is still pending.",
+                    "Warning".yellow().bold()
+                );
+                println!(
+                    "You can check the status later with: wallet tx --tx-hash 0x{:x}",
+                    tx_hash
+                );
+
+                // Return with minimal receipt info since we couldn't get the full receipt
+                return Ok(TransferResult {
+                    tx_hash,
+                    from: default_wallet.address(),
+                    to,
+                    value: amount,
+                    gas_used: U256::ZERO,
+                    gas_price: U256::ZERO,
+                    status: U64::from(0), // 0 indicates unknown/pending status
+                    token_address,
+                    token_symbol,
+                });
             }
         };
 
+        // A single receipt can still be reorged away; wait for deeper burial if asked
+        let receipt = if self.confirmations > 1 && receipt.status() {
+            eth_client
+                .wait_for_confirmations(
+                    tx_hash,
+                    self.confirmations,
+                    Duration::from_secs(self.confirmation_timeout * 60),
+                )
+                .await?
+        } else {
+            receipt
+        };
+
         // If we got here, we have a receipt
         let status = if receipt.status() { U64::from(1) } else { U64::from(0) };
         let status_str = if status == U64::from(1) {
@@ -186,7 +456,7 @@ impl TransferCommand {
             tx_hash,
             from: default_wallet.address(),
             to,
-            value: amount.into(),
+            value: amount,
             gas_used: U256::from(receipt.gas_used),
             gas_price: U256::ZERO, // Gas price not available in receipt
             status,
@@ -195,3 +465,24 @@ impl TransferCommand {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE_RBTC: u128 = 1_000_000_000_000_000_000;
+
+    #[test]
+    fn test_large_mainnet_transfer_needs_confirm_flag() {
+        let threshold = Some(U256::from(ONE_RBTC));
+        let large = U256::from(2 * ONE_RBTC);
+        assert!(check_mainnet_confirmation(Network::Mainnet, large, threshold, false).is_err());
+        assert!(check_mainnet_confirmation(Network::Mainnet, large, threshold, true).is_ok());
+        assert!(
+            check_mainnet_confirmation(Network::Mainnet, U256::from(ONE_RBTC), threshold, false)
+                .is_ok()
+        );
+        assert!(check_mainnet_confirmation(Network::Testnet, large, threshold, false).is_ok());
+        assert!(check_mainnet_confirmation(Network::Mainnet, large, None, false).is_ok());
+    }
+}
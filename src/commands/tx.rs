@@ -1,16 +1,28 @@
-use anyhow::Context;
-use clap::Parser;
+use alloy::primitives::B256;
+use anyhow::{Context, anyhow};
+use clap::{Parser, Subcommand};
 use console::style;
 use serde_json::Value;
+use std::path::PathBuf;
 
-use crate::{api::ApiProvider, config::ConfigManager, types::network::Network};
+use crate::{
+    api::ApiProvider,
+    commands::raw_tx,
+    config::ConfigManager,
+    types::{network::Network, tx_label::TxLabels},
+    utils::units::format_amount,
+};
 
 /// Command to check transaction status
 #[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct TxCommand {
+    #[command(subcommand)]
+    pub action: Option<TxAction>,
+
     /// Transaction hash to check
-    #[arg(short, long)]
-    pub tx_hash: String,
+    #[arg(short, long, required = true)]
+    pub tx_hash: Option<String>,
 
     /// Use testnet
     #[arg(long)]
@@ -21,8 +33,38 @@ pub struct TxCommand {
     pub api_key: Option<String>,
 }
 
+#[derive(Debug, Subcommand)]
+pub enum TxAction {
+    /// Show what a signed transaction does: sender, recipient, value, gas and calldata
+    Decode {
+        /// Signed transaction as 0x-prefixed hex
+        #[arg(long)]
+        raw: String,
+        /// Contract ABI (or build artifact) to decode the calldata with
+        #[arg(long)]
+        abi: Option<PathBuf>,
+    },
+}
+
 impl TxCommand {
     pub async fn execute(&self) -> anyhow::Result<()> {
+        match (&self.action, &self.tx_hash) {
+            (Some(TxAction::Decode { raw, abi }), _) => {
+                let abi = abi
+                    .as_ref()
+                    .map(|path| {
+                        std::fs::read_to_string(path)
+                            .map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))
+                    })
+                    .transpose()?;
+                raw_tx::decode(raw, abi.as_deref())
+            }
+            (None, Some(tx_hash)) => self.show_status(tx_hash).await,
+            (None, None) => Err(anyhow!("Pass --tx-hash or a subcommand")),
+        }
+    }
+
+    async fn show_status(&self, tx_hash: &str) -> anyhow::Result<()> {
         let client = reqwest::Client::new();
         let network = if self.testnet {
             Network::RootStockTestnet
@@ -58,16 +100,32 @@ impl TxCommand {
 
         // Get receipt first as it contains the status
         let receipt = self
-            .get_transaction_receipt(&client, &url, &api_key, &self.tx_hash)
+            .get_transaction_receipt(&client, &url, &api_key, tx_hash)
             .await?;
 
         // Get transaction details for additional info
         let tx_details = self
-            .get_transaction_details(&client, &url, &api_key, &self.tx_hash)
+            .get_transaction_details(&client, &url, &api_key, tx_hash)
             .await?;
 
+        // Confirmations are only meaningful once the transaction is mined
+        let confirmations = match receipt["blockNumber"].as_str().and_then(parse_hex_u64) {
+            Some(tx_block) => self
+                .get_block_number(&client, &url, &api_key)
+                .await
+                .ok()
+                .map(|latest| confirmation_depth(latest, tx_block)),
+            None => Some(0),
+        };
+
         // Display the information
-        self.display_transaction_info(&tx_details, &receipt)?;
+        self.display_transaction_info(
+            tx_hash,
+            &tx_details,
+            &receipt,
+            confirmations,
+            config.safe_confirmations,
+        )?;
 
         Ok(())
     }
@@ -146,7 +204,44 @@ impl TxCommand {
             .context("Invalid transaction details response")
     }
 
-    fn display_transaction_info(&self, tx_details: &Value, receipt: &Value) -> anyhow::Result<()> {
+    async fn get_block_number(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+        api_key: &str,
+    ) -> anyhow::Result<u64> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "eth_blockNumber",
+            "params": []
+        });
+
+        let response = client
+            .post(url)
+            .header("Authorization", format!("Bearer {}", api_key))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Request failed: {}", e))?
+            .json::<Value>()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to parse response: {}", e))?;
+
+        response["result"]
+            .as_str()
+            .and_then(parse_hex_u64)
+            .context("Invalid block number response")
+    }
+
+    fn display_transaction_info(
+        &self,
+        tx_hash: &str,
+        tx_details: &Value,
+        receipt: &Value,
+        confirmations: Option<u64>,
+        safe_confirmations: u64,
+    ) -> anyhow::Result<()> {
         // Extract values with defaults
         let block_number = receipt["blockNumber"]
             .as_str()
@@ -166,9 +261,7 @@ impl TxCommand {
                 // Parse hex string to U256
                 let value_wei =
                     alloy::primitives::U256::from_str_radix(v.trim_start_matches("0x"), 16).ok()?;
-                // Convert wei to RBTC (1e18 wei = 1 RBTC)
-                let value_rbtc = value_wei.to::<u128>() as f64 / 1e18;
-                Some(format!("{:.8} RBTC", value_rbtc))
+                Some(format!("{} RBTC", format_amount(value_wei, 18)))
             })
             .unwrap_or_else(|| "0 RBTC".to_string());
 
@@ -204,7 +297,7 @@ impl TxCommand {
         println!("\n{}\n", style("Transaction Details").bold().underlined());
         println!("{}", "-".repeat(60));
 
-        println!("{}", style(format!("  Hash: {}", self.tx_hash)).dim());
+        println!("{}", style(format!("  Hash: {}", tx_hash)).dim());
         println!("{}", style(format!("  Block: {}", block_number)).dim());
         println!("{}", style(format!("  From: {}", from)).dim());
         println!("{}", style(format!("  To: {}", to)).dim());
@@ -214,6 +307,16 @@ impl TxCommand {
         // println!("{}", style(format!("  Gas Price: {}", gas_price)).dim());
         // println!("{}", style(format!("  Gas Used: {}", gas_used)).dim());
         println!("\n{}", style(format!("  Status: {}", status)).dim());
+        if let Some(label) = tx_hash
+            .parse::<B256>()
+            .ok()
+            .and_then(|hash| TxLabels::load().get(&hash).cloned())
+        {
+            println!("{}", style(format!("  Note: {}", label.summary())).dim());
+        }
+        if let Some(confirmations) = confirmations {
+            print_confirmations(confirmations, safe_confirmations);
+        }
 
         // If there's a contract address, show it
         if let Some(contract_addr) = receipt["contractAddress"].as_str()
@@ -245,12 +348,12 @@ impl TxCommand {
         let explorer_url = if self.testnet {
             format!(
                 "https://explorer.testnet.rsk.co/tx/{}",
-                self.tx_hash.trim_start_matches("0x")
+                tx_hash.trim_start_matches("0x")
             )
         } else {
             format!(
                 "https://explorer.rsk.co/tx/{}",
-                self.tx_hash.trim_start_matches("0x")
+                tx_hash.trim_start_matches("0x")
             )
         };
 
@@ -268,3 +371,57 @@ impl TxCommand {
         Ok(())
     }
 }
+
+fn parse_hex_u64(hex: &str) -> Option<u64> {
+    u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok()
+}
+
+/// Blocks on top of (and including) the one holding the transaction
+fn confirmation_depth(latest_block: u64, tx_block: u64) -> u64 {
+    if latest_block < tx_block {
+        // The node serving the block number lags behind the one that had the receipt
+        1
+    } else {
+        latest_block - tx_block + 1
+    }
+}
+
+/// Red while unmined, yellow until `safe` confirmations, green afterwards
+fn print_confirmations(confirmations: u64, safe: u64) {
+    let plural = if confirmations == 1 { "" } else { "s" };
+    let text = format!("{} confirmation{}", confirmations, plural);
+    let text = if confirmations < 1 {
+        style(text).red().bold()
+    } else if confirmations < safe {
+        style(format!("{} (settled at {})", text, safe)).yellow().bold()
+    } else {
+        style(text).green().bold()
+    };
+    println!("  Confirmations: {}", text);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::Commands;
+
+    #[test]
+    fn test_decode_and_status_parse_from_the_command_line() {
+        let command = Commands::try_parse_from(["rsk", "tx", "decode", "--raw", "0x02"]).unwrap();
+        assert!(matches!(
+            command,
+            Commands::Tx(TxCommand { action: Some(TxAction::Decode { ref raw, abi: None }), .. })
+                if raw == "0x02"
+        ));
+
+        let command = Commands::try_parse_from(["rsk", "tx", "--tx-hash", "0xabc"]).unwrap();
+        assert!(matches!(
+            command,
+            Commands::Tx(TxCommand { action: None, tx_hash: Some(ref hash), .. }) if hash == "0xabc"
+        ));
+
+        // A status check still needs the hash
+        assert!(Commands::try_parse_from(["rsk", "tx"]).is_err());
+    }
+}
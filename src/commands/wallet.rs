@@ -1,10 +1,26 @@
-use crate::types::wallet::{Wallet, WalletData};
-use crate::utils::{constants, helper::Config, table::TableBuilder};
+use crate::api::ApiProvider;
+use crate::config::{ConfigManager, ReadBlockTag};
+use crate::types::wallet::{WALLET_COLORS, Wallet, WalletData};
+use crate::utils::alchemy::AlchemyClient;
+use crate::utils::eth::EthClient;
+use crate::utils::message::{recover_signer, sign_message};
+use crate::utils::password::{PASSWORD_POLICY, check_password_strength};
+use crate::utils::qr::{display_address_qr, generate_qr_png};
+use crate::utils::receive_log;
+use crate::utils::vcard::address_vcard;
+use crate::utils::{
+    constants, hd, helper::Config, keystore, table::TableBuilder, units::format_amount,
+};
+use alloy::consensus::Transaction as _;
+use alloy::primitives::{Address, B256};
+use alloy::providers::{Provider, RootProvider};
+use alloy::transports::http::{Client, Http};
 use anyhow::{Result, anyhow};
 use clap::Parser;
 use colored::Colorize;
 use alloy::signers::local::PrivateKeySigner;
 
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
@@ -20,11 +36,70 @@ pub enum WalletAction {
     Create {
         name: String,
         password: String,
+        /// Accept a password that fails the strength policy
+        #[arg(long)]
+        allow_weak: bool,
+    },
+    /// Create an HD wallet from a new BIP-39 seed phrase (account 0 on m/44'/137'/0'/0)
+    CreateHd {
+        name: String,
+        password: String,
+        /// Seed phrase length: 12, 15, 18, 21 or 24 words
+        #[arg(long, default_value_t = 12)]
+        word_count: usize,
+        /// Accept a password that fails the strength policy
+        #[arg(long)]
+        allow_weak: bool,
+    },
+    /// Add another account derived from an HD wallet's seed phrase
+    DeriveAccount {
+        /// HD wallet to derive from
+        name: String,
+        /// Account index on m/44'/137'/0'/0
+        index: u32,
+        password: String,
+        /// Name of the new wallet (defaults to <name>-<index>)
+        #[arg(long)]
+        new_name: Option<String>,
     },
     Import {
         private_key: String,
         name: String,
         password: String,
+        /// Don't look up the imported address on-chain (offline imports)
+        #[arg(long)]
+        skip_check: bool,
+        /// Accept a password that fails the strength policy
+        #[arg(long)]
+        allow_weak: bool,
+    },
+    /// Import an HD wallet from a BIP-39 seed phrase, as `create-hd` makes
+    ImportMnemonic {
+        mnemonic: String,
+        /// Account index on the m/44'/137'/0'/0 path
+        #[arg(long, default_value_t = 0)]
+        index: u32,
+        name: String,
+        password: String,
+        /// Don't look up the imported address on-chain (offline imports)
+        #[arg(long)]
+        skip_check: bool,
+        /// Accept a password that fails the strength policy
+        #[arg(long)]
+        allow_weak: bool,
+    },
+    /// Import a wallet from a Web3 Secret Storage (keystore) JSON file
+    ImportKeystore {
+        path: PathBuf,
+        keystore_password: String,
+        name: String,
+        password: String,
+        /// Don't look up the imported address on-chain (offline imports)
+        #[arg(long)]
+        skip_check: bool,
+        /// Accept a password that fails the strength policy
+        #[arg(long)]
+        allow_weak: bool,
     },
     List,
     Switch {
@@ -38,25 +113,149 @@ pub enum WalletAction {
         name: String,
         path: PathBuf,
     },
+    /// Export a wallet as a Web3 Secret Storage (v3 keystore) file other wallets can import
+    ExportKeystore {
+        name: String,
+        path: PathBuf,
+        /// Password of the wallet in this app
+        password: String,
+        /// Password to encrypt the keystore with (defaults to the wallet password)
+        #[arg(long)]
+        keystore_password: Option<String>,
+    },
     Delete {
         name: String,
     },
+    /// Attach a label, color and note to a wallet; pass an empty value to remove one
+    SetLabel {
+        name: String,
+        #[arg(long)]
+        label: Option<String>,
+        /// One of: red, green, yellow, blue, magenta, cyan
+        #[arg(long)]
+        color: Option<String>,
+        #[arg(long)]
+        note: Option<String>,
+        /// Remove the label, color and note
+        #[arg(long, conflicts_with_all = ["label", "color", "note"])]
+        clear: bool,
+    },
+    /// List outgoing transactions by nonce and highlight gaps or stuck nonces
+    NonceReport {
+        #[arg(long)]
+        name: String,
+        /// Maximum number of outgoing transfers to fetch from history
+        #[arg(long, default_value_t = 100)]
+        limit: u32,
+        /// Read the confirmed nonce at this block (latest, safe or finalized)
+        #[arg(long)]
+        block_tag: Option<ReadBlockTag>,
+    },
+    /// Export a wallet's receive address as a vCard (.vcf) plus a QR code image
+    ExportVcard {
+        /// Wallet to export (defaults to the active wallet)
+        #[arg(long)]
+        name: Option<String>,
+        /// Where to write the .vcf (defaults to <wallet name>.vcf); the QR code is saved next to it
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Show a wallet's address as a QR code in the terminal
+    Qr {
+        /// Wallet to show (defaults to the active wallet)
+        #[arg(long)]
+        name: Option<String>,
+        /// Also save the QR code as a PNG image
+        #[arg(long)]
+        png: Option<PathBuf>,
+    },
+    /// Sign a message with a wallet's key (EIP-191 personal_sign); works offline
+    SignMessage {
+        name: String,
+        message: String,
+        password: String,
+    },
+    /// Check that a personal_sign signature over a message was made by an address
+    VerifyMessage {
+        address: String,
+        message: String,
+        signature: String,
+    },
 }
 
 impl WalletCommand {
     pub async fn execute(&self) -> Result<()> {
         let config = Config::default(); // Use default config
         match &self.action {
-            WalletAction::Create { name, password } => {
+            WalletAction::Create {
+                name,
+                password,
+                allow_weak,
+            } => {
+                ensure_password_policy(password, *allow_weak)?;
                 self.create_wallet(&config, name, password).await?
             }
+            WalletAction::CreateHd {
+                name,
+                password,
+                word_count,
+                allow_weak,
+            } => {
+                ensure_password_policy(password, *allow_weak)?;
+                self.create_hd_wallet(name, password, *word_count)?
+            }
+            WalletAction::DeriveAccount {
+                name,
+                index,
+                password,
+                new_name,
+            } => self.derive_account(name, *index, password, new_name.as_deref())?,
             WalletAction::Import {
                 private_key,
                 name,
                 password,
+                skip_check,
+                allow_weak,
+            } => {
+                ensure_password_policy(password, *allow_weak)?;
+                let address = self
+                    .import_wallet(&config, private_key, name, password)
+                    .await?;
+                if !skip_check {
+                    report_on_chain_activity(address).await;
+                }
+            }
+            WalletAction::ImportMnemonic {
+                mnemonic,
+                index,
+                name,
+                password,
+                skip_check,
+                allow_weak,
             } => {
-                self.import_wallet(&config, private_key, name, password)
-                    .await?
+                ensure_password_policy(password, *allow_weak)?;
+                let address = self
+                    .import_mnemonic(&config, mnemonic, *index, name, password)
+                    .await?;
+                if !skip_check {
+                    report_on_chain_activity(address).await;
+                }
+            }
+            WalletAction::ImportKeystore {
+                path,
+                keystore_password,
+                name,
+                password,
+                skip_check,
+                allow_weak,
+            } => {
+                ensure_password_policy(password, *allow_weak)?;
+                let address = self
+                    .import_keystore(&config, path, keystore_password, name, password)
+                    .await?;
+                if !skip_check {
+                    report_on_chain_activity(address).await;
+                }
             }
             WalletAction::List => self.list_wallets(&config)?,
             WalletAction::Switch { name } => self.switch_wallet(name)?,
@@ -64,7 +263,39 @@ impl WalletCommand {
                 self.rename_wallet(&config, old_name, new_name)?
             }
             WalletAction::Backup { name, path } => self.backup_wallet(&config, name, path)?,
+            WalletAction::ExportKeystore {
+                name,
+                path,
+                password,
+                keystore_password,
+            } => self.export_keystore(name, path, password, keystore_password.as_deref())?,
             WalletAction::Delete { name } => self.delete_wallet(&config, name)?,
+            WalletAction::SetLabel {
+                name,
+                label,
+                color,
+                note,
+                clear,
+            } => self.set_label(name, label.as_deref(), color.as_deref(), note.as_deref(), *clear)?,
+            WalletAction::NonceReport {
+                name,
+                limit,
+                block_tag,
+            } => self.nonce_report(name, *limit, *block_tag).await?,
+            WalletAction::ExportVcard { name, output } => {
+                self.export_vcard(name.as_deref(), output.as_deref())?
+            }
+            WalletAction::Qr { name, png } => self.show_qr(name.as_deref(), png.as_deref())?,
+            WalletAction::SignMessage {
+                name,
+                message,
+                password,
+            } => self.sign_with_wallet(name, message, password)?,
+            WalletAction::VerifyMessage {
+                address,
+                message,
+                signature,
+            } => verify_message(address, message, signature)?,
         }
         Ok(())
     }
@@ -80,41 +311,137 @@ impl WalletCommand {
         }
         let wallet = PrivateKeySigner::random();
         let wallet = Wallet::new(wallet, name, password)?;
-        let mut wallet_data = if wallet_file.exists() {
-            let data = fs::read_to_string(&wallet_file)?;
-            serde_json::from_str::<WalletData>(&data)?
-        } else {
-            WalletData::new()
-        };
-        let _ = wallet_data.add_wallet(wallet.clone());
-        fs::write(&wallet_file, serde_json::to_string_pretty(&wallet_data)?)?;
+        WalletData::update(|wallet_data| {
+            // Another instance may have taken the name while the key was being encrypted
+            if wallet_data.get_wallet_by_name(name).is_some() {
+                return Err(anyhow!("Wallet with name '{}' already exists", name));
+            }
+            wallet_data.add_wallet(wallet.clone())
+        })?;
         println!("{}", "🎉 Wallet created successfully".green());
         println!("Address: {:?}", wallet.address());
         println!("Wallet saved at: {}", wallet_file.display());
         Ok(())
     }
 
+    /// Generates a seed phrase, shows it once and saves account 0 as a new wallet
+    fn create_hd_wallet(&self, name: &str, password: &str, word_count: usize) -> Result<()> {
+        let mnemonic = hd::generate_mnemonic(word_count)?;
+        let wallet = Wallet::new_hd(&mnemonic, 0, name, password)?;
+        WalletData::update(|wallet_data| {
+            if wallet_data.get_wallet_by_name(name).is_some() {
+                return Err(anyhow!("Wallet with name '{}' already exists", name));
+            }
+            wallet_data.add_wallet(wallet.clone())
+        })?;
+
+        println!("{}", "🎉 HD wallet created successfully".green());
+        println!("Address: {:?} ({})", wallet.address(), hd::derivation_path(0));
+        println!(
+            "\n{}",
+            "Write down this seed phrase and keep it offline. It is shown only once and \
+             restores every account of this wallet:"
+                .yellow()
+                .bold()
+        );
+        for (i, word) in mnemonic.split_whitespace().enumerate() {
+            println!("  {:>2}. {}", i + 1, word);
+        }
+        Ok(())
+    }
+
+    /// Saves account `index` of HD wallet `name`'s seed phrase as a new wallet
+    fn derive_account(
+        &self,
+        name: &str,
+        index: u32,
+        password: &str,
+        new_name: Option<&str>,
+    ) -> Result<()> {
+        let wallet_file = constants::wallet_file_path();
+        if !wallet_file.exists() {
+            return Err(anyhow!("No wallets found"));
+        }
+        let wallet_data: WalletData = serde_json::from_str(&fs::read_to_string(&wallet_file)?)?;
+        let source = wallet_data
+            .get_wallet_by_name(name)
+            .ok_or_else(|| anyhow!("Wallet '{}' not found", name))?;
+        let mnemonic = source.decrypt_mnemonic(password)?;
+        let new_name = new_name.map_or_else(|| format!("{}-{}", name, index), str::to_string);
+
+        let mut wallet = Wallet::new(hd::derive_account(&mnemonic, index)?, &new_name, password)?;
+        // Same seed phrase as the source wallet, so a backup of either restores both
+        wallet.hd = source.hd.clone().map(|mut seed| {
+            seed.index = index;
+            seed
+        });
+        wallet.backed_up = source.backed_up;
+        WalletData::update(|wallet_data| {
+            if wallet_data.get_wallet_by_name(&new_name).is_some() {
+                return Err(anyhow!("Wallet with name '{}' already exists", new_name));
+            }
+            wallet_data.add_wallet(wallet.clone())
+        })?;
+        println!(
+            "{} {} ({})",
+            "✅ Derived account".green(),
+            new_name,
+            hd::derivation_path(index)
+        );
+        println!("Address: {:?}", wallet.address());
+        Ok(())
+    }
+
     async fn import_wallet(
         &self,
         _config: &Config,
         private_key: &str,
         name: &str,
         password: &str,
-    ) -> Result<()> {
-        let wallet = PrivateKeySigner::from_str(private_key)?;
-        let wallet = Wallet::new(wallet, name, password)?;
+    ) -> Result<Address> {
+        let wallet = Wallet::new(PrivateKeySigner::from_str(private_key)?, name, password)?;
+        self.save_imported_wallet(wallet)
+    }
+
+    async fn import_mnemonic(
+        &self,
+        _config: &Config,
+        mnemonic: &str,
+        index: u32,
+        name: &str,
+        password: &str,
+    ) -> Result<Address> {
+        // Kept as an HD wallet so `derive-account` can add more accounts from it
+        let wallet = Wallet::new_hd(mnemonic, index, name, password)?;
+        self.save_imported_wallet(wallet)
+    }
+
+    async fn import_keystore(
+        &self,
+        _config: &Config,
+        path: &Path,
+        keystore_password: &str,
+        name: &str,
+        password: &str,
+    ) -> Result<Address> {
+        let wallet = Wallet::new(keystore::read_keystore(path, keystore_password)?, name, password)?;
+        self.save_imported_wallet(wallet)
+    }
+
+    fn save_imported_wallet(&self, mut wallet: Wallet) -> Result<Address> {
+        // The user already holds the key, seed phrase or keystore it came from
+        wallet.backed_up = true;
         let wallet_file = constants::wallet_file_path();
-        let mut wallet_data = if wallet_file.exists() {
-            let data = fs::read_to_string(&wallet_file)?;
-            serde_json::from_str::<WalletData>(&data)?
-        } else {
-            WalletData::new()
-        };
-        let _ = wallet_data.add_wallet(wallet);
-        fs::write(&wallet_file, serde_json::to_string_pretty(&wallet_data)?)?;
+        WalletData::update(|wallet_data| {
+            if wallet_data.get_wallet_by_name(&wallet.name).is_some() {
+                return Err(anyhow!("Wallet with name '{}' already exists", wallet.name));
+            }
+            wallet_data.add_wallet(wallet.clone())
+        })?;
         println!("{}", "✅ Wallet imported successfully".green());
+        println!("Address: 0x{:x}", wallet.address());
         println!("Wallet saved at: {}", wallet_file.display());
-        Ok(())
+        Ok(wallet.address())
     }
 
     fn list_wallets(&self, _config: &Config) -> Result<()> {
@@ -127,7 +454,7 @@ impl WalletCommand {
         let wallet_data = serde_json::from_str::<WalletData>(&data)?;
         let wallets = wallet_data.list_wallets();
         let mut table = TableBuilder::new();
-        table.add_row(&["Name", "Address", "Created At", "Current"]);
+        table.add_row(&["Name", "Label", "Address", "Created At", "Current"]);
         for wallet in wallets {
             let is_current = if let Some(current) = wallet_data.get_current_wallet() {
                 current.address == wallet.address
@@ -136,6 +463,7 @@ impl WalletCommand {
             };
             table.add_row(&[
                 &wallet.name,
+                &wallet.label_text().unwrap_or_default(),
                 &format!("0x{:x}", wallet.address),
                 &wallet.created_at,
                 if is_current { "✓" } else { "" },
@@ -145,49 +473,332 @@ impl WalletCommand {
         Ok(())
     }
 
-    fn switch_wallet(&self, name: &str) -> Result<()> {
+    fn set_label(
+        &self,
+        name: &str,
+        label: Option<&str>,
+        color: Option<&str>,
+        note: Option<&str>,
+        clear: bool,
+    ) -> Result<()> {
+        if let Some(color) = color.map(str::trim).filter(|c| !c.is_empty())
+            && !WALLET_COLORS.contains(&color.to_lowercase().as_str())
+        {
+            return Err(anyhow!(
+                "Unknown color '{}'. Use one of: {}",
+                color,
+                WALLET_COLORS.join(", ")
+            ));
+        }
+
+        if !constants::wallet_file_path().exists() {
+            return Err(anyhow!("No wallets found"));
+        }
+        let display = WalletData::update(|wallet_data| {
+            let wallet = wallet_data
+                .get_wallet_by_name_mut(name)
+                .ok_or_else(|| anyhow!("Wallet '{}' not found", name))?;
+
+            // An empty value removes that field
+            let normalize = |value: &str| {
+                let value = value.trim();
+                (!value.is_empty()).then(|| value.to_string())
+            };
+            if clear {
+                wallet.label = None;
+                wallet.color = None;
+                wallet.note = None;
+            }
+            if let Some(label) = label {
+                wallet.label = normalize(label);
+            }
+            if let Some(color) = color {
+                wallet.color = normalize(&color.to_lowercase());
+            }
+            if let Some(note) = note {
+                wallet.note = normalize(note);
+            }
+            Ok(wallet.label_display())
+        })?;
+
+        match display {
+            Some(display) => println!("Wallet '{}': {}", name, display),
+            None => println!("Wallet '{}' has no label", name),
+        }
+        Ok(())
+    }
+
+    async fn nonce_report(
+        &self,
+        name: &str,
+        limit: u32,
+        block_tag: Option<ReadBlockTag>,
+    ) -> Result<()> {
         let wallet_file = constants::wallet_file_path();
+        if !wallet_file.exists() {
+            return Err(anyhow!("No wallets found"));
+        }
         let data = fs::read_to_string(&wallet_file)?;
-        let mut wallet_data = serde_json::from_str::<WalletData>(&data)?;
-        let wallet_address = wallet_data
+        let wallet_data = serde_json::from_str::<WalletData>(&data)?;
+        let address = wallet_data
             .get_wallet_by_name(name)
             .ok_or_else(|| anyhow!("Wallet '{}' not found", name))?
             .address;
-        let _ = wallet_data.switch_wallet(&format!("0x{:x}", wallet_address));
-        fs::write(&wallet_file, serde_json::to_string_pretty(&wallet_data)?)?;
+
+        let app_config = ConfigManager::new()?.load()?;
+        let eth_client = EthClient::new(&Config {
+            network: app_config.default_network.get_config(),
+            wallet: Default::default(),
+        })
+        .await?;
+        let eth_client = match block_tag {
+            Some(tag) => eth_client.with_read_tag(tag),
+            None => eth_client,
+        };
+        let provider = eth_client.provider();
+
+        let confirmed_nonce = eth_client.confirmed_nonce(address).await?;
+        let pending_nonce = provider
+            .get_transaction_count(address)
+            .pending()
+            .await
+            .map_err(|e| anyhow!("Failed to get pending nonce: {}", e))?;
+
+        println!("\n{} {} (0x{:x})", "Nonce report for".bold(), name, address);
+        println!(
+            "Confirmed nonce (next to mine): {} (at the '{}' block)",
+            confirmed_nonce,
+            eth_client.read_tag()
+        );
+        println!("Pending nonce (next to use):    {}", pending_nonce);
+
+        // Outgoing transactions come from history; the nonce is read from each transaction
+        let network = app_config.default_network.to_string().to_lowercase();
+        let is_testnet = network.contains("testnet") || network.contains("regtest");
+        let history_network = if is_testnet { "testnet" } else { "mainnet" };
+        let by_nonce = match app_config.get_api_key_for(&ApiProvider::Alchemy, history_network) {
+            Some(api_key) => {
+                let alchemy = AlchemyClient::new(api_key.to_string(), is_testnet);
+                let response = alchemy
+                    .get_asset_transfers(&format!("0x{:x}", address), limit, None, None)
+                    .await?;
+                outgoing_by_nonce(provider, address, &response).await?
+            }
+            None => {
+                println!(
+                    "{}: No Alchemy API key configured, transaction history is unavailable",
+                    "Warning".yellow().bold()
+                );
+                BTreeMap::new()
+            }
+        };
+
+        if !by_nonce.is_empty() {
+            let mut table = TableBuilder::new();
+            table.add_header(&["Nonce", "Tx Hash", "Block"]);
+            for (nonce, (hash, block)) in &by_nonce {
+                table.add_row(&[&nonce.to_string(), &format!("0x{:x}", hash), block]);
+            }
+            table.print();
+        }
+
+        // Gaps only make sense within the range the fetched history covers
+        if let (Some(first), Some(last)) = (by_nonce.keys().next(), by_nonce.keys().last()) {
+            let gaps: Vec<String> = (*first..=*last)
+                .filter(|n| !by_nonce.contains_key(n))
+                .map(|n| n.to_string())
+                .collect();
+            if !gaps.is_empty() {
+                println!(
+                    "{}: Nonces missing from history: {} (contract calls or older transfers may not be listed)",
+                    "Gap".yellow().bold(),
+                    gaps.join(", ")
+                );
+            }
+        }
+
+        if pending_nonce > confirmed_nonce {
+            println!(
+                "{}: {} transaction(s) pending; nonce {} is blocking the queue",
+                "Stuck".red().bold(),
+                pending_nonce - confirmed_nonce,
+                confirmed_nonce
+            );
+            println!("Replace or speed up the transaction with nonce {} to unblock it", confirmed_nonce);
+        } else {
+            println!("{}", "✅ No pending transactions, the nonce sequence is clear".green());
+        }
+
+        Ok(())
+    }
+
+    fn switch_wallet(&self, name: &str) -> Result<()> {
+        if !constants::wallet_file_path().exists() {
+            return Err(anyhow!("No wallets found"));
+        }
+        let wallet_address = WalletData::update(|wallet_data| {
+            let wallet_address = wallet_data
+                .get_wallet_by_name(name)
+                .ok_or_else(|| anyhow!("Wallet '{}' not found", name))?
+                .address;
+            wallet_data.switch_wallet(&format!("0x{:x}", wallet_address))?;
+            Ok(wallet_address)
+        })?;
         println!("{}", format!("✅ Switched to wallet: {}", name).green());
         println!("Address: 0x{:x}", wallet_address);
         Ok(())
     }
 
     fn rename_wallet(&self, _config: &Config, old_name: &str, new_name: &str) -> Result<()> {
+        if !constants::wallet_file_path().exists() {
+            return Err(anyhow!("No wallets found"));
+        }
+        let address = WalletData::update(|wallet_data| {
+            let wallet = wallet_data
+                .get_wallet_by_name(old_name)
+                .ok_or_else(|| anyhow!("Wallet '{}' not found", old_name))?;
+            if new_name.is_empty() {
+                return Err(anyhow!("New wallet name cannot be empty"));
+            }
+            if wallet_data.get_wallet_by_name(new_name).is_some() {
+                return Err(anyhow!("Wallet with name '{}' already exists", new_name));
+            }
+            let address = format!("0x{:x}", wallet.address);
+            if let Some(wallet) = wallet_data.wallets.get_mut(&address) {
+                wallet.name = new_name.to_string();
+            } else {
+                return Err(anyhow!("Failed to rename wallet '{}'", old_name));
+            }
+            Ok(address)
+        })?;
+        println!(
+            "{}",
+            format!("✅ Wallet renamed from '{}' to '{}'", old_name, new_name).green()
+        );
+        println!("Address: {}", address);
+        Ok(())
+    }
+
+    fn sign_with_wallet(&self, name: &str, message: &str, password: &str) -> Result<()> {
         let wallet_file = constants::wallet_file_path();
         if !wallet_file.exists() {
             return Err(anyhow!("No wallets found"));
         }
-        let data = fs::read_to_string(&wallet_file)?;
-        let mut wallet_data = serde_json::from_str::<WalletData>(&data)?;
+        let wallet_data = serde_json::from_str::<WalletData>(&fs::read_to_string(&wallet_file)?)?;
         let wallet = wallet_data
-            .get_wallet_by_name(old_name)
-            .ok_or_else(|| anyhow!("Wallet '{}' not found", old_name))?;
-        if new_name.is_empty() {
-            return Err(anyhow!("New wallet name cannot be empty"));
+            .get_wallet_by_name(name)
+            .ok_or_else(|| anyhow!("Wallet '{}' not found", name))?;
+        let signer = PrivateKeySigner::from_str(&wallet.decrypt_private_key(password)?)
+            .map_err(|e| anyhow!("Invalid private key: {}", e))?;
+
+        let signature = sign_message(&signer, message)?;
+        println!("Address:   0x{:x}", signer.address());
+        println!("Message:   {}", message);
+        println!("Signature: {}", signature);
+        Ok(())
+    }
+
+    fn show_qr(&self, name: Option<&str>, png: Option<&Path>) -> Result<()> {
+        let wallet_file = constants::wallet_file_path();
+        if !wallet_file.exists() {
+            return Err(anyhow!("No wallets found"));
         }
-        if wallet_data.get_wallet_by_name(new_name).is_some() {
-            return Err(anyhow!("Wallet with name '{}' already exists", new_name));
+        let wallet_data = serde_json::from_str::<WalletData>(&fs::read_to_string(&wallet_file)?)?;
+        let wallet = match name {
+            Some(name) => wallet_data
+                .get_wallet_by_name(name)
+                .ok_or_else(|| anyhow!("Wallet '{}' not found", name))?,
+            None => wallet_data
+                .get_current_wallet()
+                .ok_or_else(|| anyhow!("No default wallet selected"))?,
+        };
+
+        let address = wallet.address.to_checksum(None);
+        display_address_qr(&address, wallet.label.as_deref().unwrap_or(&wallet.name))?;
+        if let Some(path) = png {
+            generate_qr_png(&format!("ethereum:{}", address), path)?;
+            println!("{} {}", "✅ QR code saved to".green(), path.display());
         }
-        let address = format!("0x{:x}", wallet.address);
-        if let Some(wallet) = wallet_data.wallets.get_mut(&address) {
-            wallet.name = new_name.to_string();
-        } else {
-            return Err(anyhow!("Failed to rename wallet '{}'", old_name));
+        Ok(())
+    }
+
+    fn export_vcard(&self, name: Option<&str>, output: Option<&Path>) -> Result<()> {
+        let wallet_file = constants::wallet_file_path();
+        if !wallet_file.exists() {
+            return Err(anyhow!("No wallets found"));
         }
-        fs::write(&wallet_file, serde_json::to_string_pretty(&wallet_data)?)?;
-        println!(
-            "{}",
-            format!("✅ Wallet renamed from '{}' to '{}'", old_name, new_name).green()
+        let wallet_data = serde_json::from_str::<WalletData>(&fs::read_to_string(&wallet_file)?)?;
+        let wallet = match name {
+            Some(name) => wallet_data
+                .get_wallet_by_name(name)
+                .ok_or_else(|| anyhow!("Wallet '{}' not found", name))?,
+            None => wallet_data
+                .get_current_wallet()
+                .ok_or_else(|| anyhow!("No default wallet selected"))?,
+        };
+        let config = ConfigManager::new()?.load()?;
+        let network = config.default_network;
+
+        let address = wallet.address.to_checksum(None);
+        let contact_name = wallet.label.as_deref().unwrap_or(&wallet.name);
+        let card = address_vcard(
+            contact_name,
+            &address,
+            &network.to_string(),
+            wallet.note.as_deref(),
         );
-        println!("Address: {}", address);
+
+        let vcf_path = output
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from(format!("{}.vcf", wallet.name)));
+        let qr_path = vcf_path.with_extension("png");
+        fs::write(&vcf_path, card)?;
+        generate_qr_png(&format!("ethereum:{}", address), &qr_path)?;
+
+        display_address_qr(&address, contact_name)?;
+        println!("{} {}", "✅ vCard saved to".green(), vcf_path.display());
+        println!("{} {}", "✅ QR code saved to".green(), qr_path.display());
+        if config.address_reuse_warning {
+            let count = receive_log::count(&network.to_string(), &wallet.address);
+            if let Some(warning) = receive_log::reuse_warning(&wallet.address, count) {
+                println!("{}", format!("🔒 {}", warning).yellow());
+            }
+        }
+        Ok(())
+    }
+
+    fn export_keystore(
+        &self,
+        name: &str,
+        path: &Path,
+        password: &str,
+        keystore_password: Option<&str>,
+    ) -> Result<()> {
+        let wallet_file = constants::wallet_file_path();
+        if !wallet_file.exists() {
+            return Err(anyhow!("No wallets found"));
+        }
+        let wallet_data = serde_json::from_str::<WalletData>(&fs::read_to_string(&wallet_file)?)?;
+        let wallet = wallet_data
+            .get_wallet_by_name(name)
+            .ok_or_else(|| anyhow!("Wallet '{}' not found", name))?;
+        if path.exists() {
+            return Err(anyhow!("{} already exists", path.display()));
+        }
+
+        let private_key = wallet.decrypt_private_key(password)?;
+        let key = hex::decode(private_key.trim_start_matches("0x"))?;
+        keystore::write_keystore(&key, path, keystore_password.unwrap_or(password))?;
+
+        WalletData::update(|wallet_data| {
+            if let Some(wallet) = wallet_data.get_wallet_by_name_mut(name) {
+                wallet.backed_up = true;
+            }
+            Ok(())
+        })?;
+        println!("{}", "✅ Keystore exported".green());
+        println!("Address: 0x{:x}", wallet.address);
+        println!("Keystore saved at: {}", path.display());
         Ok(())
     }
 
@@ -219,28 +830,151 @@ impl WalletCommand {
                 backup_path.display()
             ));
         }
+        WalletData::update(|wallet_data| {
+            if let Some(wallet) = wallet_data.get_wallet_by_name_mut(name) {
+                wallet.backed_up = true;
+            }
+            Ok(())
+        })?;
         println!("{}", "✅ Backup created successfully".green());
         println!("Backup saved at: {}", backup_path.display());
         Ok(())
     }
 
     fn delete_wallet(&self, _config: &Config, name: &str) -> Result<()> {
-        let wallet_file = constants::wallet_file_path();
-        let data = fs::read_to_string(&wallet_file)?;
-        let mut wallet_data = serde_json::from_str::<WalletData>(&data)?;
-        let wallet = wallet_data
-            .get_wallet_by_name(name)
-            .ok_or_else(|| anyhow!("Wallet '{}' not found", name))?;
-        let address = format!("0x{:x}", wallet.address);
-        if wallet_data.current_wallet == address {
-            return Err(anyhow!(
-                "Cannot delete currently selected wallet. Please switch to a different wallet first."
-            ));
+        if !constants::wallet_file_path().exists() {
+            return Err(anyhow!("No wallets found"));
         }
-        let _ = wallet_data.remove_wallet(&address);
-        fs::write(&wallet_file, serde_json::to_string_pretty(&wallet_data)?)?;
+        let (address, was_current) = WalletData::update(|wallet_data| {
+            let wallet = wallet_data
+                .get_wallet_by_name(name)
+                .ok_or_else(|| anyhow!("Wallet '{}' not found", name))?;
+            let address = format!("0x{:x}", wallet.address);
+            let was_current = wallet_data.current_wallet == address;
+            wallet_data.remove_wallet(&address)?;
+            Ok((address, was_current && !wallet_data.wallets.is_empty()))
+        })?;
         println!("{}", format!("✅ Deleted wallet: {}", name).green());
         println!("Address: {}", address);
+        if was_current {
+            println!("It was the active wallet; choose another with `wallet switch <name>`");
+        }
         Ok(())
     }
 }
+
+/// Looks up the nonce of every outgoing transfer in an `alchemy_getAssetTransfers` response
+async fn outgoing_by_nonce(
+    provider: &RootProvider<Http<Client>>,
+    from: Address,
+    response: &serde_json::Value,
+) -> Result<BTreeMap<u64, (B256, String)>> {
+    let transfers = response["result"]["transfers"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+
+    let mut seen = HashSet::new();
+    let mut by_nonce = BTreeMap::new();
+    for transfer in transfers {
+        let Some(hash) = transfer["hash"].as_str().and_then(|h| B256::from_str(h).ok()) else {
+            continue;
+        };
+        // A token transfer and its native counterpart share the same hash
+        if !seen.insert(hash) {
+            continue;
+        }
+        let tx = provider
+            .get_transaction_by_hash(hash)
+            .await
+            .map_err(|e| anyhow!("Failed to get transaction {}: {}", hash, e))?;
+        if let Some(tx) = tx
+            && tx.from == from
+        {
+            let block = transfer["blockNum"]
+                .as_str()
+                .and_then(|b| u64::from_str_radix(b.trim_start_matches("0x"), 16).ok())
+                .map(|b| b.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            by_nonce.insert(tx.nonce(), (hash, block));
+        }
+    }
+    Ok(by_nonce)
+}
+
+/// Recovers the signer of `signature` over `message` and compares it with `address`
+fn verify_message(address: &str, message: &str, signature: &str) -> Result<()> {
+    let expected = Address::from_str(address.trim())
+        .map_err(|_| anyhow!("Invalid address: {}", address))?;
+    let signer = recover_signer(message, signature)?;
+    if signer == expected {
+        println!("{}", format!("✅ Signed by 0x{:x}", signer).green());
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Signature does not match: it was made by 0x{:x}, not 0x{:x}",
+            signer,
+            expected
+        ))
+    }
+}
+
+/// Rejects a new wallet password that fails the strength policy, unless `allow_weak`
+fn ensure_password_policy(password: &str, allow_weak: bool) -> Result<()> {
+    let strength = check_password_strength(password);
+    if strength.is_acceptable() {
+        return Ok(());
+    }
+    if allow_weak {
+        eprintln!("{}", "⚠️  Using a weak password (--allow-weak)".yellow());
+        return Ok(());
+    }
+    Err(anyhow!(
+        "Password is {}. {}, or pass --allow-weak to use it anyway",
+        strength,
+        PASSWORD_POLICY
+    ))
+}
+
+/// Shows the balance and transaction count of a freshly imported address so a
+/// wrong key or network is noticed right away. Lookup failures only warn.
+async fn report_on_chain_activity(address: Address) {
+    let lookup = async {
+        let app_config = ConfigManager::new()?.load()?;
+        let eth_client = EthClient::new(&Config {
+            network: app_config.default_network.get_config(),
+            wallet: Default::default(),
+        })
+        .await?;
+        let balance = eth_client.get_balance(&address, &None).await?;
+        let tx_count = eth_client
+            .provider()
+            .get_transaction_count(address)
+            .await
+            .map_err(|e| anyhow!("Failed to get nonce: {}", e))?;
+        Ok::<_, anyhow::Error>((app_config.default_network, balance, tx_count))
+    };
+
+    match lookup.await {
+        Ok((network, balance, tx_count)) => {
+            println!(
+                "On {}, this address has {} RBTC and {} sent transaction(s)",
+                network,
+                format_amount(balance, 18),
+                tx_count
+            );
+            if balance.is_zero() && tx_count == 0 {
+                println!(
+                    "{}",
+                    "⚠️  No balance or activity: if you expected funds, check the key and the selected network"
+                        .yellow()
+                );
+            }
+        }
+        Err(e) => println!(
+            "{}: Could not check the address on-chain: {}",
+            "Warning".yellow().bold(),
+            e
+        ),
+    }
+}
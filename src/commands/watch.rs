@@ -0,0 +1,162 @@
+use crate::config::ConfigManager;
+use crate::utils::address::parse_address;
+use crate::utils::cancel::CancelToken;
+use crate::utils::constants;
+use crate::utils::eth::EthClient;
+use crate::utils::file_lock;
+use crate::utils::helper::Config as HelperConfig;
+use crate::utils::units::{Denomination, format_amount, from_wei, to_wei};
+use alloy::primitives::U256;
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Alert thresholds in wei, keyed by "network:0xaddr"
+type Thresholds = HashMap<String, U256>;
+
+/// Watches an address and reports balance changes as they happen
+#[derive(Parser, Debug)]
+pub struct WatchCommand {
+    /// Address to watch
+    #[arg(long)]
+    pub address: String,
+
+    /// Only alert on changes of at least this many RBTC; saved for the address (0 clears it)
+    #[arg(long)]
+    pub threshold: Option<String>,
+
+    /// Seconds between balance checks
+    #[arg(long, default_value_t = 15)]
+    pub interval: u64,
+
+    /// Also raise a desktop notification for each alert
+    #[arg(long)]
+    pub notify: bool,
+}
+
+impl WatchCommand {
+    pub async fn execute(&self) -> Result<()> {
+        let config = ConfigManager::new()?.load()?;
+        let address = parse_address(&self.address, config.default_network)?;
+        let key = format!(
+            "{}:0x{:x}",
+            config.default_network.to_string().to_lowercase(),
+            address
+        );
+
+        let threshold = match &self.threshold {
+            Some(threshold) => {
+                let threshold = to_wei(threshold, Denomination::Rbtc)?;
+                file_lock::update_json(&constants::watch_thresholds_path(), |saved: &mut Thresholds| {
+                    if threshold.is_zero() {
+                        saved.remove(&key);
+                    } else {
+                        saved.insert(key.clone(), threshold);
+                    }
+                    Ok(())
+                })?;
+                threshold
+            }
+            None => load_threshold(&key),
+        };
+
+        let eth_client = EthClient::new(&HelperConfig {
+            network: config.default_network.get_config(),
+            wallet: Default::default(),
+        })
+        .await?;
+
+        let mut last = eth_client.get_balance(&address, &None).await?;
+        println!(
+            "👀 Watching 0x{:x} on {}: {} RBTC",
+            address,
+            config.default_network,
+            format_amount(last, 18)
+        );
+        if !threshold.is_zero() {
+            println!(
+                "Alerting on changes of at least {} RBTC",
+                from_wei(threshold, Denomination::Rbtc)
+            );
+        }
+
+        let cancel = CancelToken::listen();
+        let interval = Duration::from_secs(self.interval.max(1));
+        loop {
+            let tick = async {
+                tokio::time::sleep(interval).await;
+                Ok(())
+            };
+            if cancel.run(tick).await?.is_none() {
+                break;
+            }
+            let balance = match eth_client.get_balance(&address, &None).await {
+                Ok(balance) => balance,
+                Err(e) => {
+                    eprintln!("{}: {}", "Warning".yellow().bold(), e);
+                    continue;
+                }
+            };
+            if balance == last {
+                continue;
+            }
+
+            let (sign, change) = if balance > last {
+                ("+", balance - last)
+            } else {
+                ("-", last - balance)
+            };
+            last = balance;
+            if change < threshold {
+                continue;
+            }
+
+            let message = format!(
+                "{}{} RBTC on 0x{:x} (balance {} RBTC)",
+                sign,
+                format_amount(change, 18),
+                address,
+                format_amount(balance, 18)
+            );
+            println!(
+                "\x07[{}] {}",
+                chrono::Local::now().format("%H:%M:%S"),
+                if sign == "+" { message.green() } else { message.red() }
+            );
+            if self.notify {
+                desktop_notification(&message);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn load_threshold(key: &str) -> U256 {
+    std::fs::read_to_string(constants::watch_thresholds_path())
+        .ok()
+        .and_then(|content| serde_json::from_str::<Thresholds>(&content).ok())
+        .and_then(|saved| saved.get(key).copied())
+        .unwrap_or(U256::ZERO)
+}
+
+/// Best-effort desktop notification through the platform's own tool
+fn desktop_notification(message: &str) {
+    let result = if cfg!(target_os = "macos") {
+        std::process::Command::new("osascript")
+            .arg("-e")
+            .arg(format!(
+                "display notification \"{}\" with title \"Rootstock Wallet\"",
+                message.replace('"', "'")
+            ))
+            .status()
+    } else {
+        std::process::Command::new("notify-send")
+            .args(["Rootstock Wallet", message])
+            .status()
+    };
+    if result.is_err() {
+        eprintln!("Desktop notifications are not available on this system");
+    }
+}
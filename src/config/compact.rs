@@ -0,0 +1,216 @@
+use anyhow::{Context, Result};
+use console::style;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::settings::adopt_alchemy_key;
+use crate::commands::tokens::TokenRegistry;
+use crate::config::{Config, ConfigManager};
+use crate::types::contacts::Contact;
+use crate::types::wallet::WalletData;
+use crate::utils::{constants, file_lock, table::TableBuilder};
+
+/// What compacting one file did
+struct Outcome {
+    label: &'static str,
+    path: PathBuf,
+    changes: Vec<String>,
+}
+
+/// Re-serializes the wallet, config, contacts and token files through their typed
+/// structs after backing everything up: unknown and legacy fields are dropped, the
+/// formatting is normalized and legacy Alchemy keys are moved into the config.
+pub fn run_compact() -> Result<()> {
+    println!("\n{}", style("🗜️  Compact Data Files").bold().cyan());
+    println!("{}", "=".repeat(40));
+
+    let config_manager = ConfigManager::new()?;
+    let backup = config_manager.backup_all()?;
+    // The token registry lives in the config directory, which the backup covers
+    let tokens_path = TokenRegistry::path();
+    println!("Backup: {}", backup.display());
+
+    let wallet_path = constants::wallet_file_path();
+    let contacts_path = constants::contacts_path();
+    let mut outcomes = Vec::new();
+
+    // Keys from the deprecated api_keys.json first, so they win over older copies
+    let mut migrated: Vec<String> = config_manager
+        .migrate_legacy_api_keys()?
+        .into_iter()
+        .map(|network| format!("moved {} key from api_keys.json", network))
+        .collect();
+
+    // Then `alchemyApiKey` from old configs, read before the migrations above rewrite the file
+    let config_path = config_manager.config_path().to_path_buf();
+    let legacy_config_key = read_value(&config_path)?
+        .and_then(|value| value.get("alchemyApiKey")?.as_str().map(str::to_string));
+    outcomes.push(compact_file::<Config>("Configuration", &config_path, |_, config| {
+        if let Some(key) = &legacy_config_key {
+            for network in ["mainnet", "testnet"] {
+                if adopt_alchemy_key(config, network, key) {
+                    migrated.push(format!("moved legacy Alchemy key for {}", network));
+                }
+            }
+        }
+        std::mem::take(&mut migrated)
+    })?);
+
+    // And last the key older versions stored in the wallet file, which served both networks
+    let wallet_key_networks = config_manager.migrate_wallet_api_key(&wallet_path)?;
+    outcomes.push(compact_file::<WalletData>("Wallets", &wallet_path, |raw, data| {
+        let mut changes: Vec<String> = wallet_key_networks
+            .iter()
+            .map(|network| format!("moved Alchemy key to config.json for {}", network))
+            .collect();
+        // A non-empty key is in the config for both networks by now, so the copy can go
+        let key_stored = raw
+            .get("api_key")
+            .and_then(Value::as_str)
+            .is_some_and(|key| !key.is_empty());
+        #[allow(deprecated)]
+        let removed = key_stored && data.api_key.take().is_some();
+        if removed {
+            changes.push("removed legacy api_key (now in config.json)".to_string());
+        }
+        changes
+    })?);
+    outcomes.push(compact_file::<Vec<Contact>>("Contacts", &contacts_path, |_, _| Vec::new())?);
+    outcomes.push(compact_file::<TokenRegistry>("Token registry", &tokens_path, |_, _| {
+        Vec::new()
+    })?);
+
+    let mut table = TableBuilder::new();
+    table.add_header(&["File", "Path", "Changes"]);
+    for outcome in &outcomes {
+        let changes = if outcome.changes.is_empty() {
+            "unchanged".to_string()
+        } else {
+            outcome.changes.join("; ")
+        };
+        table.add_row(&[outcome.label, &outcome.path.display().to_string(), &changes]);
+    }
+    table.print();
+    Ok(())
+}
+
+fn read_value(path: &Path) -> Result<Option<Value>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(Some(value))
+}
+
+/// Loads `path` as `T`, lets `migrate` adjust it and writes it back pretty-printed,
+/// returning what changed. Missing files are skipped.
+fn compact_file<T: DeserializeOwned + Serialize>(
+    label: &'static str,
+    path: &Path,
+    migrate: impl FnOnce(&Value, &mut T) -> Vec<String>,
+) -> Result<Outcome> {
+    let mut outcome = Outcome {
+        label,
+        path: path.to_path_buf(),
+        changes: Vec::new(),
+    };
+    if !path.exists() {
+        outcome.changes.push("missing, skipped".to_string());
+        return Ok(outcome);
+    }
+
+    file_lock::with_lock(path, || {
+        let original = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let raw: Value = serde_json::from_str(&original)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        let mut typed: T = serde_json::from_value(raw.clone())
+            .with_context(|| format!("{} does not match the expected format", path.display()))?;
+
+        outcome.changes = migrate(&raw, &mut typed);
+        let compacted = serde_json::to_value(&typed)?;
+        let dropped = dropped_fields(&raw, &compacted);
+        if !dropped.is_empty() {
+            outcome.changes.push(format!("dropped {}", dropped.join(", ")));
+        }
+
+        let content = serde_json::to_string_pretty(&typed)?;
+        if content != original {
+            if outcome.changes.is_empty() {
+                outcome.changes.push("reformatted".to_string());
+            }
+            file_lock::write_atomic(path, &content)?;
+        }
+        Ok(())
+    })?;
+    Ok(outcome)
+}
+
+/// Paths of object fields present in `before` but not in `after`
+fn dropped_fields(before: &Value, after: &Value) -> Vec<String> {
+    let mut dropped = Vec::new();
+    collect_dropped("", before, after, &mut dropped);
+    dropped
+}
+
+fn collect_dropped(prefix: &str, before: &Value, after: &Value, dropped: &mut Vec<String>) {
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => {
+            for (key, value) in before {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                match after.get(key) {
+                    Some(kept) => collect_dropped(&path, value, kept, dropped),
+                    None => dropped.push(path),
+                }
+            }
+        }
+        (Value::Array(before), Value::Array(after)) => {
+            for (index, (value, kept)) in before.iter().zip(after).enumerate() {
+                collect_dropped(&format!("{}[{}]", prefix, index), value, kept, dropped);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_dropped_fields_reports_nested_paths() {
+        let before = json!({
+            "current_wallet": "main",
+            "alchemyApiKey": "abc",
+            "wallets": { "main": { "name": "main", "legacy": true } },
+            "contacts": [{ "name": "Bob", "old": 1 }, { "name": "Eve" }]
+        });
+        let after = json!({
+            "current_wallet": "main",
+            "wallets": { "main": { "name": "main" } },
+            "contacts": [{ "name": "Bob" }, { "name": "Eve" }]
+        });
+        assert_eq!(
+            dropped_fields(&before, &after),
+            vec!["alchemyApiKey", "contacts[0].old", "wallets.main.legacy"]
+        );
+    }
+
+    #[test]
+    fn test_dropped_fields_ignores_added_and_changed_values() {
+        let before = json!({ "a": 1, "b": [1, 2] });
+        let after = json!({ "a": "one", "b": [1, 2, 3], "c": null });
+        assert!(dropped_fields(&before, &after).is_empty());
+    }
+}
@@ -1,8 +1,11 @@
 use anyhow::Result;
 use console::style;
+use std::path::{Path, PathBuf};
 
+use crate::api::ApiProvider;
 use crate::config::{Config, ConfigManager};
 use crate::types::network::Network;
+use crate::utils::{constants, table::TableBuilder};
 
 pub fn run_doctor() -> Result<()> {
     println!("\n{}", style("🩺 Running diagnostics...").bold().cyan());
@@ -38,6 +41,7 @@ pub fn run_doctor() -> Result<()> {
     check_api_key(&config, Network::AlchemyTestnet);
     check_api_key(&config, Network::RootStockTestnet);
     check_api_key(&config, Network::Regtest);
+    check_legacy_api_keys(&config_manager, &config);
 
     // Check wallet configuration
     println!("\n{}", style("💼 Wallet Configuration:").bold());
@@ -53,16 +57,114 @@ pub fn run_doctor() -> Result<()> {
     Ok(())
 }
 
-fn check_api_key(config: &Config, network: Network) {
-    let key = match network {
-        Network::Mainnet | Network::AlchemyMainnet | Network::RootStockMainnet => {
-            &config.alchemy_mainnet_key
+/// Lists every file the wallet reads or writes with its size and last
+/// modification time. File contents are never read, so no secrets are printed.
+pub fn run_file_audit() -> Result<()> {
+    println!("\n{}", style("📁 Data Files").bold().cyan());
+    println!("{}", "=".repeat(40));
+
+    let config_manager = ConfigManager::new()?;
+    let config_dir = config_manager
+        .config_path()
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let wallet_file = constants::wallet_file_path();
+    let data_dir = wallet_file.parent().map(Path::to_path_buf).unwrap_or_default();
+
+    let mut files: Vec<(&str, PathBuf)> = vec![
+        ("Wallets", wallet_file.clone()),
+        ("Configuration", config_manager.config_path().to_path_buf()),
+        ("API keys", config_dir.join("api_keys.json")),
+        ("Contacts", data_dir.join("contacts.json")),
+        ("Contacts undo snapshot", data_dir.join("contacts.json.bak")),
+        ("History last-seen blocks", constants::history_state_path()),
+        ("Pending transactions", constants::pending_transactions_path()),
+        ("Bulk transfer journal", constants::bulk_journal_path()),
+        ("Watch alert thresholds", constants::watch_thresholds_path()),
+        ("Transaction labels", constants::tx_labels_path()),
+        ("Unfinished session", constants::session_path()),
+        ("Address activity cache", constants::activity_cache_path()),
+        ("Token metadata cache", constants::token_metadata_path()),
+        ("Received payments log", constants::receive_log_path()),
+        ("Token registry", constants::token_registry_path()),
+    ];
+
+    // Anything else in the app directories (caches, backups) is listed too
+    for dir in [&config_dir, &data_dir] {
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !files.iter().any(|(_, known)| *known == path) {
+                    files.push(("Other", path));
+                }
+            }
         }
+    }
+
+    let mut table = TableBuilder::new();
+    table.add_header(&["Purpose", "Path", "Size", "Modified"]);
+    for (purpose, path) in &files {
+        let (size, modified) = match std::fs::metadata(path) {
+            Ok(meta) => (
+                format!("{} bytes", meta.len()),
+                meta.modified()
+                    .map(|t| {
+                        chrono::DateTime::<chrono::Local>::from(t)
+                            .format("%Y-%m-%d %H:%M:%S")
+                            .to_string()
+                    })
+                    .unwrap_or_else(|_| "-".to_string()),
+            ),
+            Err(_) => ("missing".to_string(), "-".to_string()),
+        };
+        table.add_row(&[purpose, &path.display().to_string(), &size, &modified]);
+    }
+    table.print();
+
+    println!("  Config directory: {}", config_dir.display());
+    println!("  Data directory:   {}", data_dir.display());
+    Ok(())
+}
+
+/// Warns when the deprecated api_keys.json disagrees with config.json
+fn check_legacy_api_keys(config_manager: &ConfigManager, config: &Config) {
+    let legacy = match config_manager.load_legacy_api_keys() {
+        Ok(Some(legacy)) => legacy,
+        Ok(None) => return,
+        Err(e) => {
+            println!("  ⚠️  Could not read legacy API keys file: {}", e);
+            return;
+        }
+    };
+
+    println!(
+        "  ⚠️  Deprecated {} found, keys are now stored in config.json",
+        config_manager.legacy_api_keys_path().display()
+    );
+    for network in ["mainnet", "testnet"] {
+        if let (Some(old), Some(current)) = (
+            legacy.get(network),
+            config.get_api_key_for(&ApiProvider::Alchemy, network),
+        ) && old != current
+        {
+            println!(
+                "  ⚠️  {} Alchemy key in api_keys.json differs from config.json; config.json is used",
+                network
+            );
+        }
+    }
+}
+
+fn check_api_key(config: &Config, network: Network) {
+    let network_str = match network {
+        Network::Mainnet | Network::AlchemyMainnet | Network::RootStockMainnet => "mainnet",
         Network::Testnet
         | Network::AlchemyTestnet
         | Network::RootStockTestnet
-        | Network::Regtest => &config.alchemy_testnet_key,
+        | Network::Regtest => "testnet",
     };
+    let key = config.get_api_key_for(&ApiProvider::Alchemy, network_str);
 
     let status = match key {
         Some(_) => style("✓ Configured").green(),
@@ -1,15 +1,20 @@
-mod config;
+mod compact;
 mod doctor;
+mod settings;
 mod setup;
 
-// Re-export types from the config module
-pub use config::{Config, ConfigManager};
+// Re-export types from the settings module
+pub use settings::{
+    Config, ConfigManager, HealthCheckSettings, HistoryView, ReadBlockTag, SponsorSettings,
+    TxTypePreference,
+};
 
 // Re-export Network from the types module
 pub use crate::types::network::Network;
 
-// Re-export setup and doctor functions
-pub use doctor::run_doctor;
+// Re-export setup, doctor and maintenance functions
+pub use compact::run_compact;
+pub use doctor::{run_doctor, run_file_audit};
 pub use setup::run_setup_wizard;
 
 // API Documentation URLs
@@ -0,0 +1,705 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use alloy::primitives::U256;
+use anyhow::{Context, Result};
+use dirs;
+use serde::{Deserialize, Serialize};
+
+// Re-export the API types for easier access
+pub use crate::api::{ApiConfig, ApiKey, ApiProvider};
+use crate::types::network::Network;
+use crate::utils::file_lock;
+use crate::utils::retry::RetryPolicy;
+use crate::utils::units::{Denomination, to_wei};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Config {
+    pub default_network: Network,
+    #[serde(default)]
+    pub api: ApiConfig,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alchemy_mainnet_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alchemy_testnet_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_wallet: Option<String>,
+    /// Maximum number of fractional digits shown for amounts
+    #[serde(default = "default_display_decimals")]
+    pub display_decimals: u8,
+    /// Group the integer part of amounts with thousands separators (1,234.5)
+    #[serde(default = "default_group_thousands")]
+    pub group_thousands: bool,
+    /// Transaction type override; `auto` probes each network for EIP-1559 support
+    #[serde(default)]
+    pub tx_type: TxTypePreference,
+    /// Token symbol pre-selected in the balance and transfer pickers, keyed by network
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub default_tokens: HashMap<String, String>,
+    /// Requests per second allowed across all Alchemy calls; raise for paid tiers
+    #[serde(default = "default_alchemy_rps")]
+    pub alchemy_requests_per_second: u32,
+    /// Shared token lists merged into `tokens.json` on load; the registry wins on conflicts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_token_files: Vec<PathBuf>,
+    /// Confirmations after which a transaction is shown as settled
+    #[serde(default = "default_safe_confirmations")]
+    pub safe_confirmations: u64,
+    /// Network health check tuning, keyed by network name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub health_check: HashMap<String, HealthCheckSettings>,
+    /// View the interactive history opens with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_view: Option<HistoryView>,
+    /// Block balances and confirmed nonces are read at
+    #[serde(default)]
+    pub read_block_tag: ReadBlockTag,
+    /// Relayer that pays gas for sponsored transfers, keyed by network name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub sponsor: HashMap<String, SponsorSettings>,
+    /// RBTC amount below which the active wallet is warned it may run out of gas,
+    /// keyed by network name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub low_balance_threshold: HashMap<String, String>,
+    /// RBTC amount above which a mainnet transfer must be acknowledged by typing
+    /// the network name; empty disables the prompt
+    #[serde(default = "default_large_transfer_threshold")]
+    pub large_transfer_threshold: String,
+    /// Warn when a wallet address has received several payments (opt-in privacy nudge)
+    #[serde(default)]
+    pub address_reuse_warning: bool,
+    /// Attempts for node requests that fail on the way (timeouts, 5xx gateway replies)
+    #[serde(default = "default_rpc_attempts")]
+    pub rpc_attempts: u32,
+    /// Pause before the first retried node request, in milliseconds; doubled each time
+    #[serde(default = "default_rpc_retry_delay_ms")]
+    pub rpc_retry_delay_ms: u64,
+}
+
+/// Relayer and ERC-2771 forwarder used for gas-sponsored transfers
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SponsorSettings {
+    /// Endpoint the signed meta-transaction is posted to
+    #[serde(default)]
+    pub relayer_url: String,
+    /// Forwarder contract that verifies the signature and relays the call
+    #[serde(default)]
+    pub forwarder: String,
+}
+
+/// How the network health check samples a network
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthCheckSettings {
+    /// Typical seconds between blocks (about 30 on RSK)
+    pub expected_block_secs: u64,
+    /// Number of recent blocks the block time is averaged over
+    pub samples: u64,
+    /// Extra attempts for each failed node request
+    pub retries: u32,
+    /// Other RPC endpoints whose latest block is compared with the active one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub endpoints: Vec<String>,
+}
+
+impl Default for HealthCheckSettings {
+    fn default() -> Self {
+        Self {
+            expected_block_secs: 30,
+            samples: 20,
+            retries: 2,
+            endpoints: Vec::new(),
+        }
+    }
+}
+
+/// Saved history preferences; filters stay per-session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryView {
+    pub sort_by: String,
+    pub sort_order: String,
+    pub detailed: bool,
+    pub limit: u32,
+    pub network: String,
+}
+
+/// Which transaction envelope to use when sending
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TxTypePreference {
+    /// Typed when the network reports a base fee, legacy otherwise
+    #[default]
+    Auto,
+    Legacy,
+    Eip1559,
+}
+
+impl fmt::Display for TxTypePreference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TxTypePreference::Auto => write!(f, "auto"),
+            TxTypePreference::Legacy => write!(f, "legacy"),
+            TxTypePreference::Eip1559 => write!(f, "eip1559"),
+        }
+    }
+}
+
+impl FromStr for TxTypePreference {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(TxTypePreference::Auto),
+            "legacy" => Ok(TxTypePreference::Legacy),
+            "eip1559" | "eip-1559" | "typed" => Ok(TxTypePreference::Eip1559),
+            _ => anyhow::bail!("Invalid transaction type '{}': use auto, legacy or eip1559", s),
+        }
+    }
+}
+
+/// Block that balance and nonce reads are pinned to; `safe` and `finalized`
+/// trade freshness for reads that cannot be reorged away
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReadBlockTag {
+    #[default]
+    Latest,
+    Safe,
+    Finalized,
+}
+
+impl fmt::Display for ReadBlockTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadBlockTag::Latest => write!(f, "latest"),
+            ReadBlockTag::Safe => write!(f, "safe"),
+            ReadBlockTag::Finalized => write!(f, "finalized"),
+        }
+    }
+}
+
+impl FromStr for ReadBlockTag {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "latest" => Ok(ReadBlockTag::Latest),
+            "safe" => Ok(ReadBlockTag::Safe),
+            "finalized" => Ok(ReadBlockTag::Finalized),
+            _ => anyhow::bail!("Invalid block tag '{}': use latest, safe or finalized", s),
+        }
+    }
+}
+
+fn default_display_decimals() -> u8 {
+    crate::utils::units::DEFAULT_DISPLAY_DECIMALS
+}
+
+fn default_group_thousands() -> bool {
+    true
+}
+
+fn default_safe_confirmations() -> u64 {
+    6
+}
+
+fn default_large_transfer_threshold() -> String {
+    "0.1".to_string()
+}
+
+fn default_rpc_attempts() -> u32 {
+    crate::utils::retry::DEFAULT_RPC_ATTEMPTS
+}
+
+fn default_rpc_retry_delay_ms() -> u64 {
+    crate::utils::retry::DEFAULT_RPC_RETRY_DELAY_MS
+}
+
+fn default_alchemy_rps() -> u32 {
+    crate::utils::alchemy::DEFAULT_ALCHEMY_RPS
+}
+
+/// Keys written by the deprecated `api_keys.json` store
+#[derive(Debug, Default, Deserialize)]
+pub struct LegacyApiKeys {
+    pub alchemy_mainnet: Option<String>,
+    pub alchemy_testnet: Option<String>,
+}
+
+impl LegacyApiKeys {
+    pub fn get(&self, network: &str) -> Option<&str> {
+        match network {
+            "mainnet" => self.alchemy_mainnet.as_deref(),
+            "testnet" => self.alchemy_testnet.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl Config {
+    /// Health check settings for `network`, or the defaults
+    pub fn health_check_for(&self, network: Network) -> HealthCheckSettings {
+        self.health_check
+            .get(&network.to_string().to_lowercase())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// How node requests are retried
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy::new(
+            self.rpc_attempts,
+            Duration::from_millis(self.rpc_retry_delay_ms),
+        )
+    }
+
+    /// Relayer settings for `network`, if both the URL and forwarder are set
+    pub fn sponsor_for(&self, network: Network) -> Option<SponsorSettings> {
+        self.sponsor
+            .get(&network.to_string().to_lowercase())
+            .filter(|settings| !settings.relayer_url.is_empty() && !settings.forwarder.is_empty())
+            .cloned()
+    }
+
+    /// Low-balance threshold for `network` in wei, if one is set
+    pub fn low_balance_threshold_for(&self, network: Network) -> Option<U256> {
+        self.low_balance_threshold
+            .get(&network.to_string().to_lowercase())
+            .and_then(|amount| to_wei(amount, Denomination::Rbtc).ok())
+            .filter(|threshold| !threshold.is_zero())
+    }
+
+    /// Large-transfer threshold in wei, if one is set
+    pub fn large_transfer_threshold_wei(&self) -> Option<U256> {
+        Some(self.large_transfer_threshold.trim())
+            .filter(|amount| !amount.is_empty())
+            .and_then(|amount| to_wei(amount, Denomination::Rbtc).ok())
+    }
+
+    /// Get the appropriate API key for the current network and provider
+    pub fn get_api_key(&self, provider: &ApiProvider) -> Option<&str> {
+        let network_str = match self.default_network {
+            Network::Mainnet | Network::AlchemyMainnet | Network::RootStockMainnet => "mainnet",
+            Network::Testnet
+            | Network::AlchemyTestnet
+            | Network::RootStockTestnet
+            | Network::Regtest => "testnet",
+        };
+
+        self.get_api_key_for(provider, network_str)
+    }
+
+    /// Get the API key for a provider on a specific network ("mainnet" or "testnet")
+    pub fn get_api_key_for(&self, provider: &ApiProvider, network_str: &str) -> Option<&str> {
+        // First try to get from the new API config
+        if let Some(key) = self
+            .api
+            .keys
+            .iter()
+            .find(|k| &k.provider == provider && k.network == network_str)
+        {
+            return Some(&key.key);
+        }
+
+        // Fall back to legacy keys for backward compatibility (Alchemy only)
+        match (provider, network_str) {
+            (ApiProvider::Alchemy, "mainnet") => self.alchemy_mainnet_key.as_deref(),
+            (ApiProvider::Alchemy, "testnet") => self.alchemy_testnet_key.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// RPC endpoint that would be used for `network` with the configured API keys,
+    /// with the provider it comes from ("RSK RPC API", "Alchemy API" or "Public Node")
+    pub fn rpc_url_for(&self, network: Network) -> (String, &'static str) {
+        let network_str = if network.chain_id() == 30 { "mainnet" } else { "testnet" };
+        let rsk_key = self.get_api_key_for(&ApiProvider::RskRpc, network_str);
+        let alchemy_key = self.get_api_key_for(&ApiProvider::Alchemy, network_str);
+        let source = match (network, rsk_key, alchemy_key) {
+            (Network::Regtest, _, _) => "Local Node",
+            (_, Some(_), _) => "RSK RPC API",
+            (_, None, Some(_)) => "Alchemy API",
+            _ => "Public Node",
+        };
+        (network.get_rpc_url_with_key(rsk_key, alchemy_key), source)
+    }
+
+    /// Default token symbol for a network ("mainnet" or "testnet")
+    pub fn default_token(&self, network: &str) -> Option<&str> {
+        self.default_tokens
+            .get(&network.to_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Get RSK RPC API key for blockchain operations
+    pub fn get_rsk_rpc_key(&self) -> Option<&str> {
+        self.get_api_key(&ApiProvider::RskRpc)
+    }
+
+    /// Get Alchemy API key for transaction history
+    pub fn get_alchemy_key(&self) -> Option<&str> {
+        self.get_api_key(&ApiProvider::Alchemy)
+    }
+
+    /// Add or update an API key
+    pub fn set_api_key(
+        &mut self,
+        provider: ApiProvider,
+        key: String,
+        name: Option<String>,
+    ) -> String {
+        let network = match self.default_network {
+            Network::Mainnet | Network::AlchemyMainnet | Network::RootStockMainnet => "mainnet",
+            _ => "testnet",
+        };
+
+        let display_name = name.as_deref().unwrap_or("unnamed");
+
+        // Create and add the API key
+        let api_key = ApiKey {
+            key: key.clone(),
+            network: network.to_string(),
+            provider: provider.clone(),
+            name: name.clone(),
+        };
+
+        // Add to the API keys list
+        self.api.keys.push(api_key);
+
+        // Also update the legacy fields for backward compatibility
+        match (provider.clone(), network) {
+            (ApiProvider::Alchemy, "mainnet") => self.alchemy_mainnet_key = Some(key),
+            (ApiProvider::Alchemy, _) => self.alchemy_testnet_key = Some(key),
+            _ => {}
+        }
+
+        format!(
+            "API key for {} on {} saved as '{}'",
+            provider, network, display_name
+        )
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            default_network: Network::Testnet,
+            api: ApiConfig::default(),
+            alchemy_mainnet_key: None,
+            alchemy_testnet_key: None,
+            default_wallet: None,
+            display_decimals: default_display_decimals(),
+            group_thousands: default_group_thousands(),
+            tx_type: TxTypePreference::default(),
+            default_tokens: HashMap::new(),
+            alchemy_requests_per_second: default_alchemy_rps(),
+            extra_token_files: Vec::new(),
+            safe_confirmations: default_safe_confirmations(),
+            health_check: HashMap::new(),
+            history_view: None,
+            read_block_tag: ReadBlockTag::default(),
+            sponsor: HashMap::new(),
+            low_balance_threshold: HashMap::new(),
+            large_transfer_threshold: default_large_transfer_threshold(),
+            address_reuse_warning: false,
+            rpc_attempts: default_rpc_attempts(),
+            rpc_retry_delay_ms: default_rpc_retry_delay_ms(),
+        }
+    }
+}
+
+/// Stores `key` as the Alchemy key for `network` unless it is empty or the network
+/// already has one; returns whether it was stored
+pub(super) fn adopt_alchemy_key(config: &mut Config, network: &str, key: &str) -> bool {
+    if key.is_empty() || config.get_api_key_for(&ApiProvider::Alchemy, network).is_some() {
+        return false;
+    }
+    config.api.keys.push(ApiKey {
+        key: key.to_string(),
+        network: network.to_string(),
+        provider: ApiProvider::Alchemy,
+        name: Some("Alchemy (migrated)".to_string()),
+    });
+    match network {
+        "mainnet" => config.alchemy_mainnet_key = Some(key.to_string()),
+        _ => config.alchemy_testnet_key = Some(key.to_string()),
+    }
+    true
+}
+
+pub struct ConfigManager {
+    config_path: PathBuf,
+}
+
+impl ConfigManager {
+    pub fn new() -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .context("Could not find config directory")?
+            .join("rootstock-wallet");
+
+        std::fs::create_dir_all(&config_dir)?;
+
+        Ok(Self {
+            config_path: config_dir.join("config.json"),
+        })
+    }
+
+    /// A manager for the config file at `config_path` instead of the user's config directory
+    #[cfg(test)]
+    pub(crate) fn at(config_path: PathBuf) -> Self {
+        Self { config_path }
+    }
+
+    pub fn load(&self) -> Result<Config> {
+        if !self.config_path.exists() {
+            return Ok(Config::default());
+        }
+
+        let content =
+            fs::read_to_string(&self.config_path).context("Failed to read config file")?;
+
+        serde_json::from_str(&content).context("Failed to parse config file")
+    }
+
+    pub fn save(&self, config: &Config) -> Result<()> {
+        let content = serde_json::to_string_pretty(config).context("Failed to serialize config")?;
+
+        file_lock::with_lock(&self.config_path, || {
+            file_lock::write_atomic(&self.config_path, &content)
+        })
+        .context("Failed to write config file")
+    }
+
+    /// Re-reads the config under an exclusive lock, applies `f` and saves it, so
+    /// settings changed by another running instance are not overwritten
+    pub fn update<R>(&self, f: impl FnOnce(&mut Config) -> Result<R>) -> Result<R> {
+        file_lock::update_json(&self.config_path, f)
+    }
+
+    pub fn config_path(&self) -> &Path {
+        &self.config_path
+    }
+
+    /// Path of the deprecated standalone API key store
+    pub fn legacy_api_keys_path(&self) -> PathBuf {
+        self.config_path.with_file_name("api_keys.json")
+    }
+
+    pub fn load_legacy_api_keys(&self) -> Result<Option<LegacyApiKeys>> {
+        let path = self.legacy_api_keys_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path).context("Failed to read API keys file")?;
+        let keys = serde_json::from_str(&content).context("Failed to parse API keys file")?;
+        Ok(Some(keys))
+    }
+
+    /// Copies keys from the deprecated `api_keys.json` into `config.json`.
+    /// Keys already present in the config win; returns the networks that were migrated.
+    pub fn migrate_legacy_api_keys(&self) -> Result<Vec<&'static str>> {
+        let Some(legacy) = self.load_legacy_api_keys()? else {
+            return Ok(Vec::new());
+        };
+
+        let mut config = self.load()?;
+        let migrated: Vec<&'static str> = ["mainnet", "testnet"]
+            .into_iter()
+            .filter(|network| {
+                legacy
+                    .get(network)
+                    .is_some_and(|key| adopt_alchemy_key(&mut config, network, key))
+            })
+            .collect();
+
+        if !migrated.is_empty() {
+            self.save(&config)?;
+        }
+        Ok(migrated)
+    }
+
+    /// Copies the Alchemy key older versions kept in the wallet file (`alchemyApiKey`,
+    /// saved by `history --api-key`, or `api_key`, saved by `set-api-key`) into
+    /// `config.json` for each network without one.
+    /// The key was used for both networks; returns the networks that received it.
+    pub fn migrate_wallet_api_key(&self, wallet_file: &Path) -> Result<Vec<&'static str>> {
+        let key = fs::read_to_string(wallet_file)
+            .ok()
+            .and_then(|data| serde_json::from_str::<serde_json::Value>(&data).ok())
+            .and_then(|wallet| {
+                let key = wallet.get("alchemyApiKey").or_else(|| wallet.get("api_key"))?;
+                key.as_str().map(str::to_string)
+            });
+        let Some(key) = key else {
+            return Ok(Vec::new());
+        };
+
+        let mut config = self.load()?;
+        let migrated: Vec<&'static str> = ["mainnet", "testnet"]
+            .into_iter()
+            .filter(|network| adopt_alchemy_key(&mut config, network, &key))
+            .collect();
+
+        if !migrated.is_empty() {
+            self.save(&config)?;
+        }
+        Ok(migrated)
+    }
+
+    pub fn ensure_configured(&self) -> Result<()> {
+        let config = self.load()?;
+
+        match config.default_network {
+            Network::Mainnet if config.alchemy_mainnet_key.is_none() => {
+                anyhow::bail!(
+                    "Mainnet API key not configured. Please run `setup` or `config set alchemy-mainnet-key <key>`"
+                );
+            }
+            Network::Testnet if config.alchemy_testnet_key.is_none() => {
+                anyhow::bail!(
+                    "Testnet API key not configured. Please run `setup` or `config set alchemy-testnet-key <key>`"
+                );
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Wallet data directory that `clear_cache` removes along with the config directory
+    fn wallet_data_dir() -> Option<PathBuf> {
+        dirs::data_local_dir().map(|dir| dir.join("rootstock-wallet"))
+    }
+
+    /// Every file and directory `clear_cache` would remove, without touching them
+    pub fn reset_targets(&self) -> Result<Vec<PathBuf>> {
+        let mut targets = Vec::new();
+        if let Some(config_dir) = self.config_path().parent()
+            && config_dir.exists()
+        {
+            for entry in fs::read_dir(config_dir)? {
+                targets.push(entry?.path());
+            }
+        }
+        if let Some(wallet_data_dir) = Self::wallet_data_dir()
+            && wallet_data_dir.exists()
+        {
+            // Both directories are the same one on some platforms
+            for entry in fs::read_dir(&wallet_data_dir)? {
+                let path = entry?.path();
+                if !targets.contains(&path) {
+                    targets.push(path);
+                }
+            }
+            targets.push(wallet_data_dir);
+        }
+        Ok(targets)
+    }
+
+    /// Copies the config and wallet data directories to a timestamped
+    /// `rootstock-wallet-backup-*` directory beside the wallet data and returns its path
+    pub fn backup_all(&self) -> Result<PathBuf> {
+        let data_dir = dirs::data_local_dir()
+            .ok_or_else(|| anyhow::anyhow!("Could not determine the data directory"))?;
+        let backup = data_dir.join(format!(
+            "rootstock-wallet-backup-{}",
+            chrono::Local::now().format("%Y%m%d%H%M%S")
+        ));
+        if let Some(config_dir) = self.config_path().parent()
+            && config_dir.exists()
+        {
+            copy_dir(config_dir, &backup.join("config"))?;
+        }
+        if let Some(wallet_data_dir) = Self::wallet_data_dir()
+            && wallet_data_dir.exists()
+        {
+            copy_dir(&wallet_data_dir, &backup.join("data"))?;
+        }
+        Ok(backup)
+    }
+
+    /// Removes all wallet data, configuration, and cache
+    /// WARNING: This will delete ALL wallet data and cannot be undone without a backup!
+    pub fn clear_cache(&self) -> Result<()> {
+        let config_dir = self
+            .config_path()
+            .parent()
+            .ok_or_else(|| anyhow::anyhow!("Invalid config directory path"))?;
+
+        for path in self.reset_targets()? {
+            if path.is_dir() {
+                fs::remove_dir_all(&path)?;
+            } else if path.exists() {
+                fs::remove_file(&path)?;
+            }
+            println!("Removed: {}", path.display());
+        }
+
+        // Recreate the empty config directory
+        fs::create_dir_all(config_dir)?;
+        Ok(())
+    }
+}
+
+/// Recursively copies `from` into `to`
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.path().is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to back up {}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_migrate_wallet_api_key_fills_only_missing_networks() {
+        let dir = TempDir::new().unwrap();
+        let config_manager = ConfigManager::at(dir.path().join("config.json"));
+        let config = Config {
+            alchemy_mainnet_key: Some("mainnet-key".to_string()),
+            ..Default::default()
+        };
+        config_manager.save(&config).unwrap();
+
+        let wallet_file = dir.path().join("wallet.json");
+        fs::write(&wallet_file, r#"{"current_wallet": "", "alchemyApiKey": "old-key"}"#).unwrap();
+
+        assert_eq!(config_manager.migrate_wallet_api_key(&wallet_file).unwrap(), vec!["testnet"]);
+        let config = config_manager.load().unwrap();
+        assert_eq!(config.get_api_key_for(&ApiProvider::Alchemy, "mainnet"), Some("mainnet-key"));
+        assert_eq!(config.get_api_key_for(&ApiProvider::Alchemy, "testnet"), Some("old-key"));
+
+        // Nothing left to move the second time
+        assert!(config_manager.migrate_wallet_api_key(&wallet_file).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_migrate_wallet_api_key_reads_the_set_api_key_field() {
+        let dir = TempDir::new().unwrap();
+        let config_manager = ConfigManager::at(dir.path().join("config.json"));
+        config_manager.save(&Config::default()).unwrap();
+
+        let wallet_file = dir.path().join("wallet.json");
+        fs::write(&wallet_file, r#"{"current_wallet": "", "api_key": "wallet-key"}"#).unwrap();
+
+        assert_eq!(
+            config_manager.migrate_wallet_api_key(&wallet_file).unwrap(),
+            vec!["mainnet", "testnet"]
+        );
+        let config = config_manager.load().unwrap();
+        assert_eq!(config.get_api_key_for(&ApiProvider::Alchemy, "mainnet"), Some("wallet-key"));
+        assert_eq!(config.get_api_key_for(&ApiProvider::Alchemy, "testnet"), Some("wallet-key"));
+    }
+}
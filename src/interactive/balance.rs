@@ -1,9 +1,10 @@
 use crate::commands::balance::BalanceCommand;
 use crate::commands::tokens::TokenRegistry;
 use crate::config::ConfigManager;
+use crate::interactive::tokens::default_token_index;
 use anyhow::{Result, anyhow};
 use console::style;
-use inquire::Select;
+use inquire::{Confirm, Select};
 
 /// Displays the balance checking interface
 pub async fn show_balance() -> Result<()> {
@@ -56,11 +57,31 @@ pub async fn show_balance() -> Result<()> {
         .collect();
 
     // Get just the display names for the selection menu
-    let token_display_names: Vec<String> =
+    let mut token_display_names: Vec<String> =
         token_choices.iter().map(|(name, _)| name.clone()).collect();
+    let all_tokens_option = "📊 All tokens".to_string();
+    token_display_names.push(all_tokens_option.clone());
 
     // Let the user select which token to check
-    let selection = Select::new("Select token to check balance:", token_display_names).prompt()?;
+    let starting = default_token_index(&token_display_names, &config, &network);
+    let selection = Select::new("Select token to check balance:", token_display_names)
+        .with_starting_cursor(starting)
+        .prompt()?;
+
+    if selection == all_tokens_option {
+        let show_zero = Confirm::new("Include tokens with a zero balance?")
+            .with_default(false)
+            .prompt()?;
+        return BalanceCommand {
+            address: None,
+            token: None,
+            block_tag: None,
+            all: true,
+            show_zero,
+        }
+        .execute()
+        .await;
+    }
 
     // Find the selected token info
     let (_, token_info) = token_choices
@@ -74,6 +95,9 @@ pub async fn show_balance() -> Result<()> {
     // Execute the balance command
     let cmd = BalanceCommand {
         address: None, // Will use default wallet
+        block_tag: None,
+        all: false,
+        show_zero: false,
         token: if token_address == "0x0000000000000000000000000000000000000000" {
             None
         } else {
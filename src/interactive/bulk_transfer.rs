@@ -1,36 +1,211 @@
 use crate::{
-    config::ConfigManager,
-    types::{network::Network, wallet::WalletData},
-    utils::constants,
+    config::{Config, ConfigManager, TxTypePreference},
+    interactive::{confirm_active_network, transfer::select_signing_wallet, warn_if_low_balance},
+    types::{network::Network, wallet::{Wallet, WalletData}},
+    utils::{
+        address::{parse_address, validate_address_input},
+        cancel::CancelToken,
+        constants,
+        gas::{DEFAULT_PRIORITY_TIP_GWEI, FeeMode},
+        helper::Helper,
+        payout::{PayoutEntry, parse_payout_rows, read_piped_stdin, validate_rows},
+        retry,
+        bulk_journal::{BulkJournal, EntryStatus},
+        session::{Session, SessionKind},
+        units::{Denomination, format_amount, from_wei, to_wei},
+    },
 };
 use anyhow::{Result, anyhow};
-use dialoguer::{Confirm, Input};
+use dialoguer::{Confirm, Input, Select};
 use alloy::{
-    primitives::{Address, U256},
-    providers::{Provider, ProviderBuilder},
+    primitives::{Address, B256, U256},
+    providers::{PendingTransactionBuilder, Provider, ProviderBuilder, RootProvider},
+    rpc::types::{BlockNumberOrTag, TransactionRequest},
     signers::local::PrivateKeySigner,
-    network::TransactionBuilder,
+    network::{Ethereum, EthereumWallet, TransactionBuilder},
+    transports::{RpcError, http::{Client, Http}},
 };
-use serde::Deserialize;
-use std::{fs, sync::Arc};
+use std::{collections::HashMap, fs, sync::Arc, time::Duration};
+
+/// How long each transfer may take to be mined before it is left unconfirmed
+const RECEIPT_TIMEOUT: Duration = Duration::from_secs(120);
 
 #[derive(Debug, Clone)]
 struct Transfer {
     to: Address,
     value: U256,
+    /// Position in the bulk journal, assigned once the batch is final
+    entry: usize,
+}
+
+impl Transfer {
+    fn new(to: Address, value: U256) -> Self {
+        Self { to, value, entry: 0 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum RecipientKind {
+    Account,
+    Contract,
+}
+
+/// Recipients sharing one cached gas estimate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum EstimateScope {
+    /// Every plain account: receiving costs the same whoever it is
+    Accounts,
+    /// One contract, whose code decides what receiving costs
+    Contract(Address),
+}
+
+impl EstimateScope {
+    fn of(to: Address, kind: RecipientKind) -> Self {
+        match kind {
+            RecipientKind::Account => EstimateScope::Accounts,
+            RecipientKind::Contract => EstimateScope::Contract(to),
+        }
+    }
+}
+
+/// Gas estimates reused across a single bulk run.
+///
+/// Transfers of the same token to plain accounts cost the same gas, so only the
+/// first of them is estimated against the node; contracts are estimated one by one.
+#[derive(Debug, Default)]
+struct GasEstimateCache {
+    estimates: HashMap<(Option<Address>, EstimateScope), u64>,
+    recipients: HashMap<Address, RecipientKind>,
+}
+
+impl GasEstimateCache {
+    async fn recipient_kind(
+        &mut self,
+        provider: &RootProvider<Http<Client>>,
+        to: Address,
+    ) -> Result<RecipientKind> {
+        if let Some(kind) = self.recipients.get(&to) {
+            return Ok(*kind);
+        }
+        let code = provider
+            .get_code_at(to)
+            .await
+            .map_err(|e| anyhow!("Failed to get code for {}: {}", to, e))?;
+        let kind = if code.is_empty() {
+            RecipientKind::Account
+        } else {
+            RecipientKind::Contract
+        };
+        self.recipients.insert(to, kind);
+        Ok(kind)
+    }
+
+    async fn estimate(
+        &mut self,
+        provider: &RootProvider<Http<Client>>,
+        from: Address,
+        transfer: &Transfer,
+        token: Option<Address>,
+    ) -> Result<u64> {
+        let kind = self.recipient_kind(provider, transfer.to).await?;
+        let key = (token, EstimateScope::of(transfer.to, kind));
+        if let Some(gas) = self.estimates.get(&key) {
+            return Ok(*gas);
+        }
+        let tx = TransactionRequest::default()
+            .with_from(from)
+            .with_to(transfer.to)
+            .with_value(transfer.value);
+        let gas = provider
+            .estimate_gas(&tx)
+            .await
+            .map_err(|e| anyhow!("Failed to estimate gas: {}", e))?;
+        self.estimates.insert(key, gas);
+        Ok(gas)
+    }
+
+    /// Drops the cached estimate for this transfer so the next call hits the node again
+    fn invalidate(&mut self, to: Address, token: Option<Address>) {
+        if let Some(kind) = self.recipients.get(&to) {
+            self.estimates.remove(&(token, EstimateScope::of(to, *kind)));
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct TransferInput {
-    to: String,
-    value: String,
+/// A transfer that reverted when simulated against the latest state
+#[derive(Debug)]
+struct PreflightFailure {
+    index: usize,
+    to: Address,
+    reason: String,
+}
+
+/// Simulates every transfer with `eth_call` and `eth_estimateGas` before anything
+/// is broadcast, so recipients that would revert are known up front.
+async fn preflight(
+    provider: &RootProvider<Http<Client>>,
+    from: Address,
+    transfers: &[Transfer],
+) -> Vec<PreflightFailure> {
+    let mut failures = Vec::new();
+    for (index, transfer) in transfers.iter().enumerate() {
+        let tx = TransactionRequest::default()
+            .with_from(from)
+            .with_to(transfer.to)
+            .with_value(transfer.value);
+        let result = match provider.call(&tx).await {
+            Ok(_) => provider.estimate_gas(&tx).await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            failures.push(PreflightFailure {
+                index,
+                to: transfer.to,
+                reason: e.to_string(),
+            });
+        }
+    }
+    failures
+}
+
+fn is_out_of_gas(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("out of gas") || error.contains("intrinsic gas too low")
+}
+
+fn is_nonce_too_low(error: &str) -> bool {
+    error.to_lowercase().contains("nonce too low")
 }
 
 /// Interactive menu for bulk token transfers
 pub async fn bulk_transfer() -> Result<()> {
+    run_bulk_transfer(Vec::new()).await
+}
+
+/// Continues a bulk transfer from recipients saved in an earlier session
+pub async fn resume_bulk_transfer(recipients: Vec<PayoutEntry>) -> Result<()> {
+    run_bulk_transfer(recipients).await
+}
+
+async fn run_bulk_transfer(resumed: Vec<PayoutEntry>) -> Result<()> {
     println!("\n💸 Bulk Token Transfer");
     println!("=====================");
 
+    if let Some(unfinished) = BulkJournal::load().filter(|journal| !journal.is_finished()) {
+        println!(
+            "⚠️  The bulk transfer started {} has {} transfer(s) not confirmed yet",
+            unfinished.started_at,
+            unfinished.entries.len() - unfinished.count(EntryStatus::Confirmed)
+        );
+        if !Confirm::new()
+            .with_prompt("Start a new batch anyway? Its progress will no longer be resumable")
+            .default(false)
+            .interact()?
+        {
+            return Ok(());
+        }
+    }
+
     // Load wallet data
     let wallet_file = constants::wallet_file_path();
     let wallet_data = if wallet_file.exists() {
@@ -40,10 +215,8 @@ pub async fn bulk_transfer() -> Result<()> {
         return Err(anyhow!("No wallet found. Please create a wallet first."));
     };
 
-    // Get current wallet
-    let current_wallet = wallet_data
-        .get_current_wallet()
-        .ok_or_else(|| anyhow!("No active wallet found. Please select a wallet first."))?;
+    // Sign with the active wallet unless another one is picked for this batch
+    let current_wallet = select_signing_wallet(&wallet_data)?;
 
     // Load config
     let config_manager = ConfigManager::new()?;
@@ -52,26 +225,15 @@ pub async fn bulk_transfer() -> Result<()> {
     // Get the network configuration
     let network_config = config.default_network.get_config();
 
-    // Get the chain ID based on the network
-    let chain_id = match config.default_network {
-        Network::RootStockMainnet => 30,
-        Network::RootStockTestnet => 31,
-        Network::Mainnet => 30,
-        Network::Testnet => 31,
-        Network::Regtest => 1337,
-        _ => return Err(anyhow!("Unsupported network for bulk transfers")),
-    };
-
-    // Prompt for password to decrypt the private key
-    let password = rpassword::prompt_password("Enter password for the wallet: ")?;
-
-    // Decrypt the private key
-    let private_key = current_wallet.decrypt_private_key(&password)?;
+    // The Alchemy networks are only used for history, not for sending
+    if matches!(
+        config.default_network,
+        Network::AlchemyMainnet | Network::AlchemyTestnet
+    ) {
+        return Err(anyhow!("Unsupported network for bulk transfers"));
+    }
 
-    // Create a wallet
-    let wallet = private_key
-        .parse::<PrivateKeySigner>()
-        .map_err(|e| anyhow!("Failed to parse private key: {}", e))?;
+    let wallet = unlock(current_wallet)?;
 
     // Create a provider with the network RPC URL
     let provider = ProviderBuilder::new()
@@ -79,42 +241,89 @@ pub async fn bulk_transfer() -> Result<()> {
 
     let client = Arc::new(provider);
 
+    // Piped recipients take the place of a file
+    let piped = read_piped_stdin()?;
+    if piped.is_some() {
+        println!("📥 Reading recipients from stdin");
+    }
+
     // Ask if user wants to use a file or manual input
-    let use_file = Confirm::new()
-        .with_prompt("Do you want to load recipients from a JSON file?")
-        .default(false)
-        .interact()?;
+    let use_file = piped.is_some()
+        || (resumed.is_empty()
+            && Confirm::new()
+                .with_prompt("Do you want to load recipients from a JSON or CSV file?")
+                .default(false)
+                .interact()?);
 
     let transfers = if use_file {
-        // Load transfers from file
-        let file_path: String = Input::new()
-            .with_prompt("Enter path to JSON file with transfer details")
-            .interact_text()?;
-
-        let file_content = std::fs::read_to_string(&file_path)
-            .map_err(|e| anyhow!("Failed to read file: {}", e))?;
-
-        let transfer_inputs: Vec<TransferInput> = serde_json::from_str(&file_content)
-            .map_err(|e| anyhow!("Failed to parse JSON: {}", e))?;
-
-        transfer_inputs
-            .into_iter()
-            .map(|input| {
-                let to_addr = input
-                    .to
-                    .parse::<Address>()
-                    .map_err(|e| anyhow!("Invalid address {}: {}", input.to, e))?;
-                let value_wei = parse_amount(&input.value)?;
-                Ok(Transfer {
-                    to: to_addr,
-                    value: value_wei,
-                })
-            })
-            .collect::<Result<Vec<_>>>()?
+        let file_content = match piped {
+            Some(content) => content,
+            None => {
+                // Load transfers from file
+                let file_path: String = Input::new()
+                    .with_prompt("Enter path to JSON or CSV file with transfer details")
+                    .interact_text()?;
+
+                std::fs::read_to_string(&file_path)
+                    .map_err(|e| anyhow!("Failed to read file: {}", e))?
+            }
+        };
+
+        // Check every row before anything else so all mistakes show up at once
+        let rows = parse_payout_rows(&file_content)?;
+        let (transfers, errors) = validate_rows(&rows, |input| {
+            let to_addr = parse_address(&input.to, config.default_network)
+                .map_err(|e| anyhow!("invalid address {}: {}", input.to, e))?;
+            let value_wei = parse_amount(&input.value)
+                .map_err(|_| anyhow!("invalid amount '{}'", input.value))?;
+            Ok(Transfer::new(to_addr, value_wei))
+        });
+        if !errors.is_empty() {
+            println!("\n⚠️  {} of {} row(s) are invalid:", errors.len(), rows.len());
+            for error in &errors {
+                println!("  {}", error);
+            }
+            if transfers.is_empty() {
+                return Err(anyhow!("No valid recipients to send to"));
+            }
+            let skip = Confirm::new()
+                .with_prompt(format!(
+                    "Skip the invalid rows and continue with the {} valid one(s)?",
+                    transfers.len()
+                ))
+                .default(false)
+                .interact()?;
+            if !skip {
+                println!("Transaction cancelled");
+                return Ok(());
+            }
+        }
+        transfers
     } else {
-        // Manual input
+        // Manual input, saved as it goes so a closed terminal doesn't lose it
+        let network = config.default_network;
+        let mut session = Session::new(
+            SessionKind::BulkTransfer,
+            &network.to_string().to_lowercase(),
+            "RBTC",
+        );
+        let mut transfers = Vec::new();
+        for entry in resumed {
+            let to = parse_address(&entry.to, network)
+                .map_err(|e| anyhow!("Invalid address {}: {}", entry.to, e))?;
+            transfers.push(Transfer::new(to, parse_amount(&entry.value)?));
+            session.record(&entry.to, &entry.value);
+        }
+        if !transfers.is_empty() {
+            println!("Resumed {} recipient(s)", transfers.len());
+        }
+
         let count_str: String = Input::new()
-            .with_prompt("How many recipients?")
+            .with_prompt(if transfers.is_empty() {
+                "How many recipients?"
+            } else {
+                "How many more recipients?"
+            })
             .validate_with(|input: &String| {
                 if input.parse::<usize>().is_ok() {
                     Ok(())
@@ -128,24 +337,15 @@ pub async fn bulk_transfer() -> Result<()> {
             .parse::<usize>()
             .map_err(|_| anyhow!("Failed to parse number of recipients"))?;
 
-        let mut transfers = Vec::with_capacity(count);
-        for i in 0..count {
-            println!("\nRecipient #{}:", i + 1);
+        for _ in 0..count {
+            println!("\nRecipient #{}:", transfers.len() + 1);
 
             let to: String = Input::new()
                 .with_prompt("Recipient address (0x...)")
-                .validate_with(|input: &String| {
-                    if input.starts_with("0x") && input.len() == 42 {
-                        Ok(())
-                    } else {
-                        Err("Please enter a valid rBTC address starting with 0x".to_string())
-                    }
-                })
+                .validate_with(|input: &String| validate_address_input(input, network))
                 .interact()?;
 
-            let to = to
-                .parse::<Address>()
-                .map_err(|e| anyhow!("Invalid address: {}", e))?;
+            let to = parse_address(&to, network)?;
 
             let amount: String = Input::new()
                 .with_prompt("Amount to send (e.g., 1.0)")
@@ -153,11 +353,166 @@ pub async fn bulk_transfer() -> Result<()> {
 
             let value = parse_amount(&amount)?;
 
-            transfers.push(Transfer { to, value });
+            session.record(&format!("0x{:x}", to), &amount);
+            transfers.push(Transfer::new(to, value));
         }
+        Session::clear();
         transfers
     };
 
+    // Simulate the whole batch before showing the summary
+    println!("\n🔎 Simulating {} transfers...", transfers.len());
+    let failures = preflight(&client, wallet.address(), &transfers).await;
+    let transfers = if failures.is_empty() {
+        println!("✅ All transfers passed simulation");
+        transfers
+    } else {
+        println!("\n⚠️  {} transfer(s) would fail:", failures.len());
+        for failure in &failures {
+            println!("{:2}. To: {} - {}", failure.index + 1, failure.to, failure.reason);
+        }
+
+        let options = &[
+            "Exclude failing recipients and continue",
+            "Keep all recipients",
+            "Abort",
+        ];
+        let choice = Select::new()
+            .with_prompt("How do you want to proceed?")
+            .items(options)
+            .default(0)
+            .interact()?;
+        match choice {
+            0 => {
+                let remaining: Vec<Transfer> = transfers
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(i, _)| !failures.iter().any(|f| f.index == *i))
+                    .map(|(_, t)| t)
+                    .collect();
+                if remaining.is_empty() {
+                    println!("No transfers left to send");
+                    return Ok(());
+                }
+                remaining
+            }
+            1 => transfers,
+            _ => {
+                println!("Transaction cancelled");
+                return Ok(());
+            }
+        }
+    };
+
+    send_all(&config, &client, &wallet, transfers, None).await
+}
+
+/// Sends what an interrupted bulk transfer left unsent. Transfers that were confirmed
+/// are skipped; ones broadcast without a receipt are checked again first and never
+/// resent automatically.
+pub async fn resume_journal(mut journal: BulkJournal) -> Result<()> {
+    println!("\n💸 Resuming Bulk Transfer");
+    println!("=========================");
+
+    let wallet_data: WalletData =
+        serde_json::from_str(&fs::read_to_string(constants::wallet_file_path())?)?;
+    let signer = wallet_data
+        .list_wallets()
+        .into_iter()
+        .find(|w| w.address == journal.from)
+        .ok_or_else(|| {
+            anyhow!(
+                "The wallet that started this batch (0x{:x}) is no longer available",
+                journal.from
+            )
+        })?;
+    let config = ConfigManager::new()?.load()?;
+    let wallet = unlock(signer)?;
+    if wallet.address() != journal.from {
+        return Err(anyhow!(
+            "The key of wallet '{}' is for 0x{:x}, not 0x{:x} that started this batch",
+            signer.name,
+            wallet.address(),
+            journal.from
+        ));
+    }
+    let client = ProviderBuilder::new().on_http(config.default_network.get_config().rpc_url.parse()?);
+
+    // Transfers broadcast without a receipt may have been mined since
+    let unconfirmed: Vec<(usize, B256)> = journal
+        .entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| entry.status == EntryStatus::Unconfirmed)
+        .filter_map(|(index, entry)| entry.tx_hash.map(|tx_hash| (index, tx_hash)))
+        .collect();
+    for (index, tx_hash) in unconfirmed {
+        if let Ok(Some(receipt)) = client.get_transaction_receipt(tx_hash).await {
+            let status = if receipt.status() {
+                EntryStatus::Confirmed
+            } else {
+                EntryStatus::Failed
+            };
+            journal.record(index, status, Some(tx_hash));
+        }
+    }
+
+    println!(
+        "Started {} from {}: {} of {} transfer(s) confirmed",
+        journal.started_at,
+        Helper::format_address(&journal.from),
+        journal.count(EntryStatus::Confirmed),
+        journal.entries.len()
+    );
+    for entry in journal.entries.iter().filter(|e| e.status == EntryStatus::Unconfirmed) {
+        println!(
+            "⏳ Not resent, still unconfirmed: {} - {} rBTC (tx {:?})",
+            entry.to,
+            format_amount(entry.value, 18),
+            entry.tx_hash.unwrap_or_default()
+        );
+    }
+
+    let transfers: Vec<Transfer> = journal
+        .unsent()
+        .into_iter()
+        .map(|entry| Transfer {
+            to: journal.entries[entry].to,
+            value: journal.entries[entry].value,
+            entry,
+        })
+        .collect();
+    if transfers.is_empty() {
+        if journal.is_finished() {
+            BulkJournal::clear();
+            println!("✅ Every transfer is confirmed");
+        } else {
+            println!("Nothing left to send; check the unconfirmed transfers or discard the batch");
+        }
+        return Ok(());
+    }
+    send_all(&config, &client, &wallet, transfers, Some(journal)).await
+}
+
+/// Asks for the wallet's password and decrypts its key
+fn unlock(wallet: &Wallet) -> Result<PrivateKeySigner> {
+    let password =
+        rpassword::prompt_password(format!("Enter password for wallet '{}': ", wallet.name))?;
+    wallet
+        .decrypt_private_key(&password)?
+        .parse::<PrivateKeySigner>()
+        .map_err(|e| anyhow!("Failed to parse private key: {}", e))
+}
+
+/// Shows the summary and cost of `transfers`, sends them once confirmed and reports
+/// the outcome. Progress goes to `journal`, or to a new one for a fresh batch.
+async fn send_all(
+    config: &Config,
+    client: &RootProvider<Http<Client>>,
+    wallet: &PrivateKeySigner,
+    transfers: Vec<Transfer>,
+    journal: Option<BulkJournal>,
+) -> Result<()> {
     // Show summary
     println!("\n📋 Transaction Summary:");
     println!("====================");
@@ -168,82 +523,324 @@ pub async fn bulk_transfer() -> Result<()> {
             "{:2}. To: {} - Amount: {} rBTC",
             i + 1,
             transfer.to,
-            format_eth(transfer.value)
+            format_amount(transfer.value, 18)
         );
     }
 
-    println!("\nTotal to send: {} rBTC", format_eth(total));
+    println!("\nTotal to send: {} rBTC", format_amount(total, 18));
 
     // Get current gas price
     let gas_price = client.get_gas_price().await?;
-    println!("Current gas price: {} Gwei", format_gwei(U256::from(gas_price)));
-
-    // Estimate gas cost (21,000 gas per basic transfer)
-    let gas_per_tx = U256::from(21000u64);
-    let total_gas = gas_per_tx
-        .checked_mul(U256::from(transfers.len()))
+    let gas_price = crate::utils::eth::enforce_minimum_gas_price(client, gas_price).await;
+    println!(
+        "Current gas price: {} Gwei",
+        from_wei(U256::from(gas_price), Denomination::Gwei)
+    );
+    let fee_mode = choose_fee_mode(client, config, gas_price).await?;
+
+    // Estimate gas once per (token, recipient kind) and reuse it for the rest of the batch
+    let mut gas_cache = GasEstimateCache::default();
+    let mut total_gas = U256::ZERO;
+    let mut unestimated = 0;
+    for transfer in &transfers {
+        match gas_cache
+            .estimate(client, wallet.address(), transfer, None)
+            .await
+        {
+            Ok(gas) => total_gas += U256::from(gas),
+            Err(_) => unestimated += 1,
+        }
+    }
+    let total_gas_cost = total_gas
+        .checked_mul(U256::from(fee_mode.max_price_per_gas()))
         .unwrap_or_default();
-    let total_gas_cost = total_gas.checked_mul(U256::from(gas_price)).unwrap_or_default();
 
-    println!("Estimated gas cost: {} rBTC", format_eth(total_gas_cost));
+    println!("Fee mode: {}", fee_mode);
+    println!("Estimated gas cost (at most): {} rBTC", format_amount(total_gas_cost, 18));
+    if unestimated > 0 {
+        println!("⚠️  Gas could not be estimated for {} transfer(s)", unestimated);
+    }
     println!(
         "Total cost (amount + gas): {} rBTC",
-        format_eth(total + total_gas_cost)
+        format_amount(total + total_gas_cost, 18)
     );
 
     // Confirm before sending
-    let confirm = Confirm::new()
-        .with_prompt("\nDo you want to send these transactions?")
-        .default(false)
-        .interact()?;
+    let confirm = confirm_active_network(config, total)?
+        && Confirm::new()
+            .with_prompt("\nDo you want to send these transactions?")
+            .default(false)
+            .interact()?;
 
     if !confirm {
         println!("Transaction cancelled");
         return Ok(());
     }
 
+    // Recorded before anything is sent so an interrupted batch can be resumed
+    let (mut journal, transfers) = match journal {
+        Some(journal) => (journal, transfers),
+        None => {
+            let mut transfers = transfers;
+            for (entry, transfer) in transfers.iter_mut().enumerate() {
+                transfer.entry = entry;
+            }
+            let journal = BulkJournal::new(
+                &config.default_network.to_string().to_lowercase(),
+                wallet.address(),
+                transfers.iter().map(|t| (t.to, t.value)),
+            );
+            journal.save()?;
+            (journal, transfers)
+        }
+    };
+
     // Send transactions
     println!("\n🚀 Sending transactions...");
 
-    let mut successful = 0;
-    let mut failed = 0;
+    // Keys are only watched while sending, so the prompts in between read them as usual
+    let cancel = CancelToken::listen();
+    let mut outcome = send_batch(
+        client,
+        wallet,
+        fee_mode,
+        &mut gas_cache,
+        &mut journal,
+        &cancel,
+        &transfers,
+    )
+    .await?;
+    let mut cancelled = cancel.is_cancelled();
+    drop(cancel);
+    let mut successful = outcome.successful;
+
+    // Offer to retry what definitely didn't go through, as often as the user likes
+    while !outcome.retryable.is_empty() && !cancelled {
+        let retry = Confirm::new()
+            .with_prompt(format!(
+                "\nRetry the {} failed transfer(s)?",
+                outcome.retryable.len()
+            ))
+            .default(true)
+            .interact()?;
+        if !retry {
+            break;
+        }
+        println!("\n🔁 Retrying failed transfers...");
+        let cancel = CancelToken::listen();
+        let retried = send_batch(
+            client,
+            wallet,
+            fee_mode,
+            &mut gas_cache,
+            &mut journal,
+            &cancel,
+            &outcome.retryable,
+        )
+        .await?;
+        cancelled = cancel.is_cancelled();
+        successful += retried.successful;
+        outcome.unconfirmed += retried.unconfirmed;
+        outcome.retryable = retried.retryable;
+    }
 
-    for (i, transfer) in transfers.clone().into_iter().enumerate() {
-        print!("Sending {}/{}... ", i + 1, transfers.clone().len());
+    let failed = transfers.len().saturating_sub(successful + outcome.unconfirmed);
+    println!("\n📊 Transaction Summary:");
+    println!("====================");
+    println!("Total transfers: {}", transfers.len());
+    println!("✅ Successful: {}", successful);
+    if outcome.unconfirmed > 0 {
+        println!(
+            "⏳ Unconfirmed (not retried, check their status): {}",
+            outcome.unconfirmed
+        );
+    }
+    println!("❌ Failed or unsent: {}", failed);
+    if journal.is_finished() {
+        BulkJournal::clear();
+    } else {
+        println!(
+            "↺ {} of {} transfer(s) in this batch are not confirmed; you'll be offered to resume it the next time the wallet starts",
+            journal.entries.len() - journal.count(EntryStatus::Confirmed),
+            journal.entries.len()
+        );
+    }
+    warn_if_low_balance(config, wallet.address()).await;
 
-        use alloy::rpc::types::TransactionRequest;
-        let tx = TransactionRequest::default()
-            .with_to(transfer.to)
-            .with_value(transfer.value)
-            .with_gas_limit(gas_per_tx.try_into().unwrap_or(0u64))
-            .with_gas_price(gas_price.try_into().unwrap_or(0u128));
-
-        match client.send_transaction(tx).await {
-            Ok(pending_tx) => {
-                let tx_hash = pending_tx.tx_hash();
-                match client.get_transaction_receipt(*tx_hash).await {
-                    Ok(Some(receipt)) => {
-                        if receipt.status() {
-                            println!("✅ Success! Tx: {:?}", receipt.transaction_hash);
-                            successful += 1;
-                        } else {
-                            println!("❌ Failed! Tx: {:?}", receipt.transaction_hash);
-                            failed += 1;
-                        }
-                    }
-                    Ok(None) => {
-                        println!("❌ Transaction was dropped from the mempool");
-                        failed += 1;
+    Ok(())
+}
+
+/// What happened to one pass over a batch of transfers
+#[derive(Debug, Default)]
+struct BatchOutcome {
+    successful: usize,
+    /// Broadcast without a confirmation; never retried automatically
+    unconfirmed: usize,
+    /// Definitely not sent or reverted, safe to try again
+    retryable: Vec<Transfer>,
+}
+
+/// What became of handing one signed transfer to the node
+enum Broadcast {
+    /// Accepted, or the node already has a transaction at this nonce
+    Sent(PendingTransactionBuilder<Http<Client>, Ethereum>),
+    /// No usable reply: the node may have accepted the transfer anyway
+    Unknown(B256, anyhow::Error),
+}
+
+/// Signs `tx` locally with the batch's wallet and broadcasts it. Only a rejection
+/// the node itself returned is an error; an "already known" or "nonce too low" reply
+/// means an earlier copy went out, so it counts as sent.
+async fn sign_and_send(
+    client: &RootProvider<Http<Client>>,
+    signer: &EthereumWallet,
+    tx: TransactionRequest,
+) -> Result<Broadcast> {
+    let envelope = tx
+        .build(signer)
+        .await
+        .map_err(|e| anyhow!("Failed to sign transaction: {}", e))?;
+    let tx_hash = *envelope.tx_hash();
+    match client.send_tx_envelope(envelope).await {
+        Ok(pending_tx) => Ok(Broadcast::Sent(pending_tx)),
+        Err(e) if retry::is_already_known(&e) || is_nonce_too_low(&e.to_string()) => Ok(
+            Broadcast::Sent(PendingTransactionBuilder::new(client.clone(), tx_hash)),
+        ),
+        Err(e @ RpcError::ErrorResp(_)) => Err(e.into()),
+        Err(e) => Ok(Broadcast::Unknown(tx_hash, e.into())),
+    }
+}
+
+/// Sends `transfers` one by one from `wallet`, waiting for each receipt and recording
+/// the outcome in `journal`; stops early on cancellation, leaving the rest pending in
+/// the journal
+async fn send_batch(
+    client: &RootProvider<Http<Client>>,
+    wallet: &PrivateKeySigner,
+    fee_mode: FeeMode,
+    gas_cache: &mut GasEstimateCache,
+    journal: &mut BulkJournal,
+    cancel: &CancelToken,
+    transfers: &[Transfer],
+) -> Result<BatchOutcome> {
+    let mut outcome = BatchOutcome::default();
+    let from = wallet.address();
+    let signer = EthereumWallet::from(wallet.clone());
+    let chain_id = client
+        .get_chain_id()
+        .await
+        .map_err(|e| anyhow!("Failed to get chain id: {}", e))?;
+    // Assigned locally so each transfer gets the next nonce even before the node
+    // reports the previous one as pending
+    let mut nonce = client
+        .get_transaction_count(from)
+        .pending()
+        .await
+        .map_err(|e| anyhow!("Failed to get nonce: {}", e))?;
+
+    for (i, transfer) in transfers.iter().cloned().enumerate() {
+        // Only stop between transfers, never halfway through one
+        if cancel.is_cancelled() {
+            println!("⏹️  Stopped with {} transfer(s) unsent", transfers.len() - i);
+            break;
+        }
+        print!("Sending {}/{}... ", i + 1, transfers.len());
+
+        let gas_limit = match gas_cache
+            .estimate(client, from, &transfer, None)
+            .await
+        {
+            Ok(gas) => gas,
+            Err(e) => {
+                println!("❌ Skipped: {}", e);
+                outcome.retryable.push(transfer);
+                continue;
+            }
+        };
+        let tx = fee_mode.apply(
+            TransactionRequest::default()
+                .with_from(from)
+                .with_to(transfer.to)
+                .with_value(transfer.value)
+                .with_nonce(nonce)
+                .with_chain_id(chain_id)
+                .with_gas_limit(gas_limit),
+        );
+
+        let sent = match sign_and_send(client, &signer, tx.clone()).await {
+            Err(e) if is_out_of_gas(&e.to_string()) => {
+                // The cached estimate was too low for this recipient, ask the node again
+                gas_cache.invalidate(transfer.to, None);
+                match gas_cache.estimate(client, from, &transfer, None).await {
+                    Ok(gas_limit) => {
+                        sign_and_send(client, &signer, tx.with_gas_limit(gas_limit)).await
                     }
                     Err(e) => {
-                        println!("❌ Error: {}", e);
-                        failed += 1;
+                        println!("❌ Skipped: {}", e);
+                        journal.record(transfer.entry, EntryStatus::Pending, None);
+                        outcome.retryable.push(transfer);
+                        continue;
+                    }
+                }
+            }
+            result => result,
+        };
+
+        let pending_tx = match sent {
+            Ok(Broadcast::Sent(pending_tx)) => pending_tx,
+            Ok(Broadcast::Unknown(tx_hash, e)) => {
+                // Recorded with its hash before anything else, so neither a retry nor a
+                // resume sends it again
+                journal.record(transfer.entry, EntryStatus::Unconfirmed, Some(tx_hash));
+                match client.get_transaction_by_hash(tx_hash).await {
+                    Ok(Some(_)) => PendingTransactionBuilder::new(client.clone(), tx_hash),
+                    _ => {
+                        println!(
+                            "⏳ No reply to the broadcast, check {:?} before resending: {}",
+                            tx_hash, e
+                        );
+                        outcome.unconfirmed += 1;
+                        // The node's pending count shows whether the nonce was used after all
+                        if let Ok(pending) = client.get_transaction_count(from).pending().await {
+                            nonce = nonce.max(pending);
+                        }
+                        continue;
                     }
                 }
             }
             Err(e) => {
                 println!("❌ Failed to send transaction: {}", e);
-                failed += 1;
+                outcome.retryable.push(transfer);
+                continue;
+            }
+        };
+
+        nonce += 1;
+        let tx_hash = *pending_tx.tx_hash();
+        match pending_tx.with_timeout(Some(RECEIPT_TIMEOUT)).get_receipt().await {
+            Ok(receipt) => {
+                if receipt.status() {
+                    println!("✅ Success! Tx: {:?}", receipt.transaction_hash);
+                    journal.record(transfer.entry, EntryStatus::Confirmed, Some(tx_hash));
+                    outcome.successful += 1;
+                } else {
+                    println!("❌ Failed! Tx: {:?}", receipt.transaction_hash);
+                    journal.record(transfer.entry, EntryStatus::Failed, Some(tx_hash));
+                    // Likely out of gas at the cached limit; estimate afresh on retry
+                    gas_cache.invalidate(transfer.to, None);
+                    outcome.retryable.push(transfer);
+                }
+            }
+            // Broadcast but not mined in time: retrying could pay twice
+            Err(e) => {
+                println!(
+                    "⏳ No receipt within {}s, check {:?} before resending: {}",
+                    RECEIPT_TIMEOUT.as_secs(),
+                    tx_hash,
+                    e
+                );
+                journal.record(transfer.entry, EntryStatus::Unconfirmed, Some(tx_hash));
+                outcome.unconfirmed += 1;
             }
         }
 
@@ -251,13 +848,47 @@ pub async fn bulk_transfer() -> Result<()> {
         tokio::time::sleep(std::time::Duration::from_secs(1)).await;
     }
 
-    println!("\n📊 Transaction Summary:");
-    println!("====================");
-    println!("Total transactions: {}", successful + failed);
-    println!("✅ Successful: {}", successful);
-    println!("❌ Failed: {}", failed);
+    Ok(outcome)
+}
 
-    Ok(())
+/// EIP-1559 pricing with a user-chosen priority tip where the network has a base fee
+/// (or `tx_type` forces it), legacy `gas_price` otherwise. Regtest stays on legacy
+/// pricing unless EIP-1559 is forced.
+async fn choose_fee_mode(
+    client: &RootProvider<Http<Client>>,
+    config: &Config,
+    gas_price: u128,
+) -> Result<FeeMode> {
+    let base_fee = client
+        .get_fee_history(1, BlockNumberOrTag::Latest, &[])
+        .await
+        .ok()
+        .and_then(|history| history.base_fee_per_gas.last().copied())
+        .unwrap_or(0);
+    let eip1559 = match config.tx_type {
+        TxTypePreference::Legacy => false,
+        TxTypePreference::Eip1559 => true,
+        TxTypePreference::Auto => config.default_network != Network::Regtest && base_fee > 0,
+    };
+    if !eip1559 {
+        return Ok(FeeMode::Legacy { gas_price });
+    }
+
+    println!("Base fee: {} Gwei", from_wei(U256::from(base_fee), Denomination::Gwei));
+    let tip: String = Input::new()
+        .with_prompt("Priority tip (Gwei)")
+        .default(DEFAULT_PRIORITY_TIP_GWEI.to_string())
+        .validate_with(|input: &String| {
+            to_wei(input, Denomination::Gwei)
+                .map(|_| ())
+                .map_err(|_| "Please enter a tip in Gwei")
+        })
+        .interact_text()?;
+    let tip: u128 = to_wei(&tip, Denomination::Gwei)?
+        .try_into()
+        .map_err(|_| anyhow!("Priority tip '{}' is too large", tip))?;
+    let minimum = crate::utils::eth::minimum_gas_price(client).await.unwrap_or(0);
+    Ok(FeeMode::eip1559(base_fee, tip, minimum))
 }
 
 /// Parse amount string (e.g., "1.0" or "0.5") into wei
@@ -297,23 +928,3 @@ fn parse_amount(amount: &str) -> Result<U256> {
     }
 }
 
-/// Format wei amount to rBTC with 6 decimal places
-fn format_eth(wei: U256) -> String {
-    let wei_str = wei.to_string();
-    let len = wei_str.len();
-
-    if len <= 18 {
-        format!("0.{:0>18}", wei_str)
-    } else {
-        let (whole, decimal) = wei_str.split_at(len - 18);
-        let decimal = &decimal[..6.min(decimal.len())]; // Show up to 6 decimal places
-        format!("{}.{}", whole, decimal)
-    }
-}
-
-/// Format wei to Gwei
-fn format_gwei(wei: U256) -> String {
-    let gwei = wei / U256::from(1_000_000_000u64);
-    format!("{} Gwei", gwei)
-}
-
@@ -4,15 +4,18 @@ use dialoguer::{Confirm, Input, Select, theme::ColorfulTheme};
 
 // Import config and API types
 use crate::api::ApiProvider;
-use crate::config::ConfigManager;
+use crate::commands::config::{SETTINGS, current_value, reset, set_value};
+use crate::config::{ConfigManager, TxTypePreference};
 use crate::types::network::Network;
 
 // This module provides configuration management functionality
 
-pub async fn show_config_menu() -> Result<()> {
+/// Returns true once the wallet has been reset, so the app can exit
+pub async fn show_config_menu() -> Result<bool> {
     let config_manager = ConfigManager::new()?;
 
     loop {
+        super::print_status_line().await;
         // Reload config in each iteration to show current state
         let config = config_manager.load()?;
 
@@ -50,9 +53,23 @@ pub async fn show_config_menu() -> Result<()> {
             println!("  • Default Wallet: {}", style(wallet).dim());
         }
 
+        println!(
+            "  • Amounts: up to {} decimals, {}",
+            style(config.display_decimals).cyan(),
+            if config.group_thousands {
+                "grouped (1,234.5)"
+            } else {
+                "ungrouped (1234.5)"
+            }
+        );
+        println!("  • Transaction Type: {}", style(config.tx_type).cyan());
+
         let options = vec![
             format!("{}  Change Network", style("🌐").bold().blue()),
             format!("{}  Manage API Keys", style("🔑").bold().green()),
+            format!("{}  Display Settings", style("🔢").bold().cyan()),
+            format!("{}  Transaction Type", style("🧾").bold().yellow()),
+            format!("{}  Advanced Settings", style("🛠️").bold().magenta()),
             format!("{}  Clear Cache & Reset", style("🧹").bold().red()),
             format!("{}  Back to Main Menu", style("⬅️").bold().blue()),
         ];
@@ -66,26 +83,162 @@ pub async fn show_config_menu() -> Result<()> {
         match selection {
             0 => change_network(&config_manager).await?,
             1 => manage_api_keys(&config_manager).await?,
-            2 => {
-                let confirm = Confirm::new()
-                    .with_prompt("⚠️  WARNING: This will delete ALL wallet data and cannot be undone! Continue?")
-                    .default(false)
+            2 => change_display_settings(&config_manager).await?,
+            3 => change_tx_type(&config_manager).await?,
+            4 => advanced_settings(&config_manager)?,
+            5 => {
+                let modes = [
+                    "Preview what would be removed (dry run)",
+                    "Back up, then reset",
+                    "Reset without a backup",
+                    "Cancel",
+                ];
+                let mode = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Clear Cache & Reset")
+                    .items(&modes)
+                    .default(0)
                     .interact()?;
-
-                if confirm {
-                    config_manager.clear_cache()?;
-                    println!("\n✅ Cache and all wallet data have been cleared successfully.");
-                    println!("Please restart the wallet to complete the reset process.");
-                    std::process::exit(0);
-                } else {
-                    println!("\nOperation cancelled. No data was deleted.");
+                if mode == 3 {
+                    continue;
+                }
+                if reset(&config_manager, mode == 0, mode == 2)? {
+                    return Ok(true);
                 }
+                println!("\nPress Enter to continue...");
+                let _ = std::io::stdin().read_line(&mut String::new())?;
             }
-            3 => break,
+            6 => break,
             _ => {}
         }
     }
 
+    Ok(false)
+}
+
+/// Every `config set` key with its current value, editable in place
+fn advanced_settings(config_manager: &ConfigManager) -> Result<()> {
+    loop {
+        let config = config_manager.load()?;
+        clearscreen::clear().ok();
+        println!(
+            "\n{}",
+            style("🛠️  Advanced Settings").bold().blue().underlined()
+        );
+        println!("{}\n", "-".repeat(40));
+
+        let mut items: Vec<String> = SETTINGS
+            .iter()
+            .map(|(key, _)| {
+                let value = current_value(&config, key);
+                let value = if value.is_empty() {
+                    style("(not set)").dim().to_string()
+                } else {
+                    style(value).cyan().to_string()
+                };
+                format!("{:<18} {}", key, value)
+            })
+            .collect();
+        items.push(format!("{}  Back", style("⬅️").bold().blue()));
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select a setting to edit")
+            .items(&items)
+            .default(0)
+            .interact()?;
+        let Some((key, description)) = SETTINGS.get(selection) else {
+            break;
+        };
+
+        println!("\n{}", style(description).dim());
+        let value: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt(*key)
+            .with_initial_text(current_value(&config, key))
+            .allow_empty(true)
+            .interact_text()?;
+        if let Err(e) = set_value(config_manager, key, value.trim()) {
+            println!("{} {}", style("✗").red().bold(), e);
+        }
+        println!("\n{}", style("Press Enter to continue...").dim());
+        let _ = std::io::stdin().read_line(&mut String::new());
+    }
+    Ok(())
+}
+
+async fn change_tx_type(config_manager: &ConfigManager) -> Result<()> {
+    let config = config_manager.load()?;
+
+    let choices = [
+        TxTypePreference::Auto,
+        TxTypePreference::Legacy,
+        TxTypePreference::Eip1559,
+    ];
+    let labels = [
+        "Auto (detect EIP-1559 support per network)",
+        "Legacy (gasPrice)",
+        "EIP-1559 (maxFeePerGas / maxPriorityFeePerGas)",
+    ];
+    let current = choices
+        .iter()
+        .position(|c| *c == config.tx_type)
+        .unwrap_or(0);
+
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Transaction type")
+        .items(&labels)
+        .default(current)
+        .interact()?;
+
+    let tx_type = choices[selection];
+    config_manager.update(|config| {
+        config.tx_type = tx_type;
+        Ok(())
+    })?;
+
+    println!(
+        "\n{} Transaction type set to {}",
+        style("✓").green().bold(),
+        tx_type
+    );
+    println!("\n{}", style("Press Enter to continue...").dim());
+    let _ = std::io::stdin().read_line(&mut String::new());
+
+    Ok(())
+}
+
+async fn change_display_settings(config_manager: &ConfigManager) -> Result<()> {
+    let config = config_manager.load()?;
+
+    let decimals: u8 = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Maximum decimals to display for amounts (0-18)")
+        .default(config.display_decimals)
+        .validate_with(|input: &u8| {
+            if *input <= 18 {
+                Ok(())
+            } else {
+                Err("Please enter a number between 0 and 18")
+            }
+        })
+        .interact_text()?;
+
+    let group_thousands = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Group thousands with separators (e.g. 1,234.5)?")
+        .default(config.group_thousands)
+        .interact()?;
+
+    config_manager.update(|config| {
+        config.display_decimals = decimals;
+        config.group_thousands = group_thousands;
+        Ok(())
+    })?;
+    crate::utils::units::forget_display_settings();
+
+    println!(
+        "\n{} Display settings updated",
+        style("✓").green().bold()
+    );
+    println!("\n{}", style("Press Enter to continue...").dim());
+    let _ = std::io::stdin().read_line(&mut String::new());
+
     Ok(())
 }
 
@@ -142,8 +295,6 @@ async fn manage_api_keys(config_manager: &ConfigManager) -> Result<()> {
 }
 
 async fn add_api_key(config_manager: &ConfigManager) -> Result<()> {
-    let mut config = config_manager.load()?;
-
     // Select provider
     let providers = [
         (ApiProvider::RskRpc, "RSK RPC (for blockchain operations)"),
@@ -181,8 +332,7 @@ async fn add_api_key(config_manager: &ConfigManager) -> Result<()> {
     let provider = (*provider).clone();
 
     // Save the API key
-    let message = config.set_api_key(provider, key, name);
-    config_manager.save(&config)?;
+    let message = config_manager.update(|config| Ok(config.set_api_key(provider, key, name)))?;
 
     println!("\n{}", style(message).green().bold());
     println!("\n{}", style("Press Enter to continue...").dim());
@@ -192,7 +342,7 @@ async fn add_api_key(config_manager: &ConfigManager) -> Result<()> {
 }
 
 async fn remove_api_key(config_manager: &ConfigManager) -> Result<()> {
-    let mut config = config_manager.load()?;
+    let config = config_manager.load()?;
 
     if config.api.keys.is_empty() {
         println!("\n{}", style("No API keys to remove").yellow().bold());
@@ -216,8 +366,21 @@ async fn remove_api_key(config_manager: &ConfigManager) -> Result<()> {
         .items(&key_names)
         .interact()?;
 
-    let removed_key = config.api.keys.remove(selection);
-    config_manager.save(&config)?;
+    // Match by value: another instance may have changed the list since it was shown
+    let selected = config.api.keys[selection].clone();
+    let removed_key = config_manager.update(|config| {
+        let index = config
+            .api
+            .keys
+            .iter()
+            .position(|k| {
+                k.key == selected.key
+                    && k.provider == selected.provider
+                    && k.network == selected.network
+            })
+            .ok_or_else(|| anyhow::anyhow!("API key was already removed"))?;
+        Ok(config.api.keys.remove(index))
+    })?;
 
     println!(
         "\n{} Removed API key for {} ({})",
@@ -233,7 +396,7 @@ async fn remove_api_key(config_manager: &ConfigManager) -> Result<()> {
 }
 
 async fn change_network(config_manager: &ConfigManager) -> Result<()> {
-    let mut config = config_manager.load()?;
+    let config = config_manager.load()?;
 
     // Define all available networks with their display names
     let networks = [
@@ -273,10 +436,10 @@ async fn change_network(config_manager: &ConfigManager) -> Result<()> {
     let selected_network = networks[selection];
 
     // Always update the network, even if it's the same, to ensure consistency
-    config.default_network = selected_network;
-
-    // Save the updated config
-    config_manager.save(&config)?;
+    config_manager.update(|config| {
+        config.default_network = selected_network;
+        Ok(())
+    })?;
 
     println!(
         "\n{} Network changed to: {}",
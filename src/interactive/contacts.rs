@@ -1,13 +1,29 @@
 use crate::{
-    commands::contacts::{ContactsAction, ContactsCommand},
-    utils::table::TableBuilder,
+    api::ApiProvider,
+    commands::{
+        contacts::{ContactsAction, ContactsCommand},
+        history::show_history_between,
+    },
+    config::ConfigManager,
+    types::wallet::WalletData,
+    utils::{
+        address::{strip_chain_prefix, validate_address_input},
+        alchemy::AlchemyClient,
+        constants,
+        eth::EthClient,
+        helper::Config as HelperConfig,
+        table::TableBuilder,
+        units::format_amount,
+    },
 };
 use anyhow::Result;
 use console::style;
 use inquire::{Confirm, Text, validator::Validation};
+use std::path::PathBuf;
 /// Interacive contacts manage
 pub async fn manage_contacts() -> Result<()> {
     loop {
+        super::print_status_line().await;
         println!("\n{}", style("📇 Contact Management").bold());
         println!("{}", "=".repeat(30));
 
@@ -17,6 +33,11 @@ pub async fn manage_contacts() -> Result<()> {
             "✏️  Update contact",
             "❌ Remove contact",
             "🔍 Search contacts",
+            "🔀 History with a contact",
+            "📷 Import from QR",
+            "📥 Import from CSV",
+            "📤 Export to CSV",
+            "↩️  Undo last change",
             "🏠 Back to main menu",
         ];
 
@@ -28,6 +49,11 @@ pub async fn manage_contacts() -> Result<()> {
             "✏️  Update contact" => update_contact().await?,
             "❌ Remove contact" => remove_contact().await?,
             "🔍 Search contacts" => search_contacts().await?,
+            "🔀 History with a contact" => history_with_contact().await?,
+            "📷 Import from QR" => import_from_qr().await?,
+            "📥 Import from CSV" => import_csv().await?,
+            "📤 Export to CSV" => export_csv().await?,
+            "↩️  Undo last change" => undo_last_change().await?,
             "🏠 Back to main menu" => break,
             _ => unreachable!(),
         }
@@ -70,8 +96,7 @@ pub async fn list_contacts() -> Result<()> {
                 "{} txs\n{} RBTC",
                 contact.get_total_transactions(),
                 // Format balance in RBTC (18 decimals)
-                alloy::primitives::utils::format_units(contact.get_total_volume(), 18)
-                    .unwrap_or_else(|_| "N/A".to_string())
+                format_amount(contact.get_total_volume(), 18)
             )
         } else {
             "No txs".to_string()
@@ -102,34 +127,22 @@ pub async fn add_contact() -> Result<()> {
         .with_help_message("Enter a name for this contact")
         .prompt()?;
 
+    let network = ConfigManager::new()?.load()?.default_network;
     let address = Text::new("Ethereum address (0x...):")
-        .with_help_message("Enter the contact's Ethereum address")
-        .with_validator(|input: &str| {
-            if input.starts_with("0x") && input.len() == 42 {
-                Ok(Validation::Valid)
-            } else {
-                Ok(Validation::Invalid(
-                    "Please enter a valid Ethereum address (0x...)".into(),
-                ))
-            }
+        .with_help_message("Enter the contact's address (an rsk:/rsktest: prefix is accepted)")
+        .with_validator(move |input: &str| match validate_address_input(input, network) {
+            Ok(()) => Ok(Validation::Valid),
+            Err(e) => Ok(Validation::Invalid(e.into())),
         })
         .prompt()?;
+    let address = strip_chain_prefix(&address, network)?;
 
     let notes = Text::new("Notes (optional):")
         .with_help_message("Add any notes about this contact")
         .prompt_skippable()?
         .filter(|s| !s.trim().is_empty());
 
-    let tags = Text::new("Tags (comma-separated, optional):")
-        .with_help_message("e.g., friend,team,client")
-        .prompt_skippable()?
-        .map(|s| {
-            s.split(',')
-                .map(|t| t.trim().to_string())
-                .filter(|t| !t.is_empty())
-                .collect()
-        })
-        .unwrap_or_default();
+    let tags = prompt_tags()?;
 
     let cmd = ContactsCommand {
         action: ContactsAction::Add {
@@ -145,6 +158,107 @@ pub async fn add_contact() -> Result<()> {
     Ok(())
 }
 
+fn prompt_tags() -> Result<Vec<String>> {
+    Ok(Text::new("Tags (comma-separated, optional):")
+        .with_help_message("e.g., friend,team,client")
+        .prompt_skippable()?
+        .map(|s| {
+            s.split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// After a transfer, offers to save a recipient that isn't in the contact book yet
+pub async fn offer_to_save_contact(address: &str) -> Result<()> {
+    let cmd = ContactsCommand {
+        action: ContactsAction::List,
+    };
+    let known = cmd
+        .load_contacts()?
+        .iter()
+        .any(|c| format!("0x{:x}", c.address).eq_ignore_ascii_case(address));
+    // The user's own wallets don't need a contact entry
+    let own_wallet = std::fs::read_to_string(constants::wallet_file_path())
+        .ok()
+        .and_then(|data| serde_json::from_str::<WalletData>(&data).ok())
+        .is_some_and(|data| {
+            data.list_wallets()
+                .iter()
+                .any(|w| format!("0x{:x}", w.address).eq_ignore_ascii_case(address))
+        });
+    if known || own_wallet {
+        return Ok(());
+    }
+
+    let save = Confirm::new(&format!("Save {} as a contact?", address))
+        .with_default(false)
+        .prompt()?;
+    if !save {
+        return Ok(());
+    }
+
+    let name = Text::new("Contact name:")
+        .with_help_message("Enter a name for this contact")
+        .prompt()?;
+    let tags = prompt_tags()?;
+
+    cmd.add_contact(&name, address, None, tags).await
+}
+
+/// Transfers between the current wallet and a contact, with the net flow
+async fn history_with_contact() -> Result<()> {
+    let contacts = ContactsCommand {
+        action: ContactsAction::List,
+    }
+    .load_contacts()?;
+    if contacts.is_empty() {
+        println!("No contacts found.");
+        return Ok(());
+    }
+
+    let wallet_data: WalletData =
+        serde_json::from_str(&std::fs::read_to_string(constants::wallet_file_path())?)?;
+    let wallet = wallet_data
+        .get_current_wallet()
+        .ok_or_else(|| anyhow::anyhow!("No default wallet selected"))?;
+
+    let config = ConfigManager::new()?.load()?;
+    let Some(api_key) = config.get_api_key(&ApiProvider::Alchemy) else {
+        println!("Transaction history requires an Alchemy API key; add one from the Configuration menu.");
+        return Ok(());
+    };
+    let is_testnet = config.default_network.to_string().to_lowercase().contains("testnet");
+
+    let contact_names: Vec<String> = contacts
+        .iter()
+        .map(|c| format!("{} ({})", c.name, c.address))
+        .collect();
+    let selection = inquire::Select::new("Select contact:", contact_names).raw_prompt()?;
+    let contact = &contacts[selection.index];
+
+    println!(
+        "\n{}",
+        style(format!("🔀 {} ↔ {}", wallet.name, contact.name)).bold()
+    );
+    let alchemy_client = AlchemyClient::new(api_key.to_string(), is_testnet);
+    let eth_client = EthClient::new(&HelperConfig {
+        network: config.default_network.get_config(),
+        wallet: Default::default(),
+    })
+    .await?;
+    show_history_between(
+        &alchemy_client,
+        &eth_client,
+        wallet.address(),
+        contact.address,
+        100,
+    )
+    .await
+}
+
 /// Update an existing contact
 pub async fn update_contact() -> Result<()> {
     let contacts = ContactsCommand {
@@ -245,6 +359,101 @@ pub async fn remove_contact() -> Result<()> {
     Ok(())
 }
 
+/// Restore the contact list as it was before the last change
+pub async fn undo_last_change() -> Result<()> {
+    let confirmed = Confirm::new("Undo the last contact change?")
+        .with_default(false)
+        .prompt()?;
+
+    if confirmed {
+        ContactsCommand {
+            action: ContactsAction::Undo,
+        }
+        .execute()
+        .await?;
+    } else {
+        println!("Operation cancelled.");
+    }
+
+    Ok(())
+}
+
+/// Import contacts from a CSV address book (name,address,notes,tags)
+pub async fn import_csv() -> Result<()> {
+    let path = Text::new("Path to the CSV file:")
+        .with_help_message("Columns: name,address,notes,tags (tags separated by ';')")
+        .with_validator(|input: &str| {
+            if std::path::Path::new(input.trim()).is_file() {
+                Ok(Validation::Valid)
+            } else {
+                Ok(Validation::Invalid("File not found".into()))
+            }
+        })
+        .prompt()?;
+
+    let cmd = ContactsCommand {
+        action: ContactsAction::ImportCsv {
+            file: PathBuf::from(path.trim()),
+        },
+    };
+    if let Err(e) = cmd.execute().await {
+        println!("{}: {}", style("Error").red().bold(), e);
+    }
+
+    Ok(())
+}
+
+/// Export all contacts to a CSV file for spreadsheets
+pub async fn export_csv() -> Result<()> {
+    let path = Text::new("Save CSV to:")
+        .with_default("contacts.csv")
+        .prompt()?;
+
+    let cmd = ContactsCommand {
+        action: ContactsAction::ExportCsv {
+            file: PathBuf::from(path.trim()),
+        },
+    };
+    if let Err(e) = cmd.execute().await {
+        println!("{}: {}", style("Error").red().bold(), e);
+    }
+
+    Ok(())
+}
+
+/// Import contacts from a scanned address-book QR code
+pub async fn import_from_qr() -> Result<()> {
+    let sources = vec!["Paste scanned QR text", "Read QR from an image file"];
+    let source = inquire::Select::new("Where is the address-book QR?", sources).prompt()?;
+
+    let (payload, image) = if source == "Paste scanned QR text" {
+        let payload = Text::new("Paste the QR payload:")
+            .with_help_message("The contacts JSON encoded in the QR code")
+            .prompt()?;
+        (Some(payload), None)
+    } else {
+        let path = Text::new("Path to the QR image:")
+            .with_validator(|input: &str| {
+                if std::path::Path::new(input.trim()).is_file() {
+                    Ok(Validation::Valid)
+                } else {
+                    Ok(Validation::Invalid("File not found".into()))
+                }
+            })
+            .prompt()?;
+        (None, Some(PathBuf::from(path.trim())))
+    };
+
+    let cmd = ContactsCommand {
+        action: ContactsAction::ImportQr { payload, image },
+    };
+    if let Err(e) = cmd.execute().await {
+        println!("{}: {}", style("Error").red().bold(), e);
+    }
+
+    Ok(())
+}
+
 /// Search contacts by name or address
 pub async fn search_contacts() -> Result<()> {
     let query = Text::new("Search contacts (name or address):")
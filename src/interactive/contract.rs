@@ -1,154 +1,219 @@
 use crate::{
     config::ConfigManager,
-    types::network::Network,
-    wallet::load_wallet,
+    interactive::transfer_preview::print_calldata,
+    utils::{
+        address::{parse_address, validate_address_input},
+        calldata::{decode_calldata, format_value_pretty, parse_abi},
+        eth::EthClient,
+        helper::Config as HelperConfig,
+    },
 };
-use anyhow::{anyhow, Result};
-use dialoguer::{Confirm, Input, Select};
 use alloy::{
-    primitives::{Address, U256},
-    providers::{Provider, ProviderBuilder, RootProvider},
-    signers::local::PrivateKeySigner,
-    transports::http::{Client, Http},
-    sol,
+    dyn_abi::{DynSolValue, FunctionExt, JsonAbiExt, Specifier},
+    json_abi::{Function, JsonAbi, StateMutability},
+    network::TransactionBuilder,
+    primitives::Address,
+    providers::Provider,
+    rpc::types::TransactionRequest,
 };
-use std::sync::Arc;
-use std::str::FromStr;
+use anyhow::{Result, anyhow};
+use console::style;
+use dialoguer::{Confirm, Input, Select, theme::ColorfulTheme};
 
 /// Interactive menu for interacting with smart contracts
-pub async fn interact_with_contract() -> Result<()> {
-    println!("\n📝 Smart Contract Interaction");
-    println!("========================");
-
-    // Load wallet
-    let wallet_data = match load_wallet()? {
-        Some(w) => w,
-        None => return Err(anyhow!("No wallet found. Please create a wallet first.")),
-    };
-    
-    // Load config
-    let config_manager = ConfigManager::new()?;
-    let config = config_manager.load()?;
-    
-    // Get the network configuration
-    let network_config = config.default_network.get_config();
-    
-    // Get the chain ID based on the network
-    let chain_id = match config.default_network {
-        Network::RootStockMainnet => 30,
-        Network::RootStockTestnet => 31,
-        Network::Mainnet => 1,
-        Network::Testnet => 5, // Goerli
-        Network::Regtest => 1337,
-        _ => return Err(anyhow!("Unsupported network for contract interaction")),
-    };
-    
-    // Create a wallet with the chain ID
-    let private_key = wallet_data.private_key
-        .as_ref()
-        .ok_or_else(|| anyhow!("No private key found in wallet"))?;
-    
-    let wallet = private_key
-        .parse::<PrivateKeySigner>()
-        .map_err(|e| anyhow!("Failed to parse private key: {}", e))?;
-    
-    // Create provider with signer
-    let provider = ProviderBuilder::new()
-        .with_recommended_fillers()
-        .wallet(wallet)
-        .on_http(network_config.rpc_url.parse()?)
-        .map_err(|e| anyhow!("Failed to connect to RPC: {}", e))?;
-    
-    // Get contract address
-    let contract_address: String = Input::new()
-        .with_prompt("Enter contract address (0x...)")
-        .validate_with(|input: &String| {
-            if input.starts_with("0x") && input.len() == 42 {
-                Ok(())
-            } else {
-                Err("Please enter a valid contract address starting with 0x".to_string())
-            }
+pub async fn contract_menu() -> Result<()> {
+    loop {
+        super::print_status_line().await;
+        println!("\n{}", style("📝 Smart Contract Interaction").bold());
+        println!("{}", "=".repeat(30));
+
+        let options = vec![
+            format!("{}  Read Contract (view functions)", style("🔍").bold().cyan()),
+            format!("{}  Build Transaction Calldata", style("📦").bold().yellow()),
+            format!("{}  Back to Main Menu", style("⬅️").bold().blue()),
+        ];
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("What would you like to do?")
+            .items(&options)
+            .default(0)
+            .interact()?;
+
+        let result = match selection {
+            0 => read_contract().await,
+            1 => build_calldata().await,
+            _ => break,
+        };
+
+        if let Err(e) = result {
+            eprintln!("Error: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Calls `view`/`pure` functions with `eth_call` and decodes the return values.
+/// Nothing is signed or broadcast.
+async fn read_contract() -> Result<()> {
+    let (contract_address, abi) = load_contract()?;
+
+    let mut functions: Vec<&Function> = abi
+        .functions()
+        .filter(|f| {
+            matches!(
+                f.state_mutability,
+                StateMutability::View | StateMutability::Pure
+            )
         })
-        .interact()?;
-    
-    let contract_address = contract_address.parse::<Address>()
-        .map_err(|e| anyhow!("Invalid contract address: {}", e))?;
-    
-    // Get ABI file path
-    let abi_path: String = Input::new()
-        .with_prompt("Enter path to ABI JSON file")
-        .interact()?;
-    
-    // Read and parse ABI
-    let abi_content = std::fs::read_to_string(&abi_path)
-        .map_err(|e| anyhow!("Failed to read ABI file: {}", e))?;
-    
-    let abi: Abi = serde_json::from_str(&abi_content)
-        .map_err(|e| anyhow!("Failed to parse ABI: {}", e))?;
-    
-    println!("\n📋 Available functions:");
-    for (i, function) in abi.functions().enumerate() {
-        println!("{:2}. {}", i + 1, function.signature());
+        .collect();
+    if functions.is_empty() {
+        return Err(anyhow!("The ABI has no view or pure functions"));
+    }
+    functions.sort_by_key(|f| f.signature());
+
+    let config = ConfigManager::new()?.load()?;
+    let eth_client = EthClient::new(&HelperConfig {
+        network: config.default_network.get_config(),
+        wallet: Default::default(),
+    })
+    .await?;
+
+    let labels: Vec<String> = functions.iter().map(|f| f.full_signature()).collect();
+    loop {
+        let index = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select function to call")
+            .items(&labels)
+            .default(0)
+            .interact()?;
+        let function = functions[index];
+
+        let args = prompt_arguments(function)?;
+        let calldata = function
+            .abi_encode_input(&args)
+            .map_err(|e| anyhow!("Failed to encode call: {}", e))?;
+
+        let tx = TransactionRequest::default()
+            .with_to(contract_address)
+            .with_input(calldata);
+        let output = eth_client
+            .provider()
+            .call(&tx)
+            .await
+            .map_err(|e| anyhow!("Call reverted: {}", e))?;
+
+        let values = function
+            .abi_decode_output(&output, true)
+            .map_err(|e| anyhow!("Failed to decode return data: {}", e))?;
+
+        println!(
+            "\n{} {}",
+            style("↩️  Result of").bold(),
+            style(function.signature()).cyan()
+        );
+        if values.is_empty() {
+            println!("  (no return values)");
+        }
+        for (i, (param, value)) in function.outputs.iter().zip(values.iter()).enumerate() {
+            let name = if param.name.is_empty() {
+                format!("output{}", i)
+            } else {
+                param.name.clone()
+            };
+            println!(
+                "  {} ({}): {}",
+                name,
+                param.selector_type(),
+                style(format_value_pretty(value, 1)).yellow()
+            );
+        }
+
+        let again = Confirm::new()
+            .with_prompt("\nCall another function on this contract?")
+            .default(true)
+            .interact()?;
+        if !again {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Encodes a call to any function and shows the calldata for verification
+async fn build_calldata() -> Result<()> {
+    let (contract_address, abi) = load_contract()?;
+
+    let functions: Vec<&Function> = abi.functions().collect();
+    if functions.is_empty() {
+        return Err(anyhow!("The ABI has no functions"));
     }
-    
-    // Select function
-    let function_index: usize = Input::new()
-        .with_prompt("Select function to call")
+    let labels: Vec<String> = functions.iter().map(|f| f.full_signature()).collect();
+    let index = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Select function")
+        .items(&labels)
         .default(0)
         .interact()?;
-    
-    let selected_function = abi.functions().nth(function_index)
-        .ok_or_else(|| anyhow!("Invalid function index"))?;
-    
+    let selected_function = functions[index];
+
     println!("\n🔧 Function: {}", selected_function.signature());
-    
-    // TODO: Add parameter input and function call logic
-    
+    let args = prompt_arguments(selected_function)?;
+
+    let calldata = selected_function
+        .abi_encode_input(&args)
+        .map_err(|e| anyhow!("Failed to encode call: {}", e))?;
+
+    // Nothing is sent from here: the calldata is shown for use in a transaction elsewhere
+    println!("\n📦 Transaction data");
+    println!("• Contract: {}", contract_address);
+    print_calldata(
+        &calldata,
+        decode_calldata(selected_function, &calldata).ok().as_ref(),
+    );
+
     Ok(())
 }
 
-// Helper function to load wallet
-fn load_wallet() -> Result<PrivateKeySigner> {
-    // TODO: Implement wallet loading logic
-    // This is a placeholder - replace with actual wallet loading logic
-    let private_key = "0x...".to_string();
-    
-    private_key.parse::<PrivateKeySigner>()
-        .map_err(|e| anyhow!("Failed to parse private key: {}", e))
-}
+/// Prompts for a contract address and the path of its ABI JSON file
+fn load_contract() -> Result<(Address, JsonAbi)> {
+    let network = ConfigManager::new()?.load()?.default_network;
 
-// Helper function to load config
-fn load_config() -> Result<Config> {
-    // TODO: Implement config loading logic
-    // This is a placeholder - replace with actual config loading logic
-    Ok(Config::default())
-}
+    let contract_address: String = Input::new()
+        .with_prompt("Enter contract address (0x...)")
+        .validate_with(|input: &String| validate_address_input(input, network))
+        .interact()?;
+    let contract_address = parse_address(&contract_address, network)?;
 
-#[derive(Default)]
-struct Config {
-    default_network: Network,
-}
+    let abi_path: String = Input::new()
+        .with_prompt("Enter path to ABI JSON file")
+        .interact()?;
+    let abi_content = std::fs::read_to_string(&abi_path)
+        .map_err(|e| anyhow!("Failed to read ABI file: {}", e))?;
+    let abi = parse_abi(&abi_content)?;
 
-#[derive(Default)]
-enum Network {
-    #[default]
-    Mainnet,
-    Testnet,
+    Ok((contract_address, abi))
 }
 
-impl Network {
-    fn get_config(&self) -> NetworkConfig {
-        match self {
-            Network::Mainnet => NetworkConfig {
-                rpc_url: "https://public-node.rsk.co".to_string(),
-            },
-            Network::Testnet => NetworkConfig {
-                rpc_url: "https://public-node.testnet.rsk.co".to_string(),
-            },
-        }
+/// Prompts for each function input, parsed by its ABI type.
+/// Arrays are entered as `[a, b]` and tuples as `(a, b)`.
+fn prompt_arguments(function: &Function) -> Result<Vec<DynSolValue>> {
+    let mut args = Vec::with_capacity(function.inputs.len());
+    for (i, input) in function.inputs.iter().enumerate() {
+        let ty = input
+            .resolve()
+            .map_err(|e| anyhow!("Unsupported parameter type {}: {}", input.ty, e))?;
+        let name = if input.name.is_empty() {
+            format!("arg{}", i)
+        } else {
+            input.name.clone()
+        };
+        let raw: String = Input::new()
+            .with_prompt(format!("{} ({})", name, input.selector_type()))
+            .interact_text()?;
+        args.push(
+            ty.coerce_str(raw.trim())
+                .map_err(|e| anyhow!("Invalid value for {}: {}", name, e))?,
+        );
     }
-}
-
-struct NetworkConfig {
-    rpc_url: String,
+    Ok(args)
 }
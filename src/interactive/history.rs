@@ -1,6 +1,10 @@
+use crate::commands::contacts::{ContactsAction, ContactsCommand};
 use crate::commands::history::HistoryCommand;
+use crate::commands::label::LabelCommand;
 use crate::commands::tokens::{TokenRegistry, list_tokens};
-use crate::config::ConfigManager;
+use crate::api::ApiProvider;
+use crate::config::{ConfigManager, HistoryView};
+use crate::utils::cancel::CancelToken;
 use anyhow::{Context, Result};
 use console::style;
 use inquire::{Confirm, Select, Text, validator::Validation};
@@ -14,44 +18,46 @@ pub async fn show_history() -> Result<()> {
     let config_manager = ConfigManager::new()?;
     let config = config_manager.load()?;
 
+    // Start from the saved view, if any
+    let view = config.history_view.clone();
+    let default_limit = view.as_ref().map_or(10, |v| v.limit);
+
     // Network selection
     let network_options = vec!["mainnet", "testnet"];
+    let preferred_network = view
+        .as_ref()
+        .map(|v| v.network.clone())
+        .unwrap_or_else(|| config.default_network.to_string().to_lowercase());
     let network_selection = Select::new("Select network:", network_options)
-        .with_starting_cursor(
-            if config
-                .default_network
-                .to_string()
-                .to_lowercase()
-                .contains("testnet")
-            {
-                1
-            } else {
-                0
-            },
-        )
+        .with_starting_cursor(if preferred_network.contains("testnet") { 1 } else { 0 })
         .prompt()?;
 
     // Default values for the history command
     let mut command = HistoryCommand {
         address: None,
         contact: None,
-        limit: 10,
-        detailed: false,
+        limit: default_limit,
+        detailed: view.as_ref().is_some_and(|v| v.detailed),
         status: None,
         token: None,
         from: None,
         to: None,
-        sort_by: "timestamp".to_string(),
-        sort_order: "desc".to_string(),
+        sort_by: view
+            .as_ref()
+            .map_or("timestamp".to_string(), |v| v.sort_by.clone()),
+        sort_order: view
+            .as_ref()
+            .map_or("desc".to_string(), |v| v.sort_order.clone()),
+        tx_type: None,
         incoming: false,
         outgoing: false,
         export_csv: None,
-        api_key: match network_selection {
-            "mainnet" => config.alchemy_mainnet_key.clone(),
-            "testnet" => config.alchemy_testnet_key.clone(),
-            _ => None,
-        },
+        api_key: config
+            .get_api_key_for(&ApiProvider::Alchemy, network_selection)
+            .map(str::to_string),
         network: network_selection.to_string(),
+        since_last_check: false,
+        between: None,
     };
 
     // Load available tokens for the selected network
@@ -70,6 +76,9 @@ pub async fn show_history() -> Result<()> {
             " ".repeat(15)
         );
         println!("Network: {}", command.network);
+        if let Some(contact) = &command.contact {
+            println!("Contact: {}", contact);
+        }
         println!(
             "Token: {}",
             command.token.as_deref().unwrap_or("All Tokens")
@@ -83,7 +92,11 @@ pub async fn show_history() -> Result<()> {
         if command.outgoing {
             println!("Showing: Outgoing transactions");
         }
+        if command.since_last_check {
+            println!("Showing: New since last check");
+        }
         println!("Limit: {} transactions", command.limit);
+        println!("Sort: {} ({})", command.sort_by, command.sort_order);
         println!("{}", "-".repeat(40));
 
         // Check if we have an API key, prompt if not
@@ -106,13 +119,18 @@ pub async fn show_history() -> Result<()> {
 
                 if !api_key.trim().is_empty() {
                     // Save the API key using ConfigManager
-                    let mut config = config_manager.load()?;
-                    match network_selection {
-                        "mainnet" => config.alchemy_mainnet_key = Some(api_key.trim().to_string()),
-                        "testnet" => config.alchemy_testnet_key = Some(api_key.trim().to_string()),
-                        _ => {}
-                    }
-                    config_manager.save(&config)?;
+                    config_manager.update(|config| {
+                        match network_selection {
+                            "mainnet" => {
+                                config.alchemy_mainnet_key = Some(api_key.trim().to_string())
+                            }
+                            "testnet" => {
+                                config.alchemy_testnet_key = Some(api_key.trim().to_string())
+                            }
+                            _ => {}
+                        }
+                        Ok(())
+                    })?;
 
                     println!("\n{}", style("✅ API key saved successfully!").green());
                     command.api_key = Some(api_key.trim().to_string());
@@ -134,8 +152,9 @@ pub async fn show_history() -> Result<()> {
             }
         }
 
-        // Execute the command and show results
-        match command.execute().await {
+        // Execute the command and show results; ESC or q returns to this menu
+        let result = CancelToken::listen().run(command.execute()).await;
+        match result {
             Ok(_) => {}
             Err(e) => {
                 if e.to_string().contains("API key") {
@@ -159,10 +178,16 @@ pub async fn show_history() -> Result<()> {
             "Change token",
             "Change limit",
             "Filter by status",
+            "Filter by type",
+            "Filter by contact",
             "Toggle incoming/outgoing",
             "Toggle detailed view",
+            "Toggle new since last check",
+            "Change sort",
             "Clear all filters",
             "Filter by date range",
+            "Label a transaction",
+            "Save current view as default",
             "Back to main menu",
         ];
 
@@ -222,6 +247,32 @@ pub async fn show_history() -> Result<()> {
                     Some(status.to_lowercase())
                 };
             }
+            "Filter by type" => {
+                let type_options = vec![
+                    "Any",
+                    "Native",
+                    "Transfer",
+                    "Approval",
+                    "Call",
+                    "Creation",
+                ];
+                let tx_type = Select::new("Select type:", type_options).prompt()?;
+                command.tx_type = tx_type.parse().ok();
+            }
+            "Filter by contact" => {
+                let contacts = ContactsCommand {
+                    action: ContactsAction::List,
+                }
+                .load_contacts()?;
+                if contacts.is_empty() {
+                    println!("No contacts saved yet; add one from the Contacts menu");
+                    continue;
+                }
+                let mut names = vec!["My wallet".to_string()];
+                names.extend(contacts.into_iter().map(|contact| contact.name));
+                let choice = Select::new("Show the history of:", names).prompt()?;
+                command.contact = (choice != "My wallet").then_some(choice);
+            }
             "Toggle incoming/outgoing" => {
                 let options = vec!["Both", "Incoming only", "Outgoing only"];
                 let selection = Select::new("Filter transactions:", options).prompt()?;
@@ -269,14 +320,72 @@ pub async fn show_history() -> Result<()> {
                     if command.detailed { "ON" } else { "OFF" }
                 );
             }
+            "Toggle new since last check" => {
+                command.since_last_check = !command.since_last_check;
+                println!(
+                    "New since last check: {}",
+                    if command.since_last_check { "ON" } else { "OFF" }
+                );
+            }
+            "Change sort" => {
+                let sort_options = vec!["timestamp", "value", "gas"];
+                let cursor = sort_options
+                    .iter()
+                    .position(|s| *s == command.sort_by)
+                    .unwrap_or(0);
+                let sort_by = Select::new("Sort by:", sort_options)
+                    .with_starting_cursor(cursor)
+                    .prompt()?;
+                let order = Select::new("Order:", vec!["Descending", "Ascending"])
+                    .with_starting_cursor(if command.sort_order == "asc" { 1 } else { 0 })
+                    .prompt()?;
+                command.sort_by = sort_by.to_string();
+                command.sort_order = if order == "Ascending" { "asc" } else { "desc" }.to_string();
+            }
+            "Label a transaction" => {
+                let tx_hash = Text::new("Transaction hash:").prompt()?;
+                let category = Text::new("Category (e.g. income, fees; empty for none):")
+                    .prompt()?;
+                let memo = Text::new("Memo (empty for none; leave both empty to remove):")
+                    .prompt()?;
+                let result = LabelCommand {
+                    tx_hash,
+                    category,
+                    memo,
+                    remove: false,
+                }
+                .execute()
+                .await;
+                if let Err(e) = result {
+                    eprintln!("Error: {}", e);
+                }
+            }
+            "Save current view as default" => {
+                let view = HistoryView {
+                    sort_by: command.sort_by.clone(),
+                    sort_order: command.sort_order.clone(),
+                    detailed: command.detailed,
+                    limit: command.limit,
+                    network: command.network.clone(),
+                };
+                config_manager.update(|config| {
+                    config.history_view = Some(view);
+                    Ok(())
+                })?;
+                println!("✓ History will open with this view (network, sort, detail and limit)");
+                continue;
+            }
             "Clear all filters" => {
                 command.status = None;
+                command.tx_type = None;
                 command.token = None;
                 command.from = None;
                 command.to = None;
                 command.incoming = false;
                 command.outgoing = false;
-                command.limit = 10;
+                command.since_last_check = false;
+                command.contact = None;
+                command.limit = default_limit;
                 println!("✓ All filters cleared");
             }
             "Filter by date range" => {
@@ -4,24 +4,39 @@ mod balance;
 mod bulk_transfer;
 mod config;
 mod contacts;
+mod contract;
 mod history;
 mod system;
 mod tokens;
+mod tools;
 mod transfer;
 mod transfer_preview;
 mod tx;
 mod wallet;
 
+use crate::config::Config;
 use crate::utils::constants;
+use crate::utils::eth::EthClient;
+use crate::utils::helper::Config as HelperConfig;
+use crate::utils::network_guard;
+use crate::utils::receive_log;
+use crate::utils::bulk_journal::{BulkJournal, EntryStatus};
+use crate::utils::session::{Session, SessionKind};
+use crate::utils::status_cache;
+use crate::utils::units::format_amount;
+use alloy::primitives::{Address, U256};
 use anyhow::Result;
 use console::style;
 use dialoguer::{Select, theme::ColorfulTheme};
+use std::time::Duration;
 
 // Re-export public functions
 pub use self::{
     balance::show_balance, bulk_transfer::bulk_transfer, config::show_config_menu,
-    contacts::manage_contacts, history::show_history, system::system_menu, tokens::token_menu,
+    contacts::manage_contacts, contacts::offer_to_save_contact, contract::contract_menu, history::show_history,
+    system::system_menu, tokens::token_menu,
     transfer::send_funds, tx::check_transaction_status, wallet::create_wallet_with_name,
+    wallet::choose_active_wallet_if_missing, wallet::remind_unbacked_up_wallets,
     wallet::wallet_menu,
 };
 
@@ -34,6 +49,182 @@ use crate::types::network::Network;
 // Re-export the Network type for consistency
 pub use crate::types::network::Network as ConfigNetwork;
 
+/// Offers to continue a batch of recipients left unfinished by a closed terminal
+async fn offer_to_resume(session: Session, network: Network) -> Result<()> {
+    println!(
+        "{} An unfinished {} with {} recipient(s) was saved on {}",
+        style("↺").cyan(),
+        session.kind,
+        session.recipients.len(),
+        session.saved_at
+    );
+    if session.network != network.to_string().to_lowercase() {
+        println!(
+            "  It was entered on {}; switch networks to resume it",
+            session.network
+        );
+        return Ok(());
+    }
+    let options = ["Resume it now", "Keep it for later", "Discard it"];
+    match Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("What would you like to do?")
+        .items(&options)
+        .default(0)
+        .interact()?
+    {
+        0 => match session.kind {
+            SessionKind::BulkTransfer => {
+                bulk_transfer::resume_bulk_transfer(session.recipients).await
+            }
+            SessionKind::SendMany => transfer::resume_send_to_many(session).await,
+        },
+        1 => Ok(()),
+        _ => {
+            Session::clear();
+            println!("Discarded the saved session");
+            Ok(())
+        }
+    }
+}
+
+/// Offers to finish a bulk transfer that stopped before every transfer was confirmed
+async fn offer_to_resume_journal(journal: BulkJournal, network: Network) -> Result<()> {
+    println!(
+        "{} A bulk transfer started on {} has {} of {} transfer(s) confirmed",
+        style("↺").cyan(),
+        journal.started_at,
+        journal.count(EntryStatus::Confirmed),
+        journal.entries.len()
+    );
+    if journal.network != network.to_string().to_lowercase() {
+        println!("  It was sent on {}; switch networks to resume it", journal.network);
+        return Ok(());
+    }
+    let options = ["Resume it now", "Keep it for later", "Discard it"];
+    match Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("What would you like to do?")
+        .items(&options)
+        .default(0)
+        .interact()?
+    {
+        0 => bulk_transfer::resume_journal(journal).await,
+        1 => Ok(()),
+        _ => {
+            BulkJournal::clear();
+            println!("Discarded the bulk transfer journal");
+            Ok(())
+        }
+    }
+}
+
+/// Warns when `address` holds less RBTC than the low-balance threshold set for the
+/// active network. Does nothing without a threshold; a slow or unreachable node is
+/// ignored so the check never holds up startup or a finished transfer.
+pub(crate) async fn warn_if_low_balance(config: &Config, address: Address) {
+    let Some(threshold) = config.low_balance_threshold_for(config.default_network) else {
+        return;
+    };
+    let lookup = async {
+        let client = EthClient::new(&HelperConfig {
+            network: config.default_network.get_config(),
+            wallet: Default::default(),
+        })
+        .await?;
+        client.get_balance(&address, &None).await
+    };
+    let Ok(Ok(balance)) = tokio::time::timeout(Duration::from_secs(5), lookup).await else {
+        return;
+    };
+    if balance < threshold {
+        println!(
+            "  {}",
+            style(format!(
+                "⚠️  Low balance: {} RBTC is below your {} RBTC threshold; top up to keep paying gas",
+                format_amount(balance, 18),
+                format_amount(threshold, 18)
+            ))
+            .yellow()
+        );
+    }
+}
+
+/// Shows which network a send is about to use and, for mainnet transfers of more
+/// than the large-transfer threshold, asks the user to type the network name.
+/// Returns false when the user did not acknowledge it.
+pub(crate) fn confirm_active_network(config: &Config, rbtc_amount: U256) -> Result<bool> {
+    let network = config.default_network;
+    let banner = network_guard::banner(network);
+    if network.is_mainnet() {
+        println!("\n{}", style(banner).red().bold().reverse());
+    } else {
+        println!("\n{}", style(banner).cyan().bold());
+    }
+
+    let threshold = config.large_transfer_threshold_wei();
+    if !network_guard::requires_acknowledgement(network, rbtc_amount, threshold) {
+        return Ok(true);
+    }
+    let input = inquire::Text::new(&format!(
+        "This sends {} RBTC on mainnet. Type {} to continue:",
+        format_amount(rbtc_amount, 18),
+        network_guard::MAINNET_ACKNOWLEDGEMENT
+    ))
+    .with_help_message("Anything else cancels the transfer")
+    .prompt()?;
+    Ok(network_guard::is_acknowledged(&input))
+}
+
+/// Prints a one-line reminder of the active wallet, network and RBTC balance at the
+/// top of a menu. The balance is cached for a short while and skipped when the node
+/// is slow, so the line never holds up navigation.
+pub(crate) async fn print_status_line() {
+    let Ok(config) = ConfigManager::new().and_then(|manager| manager.load()) else {
+        return;
+    };
+    let network = get_network_status(config.default_network);
+    let current = std::fs::read_to_string(constants::wallet_file_path())
+        .ok()
+        .and_then(|data| serde_json::from_str::<crate::types::wallet::WalletData>(&data).ok())
+        .and_then(|data| data.get_current_wallet().cloned());
+    let Some(wallet) = current else {
+        println!("\n  {} │ {}", style("🔑 No active wallet").dim(), network);
+        return;
+    };
+
+    let address = wallet.address();
+    let key = format!("{}:0x{:x}", config.default_network, address);
+    let balance = match status_cache::balance(&key) {
+        Some(balance) => Some(balance),
+        None => {
+            let lookup = async {
+                let client = EthClient::new(&HelperConfig {
+                    network: config.default_network.get_config(),
+                    wallet: Default::default(),
+                })
+                .await?;
+                client.get_balance(&address, &None).await
+            };
+            match tokio::time::timeout(Duration::from_secs(3), lookup).await {
+                Ok(Ok(balance)) => {
+                    status_cache::store_balance(&key, balance);
+                    Some(balance)
+                }
+                _ => None,
+            }
+        }
+    };
+    let balance = match balance {
+        Some(balance) => style(format!("💰 {} RBTC", format_amount(balance, 18))).green(),
+        None => style("💰 balance unavailable".to_string()).dim(),
+    };
+    println!(
+        "\n  🔑 {} │ {} │ {}",
+        style(&wallet.name).bold(),
+        network,
+        balance
+    );
+}
+
 // Helper function to get styled network status
 fn get_network_status(network: Network) -> console::StyledObject<&'static str> {
     match network {
@@ -47,6 +238,73 @@ fn get_network_status(network: Network) -> console::StyledObject<&'static str> {
     }
 }
 
+/// Entries of the main menu, in the order they are listed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MainMenuItem {
+    CheckBalance,
+    SendFunds,
+    BulkTransfer,
+    TransactionStatus,
+    History,
+    Wallets,
+    Tokens,
+    Contacts,
+    Contracts,
+    Configuration,
+    System,
+    Exit,
+}
+
+impl MainMenuItem {
+    const ALL: [Self; 12] = [
+        Self::CheckBalance,
+        Self::SendFunds,
+        Self::BulkTransfer,
+        Self::TransactionStatus,
+        Self::History,
+        Self::Wallets,
+        Self::Tokens,
+        Self::Contacts,
+        Self::Contracts,
+        Self::Configuration,
+        Self::System,
+        Self::Exit,
+    ];
+
+    fn label(self) -> String {
+        match self {
+            Self::CheckBalance => format!("{}  Check Balance", style("💰").bold().green()),
+            Self::SendFunds => format!("{}  Send Funds", style("💸").bold().yellow()),
+            Self::BulkTransfer => format!("{}  Bulk Transfer", style("📤").bold().yellow()),
+            Self::TransactionStatus => {
+                format!("{}  Check Transaction Status", style("🔍").bold().cyan())
+            }
+            Self::History => format!("{}  Transaction History", style("📜").bold().cyan()),
+            Self::Wallets => format!("{}  Wallet Management", style("🔑").bold().blue()),
+            Self::Tokens => format!("{}  Token Management", style("🪙").bold().magenta()),
+            Self::Contacts => format!("{}  Contact Management", style("📇").bold().cyan()),
+            Self::Contracts => format!("{}  Smart Contracts", style("📝").bold().magenta()),
+            Self::Configuration => format!("{}  Configuration", style("⚙️").bold().white()),
+            Self::System => format!("{}  System", style("💻").bold().cyan()),
+            Self::Exit => format!("{}  Exit", style("🚪").bold().red()),
+        }
+    }
+}
+
+/// Maps the main menu prompt's result to an entry; Ctrl+C at the prompt exits
+fn main_menu_choice(selection: dialoguer::Result<usize>) -> Result<MainMenuItem> {
+    match selection {
+        Ok(index) => MainMenuItem::ALL
+            .get(index)
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("No main menu entry {}", index)),
+        Err(dialoguer::Error::IO(e)) if e.kind() == std::io::ErrorKind::Interrupted => {
+            Ok(MainMenuItem::Exit)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
 /// Starts the interactive CLI interface
 pub async fn start() -> Result<()> {
     // Clear the screen for a fresh start
@@ -70,68 +328,114 @@ pub async fn start() -> Result<()> {
     println!("  {}", style("🟢 Online").green());
     println!("  {}", get_network_status(config.default_network));
 
+    if let Err(e) = choose_active_wallet_if_missing().await {
+        eprintln!("Could not set an active wallet: {}", e);
+    }
+
     // Check if wallet data file exists and count wallets
     let wallet_file = constants::wallet_file_path();
-    let wallet_count = if wallet_file.exists() {
-        match std::fs::read_to_string(&wallet_file) {
-            Ok(contents) => {
-                match serde_json::from_str::<crate::types::wallet::WalletData>(&contents) {
-                    Ok(wallet_data) => wallet_data.wallets.len(),
-                    Err(_) => 0,
-                }
-            }
-            Err(_) => 0,
-        }
-    } else {
-        0
-    };
+    let wallet_data = std::fs::read_to_string(&wallet_file)
+        .ok()
+        .and_then(|contents| {
+            serde_json::from_str::<crate::types::wallet::WalletData>(&contents).ok()
+        });
+    let wallet_count = wallet_data.as_ref().map_or(0, |data| data.wallets.len());
 
     let wallet_text = match wallet_count {
         0 => "💼 No wallets loaded".to_string(),
         1 => "💼 1 wallet loaded".to_string(),
         _ => format!("💼 {} wallets loaded", wallet_count),
     };
-    println!("  {}\n", style(wallet_text).dim());
+    println!("  {}", style(wallet_text).dim());
+    // Make it obvious which wallet operations will use
+    if let Some(current) = wallet_data.as_ref().and_then(|data| data.get_current_wallet()) {
+        match current.label_display() {
+            Some(label) => println!("  🔑 Active: {}  {}", style(&current.name).bold(), label),
+            None => println!("  🔑 Active: {}", style(&current.name).bold()),
+        }
+        warn_if_low_balance(&config, current.address()).await;
+        if config.address_reuse_warning {
+            let network = config.default_network.to_string();
+            let count = receive_log::count(&network, &current.address());
+            if let Some(warning) = receive_log::reuse_warning(&current.address(), count) {
+                println!("  {}", style(format!("🔒 {}", warning)).yellow());
+            }
+        }
+    }
+    println!();
 
-    loop {
-        let options = vec![
-            format!("{}  Check Balance", style("💰").bold().green()),
-            format!("{}  Send Funds", style("💸").bold().yellow()),
-            format!("{}  Bulk Transfer", style("📤").bold().yellow()),
-            format!("{}  Check Transaction Status", style("🔍").bold().cyan()),
-            format!("{}  Transaction History", style("📜").bold().cyan()),
-            format!("{}  Wallet Management", style("🔑").bold().blue()),
-            format!("{}  Token Management", style("🪙").bold().magenta()),
-            format!("{}  Contact Management", style("📇").bold().cyan()),
-            format!("{}  Configuration", style("⚙️").bold().white()),
-            format!("{}  System", style("💻").bold().cyan()),
-            format!("{}  Exit", style("🚪").bold().red()),
-        ];
+    if let Err(e) = remind_unbacked_up_wallets().await {
+        eprintln!("Could not check wallet backups: {}", e);
+    }
+    if let Some(session) = Session::load()
+        && let Err(e) = offer_to_resume(session, config.default_network).await
+    {
+        eprintln!("Could not resume the saved session: {}", e);
+    }
+    if let Some(journal) = BulkJournal::load().filter(|journal| !journal.is_finished())
+        && let Err(e) = offer_to_resume_journal(journal, config.default_network).await
+    {
+        eprintln!("Could not resume the bulk transfer: {}", e);
+    }
 
+    loop {
+        print_status_line().await;
+        let options: Vec<String> = MainMenuItem::ALL.iter().map(|item| item.label()).collect();
         let selection = Select::with_theme(&ColorfulTheme::default())
             .with_prompt("\nWhat would you like to do?")
             .items(&options)
             .default(0)
-            .interact()?;
-
-        match selection {
-            0 => show_balance().await?,
-            1 => send_funds().await?,
-            2 => bulk_transfer().await?,
-            3 => check_transaction_status().await?,
-            4 => show_history().await?,
-            5 => wallet_menu().await?,
-            6 => token_menu().await?,
-            7 => manage_contacts().await?,
-            8 => show_config_menu().await?,
-            9 => system_menu().await?,
-            10 => {
+            .interact();
+
+        match main_menu_choice(selection)? {
+            MainMenuItem::CheckBalance => show_balance().await?,
+            MainMenuItem::SendFunds => send_funds().await?,
+            MainMenuItem::BulkTransfer => bulk_transfer().await?,
+            MainMenuItem::TransactionStatus => check_transaction_status().await?,
+            MainMenuItem::History => show_history().await?,
+            MainMenuItem::Wallets => wallet_menu().await?,
+            MainMenuItem::Tokens => token_menu().await?,
+            MainMenuItem::Contacts => manage_contacts().await?,
+            MainMenuItem::Contracts => contract_menu().await?,
+            MainMenuItem::Configuration => {
+                if show_config_menu().await? {
+                    break;
+                }
+            }
+            MainMenuItem::System => system_menu().await?,
+            MainMenuItem::Exit => {
+                let _ = console::Term::stdout().show_cursor();
                 println!("\n👋 Goodbye!");
                 break;
             }
-            _ => unreachable!(),
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_main_menu_entry_is_listed_once() {
+        for (index, item) in MainMenuItem::ALL.iter().enumerate() {
+            assert_eq!(main_menu_choice(Ok(index)).unwrap(), *item);
+            assert_eq!(MainMenuItem::ALL.iter().filter(|other| *other == item).count(), 1);
+        }
+        assert_eq!(MainMenuItem::ALL.last(), Some(&MainMenuItem::Exit));
+        assert!(main_menu_choice(Ok(MainMenuItem::ALL.len())).is_err());
+    }
+
+    #[test]
+    fn test_interrupted_prompt_exits() {
+        let interrupted = std::io::Error::new(std::io::ErrorKind::Interrupted, "read interrupted");
+        assert_eq!(
+            main_menu_choice(Err(dialoguer::Error::IO(interrupted))).unwrap(),
+            MainMenuItem::Exit
+        );
+        let other = std::io::Error::other("broken pipe");
+        assert!(main_menu_choice(Err(dialoguer::Error::IO(other))).is_err());
+    }
+}
@@ -1,13 +1,24 @@
-use crate::config::ConfigManager;
+use crate::commands::bridge::{BridgeAction, BridgeCommand};
+use crate::commands::lookup::LookupCommand;
+use crate::commands::networks::NetworksCommand;
+use crate::commands::rpc::RpcCommand;
+use crate::commands::watch::WatchCommand;
+use crate::config::{ConfigManager, HealthCheckSettings};
+use crate::interactive::tools::tools_menu;
 use crate::types::network::Network;
+use crate::utils::cancel::CancelToken;
+use crate::utils::clock::{CLOCK_WARNING_SECS, clock_offset};
 use crate::utils::eth::EthClient;
-use crate::utils::helper::Config;
+use crate::utils::health::{
+    BlockTiming, Congestion, Health, assess, average_block_time, congestion, sample_window,
+};
+use crate::utils::helper::{Config, Helper};
+use crate::utils::retry::{RetryPolicy, retry};
 use crate::utils::terminal::{self, show_version};
 use anyhow::Result;
 use console::style;
-use dialoguer::{Select, theme::ColorfulTheme};
-use alloy::primitives::U256;
-use alloy::providers::Provider;
+use dialoguer::{Input, Select, theme::ColorfulTheme};
+use alloy::providers::{Provider, ProviderBuilder};
 use std::io;
 use std::time::Duration;
 
@@ -48,21 +59,101 @@ async fn get_gas_price(eth_client: &EthClient) -> Result<u128> {
         .map_err(|_| anyhow::anyhow!("Failed to get gas price"))
 }
 
-/// Check network health by measuring block time
-async fn check_network_health(eth_client: &EthClient) -> Result<String> {
-    let start_block = get_block_number(eth_client).await?;
-    tokio::time::sleep(Duration::from_secs(2)).await; // Wait 2 seconds
-    let end_block = get_block_number(eth_client).await?;
+/// Timestamp of block `number`
+async fn block_timestamp(eth_client: &EthClient, number: u64, policy: &RetryPolicy) -> Result<u64> {
+    let block: serde_json::Value = retry(policy, || {
+        eth_client
+            .provider()
+            .raw_request("eth_getBlockByNumber".into(), (format!("0x{:x}", number), false))
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("Failed to get block {}: {}", number, e))?;
+    block["timestamp"]
+        .as_str()
+        .and_then(|ts| u64::from_str_radix(ts.trim_start_matches("0x"), 16).ok())
+        .ok_or_else(|| anyhow::anyhow!("Block {} has no timestamp", number))
+}
+
+/// Health check requests are retried `retries` times, a second apart at first
+fn health_retry_policy(settings: &HealthCheckSettings) -> RetryPolicy {
+    RetryPolicy::new(settings.retries.saturating_add(1), Duration::from_secs(1))
+}
+
+/// Check network health from the average block time over recent blocks and the
+/// age of the newest one, against the block time expected on this network
+async fn check_network_health(
+    eth_client: &EthClient,
+    settings: &HealthCheckSettings,
+) -> Result<String> {
+    let policy = health_retry_policy(settings);
+    let latest = retry(&policy, || eth_client.provider().get_block_number())
+        .await
+        .map_err(|_| anyhow::anyhow!("Failed to get block number"))?;
+    let Some((earlier, samples)) = sample_window(latest, settings.samples) else {
+        return Ok(format!("⚪ Not enough blocks to measure (latest block {})", latest));
+    };
+    let latest_ts = block_timestamp(eth_client, latest, &policy).await?;
+    let earlier_ts = block_timestamp(eth_client, earlier, &policy).await?;
 
-    let block_diff = end_block.saturating_sub(start_block);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+    let timing = BlockTiming {
+        average_block_secs: average_block_time(earlier_ts, latest_ts, samples),
+        latest_block_age_secs: now.saturating_sub(latest_ts),
+    };
+    let details = format!(
+        "avg block time {:.1}s over {} blocks (expected ~{}s), latest block {}s ago",
+        timing.average_block_secs,
+        samples,
+        settings.expected_block_secs,
+        timing.latest_block_age_secs
+    );
 
-    Ok(match block_diff {
-        0 => "🟡 Idle (no new blocks in 2s)".to_string(),
-        1 => "🟢 Healthy (1 new block in 2s)".to_string(),
-        _ => format!("🟢 Very Healthy ({} new blocks in 2s)", block_diff),
+    Ok(match assess(&timing, settings.expected_block_secs) {
+        Health::Healthy => format!("🟢 Healthy ({})", details),
+        Health::Slow => format!("🟡 Slow ({})", details),
+        Health::Stalled => format!("🔴 Stalled ({})", details),
     })
 }
 
+/// Compares the head block of each extra endpoint with the active node's;
+/// `keys` are masked in the printed URLs
+async fn compare_endpoints(
+    eth_client: &EthClient,
+    settings: &HealthCheckSettings,
+    keys: &[Option<&str>],
+) {
+    let Ok(reference) = get_block_number(eth_client).await else {
+        return;
+    };
+    for endpoint in &settings.endpoints {
+        let head = match endpoint.parse() {
+            Ok(url) => {
+                let provider = ProviderBuilder::new().on_http(url);
+                retry(&health_retry_policy(settings), || provider.get_block_number())
+                    .await
+                    .map_err(|e| anyhow::anyhow!("{}", e))
+            }
+            Err(e) => Err(anyhow::anyhow!("invalid URL: {}", e)),
+        };
+        let url = Helper::mask_url(endpoint, keys);
+        match head {
+            Ok(head) if head + 2 >= reference => {
+                println!("  {} {}: block {}", style("✓").green(), url, head)
+            }
+            Ok(head) => println!(
+                "  {} {}: block {} ({} behind)",
+                style("⚠").yellow(),
+                url,
+                head,
+                reference - head
+            ),
+            Err(e) => println!("  {} {}: {}", style("✗").red(), url, e),
+        }
+    }
+}
+
 /// Display system information including network status and API key configuration
 async fn show_system_info() -> Result<()> {
     let config_manager = ConfigManager::new()?;
@@ -73,22 +164,9 @@ async fn show_system_info() -> Result<()> {
     println!("• Network: {}", get_network_status(&config.default_network));
 
     // Show configuration status
-    match config.default_network {
-        Network::Mainnet => {
-            let has_key = config
-                .alchemy_mainnet_key
-                .as_ref()
-                .map_or(false, |k| !k.is_empty());
-            println!("• Service Configuration: {}", get_config_status(has_key));
-        }
-        Network::Testnet => {
-            let has_key = config
-                .alchemy_testnet_key
-                .as_ref()
-                .map_or(false, |k| !k.is_empty());
-            println!("• Service Configuration: {}", get_config_status(has_key));
-        }
-        _ => {}
+    if matches!(config.default_network, Network::Mainnet | Network::Testnet) {
+        let has_key = config.get_alchemy_key().is_some_and(|k| !k.is_empty());
+        println!("• Service Configuration: {}", get_config_status(has_key));
     }
 
     // Show network details if connected
@@ -100,7 +178,7 @@ async fn show_system_info() -> Result<()> {
         wallet: Default::default(),
     };
 
-    match EthClient::new(&helper_config, None).await {
+    match EthClient::new(&helper_config).await {
         Ok(eth_client) => {
             // Get current block number
             match get_block_number(&eth_client).await {
@@ -119,12 +197,65 @@ async fn show_system_info() -> Result<()> {
                 }
                 Err(_) => println!("• Current Gas Price: {}", style("Unavailable").red().bold()),
             }
+            if let Some(minimum) = crate::utils::eth::minimum_gas_price(eth_client.provider()).await {
+                println!(
+                    "• Minimum Gas Price: {} Gwei",
+                    style(format!("{:.2}", minimum as f64 / 1_000_000_000.0)).yellow()
+                );
+            }
+
+            // Base fee and congestion, on networks that have a base fee
+            match eth_client.base_fee_history(20).await {
+                Some(fees) if fees.len() > 1 => {
+                    let (current, recent) = fees.split_last().unwrap();
+                    let indicator = match congestion(*current, recent) {
+                        Congestion::Low => style("🟢 Low").green(),
+                        Congestion::Normal => style("🟡 Normal").yellow(),
+                        Congestion::High => style("🔴 High").red(),
+                    };
+                    println!(
+                        "• Base Fee: {} Gwei ({} congestion vs. the last {} blocks)",
+                        style(format!("{:.2}", *current as f64 / 1_000_000_000.0)).yellow(),
+                        indicator,
+                        recent.len()
+                    );
+                }
+                _ => println!(
+                    "• Base Fee: {}",
+                    style("Not used on this network (legacy gas pricing)").dim()
+                ),
+            }
 
             // Check network health
-            match check_network_health(&eth_client).await {
-                Ok(health) => println!("• Network Health: {}", health),
+            let settings = config.health_check_for(config.default_network);
+            match CancelToken::listen()
+                .run(check_network_health(&eth_client, &settings))
+                .await
+            {
+                Ok(Some(health)) => println!("• Network Health: {}", health),
+                Ok(None) => {}
                 Err(_) => println!("• Network Health: {}", style("Unavailable").red().bold()),
             }
+            match clock_offset(&config.rpc_url_for(config.default_network).0).await {
+                Ok(offset) if offset.abs() > CLOCK_WARNING_SECS => println!(
+                    "• System Clock: {}",
+                    style(format!(
+                        "{}s {} the node; fix the system clock to avoid timestamp errors",
+                        offset.abs(),
+                        if offset > 0 { "ahead of" } else { "behind" }
+                    ))
+                    .yellow()
+                ),
+                Ok(offset) => {
+                    println!("• System Clock: {} ({:+}s)", style("In sync").green(), offset)
+                }
+                Err(_) => println!("• System Clock: {}", style("Could not compare").dim()),
+            }
+            if !settings.endpoints.is_empty() {
+                println!("• Other Endpoints:");
+                let keys = [config.get_rsk_rpc_key(), config.get_alchemy_key()];
+                compare_endpoints(&eth_client, &settings, &keys).await;
+            }
         }
         Err(e) => {
             println!("• Network Status: {}", style("Disconnected").red().bold());
@@ -136,34 +267,153 @@ async fn show_system_info() -> Result<()> {
     Ok(())
 }
 
+/// Entries of the system menu, in the order they are listed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SystemMenuItem {
+    ClearScreen,
+    Version,
+    NetworkStatus,
+    Networks,
+    Federation,
+    PegoutStatus,
+    RawRpc,
+    DataFiles,
+    Compact,
+    Watch,
+    Lookup,
+    Tools,
+    Back,
+}
+
+impl SystemMenuItem {
+    const ALL: [Self; 13] = [
+        Self::ClearScreen,
+        Self::Version,
+        Self::NetworkStatus,
+        Self::Networks,
+        Self::Federation,
+        Self::PegoutStatus,
+        Self::RawRpc,
+        Self::DataFiles,
+        Self::Compact,
+        Self::Watch,
+        Self::Lookup,
+        Self::Tools,
+        Self::Back,
+    ];
+
+    fn label(self) -> String {
+        match self {
+            Self::ClearScreen => format!("{}  Clear Screen", style("🧹").bold().cyan()),
+            Self::Version => format!("{}  Show Version", style("ℹ️").bold().blue()),
+            Self::NetworkStatus => format!("{}  Network Status", style("🌐").bold().green()),
+            Self::Networks => format!("{}  Networks & Endpoints", style("🗺️").bold().green()),
+            Self::Federation => format!("{}  Powpeg Federation", style("🌉").bold().yellow()),
+            Self::PegoutStatus => format!("{}  Peg-out Status", style("🔁").bold().yellow()),
+            Self::RawRpc => format!("{}  Raw JSON-RPC Call", style("🧪").bold().magenta()),
+            Self::DataFiles => format!("{}  Data Files", style("📁").bold().cyan()),
+            Self::Compact => format!("{}  Compact Data Files", style("🗜️").bold().cyan()),
+            Self::Watch => format!("{}  Watch Address", style("👀").bold().green()),
+            Self::Lookup => format!("{}  Address Lookup", style("🔎").bold().green()),
+            Self::Tools => format!("{}  Tools", style("🧰").bold().yellow()),
+            Self::Back => format!("{}  Back to Main Menu", style("⬅️").bold().white()),
+        }
+    }
+}
+
 /// System menu for various system-related commands
 pub async fn system_menu() -> Result<()> {
     loop {
-        let options = vec![
-            format!("{}  Clear Screen", style("🧹").bold().cyan()),
-            format!("{}  Show Version", style("ℹ️").bold().blue()),
-            format!("{}  Network Status", style("🌐").bold().green()),
-            format!("{}  Back to Main Menu", style("⬅️").bold().white()),
-        ];
+        super::print_status_line().await;
+        let options: Vec<String> = SystemMenuItem::ALL.iter().map(|item| item.label()).collect();
 
         let selection = Select::with_theme(&ColorfulTheme::default())
             .with_prompt("\nSystem Menu")
             .items(&options)
             .default(0)
             .interact()?;
+        let item = SystemMenuItem::ALL[selection];
 
-        let result = match selection {
-            0 => {
+        let result = match item {
+            SystemMenuItem::ClearScreen => {
                 terminal::clear_screen();
                 Ok(())
             }
-            1 => {
+            SystemMenuItem::Version => {
                 show_version();
                 Ok(())
             }
-            2 => show_system_info().await,
-            3 => break,
-            _ => Ok(()),
+            SystemMenuItem::NetworkStatus => show_system_info().await,
+            SystemMenuItem::Networks => NetworksCommand {}.execute().await,
+            SystemMenuItem::Federation => {
+                BridgeCommand {
+                    action: BridgeAction::Federation,
+                }
+                .execute()
+                .await
+            }
+            SystemMenuItem::PegoutStatus => {
+                let tx_hash: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Peg-out request transaction hash")
+                    .interact_text()?;
+                BridgeCommand {
+                    action: BridgeAction::PegoutStatus { tx_hash },
+                }
+                .execute()
+                .await
+            }
+            SystemMenuItem::RawRpc => {
+                let method: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Method (e.g. eth_blockNumber)")
+                    .interact_text()?;
+                let params: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Params (JSON array)")
+                    .default("[]".to_string())
+                    .interact_text()?;
+                RpcCommand {
+                    method: method.trim().to_string(),
+                    params,
+                    network: None,
+                }
+                .execute()
+                .await
+            }
+            SystemMenuItem::DataFiles => crate::config::run_file_audit(),
+            SystemMenuItem::Compact => crate::config::run_compact(),
+            SystemMenuItem::Watch => {
+                let address: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Address to watch")
+                    .interact_text()?;
+                let threshold: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Alert threshold in RBTC (empty keeps the saved one, 0 alerts on every change)")
+                    .allow_empty(true)
+                    .interact_text()?;
+                let notify = dialoguer::Confirm::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Show desktop notifications?")
+                    .default(false)
+                    .interact()?;
+                WatchCommand {
+                    address,
+                    threshold: (!threshold.trim().is_empty()).then_some(threshold),
+                    interval: 15,
+                    notify,
+                }
+                .execute()
+                .await
+            }
+            SystemMenuItem::Lookup => {
+                let address: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Address to look up")
+                    .interact_text()?;
+                LookupCommand {
+                    address,
+                    refresh: false,
+                }
+                .execute()
+                .await
+            }
+            SystemMenuItem::Tools => tools_menu().await,
+            SystemMenuItem::Back => break,
         };
 
         if let Err(e) = result {
@@ -171,8 +421,8 @@ pub async fn system_menu() -> Result<()> {
             continue;
         }
 
-        if selection < 3 {
-            // Don't pause after "Back"
+        if !matches!(item, SystemMenuItem::Tools | SystemMenuItem::Back) {
+            // Tools has its own menu to return to, so there is nothing to pause over
             println!("\nPress Enter to continue...");
             let _ = io::stdin().read_line(&mut String::new())?;
         }
@@ -1,4 +1,7 @@
 use crate::commands::tokens;
+use crate::config::{Config, ConfigManager};
+use crate::types::network::Network;
+use crate::utils::address::{strip_chain_prefix, validate_address_input};
 use anyhow::Result;
 use console::style;
 use inquire::validator::Validation;
@@ -6,10 +9,13 @@ use inquire::validator::Validation;
 /// Displays the token management menu
 pub async fn token_menu() -> Result<()> {
     loop {
+        super::print_status_line().await;
         let options = vec![
             String::from("➕ Add Token"),
             String::from("🗑️ Remove Token"),
             String::from("📋 List Tokens"),
+            String::from("⭐ Set Default Token"),
+            String::from("🔄 Refresh Token Metadata"),
             String::from("🏠 Back to Main Menu"),
         ];
 
@@ -21,6 +27,25 @@ pub async fn token_menu() -> Result<()> {
             "➕ Add Token" => add_token().await?,
             "🗑️ Remove Token" => remove_token().await?,
             "📋 List Tokens" => list_tokens().await?,
+            "⭐ Set Default Token" => set_default_token().await?,
+            "🔄 Refresh Token Metadata" => {
+                match tokens::refresh_metadata(None) {
+                    Ok(refreshed) => println!(
+                        "\n{} {}",
+                        style("✅ Token metadata refreshed:").green(),
+                        style(format!(
+                            "{} token(s) will be re-read from the chain on next use",
+                            refreshed
+                        ))
+                        .bold()
+                    ),
+                    Err(e) => eprintln!(
+                        "\n{} {}",
+                        style("❌ Failed to refresh token metadata:").red(),
+                        style(e).bold()
+                    ),
+                }
+            }
             _ => break,
         }
     }
@@ -43,17 +68,20 @@ async fn add_token() -> Result<()> {
         .with_help_message("Enter the token's ticker symbol")
         .prompt()?;
 
+    let token_network = if network == "mainnet" {
+        Network::Mainnet
+    } else {
+        Network::Testnet
+    };
     let address = inquire::Text::new("Token contract address (0x...):")
-        .with_validator(|input: &str| {
-            if input.starts_with("0x") && input.len() == 42 {
-                Ok(Validation::Valid)
-            } else {
-                Ok(Validation::Invalid(
-                    "Please enter a valid token contract address (0x...)".into(),
-                ))
+        .with_validator(move |input: &str| {
+            match validate_address_input(input, token_network) {
+                Ok(()) => Ok(Validation::Valid),
+                Err(e) => Ok(Validation::Invalid(e.into())),
             }
         })
         .prompt()?;
+    let address = strip_chain_prefix(&address, token_network)?;
 
     let decimals = inquire::Text::new("Token decimals (e.g., 18):")
         .with_default("18")
@@ -75,13 +103,7 @@ async fn add_token() -> Result<()> {
                 style(format!("{} ({}) on {}", symbol, address, network)).bold()
             );
         }
-        Err(e) => {
-            eprintln!(
-                "\n{} {}",
-                style("❌ Failed to add token:").red(),
-                style(e).bold()
-            );
-        }
+        Err(e) => print_registry_error("❌ Failed to add token:", e.as_ref()),
     }
 
     Ok(())
@@ -113,13 +135,7 @@ async fn remove_token() -> Result<()> {
                 style(&symbol).bold()
             );
         }
-        Err(e) => {
-            eprintln!(
-                "\n{} {}",
-                style("❌ Failed to remove token:").red(),
-                style(e).bold()
-            );
-        }
+        Err(e) => print_registry_error("❌ Failed to remove token:", e.as_ref()),
     }
 
     Ok(())
@@ -171,3 +187,81 @@ async fn list_tokens() -> Result<()> {
 
     Ok(())
 }
+
+/// Index of the configured default token among picker entries like "RIF" or "RBTC (Native)".
+/// Falls back to the first entry when no default is set or it is no longer in the registry.
+pub fn default_token_index(names: &[String], config: &Config, network: &str) -> usize {
+    let Some(default) = config.default_token(network) else {
+        return 0;
+    };
+    names
+        .iter()
+        .position(|name| {
+            name.split_whitespace()
+                .next()
+                .is_some_and(|symbol| symbol.eq_ignore_ascii_case(default))
+        })
+        .unwrap_or(0)
+}
+
+async fn set_default_token() -> Result<()> {
+    println!("\n{}", style("⭐ Set Default Token").bold());
+    println!("{}", "=".repeat(30));
+
+    let network = inquire::Select::new(
+        "Select network:",
+        vec![String::from("mainnet"), String::from("testnet")],
+    )
+    .prompt()?;
+
+    let mut choices = vec![String::from("RBTC")];
+    match tokens::list_tokens(Some(&network)) {
+        Ok(registry_tokens) => {
+            let mut symbols: Vec<String> =
+                registry_tokens.into_iter().map(|(symbol, _)| symbol).collect();
+            symbols.sort();
+            choices.extend(symbols);
+        }
+        Err(e) => eprintln!("⚠️  Warning: Could not load token registry: {}", e),
+    }
+    let clear_option = String::from("None (always start at RBTC)");
+    choices.push(clear_option.clone());
+
+    let config_manager = ConfigManager::new()?;
+    let config = config_manager.load()?;
+    let current = config
+        .default_token(&network)
+        .and_then(|symbol| choices.iter().position(|c| c.eq_ignore_ascii_case(symbol)))
+        .unwrap_or(0);
+
+    let symbol = inquire::Select::new("Default token:", choices)
+        .with_starting_cursor(current)
+        .prompt()?;
+
+    if symbol == clear_option {
+        config_manager.update(|config| {
+            config.default_tokens.remove(&network);
+            Ok(())
+        })?;
+        println!("\n✅ Default token cleared for {}", network);
+    } else {
+        println!("\n✅ Default token for {} set to {}", network, symbol);
+        config_manager.update(|config| {
+            config.default_tokens.insert(network, symbol);
+            Ok(())
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Prints why a token list change failed; a failed write says so plainly, since
+/// the tokens on disk are then still the previous list
+fn print_registry_error(prefix: &str, e: &(dyn std::error::Error + 'static)) {
+    if let Some(save_error) = e.downcast_ref::<tokens::SaveTokensError>() {
+        eprintln!("\n{} {}", style("❌").red(), style(save_error).bold());
+        eprintln!("   Your token list was left unchanged.");
+    } else {
+        eprintln!("\n{} {}", style(prefix).red(), style(e).bold());
+    }
+}
@@ -0,0 +1,154 @@
+use crate::commands::hash::HashCommand;
+use crate::commands::raw_tx;
+use crate::utils::qr::{decode_qr_image, join_frames};
+use crate::utils::units::{Denomination, decimal_to_hex, from_wei, hex_to_decimal, to_wei};
+use anyhow::Result;
+use console::style;
+use dialoguer::{Input, Select, theme::ColorfulTheme};
+use std::path::Path;
+
+/// Converters for debugging transactions without leaving the CLI
+pub async fn tools_menu() -> Result<()> {
+    loop {
+        super::print_status_line().await;
+        let options = vec![
+            format!("{}  Unit Converter (wei / gwei / RBTC)", style("⚖️").bold().yellow()),
+            format!("{}  Hex ↔ Decimal", style("🔢").bold().cyan()),
+            format!("{}  Keccak256 Hash", style("#️⃣").bold().magenta()),
+            format!("{}  Air-gapped Transaction (QR)", style("📷").bold().green()),
+            format!("{}  Back", style("⬅️").bold().white()),
+        ];
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("\nTools")
+            .items(&options)
+            .default(0)
+            .interact()?;
+
+        let result = match selection {
+            0 => convert_units(),
+            1 => convert_hex(),
+            2 => hash_input().await,
+            3 => air_gapped_transaction().await,
+            _ => break,
+        };
+
+        if let Err(e) = result {
+            eprintln!("Error: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+fn convert_units() -> Result<()> {
+    let names: Vec<&str> = Denomination::ALL.iter().map(|unit| unit.name()).collect();
+    let unit = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Convert from")
+        .items(&names)
+        .default(2)
+        .interact()?;
+    let unit = Denomination::ALL[unit];
+
+    let amount: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!("Amount in {}", unit.name()))
+        .interact_text()?;
+    let wei = to_wei(&amount, unit)?;
+
+    println!();
+    for unit in Denomination::ALL {
+        println!("  {:>5}: {}", unit.name(), style(from_wei(wei, unit)).green());
+    }
+    Ok(())
+}
+
+fn convert_hex() -> Result<()> {
+    let input: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Number (0x… for hex, otherwise decimal)")
+        .interact_text()?;
+    let input = input.trim();
+
+    if input.starts_with("0x") || input.starts_with("0X") {
+        println!("  Decimal: {}", style(hex_to_decimal(input)?).green());
+    } else {
+        println!("  Hex: {}", style(decimal_to_hex(input)?).green());
+    }
+    Ok(())
+}
+
+async fn hash_input() -> Result<()> {
+    let kinds = ["Text", "Hex data (0x…)", "File"];
+    let kind = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Hash")
+        .items(&kinds)
+        .default(0)
+        .interact()?;
+    let input: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt(kinds[kind])
+        .interact_text()?;
+    let verify: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Expected digest to verify (empty to skip)")
+        .allow_empty(true)
+        .interact_text()?;
+
+    if kind == 2 && !std::path::Path::new(input.trim()).is_file() {
+        anyhow::bail!("File not found: {}", input.trim());
+    }
+    HashCommand {
+        keccak256: if kind == 2 { input.trim().to_string() } else { input },
+        hex: kind == 1,
+        text: kind == 0,
+        verify: (!verify.trim().is_empty()).then_some(verify),
+    }
+    .execute()
+    .await
+}
+
+/// Shows a signed transaction as QR frames (offline side), broadcasts one
+/// scanned from QR images (online side), or decodes one for review
+async fn air_gapped_transaction() -> Result<()> {
+    let modes = [
+        "Show a signed transaction as QR",
+        "Broadcast from QR images",
+        "Decode a signed transaction",
+    ];
+    let mode = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Air-gapped transaction")
+        .items(&modes)
+        .default(0)
+        .interact()?;
+
+    if mode == 0 {
+        let raw: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("Signed transaction (0x…)")
+            .interact_text()?;
+        return raw_tx::export_qr(&raw, None);
+    }
+    if mode == 2 {
+        let raw: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("Signed transaction (0x…)")
+            .interact_text()?;
+        let abi_path: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("ABI file to decode the calldata with (empty to skip)")
+            .allow_empty(true)
+            .interact_text()?;
+        let abi = match abi_path.trim() {
+            "" => None,
+            path => Some(
+                std::fs::read_to_string(path)
+                    .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path, e))?,
+            ),
+        };
+        return raw_tx::decode(&raw, abi.as_deref());
+    }
+
+    let paths: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("QR images, separated by commas")
+        .interact_text()?;
+    let frames = paths
+        .split(',')
+        .filter(|path| !path.trim().is_empty())
+        .map(|path| decode_qr_image(Path::new(path.trim())))
+        .collect::<Result<Vec<_>>>()?;
+    raw_tx::broadcast(&join_frames(&frames)?).await
+}
@@ -1,16 +1,101 @@
 use crate::{
     commands::{
         contacts::{ContactsAction, ContactsCommand},
-        tokens::TokenRegistry,
+        tokens::{TokenInfo, TokenRegistry},
         transfer::TransferCommand,
     },
-    config::ConfigManager,
-    interactive::transfer_preview,
+    config::{Config, ConfigManager},
+    interactive::{
+        confirm_active_network, offer_to_save_contact, tokens::default_token_index,
+        transfer_preview::{self, PreviewDecision},
+        warn_if_low_balance,
+    },
+    types::{
+        network::Network,
+        tx_label::TxLabels,
+        wallet::{Wallet, WalletData},
+    },
+    utils::{
+        address::{
+            is_contract, other_network_hint, strip_chain_prefix, validate_address_input,
+        },
+        constants,
+        eth::EthClient,
+        helper::Config as HelperConfig,
+        payout::{PayoutEntry, parse_payouts, read_piped_stdin},
+        session::{Session, SessionKind},
+        status_cache,
+        units::{
+            Denomination, format_amount, format_amount_with, from_wei, parse_amount,
+            parse_base_units, shortfall, to_wei,
+        },
+    },
 };
+use alloy::primitives::{Address, U256};
 use anyhow::{Context, Result, anyhow};
 use colored::*;
 use console::style;
 use inquire::{Select, Text, validator::Validation};
+use std::str::FromStr;
+
+/// Prints a warning when `to` holds contract code; lookup failures are ignored
+async fn warn_if_contract(network: Network, to: &str) {
+    let Ok(address) = Address::from_str(to) else {
+        return;
+    };
+    let Ok(client) = EthClient::new(&HelperConfig {
+        network: network.get_config(),
+        wallet: Default::default(),
+    })
+    .await
+    else {
+        return;
+    };
+    if is_contract(client.provider(), address).await.unwrap_or(false) {
+        println!("{} {}", style("⚠️").yellow(), style("Recipient is a contract").yellow());
+    }
+}
+
+/// Asks which wallet signs when more than one exists. The active wallet is listed
+/// first and stays active whichever one is picked.
+pub(crate) fn select_signing_wallet(wallet_data: &WalletData) -> Result<&Wallet> {
+    let current = wallet_data
+        .get_current_wallet()
+        .ok_or_else(|| anyhow!("No default wallet selected"))?;
+    let mut others: Vec<&Wallet> = wallet_data
+        .list_wallets()
+        .into_iter()
+        .filter(|w| w.address != current.address)
+        .collect();
+    if others.is_empty() {
+        return Ok(current);
+    }
+    others.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let wallets: Vec<&Wallet> = std::iter::once(current).chain(others).collect();
+    let options: Vec<String> = wallets
+        .iter()
+        .enumerate()
+        .map(|(i, w)| {
+            let active = if i == 0 { " (active)" } else { "" };
+            format!("{}{} - 0x{:x}", w.name, active, w.address)
+        })
+        .collect();
+    let selection = Select::new("Sign with wallet:", options).raw_prompt()?;
+    Ok(wallets[selection.index])
+}
+
+/// Continues a send to several recipients from a saved session
+pub async fn resume_send_to_many(session: Session) -> Result<()> {
+    let config = ConfigManager::new()?.load()?;
+    let network = config.default_network.to_string().to_lowercase();
+    println!(
+        "Resuming {} recipient(s); their amounts were entered in {}",
+        session.recipients.len(),
+        session.token
+    );
+    send_to_many(&config, &network, None, session.recipients).await
+}
 
 /// Displays the fund transfer interface
 pub async fn send_funds() -> Result<()> {
@@ -22,13 +107,495 @@ pub async fn send_funds() -> Result<()> {
     let network = config.default_network.to_string().to_lowercase();
     println!("Using network: {}", network);
 
+    // A piped recipient list goes through the several-recipients flow
+    if let Some(content) = read_piped_stdin()? {
+        println!("📥 Reading recipients from stdin");
+        return send_to_many(&config, &network, Some(parse_payouts(&content)?), Vec::new()).await;
+    }
+
+    // Several recipients get their own flow with running totals
+    let count_options = vec!["👤 One recipient", "👥 Several recipients"];
+    if Select::new("Send to:", count_options).prompt()? == "👥 Several recipients" {
+        return send_to_many(&config, &network, None, Vec::new()).await;
+    }
+
+    let to = select_recipient(config.default_network)?;
+    let (token_info, token_symbol) = select_token(&config, &network)?;
+    let is_native = token_info.address == "0x0000000000000000000000000000000000000000";
+
+    let wallet_data: WalletData =
+        serde_json::from_str(&std::fs::read_to_string(constants::wallet_file_path())?)?;
+    let signer = select_signing_wallet(&wallet_data)?;
+    let (signer_name, signer_address) = (signer.name.clone(), signer.address());
+
+    // Exact base units bypass decimal conversion entirely
+    let base_units_option = "Base units (wei, exact integer)".to_string();
+    let unit_options = vec![
+        format!("{} (decimal amount)", token_symbol),
+        base_units_option.clone(),
+    ];
+    let in_base_units = Select::new("Enter amount in:", unit_options).prompt()? == base_units_option;
+
+    let (amount, base_units, gas_price) = loop {
+        let mut input = if in_base_units {
+            inquire::Text::new(&format!("Amount of {} to send (base units):", token_symbol))
+                .with_help_message("Whole number of wei / token base units")
+                .with_validator(|input: &str| match parse_base_units(input) {
+                    Ok(_) => Ok(Validation::Valid),
+                    Err(e) => Ok(Validation::Invalid(e.to_string().into())),
+                })
+                .prompt()?
+        } else {
+            let decimals = token_info.decimals;
+            inquire::Text::new(&format!("Amount of {} to send:", token_symbol))
+                .with_help_message("Enter the amount to send")
+                .with_validator(move |input: &str| {
+                    if parse_amount(input, decimals).is_ok() {
+                        Ok(Validation::Valid)
+                    } else {
+                        Ok(Validation::Invalid("Please enter a valid number".into()))
+                    }
+                })
+                .prompt()?
+        };
+
+        // Convert to the token's smallest unit
+        let mut base_units: U256 = if in_base_units {
+            parse_base_units(&input)?
+        } else {
+            parse_amount(&input, token_info.decimals)?
+        };
+
+        // Compare with the exact balance so an oversized send is caught before signing
+        let (spendable, gas_reserve) = spendable_balance(&config, signer_address, &token_info).await?;
+        if let Some(message) = shortfall(spendable, base_units, token_info.decimals, &token_symbol) {
+            let reserve_note = if gas_reserve.is_zero() {
+                String::new()
+            } else {
+                format!(" after keeping ~{} RBTC for gas", format_amount(gas_reserve, 18))
+            };
+            println!(
+                "{} {}",
+                style("⚠️").yellow(),
+                style(format!("Insufficient {}{}: {}", token_symbol, reserve_note, message)).yellow()
+            );
+            if spendable.is_zero() {
+                println!("Please top up the wallet or press Ctrl+C to exit.");
+                continue;
+            }
+            let max_option = format!(
+                "Send the maximum ({} {})",
+                format_amount_with(spendable, token_info.decimals, token_info.decimals, true),
+                token_symbol
+            );
+            if Select::new("What now?", vec![max_option.clone(), "Enter a different amount".into()])
+                .prompt()?
+                != max_option
+            {
+                continue;
+            }
+            base_units = spendable;
+            input = if in_base_units {
+                spendable.to_string()
+            } else {
+                format_amount_with(spendable, token_info.decimals, token_info.decimals, false)
+            };
+        }
+
+        // Catch insufficient token funds before the user confirms anything
+        if !is_native {
+            let warnings =
+                check_token_funds(&token_info, base_units, &token_symbol, signer_address)
+                    .await?;
+            if !warnings.is_empty() {
+                for warning in &warnings {
+                    println!("{} {}", style("⚠️").yellow(), style(warning).yellow());
+                }
+                let proceed = inquire::Confirm::new("Continue anyway?")
+                    .with_default(false)
+                    .prompt()?;
+                if !proceed {
+                    println!("Please enter a different amount or press Ctrl+C to exit.");
+                    continue;
+                }
+            }
+        }
+
+        let preview_token = (token_info.address != "0x0000000000000000000000000000000000000000")
+            .then_some((&token_info, token_symbol.as_str()));
+
+        // Show preview and ask for confirmation
+        let decision = transfer_preview::show_transaction_preview(
+            signer_address,
+            &to,
+            &base_units.to_string(),
+            config.default_network,
+            preview_token,
+        )
+        .await?;
+
+        match decision {
+            PreviewDecision::Send { gas_price } => break (input, base_units, gas_price),
+            PreviewDecision::Cancel => println!(
+                "Transaction cancelled. Please enter a new amount or press Ctrl+C to exit."
+            ),
+        }
+    };
+
+    // Clone the address since we need to use it multiple times
+    let token_address = token_info.address.clone();
+    let _token = if token_address == "0x0000000000000000000000000000000000000000" {
+        None
+    } else {
+        Some(token_address.clone())
+    };
+
+    // Show transaction summary
+    println!("\n{}", style("📝 Transaction Summary").bold());
+    println!("{}", "=".repeat(30));
+    println!("From: {}", signer_name);
+    println!("To: {}", to);
+    println!("Token: {}", token_symbol);
+    println!(
+        "Amount: {} {} ({} base units)",
+        format_amount(base_units, token_info.decimals),
+        token_symbol,
+        base_units
+    );
+    println!("Network: {}", network);
+    warn_if_contract(config.default_network, &to).await;
+    if let Ok(address) = Address::from_str(&to)
+        && let Some(hint) = other_network_hint(&config, address).await
+    {
+        println!("{} {}", style("⚠️").yellow(), style(hint).yellow());
+    }
+
+    // Confirm transaction
+    let rbtc_amount = if is_native { base_units } else { U256::ZERO };
+    if !confirm_active_network(&config, rbtc_amount)? {
+        println!("Transaction cancelled");
+        return Ok(());
+    }
+    let options = vec!["Send", "Simulate only", "Cancel"];
+    let dry_run = match Select::new("Confirm transaction?", options)
+        .with_starting_cursor(2)
+        .prompt()?
+    {
+        "Send" => false,
+        "Simulate only" => true,
+        _ => {
+            println!("Transaction cancelled");
+            return Ok(());
+        }
+    };
+
+    let note = if dry_run {
+        None
+    } else {
+        Text::new("Note (optional):")
+            .with_help_message("What the payment is for; kept locally and shown in history")
+            .prompt_skippable()?
+            .filter(|s| !s.trim().is_empty())
+    };
+
+    // Optionally hold the transfer until gas gets cheaper, unless a price was picked
+    let max_gas_price = if gas_price.is_some() || dry_run {
+        None
+    } else {
+        Text::new("Wait for gas price at or below (gwei):")
+            .with_help_message("Press Enter to send now")
+            .with_validator(|input: &str| {
+                if input.trim().is_empty() || to_wei(input, Denomination::Gwei).is_ok() {
+                    Ok(Validation::Valid)
+                } else {
+                    Ok(Validation::Invalid("Please enter a gas price in gwei".into()))
+                }
+            })
+            .prompt_skippable()?
+            .filter(|s| !s.trim().is_empty())
+    };
+
+    // Execute the transfer command
+    let cmd = TransferCommand {
+        address: to,
+        value: (!in_base_units).then_some(amount),
+        wei: in_base_units.then(|| base_units.to_string()),
+        token: if token_address == "0x0000000000000000000000000000000000000000" {
+            None
+        } else {
+            Some(token_address)
+        },
+        gas_price: gas_price.map(|wei| from_wei(U256::from(wei), Denomination::Gwei)),
+        wait_for_gas: max_gas_price.is_some(),
+        max_gas_price,
+        gas_timeout: 60,
+        sponsored: false,
+        from: Some(signer_name),
+        access_list: None,
+        note,
+        nonce: None,
+        confirmations: 1,
+        confirmation_timeout: 10,
+        dry_run,
+        // Acknowledged above by confirm_active_network
+        confirm_mainnet: true,
+    };
+
+    let result = cmd.execute().await?;
+    if dry_run {
+        return Ok(());
+    }
+    status_cache::invalidate();
+
+    println!(
+        "\n{}: Transaction confirmed! Tx Hash: {}",
+        "Success".green().bold(),
+        result.tx_hash
+    );
+
+    warn_if_low_balance(&config, signer_address).await;
+    offer_to_save_contact(&cmd.address).await?;
+
+    Ok(())
+}
+
+/// Sends one token to several hand-entered recipients. A running total of the
+/// amounts and estimated gas is shown after each recipient, and everything is
+/// listed again for a single confirmation before anything is sent.
+/// With `piped` recipients no recipient or amount is asked for.
+async fn send_to_many(
+    config: &Config,
+    network: &str,
+    piped: Option<Vec<PayoutEntry>>,
+    resumed: Vec<PayoutEntry>,
+) -> Result<()> {
+    let (token_info, token_symbol) = select_token(config, network)?;
+    // Typed recipients are saved as they are added; piped lists can simply be piped again
+    let mut session = piped
+        .is_none()
+        .then(|| Session::new(SessionKind::SendMany, network, &token_symbol));
+    let token_address = (token_info.address != "0x0000000000000000000000000000000000000000")
+        .then(|| Address::from_str(&token_info.address))
+        .transpose()
+        .map_err(|_| anyhow!("Invalid token address: {}", token_info.address))?;
+
+    // Chosen up front so gas is estimated from the wallet that will pay
+    let wallet_file = constants::wallet_file_path();
+    let wallet_data: WalletData = serde_json::from_str(&std::fs::read_to_string(&wallet_file)?)?;
+    let wallet = select_signing_wallet(&wallet_data)?;
+
+    let eth_client = EthClient::new(&HelperConfig {
+        network: config.default_network.get_config(),
+        wallet: Default::default(),
+    })
+    .await?;
+    let gas_price = U256::from(eth_client.gas_price().await?);
+
+    // (recipient, amount in base units, estimated gas cost in wei)
+    let mut recipients: Vec<(String, U256, U256)> = Vec::new();
+    let mut total_amount = U256::ZERO;
+    let mut total_gas = U256::ZERO;
+    let mut piped = piped.map(|entries| entries.into_iter());
+    let mut resumed = resumed.into_iter();
+    loop {
+        println!("\n{}", style(format!("Recipient #{}", recipients.len() + 1)).bold());
+        let decimals = token_info.decimals;
+        let listed = match piped.as_mut() {
+            Some(entries) => match entries.next() {
+                Some(entry) => Some(entry),
+                None => break,
+            },
+            None => resumed.next(),
+        };
+        let (to, input) = match listed {
+            Some(entry) => {
+                validate_address_input(&entry.to, config.default_network)
+                    .map_err(|e| anyhow!("Invalid address {}: {}", entry.to, e))?;
+                println!("{} - {} {}", entry.to, entry.value, token_symbol);
+                (strip_chain_prefix(&entry.to, config.default_network)?, entry.value)
+            }
+            None => {
+                let to = select_recipient(config.default_network)?;
+                let input = Text::new(&format!("Amount of {} to send:", token_symbol))
+                    .with_validator(move |input: &str| {
+                        if parse_amount(input, decimals).is_ok() {
+                            Ok(Validation::Valid)
+                        } else {
+                            Ok(Validation::Invalid("Please enter a valid number".into()))
+                        }
+                    })
+                    .prompt()?;
+                (to, input)
+            }
+        };
+        let to_address = Address::from_str(&to).map_err(|_| anyhow!("Invalid recipient address"))?;
+        if is_contract(eth_client.provider(), to_address).await.unwrap_or(false) {
+            println!("{} {}", style("⚠️").yellow(), style("Recipient is a contract").yellow());
+        }
+        if let Some(hint) = other_network_hint(config, to_address).await {
+            println!("{} {}", style("⚠️").yellow(), style(hint).yellow());
+        }
+        let amount = parse_amount(&input, decimals)?;
+
+        let gas_cost = match eth_client
+            .estimate_gas(wallet.address(), to_address, amount, token_address)
+            .await
+        {
+            Ok(gas) => gas * gas_price,
+            Err(e) => {
+                println!("{} Could not estimate gas: {}", style("⚠️").yellow(), e);
+                if !inquire::Confirm::new("Keep this recipient anyway?")
+                    .with_default(false)
+                    .prompt()?
+                {
+                    continue;
+                }
+                U256::ZERO
+            }
+        };
+
+        if let Some(session) = session.as_mut() {
+            session.record(&to, &input);
+        }
+        total_amount += amount;
+        total_gas += gas_cost;
+        recipients.push((to, amount, gas_cost));
+
+        println!(
+            "Running total: {} {} + ~{} RBTC gas ({} recipient(s))",
+            style(format_amount(total_amount, decimals)).green(),
+            token_symbol,
+            format_amount(total_gas, 18),
+            recipients.len()
+        );
+
+        if piped.is_none()
+            && resumed.len() == 0
+            && !inquire::Confirm::new("Add another recipient?")
+                .with_default(true)
+                .prompt()?
+        {
+            break;
+        }
+    }
+    if session.is_some() {
+        Session::clear();
+    }
+    if recipients.is_empty() {
+        println!("No recipients to send to");
+        return Ok(());
+    }
+
+    println!("\n{}", style("📝 Transaction Summary").bold());
+    println!("{}", "=".repeat(30));
+    for (i, (to, amount, gas_cost)) in recipients.iter().enumerate() {
+        println!(
+            "{:2}. {} - {} {} (gas ~{} RBTC)",
+            i + 1,
+            to,
+            format_amount(*amount, token_info.decimals),
+            token_symbol,
+            format_amount(*gas_cost, 18)
+        );
+    }
+    println!(
+        "Total: {} {}",
+        format_amount(total_amount, token_info.decimals),
+        token_symbol
+    );
+    println!("Estimated gas: {} RBTC", format_amount(total_gas, 18));
+    if token_address.is_none() {
+        println!(
+            "Total cost (amount + gas): {} RBTC",
+            format_amount(total_amount + total_gas, 18)
+        );
+    } else {
+        for warning in
+            check_token_funds(&token_info, total_amount, &token_symbol, wallet.address())
+                .await?
+        {
+            println!("{} {}", style("⚠️").yellow(), style(warning).yellow());
+        }
+    }
+    println!("Network: {}", network);
+
+    let note = Text::new("Note for these payments (optional):")
+        .with_help_message("Kept locally with each transaction and shown in history")
+        .prompt_skippable()?
+        .filter(|s| !s.trim().is_empty());
+
+    let rbtc_amount = if token_address.is_none() { total_amount } else { U256::ZERO };
+    if !confirm_active_network(config, rbtc_amount)?
+        || !inquire::Confirm::new(&format!("Send {} transactions?", recipients.len()))
+            .with_default(false)
+            .prompt()?
+    {
+        println!("Transaction cancelled");
+        return Ok(());
+    }
+
+    // Decrypt once for the whole batch
+    let password =
+        rpassword::prompt_password(format!("Enter password for wallet '{}': ", wallet.name))?;
+    let private_key = wallet.decrypt_private_key(&password)?;
+    let signer_client = EthClient::new(&HelperConfig {
+        network: config.default_network.get_config(),
+        wallet: crate::utils::helper::WalletConfig {
+            current_wallet_address: None,
+            private_key: Some(private_key),
+            mnemonic: None,
+        },
+    })
+    .await?;
+
+    let mut sent = Vec::new();
+    for (i, (to, amount, _)) in recipients.iter().enumerate() {
+        let to_address = Address::from_str(to).map_err(|_| anyhow!("Invalid recipient address"))?;
+        match signer_client
+            .send_transaction(to_address, *amount, token_address)
+            .await
+        {
+            Ok(hash) => {
+                sent.push(to);
+                println!("{:2}. {} Tx: {}", i + 1, "✅".green(), hash);
+                if let Some(note) = &note
+                    && let Err(e) = TxLabels::add_note(&hash, note)
+                {
+                    eprintln!("⚠️  Could not save the note: {}", e);
+                }
+            }
+            Err(e) => println!("{:2}. {} {}", i + 1, "❌".red(), e),
+        }
+    }
+    println!("\n{} of {} transactions sent", sent.len(), recipients.len());
+    warn_if_low_balance(config, wallet.address()).await;
+
+    sent.sort();
+    sent.dedup();
+    for to in sent {
+        offer_to_save_contact(to).await?;
+    }
+
+    Ok(())
+}
+
+/// Asks for a recipient, typed in or picked from contacts
+fn select_recipient(network: Network) -> Result<String> {
     // Ask user if they want to select from contacts or enter address manually
-    let send_options = vec!["📝 Enter address manually", "👥 Select from contacts"];
+    let send_options = vec![
+        "📝 Enter address manually",
+        "👥 Select from contacts",
+        "🔑 One of my wallets",
+    ];
 
     let send_choice =
         Select::new("How would you like to specify the recipient?", send_options).prompt()?;
 
-    let to = if send_choice == "👥 Select from contacts" {
+    if send_choice == "🔑 One of my wallets" {
+        return select_own_wallet();
+    }
+
+    if send_choice == "👥 Select from contacts" {
         // Load contacts
         let cmd = ContactsCommand {
             action: ContactsAction::List,
@@ -37,7 +604,7 @@ pub async fn send_funds() -> Result<()> {
 
         if contacts.is_empty() {
             println!("No contacts available. Please enter the address manually.");
-            get_recipient_address()?
+            get_recipient_address(network)
         } else {
             // Show contact selection
             let contact_names: Vec<String> = contacts
@@ -59,12 +626,38 @@ pub async fn send_funds() -> Result<()> {
             // Extract the address from the selection (it's in the format "Name (0x...)")
             let addr_start = selection.find('(').unwrap_or(0) + 1;
             let addr_end = selection.find(')').unwrap_or(selection.len());
-            selection[addr_start..addr_end].to_string()
+            Ok(selection[addr_start..addr_end].to_string())
         }
     } else {
-        get_recipient_address()?
-    };
+        get_recipient_address(network)
+    }
+}
+
+/// Picks one of the user's own wallets (the active one included) as the recipient,
+/// for consolidating funds or trying out a send
+fn select_own_wallet() -> Result<String> {
+    let wallet_data: WalletData =
+        serde_json::from_str(&std::fs::read_to_string(constants::wallet_file_path())?)?;
+    let current = wallet_data.get_current_wallet().map(|w| w.address);
+    let mut wallets = wallet_data.list_wallets();
+    wallets.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let options: Vec<String> = wallets
+        .iter()
+        .map(|w| {
+            let active = if Some(w.address) == current { " (active)" } else { "" };
+            match w.label_text() {
+                Some(label) => format!("{}{} [{}] - 0x{:x}", w.name, active, label, w.address),
+                None => format!("{}{} - 0x{:x}", w.name, active, w.address),
+            }
+        })
+        .collect();
+    let selection = Select::new("Send to wallet:", options).raw_prompt()?;
+    Ok(format!("0x{:x}", wallets[selection.index].address))
+}
 
+/// Asks which token to send; RBTC is listed first. Returns the token and its symbol.
+fn select_token(config: &Config, network: &str) -> Result<(TokenInfo, String)> {
     // Load token registry
     let registry = TokenRegistry::load()
         .map_err(|e| {
@@ -74,7 +667,7 @@ pub async fn send_funds() -> Result<()> {
         .unwrap_or_default();
 
     // Get tokens for the current network
-    let mut tokens = registry.list_tokens(Some(&network));
+    let mut tokens = registry.list_tokens(Some(network));
 
     // Add RBTC as the first option
     tokens.insert(
@@ -99,7 +692,7 @@ pub async fn send_funds() -> Result<()> {
             // Only include tokens that match the current network or are RBTC
             info.address == "0x0000000000000000000000000000000000000000"
                 || registry
-                    .list_tokens(Some(&network))
+                    .list_tokens(Some(network))
                     .iter()
                     .any(|(_, token_info)| token_info.address == info.address)
         })
@@ -110,7 +703,10 @@ pub async fn send_funds() -> Result<()> {
         token_choices.iter().map(|(name, _)| name.clone()).collect();
 
     // Let the user select which token to send
-    let selection = Select::new("Select token to send:", token_display_names).prompt()?;
+    let starting = default_token_index(&token_display_names, config, network);
+    let selection = Select::new("Select token to send:", token_display_names)
+        .with_starting_cursor(starting)
+        .prompt()?;
 
     // Find the selected token info
     let (display_name, token_info) = token_choices
@@ -125,100 +721,74 @@ pub async fn send_funds() -> Result<()> {
         .unwrap_or(&display_name)
         .to_string();
 
-    let amount = loop {
-        let input = inquire::Text::new(&format!("Amount of {} to send:", token_symbol))
-            .with_help_message("Enter the amount to send")
-            .with_validator(|input: &str| {
-                if input.parse::<f64>().is_ok() {
-                    Ok(Validation::Valid)
-                } else {
-                    Ok(Validation::Invalid("Please enter a valid number".into()))
-                }
-            })
-            .prompt()?;
-
-        // Convert RBTC to wei for preview
-        let rbtc: f64 = input.parse().unwrap_or(0.0);
-        let wei = (rbtc * 1e18) as u128;
-
-        // Show preview and ask for confirmation
-        let confirmed = transfer_preview::show_transaction_preview(
-            &to,
-            &wei.to_string(),
-            config.default_network,
-        )
-        .await?;
-
-        if confirmed {
-            break input;
-        } else {
-            println!("Transaction cancelled. Please enter a new amount or press Ctrl+C to exit.");
-        }
-    };
+    Ok((token_info, token_symbol))
+}
 
-    // Clone the address since we need to use it multiple times
-    let token_address = token_info.address.clone();
-    let _token = if token_address == "0x0000000000000000000000000000000000000000" {
-        None
+/// What `owner` can send of the chosen token and the RBTC held back for gas.
+/// RBTC sends keep the same gas reserve the transfer itself checks for.
+async fn spendable_balance(
+    config: &Config,
+    owner: Address,
+    token_info: &TokenInfo,
+) -> Result<(U256, U256)> {
+    let eth_client = EthClient::new(&HelperConfig {
+        network: config.default_network.get_config(),
+        wallet: Default::default(),
+    })
+    .await?;
+    if token_info.address == "0x0000000000000000000000000000000000000000" {
+        let balance = eth_client.get_balance(&owner, &None).await?;
+        let gas_reserve = U256::from(eth_client.gas_price().await?) * U256::from(100_000);
+        Ok((balance.saturating_sub(gas_reserve), gas_reserve))
     } else {
-        Some(token_address.clone())
-    };
+        let token = Address::from_str(&token_info.address)
+            .map_err(|_| anyhow!("Invalid token address: {}", token_info.address))?;
+        Ok((eth_client.get_balance(&owner, &Some(token)).await?, U256::ZERO))
+    }
+}
 
-    // Show transaction summary
-    println!("\n{}", style("📝 Transaction Summary").bold());
-    println!("{}", "=".repeat(30));
-    println!("To: {}", to);
-    println!("Token: {}", token_symbol);
-    println!("Amount: {} {}", amount, token_symbol);
-    println!("Network: {}", network);
+/// Checks that `owner` can cover an ERC20 transfer of `amount` base units.
+/// Transfers always call `transfer` as the owner (sponsored ones through an
+/// ERC-2771 forwarder), so no allowance is involved. Returns human-readable warnings.
+async fn check_token_funds(
+    token_info: &TokenInfo,
+    amount: U256,
+    token_symbol: &str,
+    owner: Address,
+) -> Result<Vec<String>> {
+    let token_address = Address::from_str(&token_info.address)
+        .map_err(|_| anyhow!("Invalid token address: {}", token_info.address))?;
+    let config = ConfigManager::new()?.load()?;
+    let eth_client = EthClient::new(&HelperConfig {
+        network: config.default_network.get_config(),
+        wallet: Default::default(),
+    })
+    .await?;
 
-    // Confirm transaction
-    let confirm = inquire::Confirm::new("Confirm transaction?")
-        .with_default(false)
-        .prompt()?;
+    let mut warnings = Vec::new();
 
-    if !confirm {
-        println!("Transaction cancelled");
-        return Ok(());
+    let balance = eth_client.get_balance(&owner, &Some(token_address)).await?;
+    if let Some(message) = shortfall(balance, amount, token_info.decimals, token_symbol) {
+        warnings.push(format!("Insufficient {} balance: {}", token_symbol, message));
     }
 
-    // Execute the transfer command
-    let cmd = TransferCommand {
-        address: to,
-        value: amount
-            .parse::<f64>()
-            .map_err(|_| anyhow::anyhow!("Invalid amount format"))?,
-        token: if token_address == "0x0000000000000000000000000000000000000000" {
-            None
-        } else {
-            Some(token_address)
-        },
-    };
-
-    let result = cmd.execute().await?;
-
-    println!(
-        "\n{}: Transaction confirmed! Tx Hash: {}",
-        "Success".green().bold(),
-        result.tx_hash
-    );
+    if eth_client.get_balance(&owner, &None).await?.is_zero() {
+        warnings.push("Your wallet has no RBTC to pay for gas".to_string());
+    }
 
-    Ok(())
+    Ok(warnings)
 }
 
-/// Helper function to get recipient address with validation
-fn get_recipient_address() -> Result<String> {
-    Text::new("Recipient address (0x...):")
-        .with_help_message("Enter the Ethereum address to send to")
-        .with_validator(|input: &str| {
-            if input.starts_with("0x") && input.len() == 42 {
-                Ok(Validation::Valid)
-            } else {
-                Ok(Validation::Invalid(
-                    "Please enter a valid Ethereum address (0x...)".into(),
-                ))
-            }
+/// Helper function to get recipient address with validation.
+/// Accepts an optional EIP-3770 prefix (`rsk:0x...`) for the active network.
+fn get_recipient_address(network: Network) -> Result<String> {
+    let input = Text::new("Recipient address (0x...):")
+        .with_help_message("Enter the address to send to (an rsk:/rsktest: prefix is accepted)")
+        .with_validator(move |input: &str| match validate_address_input(input, network) {
+            Ok(()) => Ok(Validation::Valid),
+            Err(e) => Ok(Validation::Invalid(e.into())),
         })
-        .prompt()
-        .map_err(Into::into)
+        .prompt()?;
+
+    strip_chain_prefix(&input, network)
 }
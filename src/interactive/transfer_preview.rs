@@ -1,27 +1,42 @@
 use crate::{
     config::ConfigManager,
     types::network::{Network, NetworkConfig},
+    commands::tokens::TokenInfo,
     utils::{
+        bridge::format_script_hex,
+        calldata::{DecodedCall, decode_erc20_transfer, erc20_transfer_calldata},
         eth::EthClient,
         helper::{Config as HelperConfig, WalletConfig},
+        units::{Denomination, format_amount, to_wei},
     },
 };
 use anyhow::{Result, anyhow};
 use console::style;
-use dialoguer::Confirm;
+use dialoguer::{Input, Select};
 use alloy::primitives::{Address, U256};
-use alloy::providers::Provider;
 use std::str::FromStr;
 
-/// Helper function to convert wei to RBTC
-fn convert_wei_to_rbtc(wei: U256) -> f64 {
-    // 1 RBTC = 10^18 wei
-    let wei_f64 = wei.to::<u128>() as f64;
-    wei_f64 / 1_000_000_000_000_000_000.0
+/// What the user decided after reviewing a transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewDecision {
+    Cancel,
+    /// Send, at `gas_price` wei when the user picked one
+    Send { gas_price: Option<u128> },
 }
 
-/// Displays transaction details and asks for confirmation
-pub async fn show_transaction_preview(to: &str, amount: &str, network: Network) -> Result<bool> {
+/// Displays transaction details and asks for confirmation.
+///
+/// `amount` is in the token's smallest unit and gas is estimated as sent from `from`. For ERC20 transfers pass the
+/// token and its symbol; the ABI-encoded calldata is shown alongside.
+/// The gas price can be changed to a slow/standard/fast preset or a custom
+/// value, after which the fee and total are shown again.
+pub async fn show_transaction_preview(
+    from: Address,
+    to: &str,
+    amount: &str,
+    network: Network,
+    token: Option<(&TokenInfo, &str)>,
+) -> Result<PreviewDecision> {
     println!("\n{}", style("Transaction Preview").bold().underlined());
     println!("• To: {}", style(to).cyan());
 
@@ -29,11 +44,11 @@ pub async fn show_transaction_preview(to: &str, amount: &str, network: Network)
     let amount_wei =
         U256::from_str(amount).map_err(|e| anyhow::anyhow!("Invalid amount format: {}", e))?;
 
-    // Convert to RBTC for display
-    let amount_rbtc = convert_wei_to_rbtc(amount_wei);
+    let (decimals, symbol) = token.map_or((18, "RBTC"), |(info, symbol)| (info.decimals, symbol));
     println!(
-        "• Amount: {} RBTC ({} wei)",
-        style(amount_rbtc).green(),
+        "• Amount: {} {} ({} base units)",
+        style(format_amount(amount_wei, decimals)).green(),
+        symbol,
         style(amount_wei).dim()
     );
 
@@ -51,49 +66,146 @@ pub async fn show_transaction_preview(to: &str, amount: &str, network: Network)
             mnemonic: None,
         },
     };
-    let eth_client = EthClient::new(&helper_config, None).await?;
+    let eth_client = EthClient::new(&helper_config).await?;
 
     // Fetch current gas price from the network
-    let gas_price = eth_client
-        .provider()
-        .get_gas_price()
-        .await
-        .map_err(|e| anyhow!("Failed to get gas price: {}", e))?;
+    let mut gas_price = eth_client.gas_price().await?;
+    let mut chosen_gas_price = None;
 
     // Estimate gas for the transaction
     let to_address: Address = to
         .parse()
         .map_err(|_| anyhow!("Invalid recipient address"))?;
+    let token_address = token
+        .map(|(info, _)| {
+            Address::from_str(&info.address)
+                .map_err(|_| anyhow!("Invalid token address: {}", info.address))
+        })
+        .transpose()?;
     let estimated_gas = eth_client
-        .estimate_gas(
-            to_address, amount_wei, None, // No token address for native transfers
-        )
+        .estimate_gas(from, to_address, amount_wei, token_address)
         .await?;
-    let gas_cost = U256::from(gas_price).checked_mul(estimated_gas).unwrap_or_default();
-    let gas_cost_rbtc = convert_wei_to_rbtc(gas_cost);
 
     println!("• Network: {}", style(network).cyan());
+    println!("• Estimated Gas: {}", style(estimated_gas).yellow());
+    if let Some(token_address) = token_address {
+        println!("• Token Contract: {}", style(token_address).cyan());
+        let data = erc20_transfer_calldata(to_address, amount_wei);
+        print_calldata(&data, decode_erc20_transfer(&data).ok().as_ref());
+    } else {
+        println!("• Calldata: {}", style("none (plain RBTC transfer)").dim());
+    }
+
+    loop {
+        print_fee(gas_price, estimated_gas, token_address.is_none().then_some(amount_wei));
+
+        let choice = Select::new()
+            .with_prompt("\nDo you want to send this transaction?")
+            .items(&["Send", "Change gas price", "Cancel"])
+            .default(0)
+            .interact()?;
+        match choice {
+            0 => {
+                return Ok(PreviewDecision::Send {
+                    gas_price: chosen_gas_price,
+                });
+            }
+            1 => {
+                if let Some(price) = choose_gas_price(&eth_client).await? {
+                    gas_price = price;
+                    chosen_gas_price = Some(price);
+                }
+            }
+            _ => return Ok(PreviewDecision::Cancel),
+        }
+    }
+}
+
+/// Prints the gas price, fee and, for RBTC transfers, the amount plus fee
+fn print_fee(gas_price: u128, estimated_gas: U256, rbtc_amount: Option<U256>) {
+    let gas_cost = U256::from(gas_price).checked_mul(estimated_gas).unwrap_or_default();
     println!(
         "• Gas Price: {} Gwei",
         style(convert_wei_to_gwei(U256::from(gas_price))).yellow()
     );
-    println!("• Estimated Gas: {}", style(estimated_gas).yellow());
-    println!("• Estimated Fee: {} RBTC", style(gas_cost_rbtc).red());
-
-    let total_amount = amount_wei.checked_add(gas_cost).unwrap_or(amount_wei);
-    let total_rbtc = convert_wei_to_rbtc(total_amount);
     println!(
-        "• Total (Amount + Fee): {} RBTC",
-        style(total_rbtc).green().bold()
+        "• Estimated Fee: {} RBTC",
+        style(format_amount(gas_cost, 18)).red()
     );
+    if let Some(amount_wei) = rbtc_amount {
+        let total_amount = amount_wei.checked_add(gas_cost).unwrap_or(amount_wei);
+        println!(
+            "• Total (Amount + Fee): {} RBTC",
+            style(format_amount(total_amount, 18)).green().bold()
+        );
+    }
+}
 
-    // Ask for confirmation
-    let confirm = Confirm::new()
-        .with_prompt("\nDo you want to send this transaction?")
-        .default(false)
+/// Offers the slow/standard/fast presets (re-estimated from recent blocks) and a
+/// custom price; returns the chosen price in wei, or `None` to keep the current one
+async fn choose_gas_price(eth_client: &EthClient) -> Result<Option<u128>> {
+    let presets = eth_client.gas_presets().await?;
+    let gwei = |wei: u128| convert_wei_to_gwei(U256::from(wei));
+    let items = [
+        format!("Slow ({} Gwei)", gwei(presets.slow)),
+        format!("Standard ({} Gwei)", gwei(presets.standard)),
+        format!("Fast ({} Gwei)", gwei(presets.fast)),
+        "Custom".to_string(),
+        "Keep current".to_string(),
+    ];
+    let choice = Select::new()
+        .with_prompt("Gas price")
+        .items(&items)
+        .default(1)
         .interact()?;
+    let price = match choice {
+        0 => presets.slow,
+        1 => presets.standard,
+        2 => presets.fast,
+        3 => {
+            let input: String = Input::new()
+                .with_prompt("Gas price (Gwei)")
+                .validate_with(|input: &String| {
+                    to_wei(input, Denomination::Gwei)
+                        .map(|_| ())
+                        .map_err(|_| "Please enter a gas price in Gwei")
+                })
+                .interact_text()?;
+            to_wei(&input, Denomination::Gwei)?
+                .try_into()
+                .map_err(|_| anyhow!("Gas price '{}' is too large", input))?
+        }
+        _ => return Ok(None),
+    };
+    if price < presets.slow {
+        println!(
+            "{}",
+            style("⚠️  Below the slow preset; the transaction may take long to be mined").yellow()
+        );
+    }
+    Ok(Some(price))
+}
+
+/// Prints raw calldata hex and, when available, its decoded function call
+pub fn print_calldata(data: &[u8], decoded: Option<&DecodedCall>) {
+    println!("• Calldata ({} bytes):", data.len());
+    for line in format_script_hex(data, 64) {
+        println!("    {}", style(line).dim());
+    }
 
-    Ok(confirm)
+    match decoded {
+        Some(call) => {
+            println!(
+                "• Function: {} [selector 0x{}]",
+                style(&call.signature).cyan(),
+                alloy::primitives::hex::encode(call.selector)
+            );
+            for param in &call.params {
+                println!("    {} ({}): {}", param.name, param.kind, style(&param.value).yellow());
+            }
+        }
+        None => println!("• Function: {}", style("could not decode calldata").red()),
+    }
 }
 
 /// Helper function to convert wei to Gwei
@@ -41,7 +41,8 @@ pub async fn check_transaction_status() -> Result<()> {
 
         // Create and execute the transaction status command
         let cmd = TxCommand {
-            tx_hash: tx_hash.clone(),
+            action: None,
+            tx_hash: Some(tx_hash.clone()),
             testnet: is_testnet,
             api_key: None, // Will use the configured API key
         };
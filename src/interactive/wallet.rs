@@ -1,17 +1,31 @@
 use crate::commands::wallet::{WalletAction, WalletCommand};
+use crate::types::wallet::{WALLET_COLORS, WalletData};
+use crate::utils::constants;
+use crate::utils::password::{PASSWORD_POLICY, PasswordStrength, check_password_strength};
 use anyhow::Result;
 use console::style;
+use inquire::validator::Validation;
+use std::path::{Path, PathBuf};
 
 /// Displays the wallet management menu
 pub async fn wallet_menu() -> Result<()> {
     loop {
+        super::print_status_line().await;
         let options = vec![
             String::from("📝 Create New Wallet"),
+            String::from("🌳 Create HD Wallet"),
+            String::from("🌿 Derive HD Account"),
             String::from("📤 Import Wallet"),
             String::from("📋 List Wallets"),
             String::from("🔄 Switch Wallet"),
             String::from("✏️ Rename Wallet"),
+            String::from("🏷️ Edit Label"),
             String::from("💾 Backup Wallet"),
+            String::from("🔐 Export Keystore"),
+            String::from("🔢 Nonce Report"),
+            String::from("📇 Share Address as vCard"),
+            String::from("📱 Show Address QR"),
+            String::from("✍️ Sign / Verify Message"),
             String::from("🗑️ Delete Wallet"),
             String::from("🏠 Back to Main Menu"),
         ];
@@ -22,11 +36,19 @@ pub async fn wallet_menu() -> Result<()> {
 
         let result = match selection.as_str() {
             "📝 Create New Wallet" => create_wallet().await,
+            "🌳 Create HD Wallet" => create_hd_wallet().await,
+            "🌿 Derive HD Account" => derive_hd_account().await,
             "📤 Import Wallet" => import_wallet().await,
             "📋 List Wallets" => list_wallets().await,
             "🔄 Switch Wallet" => switch_wallet().await,
             "✏️ Rename Wallet" => rename_wallet().await,
+            "🏷️ Edit Label" => edit_label().await,
             "💾 Backup Wallet" => backup_wallet().await,
+            "🔐 Export Keystore" => export_keystore().await,
+            "🔢 Nonce Report" => nonce_report().await,
+            "📇 Share Address as vCard" => export_vcard().await,
+            "📱 Show Address QR" => show_address_qr().await,
+            "✍️ Sign / Verify Message" => sign_or_verify_message().await,
             "🗑️ Delete Wallet" => delete_wallet().await,
             _ => break,
         };
@@ -38,6 +60,65 @@ pub async fn wallet_menu() -> Result<()> {
     Ok(())
 }
 
+/// Warns about wallets that were never backed up and offers to back them up now.
+/// Choosing "Don't remind me" silences the reminder for that wallet only.
+pub async fn remind_unbacked_up_wallets() -> Result<()> {
+    let wallet_file = constants::wallet_file_path();
+    if !wallet_file.exists() {
+        return Ok(());
+    }
+    let wallet_data: WalletData = serde_json::from_str(&std::fs::read_to_string(&wallet_file)?)?;
+    let names: Vec<String> = wallet_data
+        .wallets_needing_backup()
+        .iter()
+        .map(|w| w.name.clone())
+        .collect();
+    if names.is_empty() {
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        style("⚠️  These wallets have never been backed up. Losing this device means losing their funds:")
+            .yellow()
+            .bold()
+    );
+    for name in &names {
+        println!("   • {}", name);
+    }
+
+    let options = vec!["Back up now", "Remind me later", "Don't remind me for this wallet"];
+    for name in &names {
+        let choice = inquire::Select::new(&format!("Wallet '{}':", name), options.clone())
+            .prompt()?;
+        match choice {
+            "Back up now" => {
+                let backup_cmd = WalletCommand {
+                    action: WalletAction::Backup {
+                        name: name.clone(),
+                        path: PathBuf::from(format!("{}-backup.json", name)),
+                    },
+                };
+                if let Err(e) = backup_cmd.execute().await {
+                    eprintln!("Error: {}", e);
+                }
+            }
+            "Don't remind me for this wallet" => {
+                WalletData::update(|latest| {
+                    if let Some(wallet) = latest.get_wallet_by_name_mut(name) {
+                        wallet.backup_reminder_dismissed = true;
+                    }
+                    Ok(())
+                })?;
+            }
+            _ => {}
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
 /// Creates a new wallet with the given name and prompts for a password
 async fn create_wallet() -> Result<()> {
     println!("\n{}", style("🆕 Create New Wallet").bold());
@@ -74,13 +155,7 @@ pub async fn create_wallet_with_name(name: &str) -> Result<()> {
         style("This password will be required to access your wallet.").dim()
     );
 
-    let password = inquire::Password::new("Enter password:")
-        .with_display_toggle_enabled()
-        .with_display_mode(inquire::PasswordDisplayMode::Masked)
-        .with_custom_confirmation_error_message("The passwords don't match.")
-        .with_custom_confirmation_message("Please confirm your password:")
-        .with_formatter(&|_| String::from("✓ Password set"))
-        .prompt()?;
+    let password = prompt_new_password()?;
 
     println!(
         "\n{}",
@@ -91,6 +166,7 @@ pub async fn create_wallet_with_name(name: &str) -> Result<()> {
         action: WalletAction::Create {
             name: name.to_string(),
             password: password.clone(),
+            allow_weak: false,
         },
     };
 
@@ -98,10 +174,158 @@ pub async fn create_wallet_with_name(name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Creates a wallet from a new seed phrase, which is shown once to write down
+async fn create_hd_wallet() -> Result<()> {
+    println!("\n{}", style("🌳 Create HD Wallet").bold().blue());
+    println!("{}", "-".repeat(30));
+
+    let word_count = inquire::Select::new("Seed phrase length:", vec![12, 24])
+        .with_help_message("24 words are harder to guess; 12 are easier to write down")
+        .prompt()?;
+    let (name, password) = prompt_name_and_password()?;
+
+    println!(
+        "\n{}",
+        style("⏳ Creating your wallet. This may take a few seconds...").dim()
+    );
+    WalletCommand {
+        action: WalletAction::CreateHd {
+            name,
+            password,
+            word_count,
+            allow_weak: false,
+        },
+    }
+    .execute()
+    .await
+}
+
+/// Adds the next account of an HD wallet's seed phrase as a new wallet
+async fn derive_hd_account() -> Result<()> {
+    println!("\n{}", style("🌿 Derive HD Account").bold().blue());
+    println!("{}", "-".repeat(30));
+
+    let wallet_data: WalletData =
+        serde_json::from_str(&std::fs::read_to_string(constants::wallet_file_path())?)?;
+    let hd_wallets: Vec<_> = wallet_data
+        .list_wallets()
+        .into_iter()
+        .filter(|wallet| wallet.hd.is_some())
+        .collect();
+    if hd_wallets.is_empty() {
+        println!("No HD wallets yet; create one with \"Create HD Wallet\"");
+        return Ok(());
+    }
+    let names: Vec<String> = hd_wallets.iter().map(|wallet| wallet.name.clone()).collect();
+    let name = inquire::Select::new("Derive from:", names).prompt()?;
+
+    // Suggest the index after the highest one derived from the same seed phrase
+    let seed = hd_wallets
+        .iter()
+        .find(|wallet| wallet.name == name)
+        .and_then(|wallet| wallet.hd.as_ref());
+    let next_index = hd_wallets
+        .iter()
+        .filter_map(|wallet| wallet.hd.as_ref())
+        .filter(|hd| seed.is_some_and(|seed| seed.encrypted_mnemonic == hd.encrypted_mnemonic))
+        .map(|hd| hd.index + 1)
+        .max()
+        .unwrap_or(1);
+    let index = inquire::Text::new("Account index:")
+        .with_default(&next_index.to_string())
+        .with_help_message("Index on m/44'/137'/0'/0")
+        .with_validator(|input: &str| match input.parse::<u32>() {
+            Ok(_) => Ok(Validation::Valid),
+            Err(_) => Ok(Validation::Invalid("Please enter a valid number".into())),
+        })
+        .prompt()?
+        .parse::<u32>()?;
+    let new_name = inquire::Text::new("Name of the new wallet:")
+        .with_default(&format!("{}-{}", name, index))
+        .prompt()?;
+    let password = inquire::Password::new(&format!("Password of '{}':", name))
+        .with_display_mode(inquire::PasswordDisplayMode::Masked)
+        .without_confirmation()
+        .prompt()?;
+
+    WalletCommand {
+        action: WalletAction::DeriveAccount {
+            name,
+            index,
+            password,
+            new_name: Some(new_name),
+        },
+    }
+    .execute()
+    .await
+}
+
 async fn import_wallet() -> Result<()> {
     println!("\n{}", style("📤 Import Wallet").bold().blue());
     println!("{}", "-".repeat(30));
 
+    let options = vec!["🔑 Private key", "🌱 Seed phrase", "📁 Keystore file"];
+    let source = inquire::Select::new("What would you like to import from?", options)
+        .with_help_message("Choose the format of the wallet you want to import")
+        .prompt()?;
+
+    let skip_check = !inquire::Confirm::new("Check the address's balance and activity after importing?")
+        .with_default(true)
+        .with_help_message("Choose no when importing offline")
+        .prompt()?;
+
+    let action = match source {
+        "🔑 Private key" => {
+            let private_key = prompt_private_key()?;
+            let (name, password) = prompt_name_and_password()?;
+            WalletAction::Import {
+                private_key,
+                name,
+                password,
+                skip_check,
+                allow_weak: false,
+            }
+        }
+        "🌱 Seed phrase" => {
+            let (mnemonic, index) = prompt_seed_phrase()?;
+            let (name, password) = prompt_name_and_password()?;
+            WalletAction::ImportMnemonic {
+                mnemonic,
+                index,
+                name,
+                password,
+                skip_check,
+                allow_weak: false,
+            }
+        }
+        "📁 Keystore file" => {
+            let (path, keystore_password) = prompt_keystore()?;
+            let (name, password) = prompt_name_and_password()?;
+            WalletAction::ImportKeystore {
+                path,
+                keystore_password,
+                name,
+                password,
+                skip_check,
+                allow_weak: false,
+            }
+        }
+        _ => return Ok(()),
+    };
+
+    println!(
+        "\n{}",
+        style("⏳ Importing your wallet. This may take a few seconds...").dim()
+    );
+
+    let cmd = WalletCommand { action };
+    cmd.execute().await?;
+
+    println!("\n{}", style("✅ Wallet imported successfully!").green());
+    Ok(())
+}
+
+fn prompt_private_key() -> Result<String> {
     println!(
         "\n{}",
         style("Please enter the private key of the wallet you want to import.").dim()
@@ -114,8 +338,99 @@ async fn import_wallet() -> Result<()> {
     let private_key = inquire::Password::new("Private key (0x...):")
         .with_display_mode(inquire::PasswordDisplayMode::Hidden)
         .with_help_message("The private key of the wallet to import")
+        .without_confirmation()
+        .with_validator(|input: &str| {
+            let hex = input.trim().trim_start_matches("0x");
+            if hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                Ok(Validation::Valid)
+            } else {
+                Ok(Validation::Invalid(
+                    "Private key must be 64 hexadecimal characters (optionally 0x-prefixed)"
+                        .into(),
+                ))
+            }
+        })
         .prompt()?;
 
+    Ok(private_key.trim().to_string())
+}
+
+fn prompt_seed_phrase() -> Result<(String, u32)> {
+    println!(
+        "\n{}",
+        style("Please enter the seed phrase (12-24 words) of the wallet you want to import.")
+            .dim()
+    );
+
+    let mnemonic = inquire::Password::new("Seed phrase:")
+        .with_display_mode(inquire::PasswordDisplayMode::Masked)
+        .with_display_toggle_enabled()
+        .with_help_message("Words separated by spaces")
+        .without_confirmation()
+        .with_validator(|input: &str| {
+            let words = input.split_whitespace().count();
+            if [12, 15, 18, 21, 24].contains(&words) {
+                Ok(Validation::Valid)
+            } else {
+                Ok(Validation::Invalid(
+                    format!("Seed phrase must have 12, 15, 18, 21 or 24 words (got {})", words)
+                        .into(),
+                ))
+            }
+        })
+        .prompt()?;
+
+    let index = inquire::Text::new("Account index:")
+        .with_default("0")
+        .with_help_message("Derivation index on m/44'/137'/0'/0 (0 for the first account)")
+        .with_validator(|input: &str| match input.parse::<u32>() {
+            Ok(_) => Ok(Validation::Valid),
+            Err(_) => Ok(Validation::Invalid("Please enter a valid number".into())),
+        })
+        .prompt()?
+        .parse::<u32>()?;
+
+    let mnemonic = mnemonic
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+
+    Ok((mnemonic, index))
+}
+
+fn prompt_keystore() -> Result<(PathBuf, String)> {
+    let path = inquire::Text::new("Keystore file path:")
+        .with_help_message("Path to a Web3 Secret Storage (keystore) JSON file")
+        .with_validator(|input: &str| {
+            let path = Path::new(input.trim());
+            if !path.is_file() {
+                return Ok(Validation::Invalid("File not found".into()));
+            }
+            let is_keystore = std::fs::read_to_string(path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+                .is_some_and(|json| json.get("crypto").or(json.get("Crypto")).is_some());
+            if is_keystore {
+                Ok(Validation::Valid)
+            } else {
+                Ok(Validation::Invalid(
+                    "File is not a valid keystore JSON".into(),
+                ))
+            }
+        })
+        .prompt()?;
+
+    let keystore_password = inquire::Password::new("Keystore password:")
+        .with_display_mode(inquire::PasswordDisplayMode::Masked)
+        .with_help_message("The password the keystore file was encrypted with")
+        .without_confirmation()
+        .prompt()?;
+
+    Ok((PathBuf::from(path.trim()), keystore_password))
+}
+
+fn prompt_name_and_password() -> Result<(String, String)> {
     let name = inquire::Text::new("Wallet name:")
         .with_help_message("A name to identify this wallet in the app")
         .prompt()?;
@@ -129,31 +444,39 @@ async fn import_wallet() -> Result<()> {
         style("This password will be required to access your wallet.").dim()
     );
 
+    let password = prompt_new_password()?;
+
+    Ok((name, password))
+}
+
+/// Asks for a new wallet password twice, refusing weak ones, and shows how strong it is
+fn prompt_new_password() -> Result<String> {
     let password = inquire::Password::new("Enter password:")
         .with_display_toggle_enabled()
         .with_display_mode(inquire::PasswordDisplayMode::Masked)
+        .with_help_message(PASSWORD_POLICY)
+        .with_validator(|input: &str| {
+            let strength = check_password_strength(input);
+            if strength.is_acceptable() {
+                Ok(Validation::Valid)
+            } else {
+                Ok(Validation::Invalid(
+                    format!("This password is {}. {}", strength, PASSWORD_POLICY).into(),
+                ))
+            }
+        })
         .with_custom_confirmation_error_message("The passwords don't match.")
         .with_custom_confirmation_message("Please confirm your password:")
         .with_formatter(&|_| String::from("✓ Password set"))
         .prompt()?;
 
-    println!(
-        "\n{}",
-        style("⏳ Importing your wallet. This may take a few seconds...").dim()
-    );
-
-    let cmd = WalletCommand {
-        action: WalletAction::Import {
-            private_key: private_key.clone(),
-            name: name.clone(),
-            password: password.clone(),
-        },
+    let strength = check_password_strength(&password);
+    let label = match strength {
+        PasswordStrength::Strong => style(strength.to_string()).green(),
+        _ => style(strength.to_string()).yellow(),
     };
-
-    cmd.execute().await?;
-
-    println!("\n{}", style("✅ Wallet imported successfully!").green());
-    Ok(())
+    println!("Password strength: {}", label);
+    Ok(password)
 }
 
 async fn list_wallets() -> Result<()> {
@@ -187,6 +510,37 @@ async fn switch_wallet() -> Result<()> {
     Ok(())
 }
 
+/// When wallets exist but none is active (e.g. the active one was deleted), asks
+/// which one to use so balance and transfer flows don't fail with "no default wallet".
+/// A single wallet is selected without asking.
+pub async fn choose_active_wallet_if_missing() -> Result<()> {
+    let Ok(contents) = std::fs::read_to_string(constants::wallet_file_path()) else {
+        return Ok(());
+    };
+    let wallet_data: WalletData = serde_json::from_str(&contents)?;
+    if wallet_data.wallets.is_empty() || wallet_data.get_current_wallet().is_some() {
+        return Ok(());
+    }
+
+    let mut names: Vec<String> = wallet_data.wallets.values().map(|w| w.name.clone()).collect();
+    names.sort();
+    let name = if names.len() == 1 {
+        println!("{}", style("No active wallet; using the only one").yellow());
+        names.remove(0)
+    } else {
+        println!("{}", style("⚠️  No wallet is active").yellow());
+        inquire::Select::new("Which wallet should be the default?", names)
+            .with_help_message("Change it later with Wallet Management → Switch Wallet")
+            .prompt()?
+    };
+
+    WalletCommand {
+        action: WalletAction::Switch { name },
+    }
+    .execute()
+    .await
+}
+
 async fn rename_wallet() -> Result<()> {
     println!("\n{}", style("✏️ Rename Wallet").bold());
     println!("{}", "=".repeat(30));
@@ -226,9 +580,59 @@ async fn rename_wallet() -> Result<()> {
     Ok(())
 }
 
-async fn backup_wallet() -> Result<()> {
-    use std::path::PathBuf;
+async fn edit_label() -> Result<()> {
+    println!("\n{}", style("🏷️ Edit Label").bold());
+    println!("{}", "=".repeat(30));
+
+    let wallet_file = constants::wallet_file_path();
+    let wallet_data: WalletData = serde_json::from_str(&std::fs::read_to_string(&wallet_file)?)?;
+    let mut names: Vec<String> = wallet_data.wallets.values().map(|w| w.name.clone()).collect();
+    if names.is_empty() {
+        println!("No wallets found");
+        return Ok(());
+    }
+    names.sort();
 
+    let name = inquire::Select::new("Select wallet:", names).prompt()?;
+    let wallet = wallet_data
+        .get_wallet_by_name(&name)
+        .ok_or_else(|| anyhow::anyhow!("Wallet '{}' not found", name))?;
+
+    let label = inquire::Text::new("Label:")
+        .with_initial_value(wallet.label.as_deref().unwrap_or(""))
+        .with_help_message("e.g. Cold Storage (leave empty to remove)")
+        .prompt()?;
+
+    let mut colors = vec!["none"];
+    colors.extend_from_slice(WALLET_COLORS);
+    let current_color = wallet
+        .color
+        .as_deref()
+        .and_then(|c| colors.iter().position(|option| *option == c))
+        .unwrap_or(0);
+    let color = inquire::Select::new("Color:", colors)
+        .with_starting_cursor(current_color)
+        .prompt()?;
+
+    let note = inquire::Text::new("Note:")
+        .with_initial_value(wallet.note.as_deref().unwrap_or(""))
+        .with_help_message("e.g. do not spend (leave empty to remove)")
+        .prompt()?;
+
+    WalletCommand {
+        action: WalletAction::SetLabel {
+            name,
+            label: Some(label),
+            color: Some(if color == "none" { String::new() } else { color.to_string() }),
+            note: Some(note),
+            clear: false,
+        },
+    }
+    .execute()
+    .await
+}
+
+async fn backup_wallet() -> Result<()> {
     println!("\n{}", style("💾 Backup Wallet").bold());
     println!("{}", "=".repeat(30));
 
@@ -271,6 +675,157 @@ async fn backup_wallet() -> Result<()> {
     Ok(())
 }
 
+/// Writes a wallet as a v3 keystore file for geth, MetaMask and other wallets
+async fn export_keystore() -> Result<()> {
+    println!("\n{}", style("🔐 Export Keystore").bold());
+    println!("{}", "=".repeat(30));
+
+    WalletCommand {
+        action: WalletAction::List,
+    }
+    .execute()
+    .await?;
+
+    let name = inquire::Text::new("Wallet to export:").prompt()?;
+    let path = inquire::Text::new("Save the keystore to:")
+        .with_default(&format!("{}-keystore.json", name.trim()))
+        .prompt()?;
+    let password = inquire::Password::new(&format!("Password of '{}':", name.trim()))
+        .without_confirmation()
+        .prompt()?;
+    let same_password = inquire::Confirm::new("Encrypt the keystore with the same password?")
+        .with_default(true)
+        .prompt()?;
+    let keystore_password = if same_password {
+        None
+    } else {
+        Some(prompt_new_password()?)
+    };
+
+    println!(
+        "\n{}",
+        style("⏳ Encrypting the keystore. This may take a few seconds...").dim()
+    );
+    WalletCommand {
+        action: WalletAction::ExportKeystore {
+            name: name.trim().to_string(),
+            path: PathBuf::from(path.trim()),
+            password,
+            keystore_password,
+        },
+    }
+    .execute()
+    .await
+}
+
+async fn nonce_report() -> Result<()> {
+    println!("\n{}", style("🔢 Nonce Report").bold());
+    println!("{}", "=".repeat(30));
+
+    let list_cmd = WalletCommand {
+        action: WalletAction::List,
+    };
+    list_cmd.execute().await?;
+
+    let name = inquire::Text::new("Enter the name of the wallet to inspect:")
+        .with_help_message("Enter the exact name of the wallet")
+        .prompt()?;
+
+    let cmd = WalletCommand {
+        action: WalletAction::NonceReport {
+            name,
+            limit: 100,
+            block_tag: None,
+        },
+    };
+    cmd.execute().await
+}
+
+async fn export_vcard() -> Result<()> {
+    println!("\n{}", style("📇 Share Address as vCard").bold());
+    println!("{}", "=".repeat(30));
+
+    let name = inquire::Text::new("Wallet name (leave empty for the active wallet):")
+        .with_help_message("The address and label of this wallet go into the contact")
+        .prompt()?;
+    let output = inquire::Text::new("Save the vCard to (leave empty for <wallet name>.vcf):")
+        .with_help_message("A QR code image is saved next to it")
+        .prompt()?;
+
+    WalletCommand {
+        action: WalletAction::ExportVcard {
+            name: (!name.trim().is_empty()).then(|| name.trim().to_string()),
+            output: (!output.trim().is_empty()).then(|| PathBuf::from(output.trim())),
+        },
+    }
+    .execute()
+    .await
+}
+
+async fn show_address_qr() -> Result<()> {
+    println!("\n{}", style("📱 Show Address QR").bold());
+    println!("{}", "=".repeat(30));
+
+    let name = inquire::Text::new("Wallet name (leave empty for the active wallet):").prompt()?;
+    let png = inquire::Text::new("Also save as PNG to (leave empty to skip):")
+        .with_help_message("e.g. my-address.png, to share the code as an image")
+        .prompt()?;
+
+    WalletCommand {
+        action: WalletAction::Qr {
+            name: (!name.trim().is_empty()).then(|| name.trim().to_string()),
+            png: (!png.trim().is_empty()).then(|| PathBuf::from(png.trim())),
+        },
+    }
+    .execute()
+    .await
+}
+
+/// Signs a message with one of the wallets, or checks someone else's signature
+async fn sign_or_verify_message() -> Result<()> {
+    println!("\n{}", style("✍️ Sign / Verify Message").bold());
+    println!("{}", "=".repeat(30));
+
+    let mode = inquire::Select::new(
+        "What would you like to do?",
+        vec!["Sign a message", "Verify a signature"],
+    )
+    .prompt()?;
+
+    let action = if mode == "Sign a message" {
+        let wallet_data: WalletData =
+            serde_json::from_str(&std::fs::read_to_string(constants::wallet_file_path())?)?;
+        let mut names: Vec<String> =
+            wallet_data.wallets.values().map(|w| w.name.clone()).collect();
+        if names.is_empty() {
+            println!("No wallets found");
+            return Ok(());
+        }
+        names.sort();
+        let name = inquire::Select::new("Sign with:", names).prompt()?;
+        let message = inquire::Text::new("Message:").prompt()?;
+        let password = inquire::Password::new(&format!("Password of '{}':", name))
+            .without_confirmation()
+            .prompt()?;
+        WalletAction::SignMessage {
+            name,
+            message,
+            password,
+        }
+    } else {
+        let address = inquire::Text::new("Signer address:").prompt()?;
+        let message = inquire::Text::new("Message:").prompt()?;
+        let signature = inquire::Text::new("Signature (0x...):").prompt()?;
+        WalletAction::VerifyMessage {
+            address,
+            message,
+            signature,
+        }
+    };
+
+    WalletCommand { action }.execute().await
+}
+
 async fn delete_wallet() -> Result<()> {
     println!("\n{}", style("🗑️ Delete Wallet").bold());
     println!("{}", "=".repeat(30));
@@ -306,6 +861,7 @@ async fn delete_wallet() -> Result<()> {
             style("✅ Wallet deleted:").green(),
             style(wallet_name).bold()
         );
+        choose_active_wallet_if_missing().await?;
     } else {
         println!("\n{}", style("❌ Deletion cancelled").yellow());
     }
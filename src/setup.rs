@@ -4,8 +4,13 @@ use dialoguer::{Select, theme::ColorfulTheme};
 use inquire::Text;
 use std::io::{self, Write};
 
+use crate::commands::contacts::{import_contacts, migrate_wallet_contacts};
 use crate::config::{Config, ConfigManager};
 use crate::types::network::Network;
+use crate::types::wallet::{Wallet, WalletData};
+use crate::utils::constants;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 pub fn run_setup_wizard() -> Result<()> {
     let config_manager = ConfigManager::new()?;
@@ -88,59 +93,236 @@ pub async fn ensure_configured() -> Result<()> {
         // First, run the network setup
         run_setup_wizard()?;
 
-        // Then guide through wallet creation
-        println!(
-            "\n{}",
-            style("🎉 Great! Now let's create your first wallet.").bold()
-        );
-        println!(
-            "\n{}",
-            style("A wallet is like your personal bank account for cryptocurrencies.").dim()
-        );
-
-        // Prompt user for wallet name
-        println!(
-            "\n{}",
-            style("Let's create your first wallet").bold().blue()
-        );
-        println!(
-            "{}",
-            style("Please choose a name for your wallet (e.g., 'Savings', 'Trading', 'Personal')")
-                .dim()
-        );
-
-        let wallet_name = inquire::Text::new("\nWallet name:")
-            .with_help_message("Enter a name to identify this wallet")
-            .with_default("My Wallet")
-            .prompt()?;
-
-        println!("\nCreating your wallet: {}", style(&wallet_name).bold());
-
-        // Use the wallet module to create a new wallet
-        if let Err(e) = crate::interactive::create_wallet_with_name(&wallet_name).await {
-            eprintln!("Failed to create default wallet: {}", e);
+        // Restore a backup if one is found, otherwise guide through wallet creation
+        if !offer_wallet_recovery()? {
             println!(
                 "\n{}",
-                style("You can create a wallet later from the main menu.").yellow()
+                style("🎉 Great! Now let's create your first wallet.").bold()
             );
-        } else {
             println!(
-                "\n{} {}",
-                style("✓").green().bold(),
-                style("Wallet created successfully!").bold()
+                "\n{}",
+                style("A wallet is like your personal bank account for cryptocurrencies.").dim()
             );
+
+            // Prompt user for wallet name
             println!(
                 "\n{}",
-                style("Your wallet is now ready to use. You can manage it from the main menu.")
+                style("Let's create your first wallet").bold().blue()
+            );
+            println!(
+                "{}",
+                style("Please choose a name for your wallet (e.g., 'Savings', 'Trading', 'Personal')")
                     .dim()
             );
+
+            let wallet_name = inquire::Text::new("\nWallet name:")
+                .with_help_message("Enter a name to identify this wallet")
+                .with_default("My Wallet")
+                .prompt()?;
+
+            println!("\nCreating your wallet: {}", style(&wallet_name).bold());
+
+            // Use the wallet module to create a new wallet
+            if let Err(e) = crate::interactive::create_wallet_with_name(&wallet_name).await {
+                eprintln!("Failed to create default wallet: {}", e);
+                println!(
+                    "\n{}",
+                    style("You can create a wallet later from the main menu.").yellow()
+                );
+            } else {
+                println!(
+                    "\n{} {}",
+                    style("✓").green().bold(),
+                    style("Wallet created successfully!").bold()
+                );
+                println!(
+                    "\n{}",
+                    style("Your wallet is now ready to use. You can manage it from the main menu.")
+                        .dim()
+                );
+            }
         }
 
         println!("\n{}", style("Setup complete! 🚀").bold().green());
+    } else {
+        offer_wallet_recovery()?;
+    }
+
+    // Fold keys from the deprecated api_keys.json into config.json
+    let migrated = config_manager.migrate_legacy_api_keys()?;
+    if !migrated.is_empty() {
+        println!(
+            "{} Migrated Alchemy API key(s) for {} from api_keys.json into config.json",
+            style("ℹ").blue(),
+            migrated.join(", ")
+        );
+    }
+    let migrated = config_manager.migrate_wallet_api_key(&constants::wallet_file_path())?;
+    if !migrated.is_empty() {
+        println!(
+            "{} Copied the wallet file's Alchemy API key into config.json for {}",
+            style("ℹ").blue(),
+            migrated.join(", ")
+        );
+    }
+
+    let moved = migrate_wallet_contacts()?;
+    if moved > 0 {
+        println!(
+            "{} Moved {} contact(s) from the wallet file into contacts.json",
+            style("ℹ").blue(),
+            moved
+        );
     }
     Ok(())
 }
 
+/// Contents of a wallet backup file
+enum WalletBackup {
+    /// A whole wallet file (`rootstock-wallet.json`)
+    Full(WalletData),
+    /// One wallet, as written by `wallet backup`
+    Single(Wallet),
+}
+
+fn parse_wallet_backup(content: &str) -> Result<WalletBackup> {
+    if let Ok(data) = serde_json::from_str::<WalletData>(content) {
+        return Ok(WalletBackup::Full(data));
+    }
+    serde_json::from_str::<Wallet>(content)
+        .map(WalletBackup::Single)
+        .map_err(|_| anyhow::anyhow!("Not a wallet backup"))
+}
+
+/// Wallet backups in the current directory (where `wallet backup` writes them) and
+/// in the directories made by Clear Cache & Reset, newest first
+fn find_wallet_backups() -> Vec<PathBuf> {
+    let mut candidates: Vec<PathBuf> = fs::read_dir(".")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    if let Some(data_dir) = dirs::data_local_dir() {
+        candidates.extend(
+            fs::read_dir(data_dir)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter(|entry| {
+                    entry
+                        .file_name()
+                        .to_string_lossy()
+                        .starts_with("rootstock-wallet-backup-")
+                })
+                .map(|entry| entry.path().join("data").join("rootstock-wallet.json")),
+        );
+    }
+
+    let mut backups: Vec<(PathBuf, std::time::SystemTime)> = candidates
+        .into_iter()
+        .filter(|path| {
+            fs::read_to_string(path)
+                .ok()
+                .is_some_and(|content| parse_wallet_backup(&content).is_ok())
+        })
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect();
+    backups.sort_by(|a, b| b.1.cmp(&a.1));
+    backups.into_iter().map(|(path, _)| path).collect()
+}
+
+/// Merges a backup into the wallet file and its contacts into `contacts.json`;
+/// wallets already present are kept, contacts are merged by address
+fn restore_wallet_backup(path: &Path) -> Result<usize> {
+    let backup = parse_wallet_backup(&fs::read_to_string(path)?)?;
+    WalletData::update(|data| {
+        let (wallets, contacts, current) = match backup {
+            WalletBackup::Full(full) => (
+                full.wallets.into_values().collect::<Vec<_>>(),
+                full.contacts,
+                Some(full.current_wallet),
+            ),
+            WalletBackup::Single(wallet) => (vec![wallet], Vec::new(), None),
+        };
+        let previous = data.current_wallet.clone();
+        let mut restored = 0;
+        for wallet in wallets {
+            if data.add_wallet(wallet).is_ok() {
+                restored += 1;
+            }
+        }
+        import_contacts(contacts)?;
+        // Keep the active wallet if there was one, else the backup's
+        data.current_wallet = match current {
+            _ if data.wallets.contains_key(&previous) => previous,
+            Some(current) if data.wallets.contains_key(&current) => current,
+            _ => data.current_wallet.clone(),
+        };
+        Ok(restored)
+    })
+}
+
+/// Offers to restore wallets when the wallet file is missing. Returns whether any were restored.
+fn offer_wallet_recovery() -> Result<bool> {
+    let wallet_file = constants::wallet_file_path();
+    if wallet_file.exists() {
+        return Ok(false);
+    }
+    let backups = find_wallet_backups();
+    if backups.is_empty() {
+        return Ok(false);
+    }
+
+    println!(
+        "\n{} No wallet file was found at {}, but wallet backups exist.",
+        style("⚠").yellow(),
+        wallet_file.display()
+    );
+    let mut restored = 0;
+    loop {
+        let mut options: Vec<String> = backups.iter().map(|p| p.display().to_string()).collect();
+        options.push("Enter another backup path".to_string());
+        options.push("Don't restore".to_string());
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Restore wallets from a backup?")
+            .items(&options)
+            .default(0)
+            .interact()?;
+
+        let path = if selection < backups.len() {
+            backups[selection].clone()
+        } else if selection == backups.len() {
+            PathBuf::from(Text::new("Backup file path:").prompt()?.trim())
+        } else {
+            break;
+        };
+        match restore_wallet_backup(&path) {
+            Ok(count) => {
+                restored += count;
+                println!(
+                    "{} Restored {} wallet(s) from {}",
+                    style("✓").green().bold(),
+                    count,
+                    path.display()
+                );
+            }
+            Err(e) => eprintln!("Could not restore {}: {}", path.display(), e),
+        }
+        if !inquire::Confirm::new("Restore another backup?")
+            .with_default(false)
+            .prompt()?
+        {
+            break;
+        }
+    }
+    Ok(restored > 0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,4 +362,33 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_parse_wallet_backup_kinds() {
+        let wallet = r#"{
+            "address": "0x0000000000000000000000000000000000000001",
+            "balance": "0x0",
+            "network": "",
+            "name": "Savings",
+            "encrypted_private_key": "",
+            "salt": "",
+            "iv": "",
+            "created_at": "2025-01-01T00:00:00Z"
+        }"#;
+        assert!(matches!(
+            parse_wallet_backup(wallet),
+            Ok(WalletBackup::Single(w)) if w.name == "Savings"
+        ));
+
+        let full = format!(
+            r#"{{"current_wallet": "", "wallets": {{"0x01": {}}}, "contacts": [], "api_key": null}}"#,
+            wallet
+        );
+        assert!(matches!(
+            parse_wallet_backup(&full),
+            Ok(WalletBackup::Full(data)) if data.wallets.len() == 1
+        ));
+
+        assert!(parse_wallet_backup(r#"{"mainnet": {}}"#).is_err());
+    }
 }
@@ -1,4 +1,5 @@
 use crate::types::transaction::RskTransaction;
+use crate::utils::clock::{FutureCheck, check_future};
 use anyhow::Result;
 use colored::Colorize;
 use alloy::primitives::{Address, B256, U256};
@@ -19,6 +20,9 @@ pub struct Contact {
     pub notes: Option<String>,
     pub tags: Vec<String>,
     pub created_at: chrono::DateTime<chrono::Local>,
+    /// Last time the name, address, notes or tags were changed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<chrono::DateTime<chrono::Local>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transaction_stats: Option<ContactTransactionStats>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -33,6 +37,7 @@ impl Contact {
             notes,
             tags,
             created_at: chrono::Local::now(),
+            updated_at: None,
             transaction_stats: Some(ContactTransactionStats {
                 total_transactions: 0,
                 total_volume: U256::ZERO,
@@ -42,6 +47,11 @@ impl Contact {
         }
     }
 
+    /// When the contact was last edited, or created if it never was
+    pub fn last_edited(&self) -> chrono::DateTime<chrono::Local> {
+        self.updated_at.unwrap_or(self.created_at)
+    }
+
     pub fn update_transaction_stats(&mut self, tx: &RskTransaction, _is_incoming: bool) {
         // Ensure transaction_stats is initialized
         if self.transaction_stats.is_none() {
@@ -202,32 +212,21 @@ impl Contact {
         if self.tags.len() > 5 {
             return Err(anyhow::anyhow!("A contact can have a maximum of 5 tags"));
         }
-        if self.created_at.timestamp() > chrono::Local::now().timestamp() {
-            return Err(anyhow::anyhow!(
-                "Created at timestamp cannot be in the future"
-            ));
-        }
+        let now = chrono::Local::now().timestamp();
+        check_not_future("Created at", self.created_at.timestamp(), now)?;
         if self.created_at.timestamp() < 0 {
             return Err(anyhow::anyhow!("Created at timestamp cannot be negative"));
         }
         if let Some(stats) = &self.transaction_stats
             && let Some(last_tx) = stats.last_transaction
-            && last_tx.timestamp() > chrono::Local::now().timestamp()
         {
-            return Err(anyhow::anyhow!(
-                "Last transaction timestamp cannot be in the future"
-            ));
+            check_not_future("Last transaction", last_tx.timestamp(), now)?;
         }
 
         if self.created_at.timestamp() < 1_000_000_000 {
             return Err(anyhow::anyhow!("Created at timestamp is too old"));
         }
-        if self.created_at.timestamp() > chrono::Local::now().timestamp() + 60 * 60 * 24 * 365 {
-            return Err(anyhow::anyhow!(
-                "Created at timestamp is too far in the future"
-            ));
-        }
-        if self.created_at.timestamp() < chrono::Local::now().timestamp() - 60 * 60 * 24 * 365 {
+        if self.created_at.timestamp() < now - 60 * 60 * 24 * 365 {
             return Err(anyhow::anyhow!(
                 "Created at timestamp is too far in the past"
             ));
@@ -236,6 +235,26 @@ impl Contact {
     }
 }
 
+/// Rejects a timestamp further in the future than clock skew explains; a slightly
+/// fast clock on whichever machine wrote it only gets a warning
+fn check_not_future(field: &str, timestamp: i64, now: i64) -> Result<()> {
+    match check_future(timestamp, now) {
+        FutureCheck::NotFuture => Ok(()),
+        FutureCheck::Skewed(ahead) => {
+            eprintln!(
+                "⚠️  {} timestamp is {}s ahead of this computer's clock; the system clock may be off",
+                field, ahead
+            );
+            Ok(())
+        }
+        FutureCheck::TooFar(ahead) => Err(anyhow::anyhow!(
+            "{} timestamp is {}s in the future; check the system clock (see System → Network Status)",
+            field,
+            ahead
+        )),
+    }
+}
+
 impl fmt::Display for Contact {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let tx_info = if let Some(stats) = &self.transaction_stats {
@@ -1,4 +1,5 @@
 pub mod contacts;
 pub mod network;
 pub mod transaction;
+pub mod tx_label;
 pub mod wallet;
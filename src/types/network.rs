@@ -8,6 +8,7 @@ pub struct NetworkConfig {
 }
 
 use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Network {
@@ -35,6 +36,52 @@ impl fmt::Display for Network {
 }
 
 impl Network {
+    /// Every network, in the order they are listed to the user
+    pub const ALL: [Network; 7] = [
+        Network::Mainnet,
+        Network::Testnet,
+        Network::Regtest,
+        Network::AlchemyMainnet,
+        Network::AlchemyTestnet,
+        Network::RootStockMainnet,
+        Network::RootStockTestnet,
+    ];
+
+    /// Name accepted by `from_str` and `--network`
+    pub fn cli_name(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+            Network::Regtest => "regtest",
+            Network::AlchemyMainnet => "alchemy-mainnet",
+            Network::AlchemyTestnet => "alchemy-testnet",
+            Network::RootStockMainnet => "rootstock-mainnet",
+            Network::RootStockTestnet => "rootstock-testnet",
+        }
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        match self {
+            Network::Mainnet | Network::AlchemyMainnet | Network::RootStockMainnet => 30,
+            Network::Testnet | Network::AlchemyTestnet | Network::RootStockTestnet => 31,
+            Network::Regtest => 33,
+        }
+    }
+
+    /// Whether funds on this network are real
+    pub fn is_mainnet(&self) -> bool {
+        self.chain_id() == 30
+    }
+
+    /// The network on the other side of mainnet / testnet; regtest has none
+    pub fn counterpart(&self) -> Option<Network> {
+        match self.chain_id() {
+            30 => Some(Network::Testnet),
+            31 => Some(Network::Mainnet),
+            _ => None,
+        }
+    }
+
     pub fn get_config(&self) -> NetworkConfig {
         match self {
             Network::Mainnet => NetworkConfig {
@@ -114,17 +161,26 @@ impl Network {
             Network::Regtest => "http://localhost:4444".to_string(),
         }
     }
+}
+
+/// A network name that isn't one of the names [`Network`] parses
+#[derive(Debug, thiserror::Error)]
+#[error("Unknown network: {0}")]
+pub struct UnknownNetworkError(String);
 
-    pub fn from_str(s: &str) -> Option<Self> {
+impl FromStr for Network {
+    type Err = UnknownNetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
-            "mainnet" => Some(Network::Mainnet),
-            "testnet" => Some(Network::Testnet),
-            "regtest" => Some(Network::Regtest),
-            "alchemy-mainnet" => Some(Network::AlchemyMainnet),
-            "alchemy-testnet" => Some(Network::AlchemyTestnet),
-            "rootstock-mainnet" => Some(Network::RootStockMainnet),
-            "rootstock-testnet" => Some(Network::RootStockTestnet),
-            _ => None,
+            "mainnet" => Ok(Network::Mainnet),
+            "testnet" => Ok(Network::Testnet),
+            "regtest" => Ok(Network::Regtest),
+            "alchemy-mainnet" => Ok(Network::AlchemyMainnet),
+            "alchemy-testnet" => Ok(Network::AlchemyTestnet),
+            "rootstock-mainnet" => Ok(Network::RootStockMainnet),
+            "rootstock-testnet" => Ok(Network::RootStockTestnet),
+            _ => Err(UnknownNetworkError(s.to_string())),
         }
     }
 }
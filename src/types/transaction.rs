@@ -2,8 +2,8 @@ use crate::utils::alchemy::AlchemyClient;
 use anyhow::{Result, anyhow};
 use chrono::{DateTime, Utc};
 use alloy::primitives::{Address, Bytes, B256, U64, U256};
+use alloy::consensus::Transaction as _;
 use alloy::providers::{Provider, ProviderBuilder};
-use alloy::transports::http::{Client, Http};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::str::FromStr;
@@ -27,6 +27,9 @@ pub struct RskTransaction {
     pub timestamp: SystemTime,
     pub status: TransactionStatus,
     pub token_address: Option<Address>,
+    /// Decimals of `value` as reported with the transfer; `None` when unknown
+    #[serde(default)]
+    pub decimals: Option<u8>,
 
     // Additional metadata
     pub confirms: Option<U64>,
@@ -54,6 +57,72 @@ impl std::fmt::Display for TransactionStatus {
     }
 }
 
+/// What a transaction did, as far as its category and calldata tell
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TxType {
+    Native,
+    Transfer,
+    Approval,
+    Call,
+    Creation,
+}
+
+impl std::fmt::Display for TxType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Native => write!(f, "native"),
+            Self::Transfer => write!(f, "transfer"),
+            Self::Approval => write!(f, "approval"),
+            Self::Call => write!(f, "call"),
+            Self::Creation => write!(f, "creation"),
+        }
+    }
+}
+
+impl FromStr for TxType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "native" | "rbtc" => Ok(Self::Native),
+            "transfer" | "erc20" | "token" => Ok(Self::Transfer),
+            "approval" | "approve" => Ok(Self::Approval),
+            "call" | "contract" => Ok(Self::Call),
+            "creation" | "deploy" => Ok(Self::Creation),
+            _ => Err(anyhow!(
+                "Invalid type '{}': use native, transfer, approval, call or creation",
+                s
+            )),
+        }
+    }
+}
+
+/// `approve(address,uint256)`
+const APPROVE_SELECTOR: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+/// `transfer(address,uint256)` and `transferFrom(address,address,uint256)`
+const TRANSFER_SELECTORS: [[u8; 4]; 2] = [[0xa9, 0x05, 0x9c, 0xbb], [0x23, 0xb8, 0x72, 0xdd]];
+
+/// Amount of an Alchemy transfer in base units, with the decimals to display it at.
+/// `value` at the top level is a rounded decimal in whole tokens, so the exact hex
+/// amount under `rawContract` is used when present.
+fn transfer_amount(transfer: &Value) -> Result<(U256, Option<u8>)> {
+    let parse_hex = |hex: &str| U256::from_str_radix(hex.trim_start_matches("0x"), 16);
+    let raw = &transfer["rawContract"];
+    let decimals = raw["decimal"]
+        .as_str()
+        .and_then(|hex| u8::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+        .or_else(|| raw["decimal"].as_u64().and_then(|d| u8::try_from(d).ok()));
+    if let Some(hex) = raw["value"].as_str() {
+        return Ok((parse_hex(hex)?, decimals));
+    }
+    // Older replies without rawContract: a hex string is already in base units
+    match transfer["value"].as_str() {
+        Some(hex) if hex.starts_with("0x") => Ok((parse_hex(hex)?, decimals)),
+        _ => Ok((U256::ZERO, decimals)),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionReceipt {
     pub transaction_hash: B256,
@@ -101,10 +170,28 @@ impl RskTransaction {
         record.push_field(&self.gas.to_string());
         record.push_field(status);
         record.push_field(&self.block_number.map(|n| n.to_string()).unwrap_or_default());
+        record.push_field(&self.tx_type().to_string());
 
         record
     }
 
+    /// Classifies the transaction from its recipient, token and calldata
+    pub fn tx_type(&self) -> TxType {
+        let selector = self
+            .input
+            .as_ref()
+            .and_then(|input| input.get(..4))
+            .and_then(|selector| <[u8; 4]>::try_from(selector).ok());
+        match selector {
+            _ if self.to.is_none() => TxType::Creation,
+            Some(APPROVE_SELECTOR) => TxType::Approval,
+            Some(selector) if TRANSFER_SELECTORS.contains(&selector) => TxType::Transfer,
+            _ if self.token_address.is_some() => TxType::Transfer,
+            Some(_) => TxType::Call,
+            None => TxType::Native,
+        }
+    }
+
     pub async fn from_alchemy_transfer(
         transfer: &Value,
         _wallet_address: &Address,
@@ -131,17 +218,11 @@ impl RskTransaction {
             .as_str()
             .and_then(|s| Address::from_str(s).ok());
 
-        // Handle value (can be number or hex string)
-        let value = if let Some(num) = transfer["value"].as_u64() {
-            U256::from(num)
-        } else if let Some(hex_str) = transfer["value"].as_str() {
-            U256::from_str_radix(hex_str.trim_start_matches("0x"), 16)?
-        } else {
-            U256::ZERO
-        };
+        let (value, decimals) = transfer_amount(transfer)?;
 
         // Get transaction receipt for status and gas used
         let rpc_url = alchemy_client.get_base_url();
+        alchemy_client.throttle().await;
         let receipt = Self::get_transaction_receipt(&hash, &rpc_url).await?;
         let (status, gas_used) = match receipt {
             Some(r) => (r.status, r.gas_used),
@@ -201,6 +282,17 @@ impl RskTransaction {
                     .and_then(|s| U256::from_str_radix(s.trim_start_matches("0x"), 16).ok())
             });
 
+        // Top-level transactions may be contract calls; their calldata tells which
+        let input = if transfer["category"].as_str() == Some("external") {
+            alchemy_client.throttle().await;
+            Self::get_transaction_input(&hash, &rpc_url)
+                .await
+                .ok()
+                .flatten()
+        } else {
+            None
+        };
+
         // Get nonce if available
         let nonce = transfer["nonce"]
             .as_str()
@@ -215,18 +307,28 @@ impl RskTransaction {
             gas_price: gas_price.unwrap_or_default(),
             gas: gas_used,
             nonce,
-            input: None, // Could be populated from raw transaction if needed
+            input,
             block_number: block_number.map(|n| U64::from(n.to::<u64>())),
             transaction_index: None, // Could be populated from raw transaction
             timestamp,
             status,
             token_address,
+            decimals,
             confirms: None, // Would need to be calculated from current block
             cumulative_gas_used: Some(gas_used), // From receipt if available
             logs: None,     // Could be populated from receipt if needed
         })
     }
 
+    /// Calldata of the transaction, `None` when it has none
+    async fn get_transaction_input(hash: &B256, rpc_url: &str) -> Result<Option<Bytes>> {
+        let provider = ProviderBuilder::new().on_http(rpc_url.parse()?);
+        let tx = provider.get_transaction_by_hash(*hash).await?;
+        Ok(tx
+            .map(|tx| tx.input().clone())
+            .filter(|input| !input.is_empty()))
+    }
+
     async fn get_transaction_receipt(
         hash: &B256,
         rpc_url: &str,
@@ -248,3 +350,26 @@ impl RskTransaction {
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_transfer_amount_reads_raw_contract() {
+        let rbtc = json!({
+            "value": 0.5,
+            "rawContract": { "value": "0x6f05b59d3b20000", "address": null, "decimal": "0x12" }
+        });
+        assert_eq!(
+            transfer_amount(&rbtc).unwrap(),
+            (U256::from(500_000_000_000_000_000u64), Some(18))
+        );
+
+        let token = json!({ "value": 1.5, "rawContract": { "value": "0x16e360", "decimal": "0x6" } });
+        assert_eq!(transfer_amount(&token).unwrap(), (U256::from(1_500_000u64), Some(6)));
+
+        assert_eq!(transfer_amount(&json!({ "value": 2 })).unwrap(), (U256::ZERO, None));
+    }
+}
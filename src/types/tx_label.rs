@@ -0,0 +1,69 @@
+use crate::utils::{constants, file_lock};
+use alloy::primitives::B256;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+/// Bookkeeping category and memo attached to a transaction
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TxLabel {
+    #[serde(default)]
+    pub category: String,
+    #[serde(default)]
+    pub memo: String,
+}
+
+impl TxLabel {
+    /// "category: memo", or whichever of the two is set
+    pub fn summary(&self) -> String {
+        match (self.category.is_empty(), self.memo.is_empty()) {
+            (false, false) => format!("{}: {}", self.category, self.memo),
+            (false, true) => self.category.clone(),
+            _ => self.memo.clone(),
+        }
+    }
+}
+
+/// Labels stored in `tx_labels.json`, keyed by lowercase transaction hash
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TxLabels(HashMap<String, TxLabel>);
+
+impl TxLabels {
+    pub fn load() -> Self {
+        fs::read_to_string(constants::tx_labels_path())
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, hash: &B256) -> Option<&TxLabel> {
+        self.0.get(&format!("0x{:x}", hash))
+    }
+
+    /// Stores a note typed at send time as the memo of `hash`, keeping any category
+    pub fn add_note(hash: &B256, note: &str) -> Result<()> {
+        let key = format!("0x{:x}", hash);
+        let note = note.trim().to_string();
+        file_lock::update_json(&constants::tx_labels_path(), |labels: &mut TxLabels| {
+            if !note.is_empty() {
+                labels.0.entry(key).or_default().memo = note;
+            }
+            Ok(())
+        })
+    }
+
+    /// Stores the label of `hash`, or removes it when both fields are empty
+    pub fn set(hash: &B256, label: TxLabel) -> Result<()> {
+        let key = format!("0x{:x}", hash);
+        file_lock::update_json(&constants::tx_labels_path(), |labels: &mut TxLabels| {
+            if label.category.is_empty() && label.memo.is_empty() {
+                labels.0.remove(&key);
+            } else {
+                labels.0.insert(key, label);
+            }
+            Ok(())
+        })
+    }
+}
@@ -1,4 +1,5 @@
 use crate::types::contacts::Contact;
+use crate::utils::{constants, file_lock, hd};
 use aes::Aes256;
 use anyhow::Result;
 use anyhow::{Error, anyhow};
@@ -8,8 +9,9 @@ use cbc::cipher::block_padding::Pkcs7;
 use cbc::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit};
 use cbc::{Decryptor, Encryptor};
 use chrono::Utc;
+use colored::Colorize;
 use alloy::primitives::{Address, U256};
-use alloy::signers::{local::PrivateKeySigner, Signer};
+use alloy::signers::local::PrivateKeySigner;
 use generic_array::GenericArray;
 use rand::{RngCore, rngs::OsRng};
 use scrypt::{Params, scrypt};
@@ -27,13 +29,49 @@ pub struct Wallet {
     pub salt: String,
     pub iv: String,
     pub created_at: String,
+    /// Set once the wallet has been exported with `wallet backup`
+    #[serde(default)]
+    pub backed_up: bool,
+    /// The user asked not to be reminded about backing up this wallet
+    #[serde(default)]
+    pub backup_reminder_dismissed: bool,
+    /// Short freeform label, e.g. "Cold Storage"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// One of `WALLET_COLORS`, used to highlight the label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Longer note shown next to the label, e.g. "do not spend"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Seed phrase this wallet was derived from, for HD wallets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hd: Option<HdSeed>,
 }
 
+/// Encrypted BIP-39 seed phrase of an HD wallet and the account index the wallet's
+/// key was derived at (on `hd::RSK_DERIVATION_PREFIX`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HdSeed {
+    pub encrypted_mnemonic: String,
+    pub salt: String,
+    pub iv: String,
+    pub index: u32,
+}
+
+/// Colors a wallet label can be shown in
+pub const WALLET_COLORS: &[&str] = &["red", "green", "yellow", "blue", "magenta", "cyan"];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WalletData {
     pub current_wallet: String,
     pub wallets: HashMap<String, Wallet>,
+    /// Contacts saved here by older versions. They are moved into `contacts.json`
+    /// at startup; read and write contacts through `ContactsCommand` instead.
+    #[serde(default)]
     pub contacts: Vec<Contact>,
+    /// Alchemy key saved here by older versions, left for `config compact` to remove
+    #[deprecated(note = "Alchemy keys live in config.json; use `Config::get_alchemy_key`")]
     pub api_key: Option<String>,
 }
 
@@ -54,6 +92,56 @@ impl Wallet {
             salt: STANDARD.encode(&salt),
             iv: STANDARD.encode(&iv),
             created_at: Utc::now().to_rfc3339(),
+            backed_up: false,
+            backup_reminder_dismissed: false,
+            label: None,
+            color: None,
+            note: None,
+            hd: None,
+        })
+    }
+
+    /// Wallet for account `index` of `mnemonic`; the seed phrase is encrypted with the
+    /// same password and scheme as the private key and kept so more accounts can be derived
+    pub fn new_hd(mnemonic: &str, index: u32, name: &str, password: &str) -> Result<Self, Error> {
+        let mut wallet = Self::new(hd::derive_account(mnemonic, index)?, name, password)?;
+        let (encrypted, iv, salt) =
+            Self::encrypt_private_key(mnemonic.trim().as_bytes(), password)?;
+        wallet.hd = Some(HdSeed {
+            encrypted_mnemonic: STANDARD.encode(&encrypted),
+            salt: STANDARD.encode(&salt),
+            iv: STANDARD.encode(&iv),
+            index,
+        });
+        Ok(wallet)
+    }
+
+    /// Seed phrase of an HD wallet
+    pub fn decrypt_mnemonic(&self, password: &str) -> Result<String, anyhow::Error> {
+        let seed = self
+            .hd
+            .as_ref()
+            .ok_or_else(|| anyhow!("Wallet '{}' was not created from a seed phrase", self.name))?;
+        let phrase = Self::decrypt_secret(&seed.encrypted_mnemonic, &seed.salt, &seed.iv, password)?;
+        String::from_utf8(phrase).map_err(|_| anyhow!("Decrypted seed phrase is not valid text"))
+    }
+
+    /// Label and note as `● Cold Storage — do not spend`, without styling
+    pub fn label_text(&self) -> Option<String> {
+        let text = match (&self.label, &self.note) {
+            (Some(label), Some(note)) => format!("{} — {}", label, note),
+            (Some(text), None) | (None, Some(text)) => text.clone(),
+            (None, None) => return None,
+        };
+        Some(format!("● {}", text))
+    }
+
+    /// `label_text` in the wallet's color
+    pub fn label_display(&self) -> Option<String> {
+        let text = self.label_text()?;
+        Some(match self.color.as_deref() {
+            Some(color) => text.color(color).bold().to_string(),
+            None => text,
         })
     }
 
@@ -78,16 +166,38 @@ impl Wallet {
     }
 
     pub fn decrypt_private_key(&self, password: &str) -> Result<String, anyhow::Error> {
+        let decrypted =
+            Self::decrypt_secret(&self.encrypted_private_key, &self.salt, &self.iv, password)?;
+
+        // Ensure the decrypted key is exactly 32 bytes
+        if decrypted.len() != 32 {
+            return Err(anyhow!(
+                "Decrypted private key has invalid length: {} bytes (expected 32)",
+                decrypted.len()
+            ));
+        }
+
+        // Return the decrypted private key as a 0x-prefixed hex string
+        Ok(format!("0x{}", hex::encode(decrypted)))
+    }
+
+    /// Reverses `encrypt_private_key` for Base64-encoded ciphertext, salt and IV
+    fn decrypt_secret(
+        encrypted: &str,
+        salt: &str,
+        iv: &str,
+        password: &str,
+    ) -> Result<Vec<u8>, anyhow::Error> {
         // Decode Base64-encoded salt, IV, and encrypted key
         let salt = STANDARD
-            .decode(&self.salt)
+            .decode(salt)
             .map_err(|e| anyhow!("Failed to decode salt: {}", e))?;
         let iv = STANDARD
-            .decode(&self.iv)
+            .decode(iv)
             .map_err(|e| anyhow!("Failed to decode IV: {}", e))?;
         let encrypted_key = STANDARD
-            .decode(&self.encrypted_private_key)
-            .map_err(|e| anyhow!("Failed to decode encrypted private key: {}", e))?;
+            .decode(encrypted)
+            .map_err(|e| anyhow!("Failed to decode encrypted data: {}", e))?;
 
         // Validate lengths
         if salt.len() != 16 {
@@ -122,16 +232,7 @@ impl Wallet {
             .decrypt_padded_mut::<Pkcs7>(&mut buffer)
             .map_err(|e| anyhow!("Decryption failed: {}", e))?;
 
-        // Ensure the decrypted key is exactly 32 bytes
-        if decrypted.len() != 32 {
-            return Err(anyhow!(
-                "Decrypted private key has invalid length: {} bytes (expected 32)",
-                decrypted.len()
-            ));
-        }
-
-        // Return the decrypted private key as a 0x-prefixed hex string
-        Ok(format!("0x{}", hex::encode(decrypted)))
+        Ok(decrypted.to_vec())
     }
 }
 
@@ -153,6 +254,7 @@ impl Default for WalletData {
 
 impl WalletData {
     /// Creates a new, empty wallet data structure.
+    #[allow(deprecated)]
     pub fn new() -> Self {
         Self {
             current_wallet: String::new(),
@@ -162,6 +264,12 @@ impl WalletData {
         }
     }
 
+    /// Applies `f` to the wallet file under an exclusive lock, re-reading it first so
+    /// changes made by another running instance are kept. Nothing is saved if `f` fails.
+    pub fn update<R>(f: impl FnOnce(&mut WalletData) -> anyhow::Result<R>) -> anyhow::Result<R> {
+        file_lock::update_json(&constants::wallet_file_path(), f)
+    }
+
     pub fn add_wallet(&mut self, wallet: Wallet) -> anyhow::Result<()> {
         let address = format!("0x{:x}", wallet.address);
         if self.wallets.contains_key(&address) {
@@ -188,6 +296,21 @@ impl WalletData {
         self.wallets.values().find(|w| w.name == name)
     }
 
+    pub fn get_wallet_by_name_mut(&mut self, name: &str) -> Option<&mut Wallet> {
+        self.wallets.values_mut().find(|w| w.name == name)
+    }
+
+    /// Wallets that were never backed up and whose reminder was not dismissed
+    pub fn wallets_needing_backup(&self) -> Vec<&Wallet> {
+        let mut wallets: Vec<&Wallet> = self
+            .wallets
+            .values()
+            .filter(|w| !w.backed_up && !w.backup_reminder_dismissed)
+            .collect();
+        wallets.sort_by(|a, b| a.name.cmp(&b.name));
+        wallets
+    }
+
     pub fn remove_wallet(&mut self, address: &str) -> anyhow::Result<()> {
         if !self.wallets.contains_key(address) {
             return Err(anyhow!("Wallet with address {} not found", address));
@@ -215,54 +338,4 @@ impl WalletData {
     pub fn list_wallets(&self) -> Vec<&Wallet> {
         self.wallets.values().collect()
     }
-
-    pub fn add_contact(&mut self, contact: Contact) -> anyhow::Result<()> {
-        if self
-            .contacts
-            .iter()
-            .any(|c| c.name == contact.name || c.address == contact.address)
-        {
-            return Err(anyhow!("Contact with name or address already exists"));
-        }
-        self.contacts.push(contact);
-        Ok(())
-    }
-
-    pub fn remove_contact(&mut self, identifier: &str) -> anyhow::Result<()> {
-        let index = self
-            .contacts
-            .iter()
-            .position(|c| c.name == identifier || c.address.to_string() == identifier)
-            .ok_or_else(|| anyhow!("Contact not found"))?;
-        self.contacts.remove(index);
-        Ok(())
-    }
-
-    pub fn update_contact(&mut self, identifier: &str, contact: Contact) -> anyhow::Result<()> {
-        let index = self
-            .contacts
-            .iter()
-            .position(|c| c.name == identifier || c.address.to_string() == identifier)
-            .ok_or_else(|| anyhow!("Contact not found"))?;
-        self.contacts[index] = contact;
-        Ok(())
-    }
-
-    pub fn get_contact(&self, identifier: &str) -> Option<&Contact> {
-        self.contacts
-            .iter()
-            .find(|c| c.name == identifier || c.address.to_string() == identifier)
-    }
-
-    pub fn search_contacts(&self, query: &str) -> Vec<&Contact> {
-        self.contacts
-            .iter()
-            .filter(|c| {
-                c.name.to_lowercase().contains(&query.to_lowercase())
-                    || c.address.to_string().contains(query)
-                    || c.notes.as_ref().is_some_and(|n| n.contains(query))
-                    || c.tags.iter().any(|t| t.contains(query))
-            })
-            .collect()
-    }
 }
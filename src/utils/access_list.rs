@@ -0,0 +1,111 @@
+//! EIP-2930 access lists for transfers
+
+use alloy::rpc::types::AccessList;
+use anyhow::{Result, anyhow};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Where a transfer's access list comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccessListSource {
+    /// Generated by the node with `eth_createAccessList`
+    Auto,
+    /// Read from a JSON file
+    File(PathBuf),
+}
+
+impl FromStr for AccessListSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim() {
+            "" => Err(anyhow!("Use 'auto' or the path of an access list JSON file")),
+            s if s.eq_ignore_ascii_case("auto") => Ok(AccessListSource::Auto),
+            path => Ok(AccessListSource::File(PathBuf::from(path))),
+        }
+    }
+}
+
+/// Reads an access list file: either the list itself or the object `eth_createAccessList`
+/// returns, whose `accessList` field holds it
+pub fn load(path: &Path) -> Result<AccessList> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read access list {}: {}", path.display(), e))?;
+    parse(&content).map_err(|e| anyhow!("Invalid access list in {}: {}", path.display(), e))
+}
+
+fn parse(content: &str) -> Result<AccessList> {
+    let value: Value = serde_json::from_str(content)?;
+    let list = match value {
+        Value::Object(mut object) => object
+            .remove("accessList")
+            .ok_or_else(|| anyhow!("expected a list or an object with an accessList field"))?,
+        list => list,
+    };
+    Ok(serde_json::from_value(list)?)
+}
+
+/// One-line description of an access list and its effect on the gas estimate
+pub fn summary(list: &AccessList, gas_without: u64, gas_with: u64) -> String {
+    let keys: usize = list.0.iter().map(|item| item.storage_keys.len()).sum();
+    let effect = if gas_with <= gas_without {
+        format!("saves {}", gas_without - gas_with)
+    } else {
+        format!("costs {} more", gas_with - gas_without)
+    };
+    format!(
+        "Access list: {} address(es), {} storage key(s); estimated gas {} → {} ({})",
+        list.0.len(),
+        keys,
+        gas_without,
+        gas_with,
+        effect
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIST: &str = r#"[{
+        "address": "0x742d35cc6634c0532925a3b844bc454e4438f44e",
+        "storageKeys": [
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "0x0000000000000000000000000000000000000000000000000000000000000002"
+        ]
+    }]"#;
+
+    #[test]
+    fn test_source_from_str() {
+        assert_eq!("auto".parse::<AccessListSource>().unwrap(), AccessListSource::Auto);
+        assert_eq!("AUTO".parse::<AccessListSource>().unwrap(), AccessListSource::Auto);
+        assert_eq!(
+            "list.json".parse::<AccessListSource>().unwrap(),
+            AccessListSource::File(PathBuf::from("list.json"))
+        );
+        assert!("".parse::<AccessListSource>().is_err());
+    }
+
+    #[test]
+    fn test_parse_list_and_rpc_result() {
+        let list = parse(LIST).unwrap();
+        assert_eq!(list.0.len(), 1);
+        assert_eq!(list.0[0].storage_keys.len(), 2);
+
+        let rpc_result = format!(r#"{{"accessList": {}, "gasUsed": "0x5208"}}"#, LIST);
+        assert_eq!(parse(&rpc_result).unwrap(), list);
+        assert!(parse(r#"{"gasUsed": "0x5208"}"#).is_err());
+    }
+
+    #[test]
+    fn test_summary_reports_savings_or_cost() {
+        let list = parse(LIST).unwrap();
+        assert_eq!(
+            summary(&list, 52_000, 50_100),
+            "Access list: 1 address(es), 2 storage key(s); estimated gas 52000 → 50100 (saves 1900)"
+        );
+        assert!(summary(&list, 21_000, 23_500).ends_with("(costs 2500 more)"));
+    }
+}
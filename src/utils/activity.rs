@@ -0,0 +1,107 @@
+//! First and last activity dates of an address, cached between lookups
+
+use crate::utils::{constants, file_lock};
+use alloy::primitives::Address;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+/// Cached dates are reused for this long; the first-seen date never changes,
+/// the last-seen one can
+pub const CACHE_TTL_SECS: i64 = 60 * 60;
+
+/// Block timestamps (ISO 8601) of an address's oldest and newest transfers
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivityDates {
+    pub first_seen: Option<String>,
+    pub last_seen: Option<String>,
+    /// When the dates were fetched (RFC 3339)
+    pub fetched_at: String,
+}
+
+impl ActivityDates {
+    pub fn new(first_seen: Option<String>, last_seen: Option<String>) -> Self {
+        Self {
+            first_seen,
+            last_seen,
+            fetched_at: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// `activity_cache.json`, keyed by "network:0xaddress"
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+struct ActivityCache(HashMap<String, ActivityDates>);
+
+fn cache_key(network: &str, address: &Address) -> String {
+    format!("{}:0x{:x}", network.to_lowercase(), address)
+}
+
+/// Dates fetched for `address` within the last hour, if any
+pub fn cached(network: &str, address: &Address) -> Option<ActivityDates> {
+    let cache: ActivityCache = fs::read_to_string(constants::activity_cache_path())
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())?;
+    cache
+        .0
+        .get(&cache_key(network, address))
+        .filter(|dates| is_fresh(&dates.fetched_at, Utc::now()))
+        .cloned()
+}
+
+pub fn store(network: &str, address: &Address, dates: &ActivityDates) -> Result<()> {
+    let key = cache_key(network, address);
+    file_lock::update_json(&constants::activity_cache_path(), |cache: &mut ActivityCache| {
+        cache.0.insert(key, dates.clone());
+        Ok(())
+    })
+}
+
+fn is_fresh(fetched_at: &str, now: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(fetched_at)
+        .map(|fetched| (now - fetched.with_timezone(&Utc)).num_seconds() < CACHE_TTL_SECS)
+        .unwrap_or(false)
+}
+
+/// Rough age of an address since its first activity, e.g. "2 years, 3 months"
+pub fn describe_age(first_seen: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let days = (now - first_seen).num_days().max(0);
+    let plural = |n: i64, unit: &str| format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" });
+    match (days / 365, days % 365 / 30) {
+        (0, 0) if days == 0 => "less than a day".to_string(),
+        (0, 0) => plural(days, "day"),
+        (0, months) => plural(months, "month"),
+        (years, 0) => plural(years, "year"),
+        (years, months) => format!("{}, {}", plural(years, "year"), plural(months, "month")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(timestamp).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        let now = at("2024-06-01T12:00:00Z");
+        assert!(is_fresh("2024-06-01T11:30:00Z", now));
+        assert!(!is_fresh("2024-06-01T11:00:00Z", now));
+        assert!(!is_fresh("not a date", now));
+    }
+
+    #[test]
+    fn test_describe_age() {
+        let now = at("2024-06-01T12:00:00Z");
+        assert_eq!(describe_age(at("2024-06-01T08:00:00Z"), now), "less than a day");
+        assert_eq!(describe_age(at("2024-05-31T12:00:00Z"), now), "1 day");
+        assert_eq!(describe_age(at("2024-03-01T12:00:00Z"), now), "3 months");
+        assert_eq!(describe_age(at("2023-06-01T12:00:00Z"), now), "1 year");
+        assert_eq!(describe_age(at("2021-04-01T12:00:00Z"), now), "3 years, 2 months");
+    }
+}
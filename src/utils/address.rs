@@ -0,0 +1,224 @@
+use crate::config::Config;
+use crate::types::network::Network;
+use crate::utils::units::format_amount;
+use alloy::primitives::{Address, U256};
+use alloy::providers::{Provider, ProviderBuilder, RootProvider};
+use alloy::transports::http::{Client, Http};
+use anyhow::{Result, anyhow};
+use std::str::FromStr;
+
+/// EIP-3770 short names accepted for each network
+fn chain_short_names(network: Network) -> &'static [&'static str] {
+    match network {
+        Network::Mainnet | Network::AlchemyMainnet | Network::RootStockMainnet => &["rsk"],
+        Network::Testnet | Network::AlchemyTestnet | Network::RootStockTestnet => {
+            &["rsktest", "trsk"]
+        }
+        Network::Regtest => &[],
+    }
+}
+
+/// Strips an optional EIP-3770 chain prefix (`rsk:0x…`, `rsktest:0x…`) from an address.
+///
+/// A prefix is only accepted when it names the active network, so an address
+/// copied from another chain is rejected instead of silently used here.
+pub fn strip_chain_prefix(input: &str, network: Network) -> Result<String> {
+    let input = input.trim();
+    let Some((prefix, address)) = input.split_once(':') else {
+        return Ok(input.to_string());
+    };
+
+    let prefix = prefix.to_lowercase();
+    if chain_short_names(network).contains(&prefix.as_str()) {
+        Ok(address.trim().to_string())
+    } else {
+        Err(anyhow!(
+            "Address prefix '{}:' does not match the selected network ({})",
+            prefix,
+            network
+        ))
+    }
+}
+
+/// Parses an address that may carry an EIP-3770 chain prefix
+pub fn parse_address(input: &str, network: Network) -> Result<Address> {
+    validate_rsk_address(input, network)
+}
+
+/// Parses a user-entered address (optionally `rsk:`/`rsktest:` prefixed) and, when it is
+/// written in mixed case, checks its checksum: the chain-id aware RSKIP60 checksum of
+/// `network` or a plain EIP-55 one. All-lowercase and all-uppercase input carries no
+/// checksum and is accepted as is.
+pub fn validate_rsk_address(input: &str, network: Network) -> Result<Address> {
+    let input = strip_chain_prefix(input, network)?;
+    let hex = input
+        .strip_prefix("0x")
+        .filter(|hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .ok_or_else(|| anyhow!("Invalid address format: {}", input))?;
+    let address =
+        Address::from_str(&input).map_err(|_| anyhow!("Invalid address format: {}", input))?;
+
+    let mixed_case = hex.chars().any(|c| c.is_ascii_lowercase())
+        && hex.chars().any(|c| c.is_ascii_uppercase());
+    if mixed_case
+        && input != address.to_checksum(Some(network.chain_id()))
+        && input != address.to_checksum(None)
+    {
+        return Err(anyhow!(
+            "Address {} fails its checksum and is probably mistyped (on {} it reads {})",
+            input,
+            network,
+            address.to_checksum(Some(network.chain_id()))
+        ));
+    }
+    Ok(address)
+}
+
+/// Validates a user-entered address, returning a message suitable for prompt validators
+pub fn validate_address_input(input: &str, network: Network) -> Result<(), String> {
+    match validate_rsk_address(input, network) {
+        Ok(_) => Ok(()),
+        Err(e) if e.to_string().contains("checksum") => Err(e.to_string()),
+        Err(_) => Err("Please enter a valid address (0x... or rsk:0x...)".to_string()),
+    }
+}
+
+/// Whether `address` holds contract code (`eth_getCode`), as opposed to being a plain account
+pub async fn is_contract(provider: &RootProvider<Http<Client>>, address: Address) -> Result<bool> {
+    let code = provider
+        .get_code_at(address)
+        .await
+        .map_err(|e| anyhow!("Failed to get code for {}: {}", address, e))?;
+    Ok(!code.is_empty())
+}
+
+/// Transaction count and RBTC balance of an address on one network
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Activity {
+    pub nonce: u64,
+    pub balance: U256,
+}
+
+impl Activity {
+    pub fn is_empty(&self) -> bool {
+        self.nonce == 0 && self.balance.is_zero()
+    }
+}
+
+/// An address that was never used on the active network but was on the other one
+/// was most likely meant for the other network
+pub fn suggests_other_network(active: &Activity, other: &Activity) -> bool {
+    active.is_empty() && !other.is_empty()
+}
+
+async fn activity(rpc_url: &str, address: Address) -> Result<Activity> {
+    let provider = ProviderBuilder::new().on_http(rpc_url.parse()?);
+    Ok(Activity {
+        nonce: provider.get_transaction_count(address).await?,
+        balance: provider.get_balance(address).await?,
+    })
+}
+
+/// A warning when `address` looks like it belongs to the other network (mainnet vs
+/// testnet). Addresses are the same on both, so this only compares on-chain activity;
+/// lookup failures give no warning.
+pub async fn other_network_hint(config: &Config, address: Address) -> Option<String> {
+    let active = config.default_network;
+    let other = active.counterpart()?;
+    let active_url = config.rpc_url_for(active).0;
+    let other_url = config.rpc_url_for(other).0;
+    let (active_activity, other_activity) = tokio::join!(
+        activity(&active_url, address),
+        activity(&other_url, address)
+    );
+    let (active_activity, other_activity) = (active_activity.ok()?, other_activity.ok()?);
+    suggests_other_network(&active_activity, &other_activity).then(|| {
+        format!(
+            "0x{:x} has never been used on {} but has {} transaction(s) and {} RBTC on {}. \
+             Check that you are on the right network.",
+            address,
+            active,
+            other_activity.nonce,
+            format_amount(other_activity.balance, 18),
+            other
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDR: &str = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+
+    #[test]
+    fn test_suggests_other_network() {
+        let unused = Activity {
+            nonce: 0,
+            balance: U256::ZERO,
+        };
+        let used = Activity {
+            nonce: 3,
+            balance: U256::ZERO,
+        };
+        let funded = Activity {
+            nonce: 0,
+            balance: U256::from(1u64),
+        };
+        assert!(suggests_other_network(&unused, &used));
+        assert!(suggests_other_network(&unused, &funded));
+        assert!(!suggests_other_network(&used, &funded));
+        assert!(!suggests_other_network(&unused, &unused));
+    }
+
+    #[test]
+    fn test_strip_matching_prefix() {
+        let input = format!("rsk:{}", ADDR);
+        assert_eq!(strip_chain_prefix(&input, Network::Mainnet).unwrap(), ADDR);
+        let input = format!("rsktest:{}", ADDR);
+        assert_eq!(strip_chain_prefix(&input, Network::Testnet).unwrap(), ADDR);
+        assert_eq!(strip_chain_prefix(ADDR, Network::Testnet).unwrap(), ADDR);
+    }
+
+    #[test]
+    fn test_validate_rsk_address_checksums() {
+        let address = Address::from_str(ADDR).unwrap();
+        let mainnet = address.to_checksum(Some(30));
+        assert_eq!(validate_rsk_address(&mainnet, Network::Mainnet).unwrap(), address);
+        let prefixed = format!("rsk:{}", mainnet);
+        assert_eq!(validate_rsk_address(&prefixed, Network::Mainnet).unwrap(), address);
+        let eip55 = address.to_checksum(None);
+        assert!(validate_rsk_address(&eip55, Network::Testnet).is_ok());
+        assert!(validate_rsk_address(&ADDR.to_lowercase(), Network::Mainnet).is_ok());
+
+        // One letter in the wrong case
+        let index = mainnet[2..].find(|c: char| c.is_ascii_alphabetic()).unwrap() + 2;
+        let mut typo = mainnet.clone();
+        let flipped = if typo.as_bytes()[index].is_ascii_uppercase() {
+            typo[index..=index].to_lowercase()
+        } else {
+            typo[index..=index].to_uppercase()
+        };
+        typo.replace_range(index..=index, &flipped);
+        let error = validate_rsk_address(&typo, Network::Mainnet).unwrap_err();
+        assert!(error.to_string().contains("checksum"));
+        assert!(validate_address_input(&typo, Network::Mainnet).unwrap_err().contains("checksum"));
+    }
+
+    #[test]
+    fn test_validate_rsk_address_rejects_malformed_input() {
+        assert!(validate_rsk_address("0x1234", Network::Mainnet).is_err());
+        assert!(validate_rsk_address(&ADDR[2..], Network::Mainnet).is_err());
+        assert!(validate_rsk_address(&format!("{}zz", &ADDR[..40]), Network::Mainnet).is_err());
+        assert_eq!(
+            validate_address_input("garbage", Network::Mainnet).unwrap_err(),
+            "Please enter a valid address (0x... or rsk:0x...)"
+        );
+    }
+
+    #[test]
+    fn test_reject_other_chain_prefix() {
+        assert!(strip_chain_prefix(&format!("rsk:{}", ADDR), Network::Testnet).is_err());
+        assert!(strip_chain_prefix(&format!("eth:{}", ADDR), Network::Mainnet).is_err());
+    }
+}
@@ -1,7 +1,29 @@
 // src/utils/alchemy.rs
+use crate::config::ConfigManager;
+use crate::utils::rate_limit::RequestScheduler;
 use anyhow::{Result, anyhow};
 use reqwest::Client;
 use serde_json::Value;
+use std::sync::OnceLock;
+
+/// Requests per second used when the config cannot be read; fits Alchemy's free tier
+pub const DEFAULT_ALCHEMY_RPS: u32 = 5;
+
+/// Most transfers `alchemy_getAssetTransfers` returns per page
+pub const MAX_TRANSFERS_PER_PAGE: u32 = 1000;
+
+/// One scheduler for the whole process so every client shares the same budget
+static SCHEDULER: OnceLock<RequestScheduler> = OnceLock::new();
+
+fn scheduler() -> &'static RequestScheduler {
+    SCHEDULER.get_or_init(|| {
+        let rps = ConfigManager::new()
+            .and_then(|manager| manager.load())
+            .map(|config| config.alchemy_requests_per_second)
+            .unwrap_or(DEFAULT_ALCHEMY_RPS);
+        RequestScheduler::new(rps)
+    })
+}
 
 pub struct AlchemyClient {
     client: Client,
@@ -39,25 +61,127 @@ impl AlchemyClient {
         format!("https://rootstock-{}.g.alchemy.com/v2", network)
     }
 
+    /// Waits for a slot in the shared rate limit. Called before every request to
+    /// Alchemy, including ones sent through a provider on `get_base_url`.
+    pub async fn throttle(&self) {
+        scheduler().acquire().await;
+    }
+
     pub async fn get_asset_transfers(
         &self,
         address: &str,
         limit: u32,
         from_block: Option<&str>,
         to_block: Option<&str>,
+    ) -> Result<Value> {
+        self.asset_transfers(&[("fromAddress", address)], limit, from_block, to_block, "asc")
+            .await
+    }
+
+    /// Transfers sent by `from` to `to`
+    pub async fn get_transfers_between(&self, from: &str, to: &str, limit: u32) -> Result<Value> {
+        let filters = [("fromAddress", from), ("toAddress", to)];
+        self.asset_transfers(&filters, limit, None, None, "asc").await
+    }
+
+    /// Same as `get_asset_transfers` but for transfers received by `address`
+    pub async fn get_incoming_asset_transfers(
+        &self,
+        address: &str,
+        limit: u32,
+        from_block: Option<&str>,
+        to_block: Option<&str>,
+    ) -> Result<Value> {
+        self.asset_transfers(&[("toAddress", address)], limit, from_block, to_block, "asc")
+            .await
+    }
+
+    /// Block timestamp (ISO 8601) of the oldest, or with `newest` the latest, transfer
+    /// sent or received by `address`; `None` for an address without transfers
+    pub async fn edge_transfer_time(&self, address: &str, newest: bool) -> Result<Option<String>> {
+        let order = if newest { "desc" } else { "asc" };
+        let mut times = Vec::new();
+        for key in ["fromAddress", "toAddress"] {
+            let response = self
+                .asset_transfers(&[(key, address)], 1, None, None, order)
+                .await?;
+            if let Some(time) =
+                response["result"]["transfers"][0]["metadata"]["blockTimestamp"].as_str()
+            {
+                times.push(time.to_string());
+            }
+        }
+        // Same-format ISO 8601 timestamps sort chronologically
+        Ok(if newest {
+            times.into_iter().max()
+        } else {
+            times.into_iter().min()
+        })
+    }
+
+    /// Up to `limit` transfers matching the filters, following `pageKey` across as
+    /// many pages as needed. The result has the shape of a single response, with
+    /// every page's transfers in `result.transfers`.
+    async fn asset_transfers(
+        &self,
+        address_filters: &[(&str, &str)],
+        limit: u32,
+        from_block: Option<&str>,
+        to_block: Option<&str>,
+        order: &str,
+    ) -> Result<Value> {
+        let mut transfers = Vec::new();
+        let mut page_key: Option<String> = None;
+        // collect_page stops paging once `limit` transfers are in
+        while transfers.len() < limit as usize {
+            let remaining = limit.saturating_sub(transfers.len() as u32);
+            let response = self
+                .asset_transfers_page(
+                    address_filters,
+                    remaining.min(MAX_TRANSFERS_PER_PAGE),
+                    from_block,
+                    to_block,
+                    order,
+                    page_key.as_deref(),
+                )
+                .await?;
+            page_key = collect_page(&mut transfers, &response, limit);
+            if page_key.is_none() {
+                break;
+            }
+        }
+        Ok(serde_json::json!({ "result": { "transfers": transfers } }))
+    }
+
+    async fn asset_transfers_page(
+        &self,
+        address_filters: &[(&str, &str)],
+        max_count: u32,
+        from_block: Option<&str>,
+        to_block: Option<&str>,
+        order: &str,
+        page_key: Option<&str>,
     ) -> Result<Value> {
         let url = self.get_base_url();
 
-        let params = serde_json::json!([{
+        let mut filter = serde_json::json!({
             "fromBlock": from_block.unwrap_or("0x0"),
             "toBlock": to_block.unwrap_or("latest"),
-            "fromAddress": address,
             "category": ["external", "erc20"],
             "withMetadata": true,
             "excludeZeroValue": false,
-            "maxCount": format!("0x{:x}", limit),
-        }]);
+            "maxCount": format!("0x{:x}", max_count),
+            "order": order,
+        });
+        for (key, address) in address_filters {
+            filter[*key] = Value::from(*address);
+        }
+        if let Some(page_key) = page_key {
+            filter["pageKey"] = Value::from(page_key);
+        }
+        let params = serde_json::json!([filter]);
 
+        self.throttle().await;
         let response = self
             .client
             .post(&url)
@@ -86,6 +210,7 @@ impl AlchemyClient {
         let url = self.get_base_url();
         let block_number_hex = format!("0x{:x}", block_number);
 
+        self.throttle().await;
         let response = self
             .client
             .post(&url)
@@ -111,3 +236,50 @@ impl AlchemyClient {
             .and_then(|r| if r.is_null() { None } else { Some(r.clone()) }))
     }
 }
+
+/// Appends the transfers of one `alchemy_getAssetTransfers` response, up to `limit`
+/// in total, and returns the key of the next page if more are still wanted
+fn collect_page(transfers: &mut Vec<Value>, response: &Value, limit: u32) -> Option<String> {
+    let page = response["result"]["transfers"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let wanted = (limit as usize).saturating_sub(transfers.len());
+    let empty = page.is_empty();
+    transfers.extend(page.into_iter().take(wanted));
+    if empty || transfers.len() >= limit as usize {
+        return None;
+    }
+    response["result"]["pageKey"].as_str().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn page(count: usize, page_key: Option<&str>) -> Value {
+        let transfers: Vec<Value> = (0..count).map(|i| json!({ "hash": i })).collect();
+        json!({ "result": { "transfers": transfers, "pageKey": page_key } })
+    }
+
+    #[test]
+    fn test_collect_page_follows_page_keys_until_limit() {
+        let mut transfers = Vec::new();
+        assert_eq!(
+            collect_page(&mut transfers, &page(1000, Some("next")), 2500),
+            Some("next".to_string())
+        );
+        assert_eq!(transfers.len(), 1000);
+        assert_eq!(collect_page(&mut transfers, &page(1000, Some("more")), 1500), None);
+        assert_eq!(transfers.len(), 1500);
+    }
+
+    #[test]
+    fn test_collect_page_stops_on_last_or_empty_page() {
+        let mut transfers = Vec::new();
+        assert_eq!(collect_page(&mut transfers, &page(3, None), 10), None);
+        assert_eq!(transfers.len(), 3);
+        assert_eq!(collect_page(&mut transfers, &page(0, Some("loop")), 10), None);
+    }
+}
@@ -0,0 +1,515 @@
+use crate::utils::constants::{ALLOWED_BRIDGE_METHODS, BRIDGE_ADDRESS, METHOD_TYPES};
+use crate::utils::eth::EthClient;
+use alloy::primitives::{Address, B256, Bytes, I256, U256};
+use alloy::providers::Provider;
+use alloy::rpc::types::Filter;
+use alloy::sol;
+use alloy::sol_types::SolEvent;
+use anyhow::{Result, anyhow};
+use std::str::FromStr;
+
+// Read-only subset of the RSK Bridge precompile used by the CLI
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    contract IBridge {
+        function getActivePowpegRedeemScript() external view returns (bytes);
+        function getFederationAddress() external view returns (string);
+        function getFederationSize() external view returns (int256);
+        function getFederationThreshold() external view returns (int256);
+        function getFederationCreationTime() external view returns (int256);
+        function getFederationCreationBlockNumber() external view returns (int256);
+        function getRetiringFederationAddress() external view returns (string);
+        function getRetiringFederationSize() external view returns (int256);
+        function getRetiringFederationThreshold() external view returns (int256);
+        function getRetiringFederationCreationTime() external view returns (int256);
+        function getRetiringFederationCreationBlockNumber() external view returns (int256);
+        function getPendingFederationSize() external view returns (int256);
+        function getStateForBtcReleaseClient() external view returns (bytes);
+        function getQueuedPegoutsCount() external view returns (int256);
+        function getEstimatedFeesForNextPegOutEvent() external view returns (int256);
+        function getNextPegoutCreationBlockNumber() external view returns (int256);
+
+        event release_request_received(address indexed sender, string btcDestinationAddress, uint256 amount);
+        event release_request_rejected(address indexed sender, uint256 amount, int256 reason);
+        event batch_pegout_created(bytes32 indexed btcTxHash, bytes releaseRskTxHashes);
+        event release_btc(bytes32 indexed releaseRskTxHash, bytes btcRawTransaction);
+    }
+}
+
+/// Details of a single federation as reported by the Bridge
+#[derive(Debug, Clone)]
+pub struct FederationInfo {
+    pub address: String,
+    pub size: i64,
+    pub threshold: i64,
+    pub creation_time: i64,
+    pub creation_block: i64,
+}
+
+/// Active and retiring federations plus any pending (voted but not yet committed) one
+#[derive(Debug, Clone)]
+pub struct FederationStatus {
+    pub active: FederationInfo,
+    pub retiring: Option<FederationInfo>,
+    pub pending_size: Option<i64>,
+    pub redeem_script: Bytes,
+}
+
+impl FederationStatus {
+    /// A retiring federation only exists while funds are being migrated to the new powpeg
+    pub fn migration_in_progress(&self) -> bool {
+        self.retiring.is_some()
+    }
+}
+
+/// Where a peg-out request is in the release pipeline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PegoutStage {
+    /// The Bridge refused the request and refunded the RBTC
+    Rejected { amount_sats: u64, reason: i64 },
+    /// Accepted and waiting for the next batch to be created
+    Queued {
+        amount_sats: u64,
+        btc_destination: String,
+    },
+    /// Batched into a BTC transaction that the federation has not signed yet
+    AwaitingSignatures { btc_tx_hash: B256, batch_rsk_tx: B256 },
+    /// Signed by the federation and broadcast to the Bitcoin network
+    Released { btc_tx_hash: B256, batch_rsk_tx: B256 },
+}
+
+/// Bridge-wide peg-out queue figures
+#[derive(Debug, Clone)]
+pub struct PegoutQueue {
+    pub queued_count: i64,
+    pub estimated_fees_sats: i64,
+    pub next_creation_block: i64,
+    pub release_client_state: Bytes,
+}
+
+/// Multisig parameters decoded from a powpeg redeem script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedeemScriptInfo {
+    pub threshold: u8,
+    pub total: u8,
+    pub public_keys: usize,
+}
+
+pub struct BridgeClient<'a> {
+    eth_client: &'a EthClient,
+    address: Address,
+}
+
+impl<'a> BridgeClient<'a> {
+    pub fn new(eth_client: &'a EthClient) -> Result<Self> {
+        let address = Address::from_str(BRIDGE_ADDRESS)
+            .map_err(|e| anyhow!("Invalid bridge address: {}", e))?;
+        Ok(Self {
+            eth_client,
+            address,
+        })
+    }
+
+    pub async fn get_federation_status(&self) -> Result<FederationStatus> {
+        ensure_allowed("getActivePowpegRedeemScript")?;
+        ensure_allowed("getFederationCreationBlockNumber")?;
+        ensure_allowed("getRetiringFederationSize")?;
+
+        let contract = IBridge::new(self.address, self.eth_client.provider());
+
+        let redeem_script = contract
+            .getActivePowpegRedeemScript()
+            .call()
+            .await
+            .map_err(|e| anyhow!("Failed to get active powpeg redeem script: {}", e))?
+            ._0;
+
+        let active = FederationInfo {
+            address: contract
+                .getFederationAddress()
+                .call()
+                .await
+                .map_err(|e| anyhow!("Failed to get federation address: {}", e))?
+                ._0,
+            size: to_i64(contract.getFederationSize().call().await?._0),
+            threshold: to_i64(contract.getFederationThreshold().call().await?._0),
+            creation_time: to_i64(contract.getFederationCreationTime().call().await?._0),
+            creation_block: to_i64(
+                contract
+                    .getFederationCreationBlockNumber()
+                    .call()
+                    .await
+                    .map_err(|e| anyhow!("Failed to get federation creation block: {}", e))?
+                    ._0,
+            ),
+        };
+
+        // The Bridge returns -1 for every retiring getter when there is no retiring federation
+        let retiring_size = to_i64(
+            contract
+                .getRetiringFederationSize()
+                .call()
+                .await
+                .map_err(|e| anyhow!("Failed to get retiring federation size: {}", e))?
+                ._0,
+        );
+        let retiring = if retiring_size < 0 {
+            None
+        } else {
+            Some(FederationInfo {
+                address: contract.getRetiringFederationAddress().call().await?._0,
+                size: retiring_size,
+                threshold: to_i64(contract.getRetiringFederationThreshold().call().await?._0),
+                creation_time: to_i64(
+                    contract
+                        .getRetiringFederationCreationTime()
+                        .call()
+                        .await?
+                        ._0,
+                ),
+                creation_block: to_i64(
+                    contract
+                        .getRetiringFederationCreationBlockNumber()
+                        .call()
+                        .await?
+                        ._0,
+                ),
+            })
+        };
+
+        let pending_size = to_i64(contract.getPendingFederationSize().call().await?._0);
+
+        Ok(FederationStatus {
+            active,
+            retiring,
+            pending_size: if pending_size < 0 {
+                None
+            } else {
+                Some(pending_size)
+            },
+            redeem_script,
+        })
+    }
+
+    pub async fn get_pegout_queue(&self) -> Result<PegoutQueue> {
+        ensure_allowed("getStateForBtcReleaseClient")?;
+        ensure_allowed("getQueuedPegoutsCount")?;
+        ensure_allowed("getEstimatedFeesForNextPegOutEvent")?;
+        ensure_allowed("getNextPegoutCreationBlockNumber")?;
+
+        let contract = IBridge::new(self.address, self.eth_client.provider());
+
+        Ok(PegoutQueue {
+            queued_count: to_i64(
+                contract
+                    .getQueuedPegoutsCount()
+                    .call()
+                    .await
+                    .map_err(|e| anyhow!("Failed to get queued peg-outs count: {}", e))?
+                    ._0,
+            ),
+            estimated_fees_sats: to_i64(
+                contract
+                    .getEstimatedFeesForNextPegOutEvent()
+                    .call()
+                    .await
+                    .map_err(|e| anyhow!("Failed to get estimated peg-out fees: {}", e))?
+                    ._0,
+            ),
+            next_creation_block: to_i64(
+                contract
+                    .getNextPegoutCreationBlockNumber()
+                    .call()
+                    .await
+                    .map_err(|e| anyhow!("Failed to get next peg-out creation block: {}", e))?
+                    ._0,
+            ),
+            release_client_state: contract
+                .getStateForBtcReleaseClient()
+                .call()
+                .await
+                .map_err(|e| anyhow!("Failed to get release client state: {}", e))?
+                ._0,
+        })
+    }
+
+    /// Follows a peg-out request through the Bridge events: request, batch, release
+    pub async fn get_pegout_stage(&self, tx_hash: B256) -> Result<PegoutStage> {
+        let receipt = self.eth_client.get_transaction_receipt(tx_hash).await?;
+        if receipt.to != Some(self.address) {
+            return Err(anyhow!("Transaction {} was not sent to the Bridge", tx_hash));
+        }
+        if !receipt.status() {
+            return Err(anyhow!("Transaction {} failed on-chain", tx_hash));
+        }
+        let request_block = receipt
+            .block_number
+            .ok_or_else(|| anyhow!("Transaction {} is not mined yet", tx_hash))?;
+
+        let mut accepted = None;
+        for log in receipt.inner.logs() {
+            if let Ok(rejected) = log.log_decode::<IBridge::release_request_rejected>() {
+                let event = rejected.inner.data;
+                return Ok(PegoutStage::Rejected {
+                    amount_sats: u256_to_u64(event.amount),
+                    reason: to_i64(event.reason),
+                });
+            }
+            if let Ok(received) = log.log_decode::<IBridge::release_request_received>() {
+                accepted = Some(received.inner.data);
+            }
+        }
+        let request = accepted
+            .ok_or_else(|| anyhow!("Transaction {} is not a peg-out request", tx_hash))?;
+
+        let provider = self.eth_client.provider();
+        let batches = provider
+            .get_logs(
+                &Filter::new()
+                    .address(self.address)
+                    .event_signature(IBridge::batch_pegout_created::SIGNATURE_HASH)
+                    .from_block(request_block),
+            )
+            .await
+            .map_err(|e| anyhow!("Failed to get peg-out batches: {}", e))?;
+
+        let batch = batches.iter().find_map(|log| {
+            let event = log.log_decode::<IBridge::batch_pegout_created>().ok()?;
+            split_rsk_tx_hashes(&event.inner.data.releaseRskTxHashes)
+                .contains(&tx_hash)
+                .then_some((event.inner.data.btcTxHash, log.transaction_hash))
+        });
+        let Some((btc_tx_hash, Some(batch_rsk_tx))) = batch else {
+            return Ok(PegoutStage::Queued {
+                amount_sats: u256_to_u64(request.amount),
+                btc_destination: request.btcDestinationAddress,
+            });
+        };
+
+        let releases = provider
+            .get_logs(
+                &Filter::new()
+                    .address(self.address)
+                    .event_signature(IBridge::release_btc::SIGNATURE_HASH)
+                    .topic1(batch_rsk_tx)
+                    .from_block(request_block),
+            )
+            .await
+            .map_err(|e| anyhow!("Failed to get peg-out releases: {}", e))?;
+
+        Ok(if releases.is_empty() {
+            PegoutStage::AwaitingSignatures {
+                btc_tx_hash,
+                batch_rsk_tx,
+            }
+        } else {
+            PegoutStage::Released {
+                btc_tx_hash,
+                batch_rsk_tx,
+            }
+        })
+    }
+}
+
+/// Rejects bridge calls that are not whitelisted for the configured method type
+pub fn ensure_allowed(method: &str) -> Result<()> {
+    let allowed = ALLOWED_BRIDGE_METHODS
+        .iter()
+        .find(|(kind, _)| *kind == METHOD_TYPES)
+        .is_some_and(|(_, methods)| methods.contains(&method));
+    if allowed {
+        Ok(())
+    } else {
+        Err(anyhow!("Bridge method '{}' is not allowed", method))
+    }
+}
+
+fn to_i64(value: I256) -> i64 {
+    i64::try_from(value).unwrap_or(-1)
+}
+
+fn u256_to_u64(value: U256) -> u64 {
+    u64::try_from(value).unwrap_or(u64::MAX)
+}
+
+/// `batch_pegout_created` lists the included peg-out requests as concatenated 32-byte hashes
+pub fn split_rsk_tx_hashes(data: &[u8]) -> Vec<B256> {
+    data.chunks_exact(32).map(B256::from_slice).collect()
+}
+
+/// Whether a raw Bridge state blob mentions the given transaction hash
+pub fn contains_hash(state: &[u8], hash: B256) -> bool {
+    state.windows(32).any(|window| window == hash.as_slice())
+}
+
+/// Formats a satoshi amount as `12,345 sats (0.00012345 BTC)`
+pub fn format_satoshis(sats: i64) -> String {
+    let digits = sats.unsigned_abs().to_string();
+    let mut grouped = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    let sign = if sats < 0 { "-" } else { "" };
+    let abs = sats.unsigned_abs();
+    format!(
+        "{}{} sats ({}{}.{:08} BTC)",
+        sign,
+        grouped,
+        sign,
+        abs / 100_000_000,
+        abs % 100_000_000
+    )
+}
+
+/// Reasons the Bridge reports in `release_request_rejected`
+pub fn pegout_rejection_reason(code: i64) -> &'static str {
+    match code {
+        1 => "amount is below the minimum peg-out value",
+        2 => "peg-outs must be sent from an externally owned account, not a contract",
+        3 => "estimated BTC fees are higher than the amount",
+        _ => "unknown reason",
+    }
+}
+
+/// Decodes the first `OP_M <pubkeys...> OP_N` multisig section of a redeem script.
+/// Works for both plain multisig and ERP-style powpeg scripts.
+pub fn decode_redeem_script(script: &[u8]) -> Option<RedeemScriptInfo> {
+    let mut threshold = None;
+    let mut public_keys = 0usize;
+    let mut i = 0;
+
+    while i < script.len() {
+        let op = script[i];
+        let (push_len, header) = match op {
+            0x01..=0x4b => (op as usize, 1),
+            0x4c => (*script.get(i + 1)? as usize, 2),
+            0x4d => (
+                u16::from_le_bytes([*script.get(i + 1)?, *script.get(i + 2)?]) as usize,
+                3,
+            ),
+            // OP_1 .. OP_16
+            0x51..=0x60 => {
+                let n = op - 0x50;
+                match threshold {
+                    Some(m) if public_keys > 0 => {
+                        return Some(RedeemScriptInfo {
+                            threshold: m,
+                            total: n,
+                            public_keys,
+                        });
+                    }
+                    _ => {
+                        threshold = Some(n);
+                        public_keys = 0;
+                    }
+                }
+                i += 1;
+                continue;
+            }
+            _ => (0, 1),
+        };
+
+        if threshold.is_some() && push_len == 33 {
+            public_keys += 1;
+        }
+        i += header + push_len;
+    }
+
+    None
+}
+
+/// Formats script bytes as 0x-prefixed hex wrapped at `width` characters
+pub fn format_script_hex(script: &[u8], width: usize) -> Vec<String> {
+    let hex = hex::encode(script);
+    let mut lines: Vec<String> = hex
+        .as_bytes()
+        .chunks(width.max(2))
+        .map(|chunk| String::from_utf8_lossy(chunk).to_string())
+        .collect();
+    if let Some(first) = lines.first_mut() {
+        first.insert_str(0, "0x");
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn multisig_script(m: u8, n: u8) -> Vec<u8> {
+        let mut script = vec![0x50 + m];
+        for i in 0..n {
+            script.push(33);
+            script.extend(std::iter::repeat_n(i + 2, 33));
+        }
+        script.push(0x50 + n);
+        script.push(0xae); // OP_CHECKMULTISIG
+        script
+    }
+
+    #[test]
+    fn test_decode_plain_multisig() {
+        let info = decode_redeem_script(&multisig_script(5, 9)).unwrap();
+        assert_eq!(
+            info,
+            RedeemScriptInfo {
+                threshold: 5,
+                total: 9,
+                public_keys: 9
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_erp_script_uses_first_section() {
+        // OP_NOTIF <5-of-9> OP_ELSE <csv> OP_CSV OP_DROP <2-of-3> OP_ENDIF OP_CHECKMULTISIG
+        let mut script = vec![0x64];
+        let mut main = multisig_script(5, 9);
+        main.pop();
+        script.extend(main);
+        script.extend([0x67, 0x02, 0xcd, 0x50, 0xb2, 0x75]);
+        let mut emergency = multisig_script(2, 3);
+        emergency.pop();
+        script.extend(emergency);
+        script.extend([0x68, 0xae]);
+
+        let info = decode_redeem_script(&script).unwrap();
+        assert_eq!(info.threshold, 5);
+        assert_eq!(info.total, 9);
+    }
+
+    #[test]
+    fn test_format_satoshis() {
+        assert_eq!(format_satoshis(0), "0 sats (0.00000000 BTC)");
+        assert_eq!(format_satoshis(12_345), "12,345 sats (0.00012345 BTC)");
+        assert_eq!(
+            format_satoshis(150_000_000),
+            "150,000,000 sats (1.50000000 BTC)"
+        );
+    }
+
+    #[test]
+    fn test_split_and_find_rsk_tx_hashes() {
+        let a = B256::repeat_byte(0x11);
+        let b = B256::repeat_byte(0x22);
+        let mut data = a.to_vec();
+        data.extend_from_slice(b.as_slice());
+        assert_eq!(split_rsk_tx_hashes(&data), vec![a, b]);
+
+        let mut state = vec![0xf8, 0x42];
+        state.extend_from_slice(b.as_slice());
+        assert!(contains_hash(&state, b));
+        assert!(!contains_hash(&state, a));
+    }
+
+    #[test]
+    fn test_format_script_hex_wraps() {
+        let lines = format_script_hex(&[0xab; 40], 32);
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("0xabab"));
+    }
+}
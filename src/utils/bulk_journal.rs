@@ -0,0 +1,160 @@
+//! Progress of a bulk transfer, saved after every send so a batch cut short by a
+//! dropped connection can be resumed without paying anyone twice
+
+use crate::utils::{constants, file_lock};
+use alloy::primitives::{Address, B256, U256};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Where one transfer of the batch stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EntryStatus {
+    /// Not sent yet, or rejected before it reached the node
+    Pending,
+    /// Mined and successful
+    Confirmed,
+    /// Mined but reverted
+    Failed,
+    /// Broadcast without a receipt; sending it again could pay twice
+    Unconfirmed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub to: Address,
+    /// Amount in wei
+    pub value: U256,
+    pub status: EntryStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<B256>,
+}
+
+/// A bulk transfer in progress, stored in `bulk_journal.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkJournal {
+    pub network: String,
+    /// Wallet the batch is signed with
+    pub from: Address,
+    pub started_at: String,
+    pub entries: Vec<JournalEntry>,
+}
+
+impl BulkJournal {
+    pub fn new(
+        network: &str,
+        from: Address,
+        transfers: impl IntoIterator<Item = (Address, U256)>,
+    ) -> Self {
+        Self {
+            network: network.to_string(),
+            from,
+            started_at: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            entries: transfers
+                .into_iter()
+                .map(|(to, value)| JournalEntry {
+                    to,
+                    value,
+                    status: EntryStatus::Pending,
+                    tx_hash: None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn load() -> Option<Self> {
+        fs::read_to_string(constants::bulk_journal_path())
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+    }
+
+    pub fn save(&self) -> Result<()> {
+        file_lock::write_atomic(&constants::bulk_journal_path(), &serde_json::to_string_pretty(self)?)
+    }
+
+    /// Records the outcome of entry `index` and saves right away; failing to save only warns
+    pub fn record(&mut self, index: usize, status: EntryStatus, tx_hash: Option<B256>) {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.status = status;
+            entry.tx_hash = tx_hash.or(entry.tx_hash);
+        }
+        if let Err(e) = self.save() {
+            eprintln!("⚠️  Could not save the bulk transfer journal: {}", e);
+        }
+    }
+
+    /// Indices of the entries that still have to be sent: pending or reverted ones.
+    /// Confirmed and unconfirmed entries are left out.
+    pub fn unsent(&self) -> Vec<usize> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| matches!(entry.status, EntryStatus::Pending | EntryStatus::Failed))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    pub fn count(&self, status: EntryStatus) -> usize {
+        self.entries.iter().filter(|entry| entry.status == status).count()
+    }
+
+    /// Every transfer is confirmed, so there is nothing left to resume
+    pub fn is_finished(&self) -> bool {
+        self.count(EntryStatus::Confirmed) == self.entries.len()
+    }
+
+    /// Forgets the journal once the batch is done or abandoned
+    pub fn clear() {
+        let _ = fs::remove_file(constants::bulk_journal_path());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+
+    const ALICE: Address = address!("1111111111111111111111111111111111111111");
+    const BOB: Address = address!("2222222222222222222222222222222222222222");
+
+    fn journal() -> BulkJournal {
+        BulkJournal::new(
+            "testnet",
+            ALICE,
+            [(ALICE, U256::from(1u64)), (BOB, U256::from(2u64)), (BOB, U256::from(2u64))],
+        )
+    }
+
+    #[test]
+    fn test_unsent_skips_confirmed_and_unconfirmed_entries() {
+        let mut journal = journal();
+        assert_eq!(journal.unsent(), vec![0, 1, 2]);
+
+        journal.entries[0].status = EntryStatus::Confirmed;
+        journal.entries[1].status = EntryStatus::Unconfirmed;
+        journal.entries[2].status = EntryStatus::Failed;
+        assert_eq!(journal.unsent(), vec![2]);
+        assert_eq!(journal.count(EntryStatus::Unconfirmed), 1);
+        assert!(!journal.is_finished());
+
+        journal.entries[1].status = EntryStatus::Confirmed;
+        journal.entries[2].status = EntryStatus::Confirmed;
+        assert!(journal.unsent().is_empty());
+        assert!(journal.is_finished());
+    }
+
+    #[test]
+    fn test_journal_round_trips() {
+        let mut journal = journal();
+        journal.entries[1].status = EntryStatus::Confirmed;
+        journal.entries[1].tx_hash = Some(B256::repeat_byte(0xab));
+
+        let json = serde_json::to_string(&journal).unwrap();
+        assert!(json.contains("\"confirmed\""));
+        let read: BulkJournal = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.entries, journal.entries);
+        assert_eq!(read.from, ALICE);
+        assert_eq!(read.entries[0].tx_hash, None);
+    }
+}
@@ -0,0 +1,207 @@
+use crate::utils::eth::IERC20;
+use alloy::dyn_abi::{DynSolValue, JsonAbiExt};
+use alloy::json_abi::{Function, JsonAbi};
+use alloy::primitives::{Address, Bytes, U256, hex};
+use alloy::sol_types::SolCall;
+use anyhow::{Result, anyhow};
+
+/// Human-readable signature of the ERC20 call the wallet builds for token transfers
+const ERC20_TRANSFER: &str = "function transfer(address recipient, uint256 amount)";
+
+/// A single decoded argument of a contract call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedParam {
+    pub name: String,
+    pub kind: String,
+    pub value: String,
+}
+
+/// Calldata split into its function selector and decoded arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedCall {
+    pub signature: String,
+    pub selector: [u8; 4],
+    pub params: Vec<DecodedParam>,
+}
+
+/// Encodes the calldata of an ERC20 `transfer(recipient, amount)` call
+pub fn erc20_transfer_calldata(to: Address, amount: U256) -> Bytes {
+    IERC20::transferCall {
+        recipient: to,
+        amount,
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Decodes calldata produced by [`erc20_transfer_calldata`]
+pub fn decode_erc20_transfer(data: &[u8]) -> Result<DecodedCall> {
+    let function = Function::parse(ERC20_TRANSFER)
+        .map_err(|e| anyhow!("Invalid ERC20 signature: {}", e))?;
+    decode_calldata(&function, data)
+}
+
+/// Decodes calldata against a function from a contract ABI
+pub fn decode_calldata(function: &Function, data: &[u8]) -> Result<DecodedCall> {
+    if data.len() < 4 {
+        return Err(anyhow!("Calldata is shorter than a function selector"));
+    }
+    let (selector, args) = data.split_at(4);
+    if selector != function.selector().as_slice() {
+        return Err(anyhow!(
+            "Selector 0x{} does not match {}",
+            hex::encode(selector),
+            function.signature()
+        ));
+    }
+
+    let values = function
+        .abi_decode_input(args, true)
+        .map_err(|e| anyhow!("Failed to decode arguments: {}", e))?;
+
+    let params = function
+        .inputs
+        .iter()
+        .zip(values.iter())
+        .enumerate()
+        .map(|(i, (input, value))| DecodedParam {
+            name: if input.name.is_empty() {
+                format!("arg{}", i)
+            } else {
+                input.name.clone()
+            },
+            kind: input.selector_type().into_owned(),
+            value: format_value(value),
+        })
+        .collect();
+
+    Ok(DecodedCall {
+        signature: function.signature(),
+        selector: function.selector().0,
+        params,
+    })
+}
+
+/// Parses an ABI file's contents: a bare ABI array or a build artifact with an "abi" field
+pub fn parse_abi(content: &str) -> Result<JsonAbi> {
+    let value: serde_json::Value =
+        serde_json::from_str(content).map_err(|e| anyhow!("Failed to parse ABI: {}", e))?;
+    let abi_value = value.get("abi").cloned().unwrap_or(value);
+    serde_json::from_value(abi_value).map_err(|e| anyhow!("Failed to parse ABI: {}", e))
+}
+
+/// Decodes calldata against whichever function of `abi` its selector names
+pub fn decode_with_abi(abi: &JsonAbi, data: &[u8]) -> Result<DecodedCall> {
+    let selector = data
+        .get(..4)
+        .ok_or_else(|| anyhow!("Calldata is shorter than a function selector"))?;
+    let function = abi
+        .functions()
+        .find(|function| function.selector().as_slice() == selector)
+        .ok_or_else(|| {
+            anyhow!("No function in the ABI has selector 0x{}", hex::encode(selector))
+        })?;
+    decode_calldata(function, data)
+}
+
+/// Formats a decoded value on one line; tuples and arrays become `[a, b]`
+pub fn format_value(value: &DynSolValue) -> String {
+    match value {
+        DynSolValue::Address(address) => address.to_checksum(None),
+        DynSolValue::Bool(b) => b.to_string(),
+        DynSolValue::Int(i, _) => i.to_string(),
+        DynSolValue::Uint(u, _) => u.to_string(),
+        DynSolValue::FixedBytes(word, size) => format!("0x{}", hex::encode(&word[..*size])),
+        DynSolValue::Bytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        DynSolValue::String(s) => format!("{:?}", s),
+        DynSolValue::Array(values)
+        | DynSolValue::FixedArray(values)
+        | DynSolValue::Tuple(values) => format!(
+            "[{}]",
+            values.iter().map(format_value).collect::<Vec<_>>().join(", ")
+        ),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Formats a decoded value over several lines, one element per line for
+/// tuples and arrays, indented by nesting depth
+pub fn format_value_pretty(value: &DynSolValue, indent: usize) -> String {
+    let pad = "  ".repeat(indent);
+    match value {
+        DynSolValue::Array(values)
+        | DynSolValue::FixedArray(values)
+        | DynSolValue::Tuple(values)
+            if !values.is_empty() =>
+        {
+            let (open, close) = if matches!(value, DynSolValue::Tuple(_)) {
+                ("(", ")")
+            } else {
+                ("[", "]")
+            };
+            let items: Vec<String> = values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| format!("{}  [{}] {}", pad, i, format_value_pretty(v, indent + 1)))
+                .collect();
+            format!("{}\n{}\n{}{}", open, items.join("\n"), pad, close)
+        }
+        other => format_value(other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_erc20_transfer_roundtrip() {
+        let to = Address::from_str("0x742d35Cc6634C0532925a3b844Bc454e4438f44e").unwrap();
+        let data = erc20_transfer_calldata(to, U256::from(1_500_000u64));
+
+        assert_eq!(&data[..4], &[0xa9, 0x05, 0x9c, 0xbb]);
+        let decoded = decode_erc20_transfer(&data).unwrap();
+        assert_eq!(decoded.signature, "transfer(address,uint256)");
+        assert_eq!(decoded.params[0].name, "recipient");
+        assert_eq!(decoded.params[0].value, to.to_checksum(None));
+        assert_eq!(decoded.params[1].value, "1500000");
+    }
+
+    #[test]
+    fn test_format_nested_values() {
+        let value = DynSolValue::Tuple(vec![
+            DynSolValue::Uint(U256::from(7u64), 256),
+            DynSolValue::Array(vec![DynSolValue::Bool(true), DynSolValue::Bool(false)]),
+        ]);
+        assert_eq!(format_value(&value), "[7, [true, false]]");
+        assert_eq!(
+            format_value_pretty(&value, 0),
+            "(\n  [0] 7\n  [1] [\n    [0] true\n    [1] false\n  ]\n)"
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_wrong_selector() {
+        let function = Function::parse("function approve(address spender, uint256 amount)").unwrap();
+        let data = erc20_transfer_calldata(Address::ZERO, U256::from(1u64));
+        assert!(decode_calldata(&function, &data).is_err());
+        assert!(decode_calldata(&function, &[0xde, 0xad]).is_err());
+    }
+
+    #[test]
+    fn test_decode_with_abi_finds_function_by_selector() {
+        let abi = parse_abi(
+            r#"{"abi":[{"type":"function","name":"transfer","stateMutability":"nonpayable",
+            "inputs":[{"name":"to","type":"address"},{"name":"value","type":"uint256"}],
+            "outputs":[{"name":"","type":"bool"}]}]}"#,
+        )
+        .unwrap();
+        let data = erc20_transfer_calldata(Address::ZERO, U256::from(7u64));
+        let decoded = decode_with_abi(&abi, &data).unwrap();
+        assert_eq!(decoded.signature, "transfer(address,uint256)");
+        assert_eq!(decoded.params[1].value, "7");
+        assert!(decode_with_abi(&abi, &[0xde, 0xad, 0xbe, 0xef]).is_err());
+        assert!(decode_with_abi(&abi, &[0xde]).is_err());
+    }
+}
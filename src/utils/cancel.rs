@@ -0,0 +1,169 @@
+//! Cooperative cancellation of long interactive operations
+//!
+//! While a [`CancelToken`] is alive, pressing ESC or 'q' marks it cancelled, so the
+//! operation can stop at a safe point and the menu carries on. Keys are read from
+//! the terminal with line buffering and echo turned off, and the terminal is put
+//! back as it was when the token is dropped. Ctrl-C keeps its usual meaning.
+
+use anyhow::Result;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+    /// Stops reading keys and restores the terminal when dropped
+    _keys: Option<keys::KeyListener>,
+}
+
+impl CancelToken {
+    /// Watches the terminal for ESC or 'q' until the token is dropped. Without a
+    /// terminal to read from the operation simply runs to the end.
+    pub fn listen() -> Self {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let keys = keys::KeyListener::start(cancelled.clone());
+        if keys.is_some() {
+            println!("(Press ESC or q to cancel and return to the menu)");
+        }
+        Self {
+            cancelled,
+            _keys: keys,
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Runs `operation`, dropping it if cancelled; `Ok(None)` means it was cancelled
+    pub async fn run<T>(&self, operation: impl Future<Output = Result<T>>) -> Result<Option<T>> {
+        tokio::select! {
+            result = operation => result.map(Some),
+            _ = self.cancelled() => {
+                println!("Operation cancelled");
+                Ok(None)
+            }
+        }
+    }
+
+    async fn cancelled(&self) {
+        while !self.is_cancelled() {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+}
+
+/// Whether one read from the terminal is a cancel key: 'q', or ESC on its own
+/// (arrow and function keys also start with ESC but arrive with more bytes)
+fn is_cancel_key(input: &[u8]) -> bool {
+    input == [0x1b] || input.iter().any(|byte| matches!(byte, b'q' | b'Q'))
+}
+
+#[cfg(unix)]
+mod keys {
+    use super::is_cancel_key;
+    use std::fs::File;
+    use std::io::Read;
+    use std::os::fd::AsRawFd;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread::JoinHandle;
+
+    /// Reads keys from the controlling terminal on a background thread
+    pub struct KeyListener {
+        tty: File,
+        original: libc::termios,
+        stop: Arc<AtomicBool>,
+        reader: Option<JoinHandle<()>>,
+    }
+
+    impl KeyListener {
+        /// `None` when there is no terminal, e.g. when run from a script
+        pub fn start(cancelled: Arc<AtomicBool>) -> Option<Self> {
+            let tty = File::open("/dev/tty").ok()?;
+            let fd = tty.as_raw_fd();
+            let mut original = unsafe { std::mem::zeroed::<libc::termios>() };
+            if unsafe { libc::tcgetattr(fd, &mut original) } != 0 {
+                return None;
+            }
+            // Single keys without Enter and without echo; reads give up after a tenth
+            // of a second so the thread notices when to stop. Output processing and
+            // signals are untouched, so printing and Ctrl-C behave as usual.
+            let mut keys = original;
+            keys.c_lflag &= !(libc::ICANON | libc::ECHO);
+            keys.c_cc[libc::VMIN] = 0;
+            keys.c_cc[libc::VTIME] = 1;
+            if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &keys) } != 0 {
+                return None;
+            }
+
+            let stop = Arc::new(AtomicBool::new(false));
+            let reader = {
+                let stop = stop.clone();
+                let mut tty = tty.try_clone().ok()?;
+                std::thread::spawn(move || {
+                    let mut buf = [0u8; 16];
+                    while !stop.load(Ordering::SeqCst) {
+                        match tty.read(&mut buf) {
+                            Ok(n) if n > 0 && is_cancel_key(&buf[..n]) => {
+                                if !cancelled.swap(true, Ordering::SeqCst) {
+                                    println!("\n⏹️  Cancelling...");
+                                }
+                            }
+                            Ok(_) => {}
+                            Err(_) => break,
+                        }
+                    }
+                })
+            };
+            Some(Self {
+                tty,
+                original,
+                stop,
+                reader: Some(reader),
+            })
+        }
+    }
+
+    impl Drop for KeyListener {
+        fn drop(&mut self) {
+            self.stop.store(true, Ordering::SeqCst);
+            if let Some(reader) = self.reader.take() {
+                let _ = reader.join();
+            }
+            unsafe { libc::tcsetattr(self.tty.as_raw_fd(), libc::TCSANOW, &self.original) };
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod keys {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+
+    /// Key reading is only implemented for Unix terminals
+    pub struct KeyListener;
+
+    impl KeyListener {
+        pub fn start(_cancelled: Arc<AtomicBool>) -> Option<Self> {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_keys() {
+        assert!(is_cancel_key(b"q"));
+        assert!(is_cancel_key(b"Q"));
+        assert!(is_cancel_key(&[0x1b]));
+        // Arrow up is ESC [ A
+        assert!(!is_cancel_key(&[0x1b, b'[', b'A']));
+        assert!(!is_cancel_key(b"\n"));
+        assert!(!is_cancel_key(&[0x03]));
+    }
+}
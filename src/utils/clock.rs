@@ -0,0 +1,88 @@
+//! Tolerance for a local clock that disagrees with the rest of the world
+
+use anyhow::{Result, anyhow};
+use chrono::DateTime;
+
+/// Timestamps up to this far ahead of the local clock are accepted with a warning,
+/// so a clock that drifted (e.g. after a suspend) doesn't block anything
+pub const CLOCK_SKEW_TOLERANCE_SECS: i64 = 10 * 60;
+
+/// Offsets from network time beyond this are reported as a wrong system clock
+pub const CLOCK_WARNING_SECS: i64 = 2 * 60;
+
+/// How a timestamp compares with the local clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FutureCheck {
+    /// Not after the local time
+    NotFuture,
+    /// Ahead by this many seconds, within the tolerance for clock skew
+    Skewed(i64),
+    /// Ahead by this many seconds, more than clock skew explains
+    TooFar(i64),
+}
+
+pub fn check_future(timestamp: i64, now: i64) -> FutureCheck {
+    let ahead = timestamp - now;
+    if ahead <= 0 {
+        FutureCheck::NotFuture
+    } else if ahead <= CLOCK_SKEW_TOLERANCE_SECS {
+        FutureCheck::Skewed(ahead)
+    } else {
+        FutureCheck::TooFar(ahead)
+    }
+}
+
+/// Local time minus the `Date` header of an HTTP response, in seconds
+pub fn offset_from_http_date(local_now: i64, date_header: &str) -> Result<i64> {
+    let server = DateTime::parse_from_rfc2822(date_header.trim())
+        .map_err(|e| anyhow!("Invalid Date header '{}': {}", date_header, e))?;
+    Ok(local_now - server.timestamp())
+}
+
+/// Measures how far the local clock is from a server's, using the `Date` header of
+/// a request to `url` (e.g. the RPC node). Accurate to about a second.
+pub async fn clock_offset(url: &str) -> Result<i64> {
+    let response = reqwest::Client::new()
+        .head(url)
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await
+        .map_err(|e| anyhow!("Could not reach {}: {}", url, e))?;
+    let date = response
+        .headers()
+        .get(reqwest::header::DATE)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| anyhow!("{} did not send a Date header", url))?;
+    offset_from_http_date(chrono::Utc::now().timestamp(), date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_future_boundaries() {
+        let now = 1_700_000_000;
+        assert_eq!(check_future(now - 1, now), FutureCheck::NotFuture);
+        assert_eq!(check_future(now, now), FutureCheck::NotFuture);
+        assert_eq!(check_future(now + 1, now), FutureCheck::Skewed(1));
+        assert_eq!(
+            check_future(now + CLOCK_SKEW_TOLERANCE_SECS, now),
+            FutureCheck::Skewed(CLOCK_SKEW_TOLERANCE_SECS)
+        );
+        assert_eq!(
+            check_future(now + CLOCK_SKEW_TOLERANCE_SECS + 1, now),
+            FutureCheck::TooFar(CLOCK_SKEW_TOLERANCE_SECS + 1)
+        );
+    }
+
+    #[test]
+    fn test_offset_from_http_date() {
+        // Tue, 14 Nov 2023 22:13:20 GMT is 1_700_000_000
+        let date = "Tue, 14 Nov 2023 22:13:20 GMT";
+        assert_eq!(offset_from_http_date(1_700_000_000, date).unwrap(), 0);
+        assert_eq!(offset_from_http_date(1_700_000_090, date).unwrap(), 90);
+        assert_eq!(offset_from_http_date(1_699_999_700, date).unwrap(), -300);
+        assert!(offset_from_http_date(1_700_000_000, "yesterday").is_err());
+    }
+}
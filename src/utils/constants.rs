@@ -11,6 +11,68 @@ pub fn wallet_file_path() -> PathBuf {
     dir.join("rootstock-wallet.json")
 }
 
+/// Transactions sent by this wallet that were not seen as mined yet
+pub fn pending_transactions_path() -> PathBuf {
+    wallet_file_path().with_file_name("pending_transactions.json")
+}
+
+/// Last block seen per address by `history --since-last-check`
+pub fn history_state_path() -> PathBuf {
+    wallet_file_path().with_file_name("history_state.json")
+}
+
+/// The address book, the one place contacts are stored
+pub fn contacts_path() -> PathBuf {
+    wallet_file_path().with_file_name("contacts.json")
+}
+
+/// Progress of the last bulk transfer, kept until every transfer in it is confirmed
+pub fn bulk_journal_path() -> PathBuf {
+    wallet_file_path().with_file_name("bulk_journal.json")
+}
+
+/// Per-address alert thresholds of the balance watcher
+pub fn watch_thresholds_path() -> PathBuf {
+    wallet_file_path().with_file_name("watch_thresholds.json")
+}
+
+/// Recipients of an unfinished interactive batch, offered for resuming on launch
+pub fn session_path() -> PathBuf {
+    wallet_file_path().with_file_name("session.json")
+}
+
+/// Bookkeeping labels attached to transaction hashes
+pub fn tx_labels_path() -> PathBuf {
+    wallet_file_path().with_file_name("tx_labels.json")
+}
+
+/// First and last activity dates of looked-up addresses
+pub fn activity_cache_path() -> PathBuf {
+    wallet_file_path().with_file_name("activity_cache.json")
+}
+
+/// Symbol, decimals and name of tokens read from the chain
+pub fn token_metadata_path() -> PathBuf {
+    wallet_file_path().with_file_name("token_metadata.json")
+}
+
+/// Incoming transactions seen per address, for the address reuse warning
+pub fn receive_log_path() -> PathBuf {
+    wallet_file_path().with_file_name("receive_log.json")
+}
+
+/// User token registry, next to `config.json` in the config directory
+pub fn token_registry_path() -> PathBuf {
+    let dir = dirs::config_dir()
+        .expect("Failed to get config directory")
+        .join("rootstock-wallet");
+    std::fs::create_dir_all(&dir).expect("Failed to create config directory");
+    dir.join("tokens.json")
+}
+
+/// Address of the RSK Bridge precompiled contract
+pub const BRIDGE_ADDRESS: &str = "0x0000000000000000000000000000000001000006";
+
 pub const METHOD_TYPES: &str = "read";
 
 pub const ALLOWED_BRIDGE_METHODS: &[(&str, &[&str])] = &[
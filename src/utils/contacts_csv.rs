@@ -0,0 +1,153 @@
+//! Address books as CSV, for sharing contacts through spreadsheets
+//!
+//! Columns are `name,address,notes,tags`, with tags separated by semicolons.
+//! Only these fields travel; transaction stats stay in the local contacts file.
+
+use crate::types::contacts::Contact;
+use crate::types::network::Network;
+use crate::utils::address::parse_address;
+use anyhow::{Result, anyhow};
+use serde::Deserialize;
+
+const HEADER: [&str; 4] = ["name", "address", "notes", "tags"];
+
+/// Separates tags inside the `tags` column
+const TAG_SEPARATOR: char = ';';
+
+#[derive(Debug, Deserialize)]
+struct CsvContact {
+    name: String,
+    address: String,
+    #[serde(default)]
+    notes: String,
+    #[serde(default)]
+    tags: String,
+}
+
+/// Writes `contacts` as CSV with a header row
+pub fn contacts_to_csv(contacts: &[Contact]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(HEADER)?;
+    for contact in contacts {
+        writer.write_record([
+            contact.name.as_str(),
+            &format!("0x{:x}", contact.address),
+            contact.notes.as_deref().unwrap_or_default(),
+            &contact.tags.join(&TAG_SEPARATOR.to_string()),
+        ])?;
+    }
+    let bytes = writer.into_inner().map_err(|e| anyhow!("Failed to write CSV: {}", e))?;
+    Ok(String::from_utf8(bytes)?)
+}
+
+/// Reads contacts for `network` from CSV with a `name,address,notes,tags` header.
+/// Every row is checked with [`Contact::validate`]; rows that fail are left out and
+/// reported as "row N: reason", N being the line in the file.
+pub fn contacts_from_csv(content: &str, network: Network) -> Result<(Vec<Contact>, Vec<String>)> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    let headers: csv::StringRecord = reader
+        .headers()
+        .map_err(|e| anyhow!("Failed to read CSV header: {}", e))?
+        .iter()
+        .map(str::to_lowercase)
+        .collect();
+    if !headers.iter().any(|h| h == "name") || !headers.iter().any(|h| h == "address") {
+        return Err(anyhow!("The CSV needs a header row with name and address columns"));
+    }
+
+    let mut contacts = Vec::new();
+    let mut errors = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        // The header is line 1
+        let row = record
+            .as_ref()
+            .ok()
+            .and_then(|record| record.position())
+            .map_or(index as u64 + 2, |position| position.line());
+        let contact = record
+            .map_err(|e| anyhow!("{}", e))
+            .and_then(|record| {
+                record
+                    .deserialize::<CsvContact>(Some(&headers))
+                    .map_err(|e| anyhow!("{}", e))
+            })
+            .and_then(|entry| to_contact(entry, network));
+        match contact {
+            Ok(contact) => contacts.push(contact),
+            Err(e) => errors.push(format!("row {}: {}", row, e)),
+        }
+    }
+    Ok((contacts, errors))
+}
+
+fn to_contact(entry: CsvContact, network: Network) -> Result<Contact> {
+    let address = parse_address(&entry.address, network)
+        .map_err(|e| anyhow!("invalid address {}: {}", entry.address, e))?;
+    let notes = Some(entry.notes).filter(|notes| !notes.is_empty());
+    let tags = entry
+        .tags
+        .split(TAG_SEPARATOR)
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect();
+    let contact = Contact::new(entry.name, address, notes, tags);
+    contact.validate()?;
+    Ok(contact)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "0x742d35cc6634c0532925a3b844bc454e4438f44e";
+    const BOB: &str = "0x1234567890123456789012345678901234567890";
+
+    #[test]
+    fn test_round_trips_through_csv() {
+        let contact = Contact::new(
+            "Alice, treasury".to_string(),
+            parse_address(ALICE, Network::Testnet).unwrap(),
+            Some("multisig".to_string()),
+            vec!["team".to_string(), "payroll".to_string()],
+        );
+        let csv = contacts_to_csv(std::slice::from_ref(&contact)).unwrap();
+        assert!(csv.starts_with("name,address,notes,tags\n"));
+        assert!(csv.contains("team;payroll"));
+
+        let (contacts, errors) = contacts_from_csv(&csv, Network::Testnet).unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[0].name, contact.name);
+        assert_eq!(contacts[0].address, contact.address);
+        assert_eq!(contacts[0].notes, contact.notes);
+        assert_eq!(contacts[0].tags, contact.tags);
+    }
+
+    #[test]
+    fn test_reports_each_invalid_row() {
+        let csv = format!(
+            "Name,Address,Notes,Tags\n\
+             Alice,{ALICE},,\n\
+             Bob,0xnot-an-address,,\n\
+             ,{BOB},,\n\
+             Carol,{BOB},,a;b;c;d;e;f\n\
+             Dave,{BOB},friend,\n"
+        );
+        let (contacts, errors) = contacts_from_csv(&csv, Network::Testnet).unwrap();
+        let names: Vec<&str> = contacts.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Alice", "Dave"]);
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].starts_with("row 3: invalid address 0xnot-an-address"));
+        assert_eq!(errors[1], "row 4: Contact name cannot be empty");
+        assert_eq!(errors[2], "row 5: A contact can have a maximum of 5 tags");
+    }
+
+    #[test]
+    fn test_requires_name_and_address_columns() {
+        assert!(contacts_from_csv(&format!("label,wallet\nAlice,{ALICE}\n"), Network::Testnet).is_err());
+    }
+}
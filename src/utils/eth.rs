@@ -1,15 +1,85 @@
-use crate::types::wallet::WalletData;
-use crate::utils::constants;
+use crate::config::{ConfigManager, ReadBlockTag, SponsorSettings, TxTypePreference};
+use crate::utils::access_list::{self, AccessListSource};
+use crate::utils::calldata;
+use crate::utils::gas::{FEE_PERCENTILES, GasPresets};
 use crate::utils::helper::Config;
+use crate::utils::sponsor;
+use crate::utils::token_metadata::{self, TokenMetadata};
 use anyhow::anyhow;
 use alloy::primitives::{Address, B256, U256};
 use alloy::providers::{Provider, ProviderBuilder, RootProvider};
 use alloy::signers::local::PrivateKeySigner;
 use alloy::transports::http::{Client, Http};
-use alloy::network::TransactionBuilder;
+use alloy::eips::eip2718::Encodable2718;
+use alloy::network::{EthereumWallet, TransactionBuilder};
+use alloy::rpc::types::{BlockId, BlockNumberOrTag, TransactionRequest};
 use alloy::sol;
-use std::fs;
-use std::sync::Arc;
+use crate::utils::pending::{self, PendingStore, PendingTransaction};
+use crate::utils::retry::{self, RetryPolicy};
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How often `wait_for_gas_price` re-checks the network
+const GAS_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// EIP-1559 probe results by network name, so a session probes each network once
+static EIP1559_SUPPORT: Mutex<BTreeMap<String, bool>> = Mutex::new(BTreeMap::new());
+
+/// How often `wait_for_confirmations` checks for new blocks (RSK mines about every 30s)
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The `minimumGasPrice` RSK puts in every block header; transactions priced below
+/// it are rejected. Returns `None` on nodes that don't report it.
+pub async fn minimum_gas_price(provider: &RootProvider<Http<Client>>) -> Option<u128> {
+    let block: serde_json::Value = provider
+        .raw_request("eth_getBlockByNumber".into(), ("latest", false))
+        .await
+        .ok()?;
+    let hex = block.get("minimumGasPrice")?.as_str()?;
+    u128::from_str_radix(hex.trim_start_matches("0x"), 16).ok()
+}
+
+/// Raises `gas_price` to the node's minimum, warning when it had to be bumped
+pub async fn enforce_minimum_gas_price(
+    provider: &RootProvider<Http<Client>>,
+    gas_price: u128,
+) -> u128 {
+    match minimum_gas_price(provider).await {
+        Some(minimum) if gas_price < minimum => {
+            eprintln!(
+                "⚠️  Gas price {} wei is below the node's minimum of {} wei; using the minimum",
+                gas_price, minimum
+            );
+            minimum
+        }
+        _ => gas_price,
+    }
+}
+
+/// Worst-case fee of `tx` in wei: gas limit × (max fee per gas, or the gas price)
+fn max_fee(tx: &TransactionRequest, gas_limit: u64, gas_price: u128) -> U256 {
+    let per_gas = tx.max_fee_per_gas.or(tx.gas_price).unwrap_or(gas_price);
+    U256::from(gas_limit) * U256::from(per_gas)
+}
+
+/// A fully built transfer that has not been signed or sent
+#[derive(Debug, Clone)]
+pub struct PreparedTransaction {
+    pub tx: TransactionRequest,
+    pub from: Address,
+    pub nonce: u64,
+    /// `None` for RBTC transfers
+    pub token: Option<Address>,
+    /// Amount sent, in RBTC wei or token base units
+    pub value: U256,
+    pub gas_limit: u64,
+    /// Gas price in wei the fees were derived from
+    pub gas_price: u128,
+    /// Worst-case fee in wei: gas limit times the highest price per gas
+    pub max_fee: U256,
+}
 
 // Define ERC20 interface using alloy's sol! macro
 sol! {
@@ -20,33 +90,32 @@ sol! {
         function transfer(address recipient, uint256 amount) external returns (bool);
         function decimals() external view returns (uint8);
         function symbol() external view returns (string);
+        function name() external view returns (string);
     }
 }
 
 pub struct EthClient {
     provider: Arc<RootProvider<Http<Client>>>,
     wallet: Option<PrivateKeySigner>,
+    network_name: String,
+    read_tag: ReadBlockTag,
+    access_list: Option<AccessListSource>,
+    gas_price_override: Option<u128>,
+    max_gas_price: Option<u128>,
+    nonce_override: Option<u64>,
+    retry: RetryPolicy,
 }
 
-impl EthClient {
-    pub async fn new(config: &Config, cli_api_key: Option<String>) -> Result<Self, anyhow::Error> {
-        // Load or update API key
-        let wallet_file = constants::wallet_file_path();
-        let mut wallet_data = if wallet_file.exists() {
-            let data = fs::read_to_string(&wallet_file)?;
-            serde_json::from_str::<WalletData>(&data)?
-        } else {
-            WalletData::new()
-        };
-
-        let _api_key = if let Some(key) = cli_api_key {
-            wallet_data.api_key = Some(key.clone());
-            fs::write(&wallet_file, serde_json::to_string_pretty(&wallet_data)?)?;
-            Some(key)
-        } else {
-            wallet_data.api_key.clone()
-        };
+fn block_id(tag: ReadBlockTag) -> BlockId {
+    BlockId::Number(match tag {
+        ReadBlockTag::Latest => BlockNumberOrTag::Latest,
+        ReadBlockTag::Safe => BlockNumberOrTag::Safe,
+        ReadBlockTag::Finalized => BlockNumberOrTag::Finalized,
+    })
+}
 
+impl EthClient {
+    pub async fn new(config: &Config) -> Result<Self, anyhow::Error> {
         // Use the RPC URL from config (which defaults to public nodes)
         let provider = ProviderBuilder::new()
             .on_http(config.network.rpc_url.parse()?);
@@ -59,136 +128,644 @@ impl EthClient {
                     .map_err(|e| anyhow!("Invalid private key: {}", e))
             })
             .transpose()?;
+        let app_config = ConfigManager::new().and_then(|manager| manager.load()).ok();
+        let read_tag = app_config
+            .as_ref()
+            .map(|config| config.read_block_tag)
+            .unwrap_or_default();
+        let retry = app_config
+            .as_ref()
+            .map(|config| config.retry_policy())
+            .unwrap_or_default();
         Ok(Self {
             provider: Arc::new(provider),
             wallet,
+            network_name: config.network.name.clone(),
+            read_tag,
+            access_list: None,
+            gas_price_override: None,
+            max_gas_price: None,
+            nonce_override: None,
+            retry,
+        })
+    }
+
+    /// Pins balance and confirmed-nonce reads to `tag` instead of the configured one
+    pub fn with_read_tag(mut self, tag: ReadBlockTag) -> Self {
+        self.read_tag = tag;
+        self
+    }
+
+    /// Sends transfers at `gas_price` wei instead of the node's current price
+    pub fn with_gas_price(mut self, gas_price: u128) -> Self {
+        self.gas_price_override = Some(gas_price);
+        self
+    }
+
+    /// Never lets the fee per gas of a transfer exceed `max_gas_price` wei,
+    /// including the EIP-1559 max fee
+    pub fn with_max_gas_price(mut self, max_gas_price: u128) -> Self {
+        self.max_gas_price = Some(max_gas_price);
+        self
+    }
+
+    /// Sends the next transfer with `nonce` instead of the next free one, e.g. to
+    /// replace a stuck transaction
+    pub fn with_nonce(mut self, nonce: u64) -> Self {
+        self.nonce_override = Some(nonce);
+        self
+    }
+
+    /// Sends transfers with an EIP-2930 access list from `source`
+    pub fn with_access_list(mut self, source: AccessListSource) -> Self {
+        self.access_list = Some(source);
+        self
+    }
+
+    /// Retries node requests according to `policy` instead of the configured one
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
+    pub fn read_tag(&self) -> ReadBlockTag {
+        self.read_tag
+    }
+
+    /// Current gas price, never below the minimum the RSK node accepts
+    pub async fn gas_price(&self) -> Result<u128, anyhow::Error> {
+        let gas_price = retry::retry(&self.retry, || async {
+            self.provider.get_gas_price().await
         })
+        .await
+        .map_err(|e| anyhow!("Failed to get gas price: {}", e))?;
+        Ok(enforce_minimum_gas_price(&self.provider, gas_price).await)
+    }
+
+    /// Polls the gas price until it is at or below `target` (in wei), showing the
+    /// current price against the target. Fails once `timeout` has elapsed.
+    pub async fn wait_for_gas_price(
+        &self,
+        target: u128,
+        timeout: Duration,
+    ) -> Result<u128, anyhow::Error> {
+        let started = Instant::now();
+        let gwei = |wei: u128| wei as f64 / 1_000_000_000.0;
+        loop {
+            let current = self.gas_price().await?;
+            if current <= target {
+                println!(
+                    "\n✅ Gas price {:.2} gwei is within the {:.2} gwei target",
+                    gwei(current),
+                    gwei(target)
+                );
+                return Ok(current);
+            }
+            if started.elapsed() >= timeout {
+                return Err(anyhow!(
+                    "Gas price stayed above {:.2} gwei for {} minutes; transaction not sent",
+                    gwei(target),
+                    timeout.as_secs() / 60
+                ));
+            }
+            print!(
+                "\r⏳ Gas price {:.2} gwei, waiting for {:.2} gwei ({}s elapsed)   ",
+                gwei(current),
+                gwei(target),
+                started.elapsed().as_secs()
+            );
+            let _ = std::io::Write::flush(&mut std::io::stdout());
+            tokio::time::sleep(GAS_POLL_INTERVAL).await;
+        }
     }
 
+    /// Polls until the block holding `tx_hash` has `confirmations` blocks on top of
+    /// it (counting its own), showing the count as it grows. The receipt is read
+    /// again each time, so a transaction reorged into another block is followed.
+    /// Fails once `timeout` has elapsed.
+    pub async fn wait_for_confirmations(
+        &self,
+        tx_hash: B256,
+        confirmations: u64,
+        timeout: Duration,
+    ) -> Result<alloy::rpc::types::TransactionReceipt, anyhow::Error> {
+        let started = Instant::now();
+        loop {
+            let receipt = retry::retry(&self.retry, || async {
+                self.provider.get_transaction_receipt(tx_hash).await
+            })
+            .await
+            .map_err(|e| anyhow!("Failed to get transaction receipt: {}", e))?;
+            let latest = retry::retry(&self.retry, || async {
+                self.provider.get_block_number().await
+            })
+            .await
+            .map_err(|e| anyhow!("Failed to get block number: {}", e))?;
+            let confirmed = receipt
+                .as_ref()
+                .and_then(|receipt| receipt.block_number)
+                .map(|block| latest.saturating_sub(block) + 1)
+                .unwrap_or(0);
+
+            print!("\r⏳ {}/{} confirmations   ", confirmed.min(confirmations), confirmations);
+            let _ = std::io::Write::flush(&mut std::io::stdout());
+            if let Some(receipt) = receipt.filter(|_| confirmed >= confirmations) {
+                println!();
+                return Ok(receipt);
+            }
+            if started.elapsed() >= timeout {
+                println!();
+                return Err(anyhow!(
+                    "Only {} of {} confirmations after {} minutes; check later with: wallet tx --tx-hash 0x{:x}",
+                    confirmed,
+                    confirmations,
+                    timeout.as_secs() / 60,
+                    tx_hash
+                ));
+            }
+            tokio::time::sleep(CONFIRMATION_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Name of the network this client talks to, as stored in the config
+    pub fn network_name(&self) -> &str {
+        &self.network_name
+    }
+
+    /// Pending transactions sent from `from` on this network. Entries that were
+    /// mined, replaced or dropped by the node are removed from the store.
+    pub async fn pending_transactions(
+        &self,
+        from: Address,
+    ) -> Result<Vec<PendingTransaction>, anyhow::Error> {
+        let tracked: Vec<PendingTransaction> = PendingStore::load()
+            .transactions
+            .into_iter()
+            .filter(|tx| tx.network == self.network_name && tx.from == from)
+            .collect();
+        if tracked.is_empty() {
+            return Ok(tracked);
+        }
+
+        let confirmed_nonce = self
+            .provider
+            .get_transaction_count(from)
+            .await
+            .map_err(|e| anyhow!("Failed to get nonce: {}", e))?;
+        let mut done = Vec::new();
+        for tx in &tracked {
+            let known = tx.nonce >= confirmed_nonce
+                && self
+                    .provider
+                    .get_transaction_by_hash(tx.hash)
+                    .await
+                    .map_err(|e| anyhow!("Failed to look up transaction: {}", e))?
+                    .is_some();
+            if !known {
+                done.push(tx.hash);
+            }
+        }
+        PendingStore::prune(&self.network_name, from, &done)
+    }
+
+    /// Balance at the read block tag, falling back to `latest` on nodes that don't know the tag
     pub async fn get_balance(
         &self,
         address: &Address,
         token_address: &Option<Address>,
+    ) -> Result<U256, anyhow::Error> {
+        match self.balance_at(address, token_address, self.read_tag).await {
+            Err(e) if self.read_tag != ReadBlockTag::Latest => {
+                eprintln!(
+                    "⚠️  Could not read at the '{}' block ({}); using latest",
+                    self.read_tag, e
+                );
+                self.balance_at(address, token_address, ReadBlockTag::Latest)
+                    .await
+            }
+            result => result,
+        }
+    }
+
+    async fn balance_at(
+        &self,
+        address: &Address,
+        token_address: &Option<Address>,
+        tag: ReadBlockTag,
     ) -> Result<U256, anyhow::Error> {
         match token_address {
             Some(token_addr) => {
                 let contract = IERC20::new(*token_addr, &self.provider);
-                let balance = contract
-                    .balanceOf(*address)
-                    .call()
-                    .await
-                    .map_err(|e| anyhow!("Failed to get token balance: {}", e))?;
+                let balance = retry::retry(&self.retry, || async {
+                    contract.balanceOf(*address).block(block_id(tag)).call().await
+                })
+                .await
+                .map_err(|e| anyhow!("Failed to get token balance: {}", e))?;
                 Ok(balance._0)
             }
-            None => self
-                .provider
-                .get_balance(*address)
-                .await
-                .map_err(|e| anyhow!("Failed to get RBTC balance: {}", e)),
+            None => retry::retry(&self.retry, || async {
+                self.provider
+                    .get_balance(*address)
+                    .block_id(block_id(tag))
+                    .await
+            })
+            .await
+            .map_err(|e| anyhow!("Failed to get RBTC balance: {}", e)),
         }
     }
 
-    pub async fn send_transaction(
+    /// Number of mined transactions sent from `address` at the read block tag
+    pub async fn confirmed_nonce(&self, address: Address) -> Result<u64, anyhow::Error> {
+        let nonce = self
+            .provider
+            .get_transaction_count(address)
+            .block_id(block_id(self.read_tag))
+            .await;
+        match nonce {
+            Err(e) if self.read_tag != ReadBlockTag::Latest => {
+                eprintln!(
+                    "⚠️  Could not read at the '{}' block ({}); using latest",
+                    self.read_tag, e
+                );
+                self.provider
+                    .get_transaction_count(address)
+                    .await
+                    .map_err(|e| anyhow!("Failed to get nonce: {}", e))
+            }
+            result => result.map_err(|e| anyhow!("Failed to get nonce: {}", e)),
+        }
+    }
+
+    /// Nonce the next transaction from `from` should use. Transactions this machine
+    /// sent are counted until their receipt lands, so back-to-back sends don't
+    /// reuse a nonce the node hasn't caught up with yet.
+    pub async fn next_nonce(&self, from: Address) -> Result<u64, anyhow::Error> {
+        let node_nonce = retry::retry(&self.retry, || async {
+            self.provider.get_transaction_count(from).pending().await
+        })
+        .await
+        .map_err(|e| anyhow!("Failed to get nonce: {}", e))?;
+        let in_flight = match self.pending_transactions(from).await {
+            Ok(in_flight) => in_flight,
+            Err(e) => {
+                eprintln!("⚠️  Could not check pending transactions: {}", e);
+                Vec::new()
+            }
+        };
+        Ok(pending::next_nonce(node_nonce, &in_flight))
+    }
+
+    /// Builds, prices and estimates a transfer exactly as `send_transaction` would,
+    /// without signing or broadcasting it
+    pub async fn prepare_transaction(
         &self,
         to: Address,
         amount: U256,
         token_address: Option<Address>,
-    ) -> Result<B256, anyhow::Error> {
+    ) -> Result<PreparedTransaction, anyhow::Error> {
         let wallet = self
             .wallet
             .as_ref()
             .ok_or_else(|| anyhow!("No wallet configured"))?;
-        let nonce = self
-            .provider
-            .get_transaction_count(wallet.address())
-            .await
-            .map_err(|e| anyhow!("Failed to get nonce: {}", e))?;
-        let gas_price = self
-            .provider
-            .get_gas_price()
-            .await
-            .map_err(|e| anyhow!("Failed to get gas price: {}", e))?;
-        let rbtc_balance = self
-            .provider
-            .get_balance(wallet.address())
-            .await
-            .map_err(|e| anyhow!("Failed to get RBTC balance: {}", e))?;
+        let nonce = match self.nonce_override {
+            Some(nonce) => nonce,
+            None => self.next_nonce(wallet.address()).await?,
+        };
+        let gas_price = match self.gas_price_override {
+            Some(gas_price) => enforce_minimum_gas_price(&self.provider, gas_price).await,
+            None => self.gas_price().await?,
+        };
+        let rbtc_balance = retry::retry(&self.retry, || async {
+            self.provider.get_balance(wallet.address()).await
+        })
+        .await
+        .map_err(|e| anyhow!("Failed to get RBTC balance: {}", e))?;
         let estimated_gas_cost = U256::from(gas_price) * U256::from(100_000);
         if rbtc_balance < estimated_gas_cost {
             return Err(anyhow!("Insufficient RBTC for gas fees"));
         }
-        let chain_id = self.provider.get_chain_id().await?;
+        let chain_id =
+            retry::retry(&self.retry, || async { self.provider.get_chain_id().await }).await?;
 
-        match token_address {
+        let tx = match token_address {
             Some(token_addr) => {
+                self.check_token_balance(&wallet.address(), token_addr, amount)
+                    .await?;
+
                 let contract = IERC20::new(token_addr, &self.provider);
-                let token_balance = contract
-                    .balanceOf(wallet.address())
-                    .call()
-                    .await
-                    .map_err(|e| anyhow!("Failed to get token balance: {}", e))?;
-                if token_balance._0 < amount {
-                    return Err(anyhow!("Insufficient token balance"));
-                }
-                
-                use alloy::rpc::types::TransactionRequest;
                 let call_data = contract.transfer(to, amount).calldata().clone();
-                let tx = TransactionRequest::default()
+                TransactionRequest::default()
                     .with_to(token_addr)
                     .with_from(wallet.address())
                     .with_nonce(nonce)
-                    .with_gas_price(gas_price)
                     .with_value(U256::ZERO)
                     .with_input(call_data)
-                    .with_chain_id(chain_id);
-                
-                let gas_estimate = self
-                    .provider
-                    .estimate_gas(&tx)
-                    .await
-                    .map_err(|e| anyhow!("Failed to estimate gas for token transfer: {}", e))?;
-                
-                let tx = tx.with_gas_limit(gas_estimate);
-                
-                let pending_tx = self
-                    .provider
-                    .send_transaction(tx)
-                    .await
-                    .map_err(|e| anyhow!("Failed to send token transaction: {}", e))?;
-                let tx_hash = pending_tx.tx_hash();
-                Ok(*tx_hash)
+                    .with_chain_id(chain_id)
             }
             None => {
                 if rbtc_balance < amount + estimated_gas_cost {
                     return Err(anyhow!("Insufficient RBTC for transfer and gas"));
                 }
-                
-                use alloy::rpc::types::TransactionRequest;
-                let tx = TransactionRequest::default()
+
+                TransactionRequest::default()
                     .with_to(to)
                     .with_value(amount)
                     .with_from(wallet.address())
                     .with_nonce(nonce)
-                    .with_gas_price(gas_price)
-                    .with_chain_id(chain_id);
-                
-                let gas_estimate = self
-                    .provider
-                    .estimate_gas(&tx)
-                    .await
-                    .map_err(|e| anyhow!("Failed to estimate gas for RBTC transfer: {}", e))?;
-                
-                let tx = tx.with_gas_limit(gas_estimate);
-                
-                let pending_tx = self
-                    .provider
-                    .send_transaction(tx)
+                    .with_chain_id(chain_id)
+            }
+        };
+        let tx = self.with_fees(tx, gas_price).await?;
+        let tx = self.attach_access_list(tx).await?;
+
+        let kind = if token_address.is_some() { "token" } else { "RBTC" };
+        let gas_limit = retry::retry(&self.retry, || async {
+            self.provider.estimate_gas(&tx).await
+        })
+        .await
+        .map_err(|e| anyhow!("Failed to estimate gas for {} transfer: {}", kind, e))?;
+
+        let tx = tx.with_gas_limit(gas_limit);
+        let max_fee = max_fee(&tx, gas_limit, gas_price);
+        Ok(PreparedTransaction {
+            tx,
+            from: wallet.address(),
+            nonce,
+            token: token_address,
+            value: amount,
+            gas_limit,
+            gas_price,
+            max_fee,
+        })
+    }
+
+    pub async fn send_transaction(
+        &self,
+        to: Address,
+        amount: U256,
+        token_address: Option<Address>,
+    ) -> Result<B256, anyhow::Error> {
+        let prepared = self.prepare_transaction(to, amount, token_address).await?;
+        self.send_prepared(prepared).await
+    }
+
+    /// Signs a transfer built by `prepare_transaction` with the wallet's key,
+    /// broadcasts it and tracks it as pending
+    pub async fn send_prepared(
+        &self,
+        prepared: PreparedTransaction,
+    ) -> Result<B256, anyhow::Error> {
+        let kind = if prepared.token.is_some() { "token" } else { "RBTC" };
+        let wallet = self
+            .wallet
+            .as_ref()
+            .ok_or_else(|| anyhow!("No wallet configured"))?;
+        let envelope = prepared
+            .tx
+            .clone()
+            .build(&EthereumWallet::from(wallet.clone()))
+            .await
+            .map_err(|e| anyhow!("Failed to sign {} transaction: {}", kind, e))?;
+        let tx_hash = *envelope.tx_hash();
+        let raw = envelope.encoded_2718();
+        // A dropped reply may hide a broadcast the node accepted, so a resend that the
+        // node reports as already known counts as sent. Any other rejection of a resend
+        // is returned as is: the first attempt may still have gone through.
+        let attempts = Cell::new(0);
+        retry::retry(&self.retry, || async {
+            attempts.set(attempts.get() + 1);
+            match self.provider.send_raw_transaction(&raw).await {
+                Ok(_) => Ok(()),
+                Err(e) if attempts.get() > 1 && retry::is_already_known(&e) => Ok(()),
+                Err(e) => Err(e),
+            }
+        })
+        .await
+        .map_err(|e| {
+            anyhow!(
+                "Failed to send {} transaction: {} (check 0x{:x} before sending again)",
+                kind,
+                e,
+                tx_hash
+            )
+        })?;
+        self.track_pending(
+            tx_hash,
+            prepared.from,
+            prepared.nonce,
+            prepared.token,
+            prepared.value,
+            prepared.max_fee,
+        );
+        Ok(tx_hash)
+    }
+
+    /// Remembers a sent transaction so balances can show what is still in flight.
+    /// The transaction is already broadcast, so a failure here only warns.
+    fn track_pending(
+        &self,
+        hash: B256,
+        from: Address,
+        nonce: u64,
+        token: Option<Address>,
+        value: U256,
+        max_fee: U256,
+    ) {
+        let tx = PendingTransaction {
+            hash,
+            from,
+            network: self.network_name.clone(),
+            nonce,
+            token,
+            value,
+            max_fee,
+            sent_at: chrono::Utc::now().to_rfc3339(),
+        };
+        if let Err(e) = PendingStore::record(tx) {
+            eprintln!("⚠️  Could not track pending transaction {}: {}", hash, e);
+        }
+    }
+
+    /// Probes the node for EIP-1559 support: a non-zero base fee in `eth_feeHistory`
+    pub async fn probe_eip1559(&self) -> bool {
+        match self
+            .provider
+            .get_fee_history(1, BlockNumberOrTag::Latest, &[])
+            .await
+        {
+            Ok(history) => history.base_fee_per_gas.iter().any(|fee| *fee > 0),
+            Err(_) => false,
+        }
+    }
+
+    /// Base fees of the last `blocks` blocks followed by the next block's, oldest first;
+    /// `None` on networks without a base fee
+    pub async fn base_fee_history(&self, blocks: u64) -> Option<Vec<u128>> {
+        let history = self
+            .provider
+            .get_fee_history(blocks, BlockNumberOrTag::Latest, &[])
+            .await
+            .ok()?;
+        history
+            .base_fee_per_gas
+            .iter()
+            .any(|fee| *fee > 0)
+            .then_some(history.base_fee_per_gas)
+    }
+
+    /// Slow / standard / fast gas prices from `eth_feeHistory`, falling back to
+    /// multiples of the current gas price; never below the node's minimum
+    pub async fn gas_presets(&self) -> Result<GasPresets, anyhow::Error> {
+        let from_history = match self
+            .provider
+            .get_fee_history(20, BlockNumberOrTag::Latest, &FEE_PERCENTILES)
+            .await
+        {
+            Ok(history) => GasPresets::from_fee_history(
+                history.base_fee_per_gas.last().copied().unwrap_or(0),
+                history.reward.as_deref().unwrap_or_default(),
+            ),
+            Err(_) => None,
+        };
+        let presets = match from_history {
+            Some(presets) => presets,
+            None => GasPresets::from_gas_price(self.gas_price().await?),
+        };
+        Ok(presets.at_least(minimum_gas_price(&self.provider).await.unwrap_or(0)))
+    }
+
+    /// Whether to send typed (EIP-1559) transactions on this network.
+    /// Honours the `tx_type` config override; probe results are kept per network
+    /// for the rest of the session.
+    pub async fn supports_eip1559(&self) -> Result<bool, anyhow::Error> {
+        let config = ConfigManager::new()?.load()?;
+        match config.tx_type {
+            TxTypePreference::Legacy => return Ok(false),
+            TxTypePreference::Eip1559 => return Ok(true),
+            TxTypePreference::Auto => {}
+        }
+
+        let cached = EIP1559_SUPPORT
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&self.network_name)
+            .copied();
+        if let Some(supported) = cached {
+            return Ok(supported);
+        }
+        let supported = self.probe_eip1559().await;
+        EIP1559_SUPPORT
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(self.network_name.clone(), supported);
+        Ok(supported)
+    }
+
+    /// Sets typed or legacy fee fields on `tx` depending on network support
+    async fn with_fees(
+        &self,
+        tx: TransactionRequest,
+        gas_price: u128,
+    ) -> Result<TransactionRequest, anyhow::Error> {
+        if !self.supports_eip1559().await? {
+            return Ok(tx.with_gas_price(gas_price));
+        }
+        let fees = self
+            .provider
+            .estimate_eip1559_fees(None)
+            .await
+            .map_err(|e| anyhow!("Failed to estimate EIP-1559 fees: {}", e))?;
+        // The max fee must also clear the node's minimum gas price; a chosen
+        // gas price is used as the max fee as is
+        let max_fee = match self.gas_price_override {
+            Some(_) => gas_price,
+            None => fees.max_fee_per_gas.max(gas_price),
+        };
+        let max_fee = self.max_gas_price.map_or(max_fee, |cap| max_fee.min(cap));
+        Ok(tx
+            .with_max_fee_per_gas(max_fee)
+            .with_max_priority_fee_per_gas(fees.max_priority_fee_per_gas.min(max_fee)))
+    }
+
+    /// Adds the configured access list to `tx` (making it a type-1 or type-2 transaction)
+    /// and prints the gas estimate with and without it
+    async fn attach_access_list(
+        &self,
+        tx: TransactionRequest,
+    ) -> Result<TransactionRequest, anyhow::Error> {
+        let Some(source) = &self.access_list else {
+            return Ok(tx);
+        };
+        let gas_without = self
+            .provider
+            .estimate_gas(&tx)
+            .await
+            .map_err(|e| anyhow!("Failed to estimate gas: {}", e))?;
+        let list = match source {
+            AccessListSource::Auto => {
+                self.provider
+                    .create_access_list(&tx)
                     .await
-                    .map_err(|e| anyhow!("Failed to send RBTC transaction: {}", e))?;
-                let tx_hash = pending_tx.tx_hash();
-                Ok(*tx_hash)
+                    .map_err(|e| anyhow!("The node could not create an access list: {}", e))?
+                    .access_list
             }
+            AccessListSource::File(path) => access_list::load(path)?,
+        };
+        let tx = tx.with_access_list(list.clone());
+        let gas_with = self
+            .provider
+            .estimate_gas(&tx)
+            .await
+            .map_err(|e| anyhow!("Failed to estimate gas with the access list: {}", e))?;
+        println!("{}", access_list::summary(&list, gas_without, gas_with));
+        Ok(tx)
+    }
+
+    /// Sends a token transfer as a meta-transaction through the configured relayer,
+    /// which pays the gas; the wallet needs no RBTC
+    pub async fn send_sponsored_transfer(
+        &self,
+        settings: &SponsorSettings,
+        to: Address,
+        amount: U256,
+        token_address: Address,
+    ) -> Result<B256, anyhow::Error> {
+        let wallet = self
+            .wallet
+            .as_ref()
+            .ok_or_else(|| anyhow!("No wallet configured"))?;
+        self.check_token_balance(&wallet.address(), token_address, amount)
+            .await?;
+
+        let contract = IERC20::new(token_address, &self.provider);
+        let call_data = contract.transfer(to, amount).calldata().clone();
+        // Leave headroom over the estimate; the forwarder passes exactly this much gas on
+        let gas = self.estimate_gas(wallet.address(), to, amount, Some(token_address)).await? * U256::from(12)
+            / U256::from(10);
+        sponsor::send_sponsored(&self.provider, wallet, settings, token_address, call_data, gas)
+            .await
+    }
+
+    /// Fails if `owner` holds less than `amount` of the given token
+    pub async fn check_token_balance(
+        &self,
+        owner: &Address,
+        token_address: Address,
+        amount: U256,
+    ) -> Result<U256, anyhow::Error> {
+        let balance = self.get_balance(owner, &Some(token_address)).await?;
+        if balance < amount {
+            return Err(anyhow!(
+                "Insufficient token balance: have {}, need {}",
+                balance,
+                amount
+            ));
         }
+        Ok(balance)
     }
 
     /// Get transaction receipt by hash
@@ -196,21 +773,40 @@ impl EthClient {
         &self,
         tx_hash: B256,
     ) -> Result<alloy::rpc::types::TransactionReceipt, anyhow::Error> {
-        self.provider
-            .get_transaction_receipt(tx_hash)
-            .await
-            .map_err(|e| anyhow!("Failed to get transaction receipt: {}", e))
-            .and_then(|receipt| receipt.ok_or_else(|| anyhow!("Transaction receipt not found")))
+        retry::retry(&self.retry, || async {
+            self.provider.get_transaction_receipt(tx_hash).await
+        })
+        .await
+        .map_err(|e| anyhow!("Failed to get transaction receipt: {}", e))
+        .and_then(|receipt| receipt.ok_or_else(|| anyhow!("Transaction receipt not found")))
     }
 
     pub async fn get_token_info(
         &self,
         token_address: Address,
     ) -> Result<(u8, String), anyhow::Error> {
+        let metadata = self.token_metadata(token_address).await?;
+        Ok((metadata.decimals, metadata.symbol))
+    }
+
+    /// Decimals, symbol and name of a token, read from the chain only the first time
+    pub async fn token_metadata(
+        &self,
+        token_address: Address,
+    ) -> Result<TokenMetadata, anyhow::Error> {
+        if let Some(metadata) = token_metadata::cached(&self.network_name, &token_address) {
+            return Ok(metadata);
+        }
         let contract = IERC20::new(token_address, &self.provider);
-        let decimals = contract.decimals().call().await?._0;
-        let symbol = contract.symbol().call().await?._0;
-        Ok((decimals, symbol))
+        let metadata = TokenMetadata {
+            decimals: contract.decimals().call().await?._0,
+            symbol: contract.symbol().call().await?._0,
+            name: contract.name().call().await.ok().map(|r| r._0),
+        };
+        if let Err(e) = token_metadata::store(&self.network_name, &token_address, &metadata) {
+            eprintln!("⚠️  Could not cache token metadata: {}", e);
+        }
+        Ok(metadata)
     }
 
     /// Get a reference to the underlying provider
@@ -218,33 +814,38 @@ impl EthClient {
         &self.provider
     }
 
+    /// Gas a transfer of `amount` sent from `from` would use
     pub async fn estimate_gas(
         &self,
+        from: Address,
         to: Address,
         amount: U256,
         token_address: Option<Address>,
     ) -> Result<U256, anyhow::Error> {
-        match token_address {
-            Some(token_addr) => {
-                let contract = IERC20::new(token_addr, &self.provider);
-                let call = contract.transfer(to, amount);
-                call.estimate_gas()
-                    .await
-                    .map(|gas| U256::from(gas))
-                    .map_err(|e| anyhow!("Failed to estimate gas for token transfer: {}", e))
-            }
-            None => {
-                use alloy::rpc::types::TransactionRequest;
-                let tx = TransactionRequest::default()
-                    .with_to(to)
-                    .with_value(amount);
-                self.provider
-                    .estimate_gas(&tx)
-                    .await
-                    .map(U256::from)
-                    .map_err(|e| anyhow!("Failed to estimate gas for RBTC transfer: {}", e))
-            }
-        }
+        let kind = if token_address.is_some() { "token" } else { "RBTC" };
+        self.provider
+            .estimate_gas(&transfer_request(from, to, amount, token_address))
+            .await
+            .map(U256::from)
+            .map_err(|e| anyhow!("Failed to estimate gas for {} transfer: {}", kind, e))
+    }
+}
+
+/// Unsigned transfer of `amount` from `from`, as a `transfer` call on the token
+/// contract or a plain RBTC send. Estimates need the sender: a token transfer from
+/// an address without a balance reverts.
+fn transfer_request(
+    from: Address,
+    to: Address,
+    amount: U256,
+    token_address: Option<Address>,
+) -> TransactionRequest {
+    let tx = TransactionRequest::default().with_from(from);
+    match token_address {
+        Some(token_addr) => tx
+            .with_to(token_addr)
+            .with_input(calldata::erc20_transfer_calldata(to, amount)),
+        None => tx.with_to(to).with_value(amount),
     }
 }
 
@@ -256,3 +857,29 @@ pub fn get_explorer_url(tx_hash: &str, is_testnet: bool) -> String {
         format!("https://explorer.rsk.co/tx/{}", tx_hash)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{TxKind, address};
+
+    #[test]
+    fn test_transfer_request_is_sent_from_the_wallet() {
+        let from = address!("742d35cc6634c0532925a3b844bc454e4438f44e");
+        let to = address!("2acc95758f8b5f583470ba265eb685a8f45fc9d5");
+        let token = address!("19f64674d8a5b4e652319f5e239efd3bc969a1fe");
+
+        let tx = transfer_request(from, to, U256::from(5u64), Some(token));
+        assert_eq!(tx.from, Some(from));
+        assert_eq!(tx.to, Some(TxKind::Call(token)));
+        assert_eq!(
+            tx.input.input().cloned(),
+            Some(calldata::erc20_transfer_calldata(to, U256::from(5u64)))
+        );
+
+        let tx = transfer_request(from, to, U256::from(5u64), None);
+        assert_eq!(tx.from, Some(from));
+        assert_eq!(tx.to, Some(TxKind::Call(to)));
+        assert_eq!(tx.value, Some(U256::from(5u64)));
+    }
+}
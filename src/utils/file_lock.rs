@@ -0,0 +1,173 @@
+use anyhow::{Context, Result};
+use fs2::FileExt;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+
+/// Sidecar file locked while `path` is being read-modified-written
+fn lock_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    path.with_file_name(name)
+}
+
+/// Runs `f` while holding an exclusive lock on `<path>.lock`, so another
+/// wallet instance updating the same file waits instead of clobbering it
+pub fn with_lock<R>(path: &Path, f: impl FnOnce() -> Result<R>) -> Result<R> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path(path))
+        .with_context(|| format!("Failed to open lock for {}", path.display()))?;
+    lock.lock_exclusive()
+        .with_context(|| format!("Failed to lock {}", path.display()))?;
+    let result = f();
+    let _ = lock.unlock();
+    result
+}
+
+/// Writes through a temporary file and a rename, so readers never see a partial file
+pub fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}.tmp", std::process::id()));
+    let tmp = path.with_file_name(name);
+    let written = fs::write(&tmp, contents)
+        .with_context(|| format!("Failed to write {}", tmp.display()))
+        .and_then(|_| {
+            fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))
+        });
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    written
+}
+
+/// Tries of [`write_atomic_retrying`] before giving up
+const WRITE_ATTEMPTS: u32 = 3;
+
+/// [`write_atomic`], retried after a short pause when it fails; covers a file briefly
+/// held open by a sync client or virus scanner. The last error is returned.
+pub fn write_atomic_retrying(path: &Path, contents: &str) -> Result<()> {
+    let mut attempt = 1;
+    loop {
+        match write_atomic(path, contents) {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt >= WRITE_ATTEMPTS => return Err(e),
+            Err(_) => {
+                std::thread::sleep(std::time::Duration::from_millis(100 * u64::from(attempt)));
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Re-reads `path` under the lock (or starts from `T::default()` when it is missing),
+/// applies `f` and writes the result back. Nothing is written if `f` fails.
+pub fn update_json<T, R>(path: &Path, f: impl FnOnce(&mut T) -> Result<R>) -> Result<R>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    with_lock(path, || {
+        let mut value = if path.exists() {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?
+        } else {
+            T::default()
+        };
+        let result = f(&mut value)?;
+        write_atomic(path, &serde_json::to_string_pretty(&value)?)?;
+        Ok(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_update_json_creates_and_merges() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("merge.json");
+
+        update_json(&path, |map: &mut BTreeMap<String, u32>| {
+            map.insert("a".into(), 1);
+            Ok(())
+        })
+        .unwrap();
+        update_json(&path, |map: &mut BTreeMap<String, u32>| {
+            map.insert("b".into(), 2);
+            Ok(())
+        })
+        .unwrap();
+
+        let map: BTreeMap<String, u32> =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(map.len(), 2);
+    }
+
+    #[test]
+    fn test_update_json_skips_write_on_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("error.json");
+        fs::write(&path, r#"{"a":1}"#).unwrap();
+
+        let result: anyhow::Result<()> = update_json(&path, |map: &mut BTreeMap<String, u32>| {
+            map.clear();
+            anyhow::bail!("rejected")
+        });
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), r#"{"a":1}"#);
+    }
+
+    #[test]
+    fn test_concurrent_updates_are_not_lost() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("concurrent.json");
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    update_json(&path, |map: &mut BTreeMap<String, u32>| {
+                        map.insert(format!("k{}", i), i);
+                        Ok(())
+                    })
+                    .unwrap();
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let map: BTreeMap<String, u32> =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(map.len(), 8);
+    }
+
+    #[test]
+    fn test_failed_write_leaves_original_intact() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("interrupted.json");
+        fs::write(&path, r#"{"a":1}"#).unwrap();
+        // A directory where the temporary file goes makes the write fail midway
+        let tmp = path.with_file_name(format!("interrupted.json.{}.tmp", std::process::id()));
+        fs::create_dir_all(tmp.join("blocker")).unwrap();
+
+        assert!(write_atomic(&path, r#"{"b":2}"#).is_err());
+        assert!(write_atomic_retrying(&path, r#"{"b":2}"#).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), r#"{"a":1}"#);
+
+        fs::remove_dir_all(&tmp).unwrap();
+        write_atomic_retrying(&path, r#"{"b":2}"#).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), r#"{"b":2}"#);
+    }
+}
@@ -0,0 +1,186 @@
+//! Slow / standard / fast gas price suggestions and legacy vs EIP-1559 pricing
+
+use crate::utils::units::{Denomination, from_wei};
+use alloy::network::TransactionBuilder;
+use alloy::primitives::U256;
+use alloy::rpc::types::TransactionRequest;
+use std::fmt;
+
+/// Reward percentiles requested from `eth_feeHistory`, one per preset
+pub const FEE_PERCENTILES: [f64; 3] = [10.0, 50.0, 90.0];
+
+/// Gas prices in wei for the three speeds offered when reviewing a transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasPresets {
+    pub slow: u128,
+    pub standard: u128,
+    pub fast: u128,
+}
+
+impl GasPresets {
+    /// Presets from `eth_feeHistory`: the latest base fee plus the median of each
+    /// reward percentile over the sampled blocks. `None` when the node reports no
+    /// fees at all (RSK nodes without EIP-1559 return zeros).
+    pub fn from_fee_history(latest_base_fee: u128, rewards: &[Vec<u128>]) -> Option<Self> {
+        let percentile = |index: usize| {
+            let mut values: Vec<u128> =
+                rewards.iter().filter_map(|block| block.get(index).copied()).collect();
+            values.sort_unstable();
+            values.get(values.len() / 2).copied().unwrap_or(0)
+        };
+        let presets = Self {
+            slow: latest_base_fee + percentile(0),
+            standard: latest_base_fee + percentile(1),
+            fast: latest_base_fee + percentile(2),
+        };
+        (presets.fast > 0).then_some(presets)
+    }
+
+    /// Presets around the node's current gas price, used when fee history is empty
+    pub fn from_gas_price(gas_price: u128) -> Self {
+        Self {
+            slow: gas_price,
+            standard: gas_price + gas_price / 10,
+            fast: gas_price + gas_price / 4,
+        }
+    }
+
+    /// Raises every preset to at least `minimum` (the node's minimum gas price)
+    pub fn at_least(self, minimum: u128) -> Self {
+        Self {
+            slow: self.slow.max(minimum),
+            standard: self.standard.max(minimum),
+            fast: self.fast.max(minimum),
+        }
+    }
+}
+
+/// Priority tip offered by default for EIP-1559 transactions, in gwei
+pub const DEFAULT_PRIORITY_TIP_GWEI: &str = "1";
+
+/// How the fees of a transaction are set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeMode {
+    Legacy {
+        gas_price: u128,
+    },
+    Eip1559 {
+        max_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+    },
+}
+
+impl FeeMode {
+    /// EIP-1559 fees for a `tip` on top of `base_fee`. The max fee leaves room for
+    /// the base fee to double and never drops below `minimum` (the node's minimum
+    /// gas price).
+    pub fn eip1559(base_fee: u128, tip: u128, minimum: u128) -> Self {
+        let max_fee_per_gas = base_fee.saturating_mul(2).saturating_add(tip).max(minimum);
+        Self::Eip1559 {
+            max_fee_per_gas,
+            max_priority_fee_per_gas: tip.min(max_fee_per_gas),
+        }
+    }
+
+    /// The most a unit of gas can cost, for worst-case fee estimates
+    pub fn max_price_per_gas(&self) -> u128 {
+        match self {
+            Self::Legacy { gas_price } => *gas_price,
+            Self::Eip1559 {
+                max_fee_per_gas, ..
+            } => *max_fee_per_gas,
+        }
+    }
+
+    /// Sets the fee fields of `tx`
+    pub fn apply(&self, tx: TransactionRequest) -> TransactionRequest {
+        match *self {
+            Self::Legacy { gas_price } => tx.with_gas_price(gas_price),
+            Self::Eip1559 {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => tx
+                .with_max_fee_per_gas(max_fee_per_gas)
+                .with_max_priority_fee_per_gas(max_priority_fee_per_gas),
+        }
+    }
+}
+
+impl fmt::Display for FeeMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gwei = |wei: u128| from_wei(U256::from(wei), Denomination::Gwei);
+        match *self {
+            Self::Legacy { gas_price } => write!(f, "legacy, gas price {} gwei", gwei(gas_price)),
+            Self::Eip1559 {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => write!(
+                f,
+                "EIP-1559, max fee {} gwei, priority tip {} gwei",
+                gwei(max_fee_per_gas),
+                gwei(max_priority_fee_per_gas)
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eip1559_fee_mode() {
+        let mode = FeeMode::eip1559(50, 10, 0);
+        assert_eq!(
+            mode,
+            FeeMode::Eip1559 {
+                max_fee_per_gas: 110,
+                max_priority_fee_per_gas: 10
+            }
+        );
+        assert_eq!(mode.max_price_per_gas(), 110);
+        assert_eq!(FeeMode::eip1559(50, 10, 200).max_price_per_gas(), 200);
+
+        let tx = mode.apply(TransactionRequest::default());
+        assert_eq!(tx.max_fee_per_gas, Some(110));
+        assert_eq!(tx.max_priority_fee_per_gas, Some(10));
+        assert_eq!(tx.gas_price, None);
+        assert!(mode.to_string().starts_with("EIP-1559"));
+    }
+
+    #[test]
+    fn test_legacy_fee_mode() {
+        let mode = FeeMode::Legacy { gas_price: 60 };
+        assert_eq!(mode.max_price_per_gas(), 60);
+        assert_eq!(mode.apply(TransactionRequest::default()).gas_price, Some(60));
+        assert!(mode.to_string().starts_with("legacy"));
+    }
+
+    #[test]
+    fn test_from_fee_history_uses_median_rewards() {
+        let rewards = vec![vec![1, 5, 9], vec![3, 6, 20], vec![2, 4, 10]];
+        assert_eq!(
+            GasPresets::from_fee_history(100, &rewards),
+            Some(GasPresets {
+                slow: 102,
+                standard: 105,
+                fast: 110
+            })
+        );
+        assert_eq!(GasPresets::from_fee_history(0, &[vec![0, 0, 0]]), None);
+        assert_eq!(GasPresets::from_fee_history(0, &[]), None);
+    }
+
+    #[test]
+    fn test_from_gas_price_and_minimum() {
+        let presets = GasPresets::from_gas_price(60_000_000);
+        assert_eq!(presets.slow, 60_000_000);
+        assert_eq!(presets.standard, 66_000_000);
+        assert_eq!(presets.fast, 75_000_000);
+        let raised = presets.at_least(70_000_000);
+        assert_eq!(
+            (raised.slow, raised.standard, raised.fast),
+            (70_000_000, 70_000_000, 75_000_000)
+        );
+    }
+}
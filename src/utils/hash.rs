@@ -0,0 +1,55 @@
+use alloy::primitives::keccak256;
+use anyhow::{Result, anyhow};
+
+/// 0x-prefixed keccak256 digest of `data`
+pub fn keccak256_hex(data: &[u8]) -> String {
+    format!("0x{}", hex::encode(keccak256(data)))
+}
+
+/// Decodes 0x-prefixed (or bare) hex input into bytes
+pub fn decode_hex_input(input: &str) -> Result<Vec<u8>> {
+    let input = input.trim();
+    let digits = input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+        .unwrap_or(input);
+    hex::decode(digits).map_err(|e| anyhow!("Invalid hex input: {}", e))
+}
+
+/// Compares two digests, ignoring case and a 0x prefix
+pub fn digest_matches(digest: &str, expected: &str) -> bool {
+    let normalize = |d: &str| {
+        let d = d.trim().to_lowercase();
+        d.strip_prefix("0x").map(str::to_string).unwrap_or(d)
+    };
+    normalize(digest) == normalize(expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keccak256_known_vectors() {
+        assert_eq!(
+            keccak256_hex(b""),
+            "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        // Function selectors are the first four bytes
+        assert!(keccak256_hex(b"transfer(address,uint256)").starts_with("0xa9059cbb"));
+        assert!(keccak256_hex(b"approve(address,uint256)").starts_with("0x095ea7b3"));
+    }
+
+    #[test]
+    fn test_decode_hex_input() {
+        assert_eq!(decode_hex_input("0xdeadBEEF").unwrap(), vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(decode_hex_input("00ff").unwrap(), vec![0x00, 0xff]);
+        assert!(decode_hex_input("0xabc").is_err());
+    }
+
+    #[test]
+    fn test_digest_matches() {
+        assert!(digest_matches("0xABCDEF", "abcdef"));
+        assert!(!digest_matches("0xabcdef", "0xabcde0"));
+    }
+}
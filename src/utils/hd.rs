@@ -0,0 +1,68 @@
+//! BIP-39 seed phrases and BIP-44 account derivation for HD wallets
+
+use alloy::signers::local::coins_bip39::{English, Mnemonic};
+use alloy::signers::local::{MnemonicBuilder, PrivateKeySigner};
+use anyhow::{Result, anyhow};
+
+/// BIP-44 path of Rootstock accounts (coin type 137); the account index is appended
+pub const RSK_DERIVATION_PREFIX: &str = "m/44'/137'/0'/0";
+
+/// Seed phrase lengths BIP-39 allows
+pub const WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+/// Full derivation path of account `index`, e.g. `m/44'/137'/0'/0/0`
+pub fn derivation_path(index: u32) -> String {
+    format!("{}/{}", RSK_DERIVATION_PREFIX, index)
+}
+
+/// A new random English seed phrase of `word_count` words
+pub fn generate_mnemonic(word_count: usize) -> Result<String> {
+    if !WORD_COUNTS.contains(&word_count) {
+        return Err(anyhow!(
+            "A seed phrase has 12, 15, 18, 21 or 24 words, not {}",
+            word_count
+        ));
+    }
+    let mnemonic = Mnemonic::<English>::new_with_count(&mut rand::thread_rng(), word_count)
+        .map_err(|e| anyhow!("Failed to generate a seed phrase: {}", e))?;
+    Ok(mnemonic.to_phrase())
+}
+
+/// The signer of account `index` on the Rootstock derivation path of `mnemonic`
+pub fn derive_account(mnemonic: &str, index: u32) -> Result<PrivateKeySigner> {
+    MnemonicBuilder::<English>::default()
+        .phrase(mnemonic.trim())
+        .derivation_path(derivation_path(index))
+        .map_err(|e| anyhow!("Invalid derivation index {}: {}", index, e))?
+        .build()
+        .map_err(|e| anyhow!("Invalid seed phrase: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHRASE: &str = "test test test test test test test test test test test junk";
+
+    #[test]
+    fn test_derivation_path() {
+        assert_eq!(derivation_path(0), "m/44'/137'/0'/0/0");
+        assert_eq!(derivation_path(7), "m/44'/137'/0'/0/7");
+    }
+
+    #[test]
+    fn test_derive_account_is_deterministic_per_index() {
+        let first = derive_account(PHRASE, 0).unwrap().address();
+        assert_eq!(derive_account(PHRASE, 0).unwrap().address(), first);
+        assert_ne!(derive_account(PHRASE, 1).unwrap().address(), first);
+        assert!(derive_account("not a seed phrase", 0).is_err());
+    }
+
+    #[test]
+    fn test_generate_mnemonic_word_counts() {
+        let phrase = generate_mnemonic(24).unwrap();
+        assert_eq!(phrase.split_whitespace().count(), 24);
+        assert!(derive_account(&phrase, 0).is_ok());
+        assert!(generate_mnemonic(13).is_err());
+    }
+}
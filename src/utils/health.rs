@@ -0,0 +1,111 @@
+//! Network health judged from block timestamps rather than a short live sample
+
+/// Block production as observed over the last few blocks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockTiming {
+    pub average_block_secs: f64,
+    /// How long ago the newest block was mined
+    pub latest_block_age_secs: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Health {
+    Healthy,
+    Slow,
+    Stalled,
+}
+
+/// Compares the observed timing with the block time expected on the network.
+/// Blocks are random, so only clearly long gaps count as slow or stalled.
+pub fn assess(timing: &BlockTiming, expected_block_secs: u64) -> Health {
+    let expected = expected_block_secs.max(1) as f64;
+    let age = timing.latest_block_age_secs as f64;
+    if age > expected * 10.0 {
+        Health::Stalled
+    } else if timing.average_block_secs > expected * 2.0 || age > expected * 4.0 {
+        Health::Slow
+    } else {
+        Health::Healthy
+    }
+}
+
+/// Average seconds per block between two block timestamps `blocks` apart
+pub fn average_block_time(earlier_timestamp: u64, latest_timestamp: u64, blocks: u64) -> f64 {
+    latest_timestamp.saturating_sub(earlier_timestamp) as f64 / blocks.max(1) as f64
+}
+
+/// Blocks to average over when the chain head is `latest` and `samples` are wanted:
+/// the earlier block and the number of blocks between it and the head.
+/// `None` while the chain has no block before the head to measure against.
+pub fn sample_window(latest: u64, samples: u64) -> Option<(u64, u64)> {
+    let samples = samples.min(latest);
+    (samples > 0).then(|| (latest.saturating_sub(samples), samples))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Congestion {
+    Low,
+    Normal,
+    High,
+}
+
+/// Compares the current base fee with the average over recent blocks
+pub fn congestion(base_fee: u128, recent: &[u128]) -> Congestion {
+    if recent.is_empty() {
+        return Congestion::Normal;
+    }
+    let baseline = recent.iter().sum::<u128>() as f64 / recent.len() as f64;
+    let base_fee = base_fee as f64;
+    if base_fee > baseline * 1.25 {
+        Congestion::High
+    } else if base_fee < baseline * 0.9 {
+        Congestion::Low
+    } else {
+        Congestion::Normal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(average_block_secs: f64, latest_block_age_secs: u64) -> BlockTiming {
+        BlockTiming {
+            average_block_secs,
+            latest_block_age_secs,
+        }
+    }
+
+    #[test]
+    fn test_assess_against_expected_block_time() {
+        // A 45s gap is normal on a 30s network, not "idle"
+        assert_eq!(assess(&timing(31.0, 45), 30), Health::Healthy);
+        assert_eq!(assess(&timing(75.0, 10), 30), Health::Slow);
+        assert_eq!(assess(&timing(30.0, 150), 30), Health::Slow);
+        assert_eq!(assess(&timing(30.0, 400), 30), Health::Stalled);
+    }
+
+    #[test]
+    fn test_average_block_time() {
+        assert_eq!(average_block_time(1_000, 1_600, 20), 30.0);
+        assert_eq!(average_block_time(1_600, 1_000, 20), 0.0);
+        assert_eq!(average_block_time(1_000, 1_030, 0), 30.0);
+    }
+
+    #[test]
+    fn test_sample_window() {
+        assert_eq!(sample_window(0, 20), None);
+        assert_eq!(sample_window(1, 20), Some((0, 1)));
+        assert_eq!(sample_window(100, 20), Some((80, 20)));
+        assert_eq!(sample_window(100, 0), None);
+    }
+
+    #[test]
+    fn test_congestion_against_recent_base_fees() {
+        let recent = [100, 100, 100, 100];
+        assert_eq!(congestion(100, &recent), Congestion::Normal);
+        assert_eq!(congestion(130, &recent), Congestion::High);
+        assert_eq!(congestion(80, &recent), Congestion::Low);
+        assert_eq!(congestion(500, &[]), Congestion::Normal);
+    }
+}
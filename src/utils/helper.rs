@@ -39,7 +39,7 @@ pub struct Helper;
 
 impl Helper {
     pub async fn init_eth_client(network: &str) -> Result<(Config, EthClient)> {
-        let network_enum = Network::from_str(network).unwrap_or(Network::Mainnet);
+        let network_enum = network.parse().unwrap_or(Network::Mainnet);
 
         // Load configuration to get API keys
         let config_manager = ConfigManager::new()?;
@@ -56,8 +56,10 @@ impl Helper {
         let mut net_cfg = network_enum.get_config();
         net_cfg.rpc_url = rpc_url.clone();
 
-        let mut config = Config::default();
-        config.network = net_cfg.clone();
+        let config = Config {
+            network: net_cfg.clone(),
+            ..Default::default()
+        };
 
         // Log which RPC endpoint is being used
         let rpc_type = if rsk_api_key.is_some() {
@@ -71,14 +73,22 @@ impl Helper {
         println!(
             "[rootstock-wallet] Connected to {} at {} ({})",
             config.network.name,
-            config.network.rpc_url,
+            Self::mask_url(&config.network.rpc_url, &[rsk_api_key, alchemy_api_key]),
             rpc_type.dimmed()
         );
 
-        let eth_client = EthClient::new(&config, None).await?;
+        let eth_client = EthClient::new(&config).await?;
         Ok((config, eth_client))
     }
 
+    /// Replaces any API key embedded in `url` with `****` so it can be printed
+    pub fn mask_url(url: &str, keys: &[Option<&str>]) -> String {
+        keys.iter()
+            .flatten()
+            .filter(|key| !key.is_empty())
+            .fold(url.to_string(), |url, key| url.replace(key, "****"))
+    }
+
     pub fn format_network(network: &str) -> String {
         match network.to_lowercase().as_str() {
             "mainnet" => format!("{}", "Mainnet".yellow().bold()),
@@ -95,7 +105,7 @@ impl Helper {
         if as_tokens {
             Ok(format!(
                 "{} RBTC",
-                alloy::primitives::utils::format_units(balance, 18)?
+                crate::utils::units::format_amount(alloy::primitives::U256::from(balance), 18)
             ))
         } else {
             Ok(format!("{} wei", balance))
@@ -111,3 +121,21 @@ impl Helper {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_url_hides_keys() {
+        let url = "https://rootstock-mainnet.g.alchemy.com/v2/abc123secret";
+        assert_eq!(
+            Helper::mask_url(url, &[None, Some("abc123secret")]),
+            "https://rootstock-mainnet.g.alchemy.com/v2/****"
+        );
+        assert_eq!(
+            Helper::mask_url("https://public-node.rsk.co", &[Some(""), None]),
+            "https://public-node.rsk.co"
+        );
+    }
+}
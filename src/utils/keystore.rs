@@ -0,0 +1,87 @@
+//! Web3 Secret Storage (v3 keystore) files, the format geth and MetaMask read and write
+
+use alloy::signers::local::PrivateKeySigner;
+use anyhow::{Result, anyhow};
+use std::path::Path;
+
+/// Encrypts `private_key` (32 bytes) with `password` into a v3 keystore at `path`
+/// (scrypt key derivation, aes-128-ctr, keccak MAC)
+pub fn write_keystore(private_key: &[u8], path: &Path, password: &str) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow!("Invalid keystore file name: {}", path.display()))?;
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    eth_keystore::encrypt_key(
+        dir,
+        &mut rand::thread_rng(),
+        private_key,
+        password,
+        Some(file_name),
+    )
+    .map_err(|e| anyhow!("Failed to write keystore {}: {}", path.display(), e))?;
+    Ok(())
+}
+
+/// Decrypts the v3 keystore at `path` with `password`
+pub fn read_keystore(path: &Path, password: &str) -> Result<PrivateKeySigner> {
+    if !path.exists() {
+        return Err(anyhow!("Keystore file not found: {}", path.display()));
+    }
+    let key = eth_keystore::decrypt_key(path, password)
+        .map_err(|e| anyhow!("Failed to decrypt keystore: {}", e))?;
+    PrivateKeySigner::from_slice(&key)
+        .map_err(|e| anyhow!("Keystore does not contain a valid private key: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// The PBKDF2 test vector from the Web3 Secret Storage definition; its scrypt
+    /// vector uses r = 1, which the scrypt crate rejects for n = 2^18
+    const SPEC_KEYSTORE: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+            "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+            "kdf": "pbkdf2",
+            "kdfparams": {
+                "c": 262144,
+                "dklen": 32,
+                "prf": "hmac-sha256",
+                "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+            },
+            "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3
+    }"#;
+    const SPEC_PASSWORD: &str = "testpassword";
+    const SPEC_KEY: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+
+    #[test]
+    fn test_reads_spec_vector_and_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let spec_path = dir.path().join("spec.json");
+        fs::write(&spec_path, SPEC_KEYSTORE).unwrap();
+
+        let signer = read_keystore(&spec_path, SPEC_PASSWORD).unwrap();
+        assert_eq!(hex::encode(signer.to_bytes()), SPEC_KEY);
+        assert!(read_keystore(&spec_path, "wrong password").is_err());
+
+        let exported = dir.path().join("exported.json");
+        write_keystore(signer.to_bytes().as_slice(), &exported, "another password").unwrap();
+        let reread = read_keystore(&exported, "another password").unwrap();
+        assert_eq!(reread.address(), signer.address());
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&exported).unwrap()).unwrap();
+        assert_eq!(json["version"], 3);
+        assert_eq!(json["crypto"]["kdf"], "scrypt");
+        assert_eq!(json["crypto"]["cipher"], "aes-128-ctr");
+    }
+}
@@ -0,0 +1,57 @@
+//! EIP-191 `personal_sign` signatures for proving ownership of an address off-chain
+
+use alloy::primitives::{Address, PrimitiveSignature};
+use alloy::signers::SignerSync;
+use alloy::signers::local::PrivateKeySigner;
+use anyhow::{Result, anyhow};
+
+/// Signs `message` the way `personal_sign` does and returns the 65-byte r‖s‖v
+/// signature as 0x-prefixed hex
+pub fn sign_message(signer: &PrivateKeySigner, message: &str) -> Result<String> {
+    let signature = signer
+        .sign_message_sync(message.as_bytes())
+        .map_err(|e| anyhow!("Failed to sign message: {}", e))?;
+    Ok(format!("0x{}", hex::encode(signature.as_bytes())))
+}
+
+/// Address whose key produced `signature` (hex, with or without 0x) over `message`
+pub fn recover_signer(message: &str, signature: &str) -> Result<Address> {
+    let bytes = hex::decode(signature.trim().trim_start_matches("0x"))
+        .map_err(|_| anyhow!("Signature is not valid hex"))?;
+    if bytes.len() != 65 {
+        return Err(anyhow!("Signature must be 65 bytes, got {}", bytes.len()));
+    }
+    let signature = PrimitiveSignature::try_from(bytes.as_slice())
+        .map_err(|e| anyhow!("Invalid signature: {}", e))?;
+    signature
+        .recover_address_from_msg(message.as_bytes())
+        .map_err(|e| anyhow!("Could not recover the signer: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[test]
+    fn test_sign_and_recover() {
+        let signer: PrivateKeySigner = KEY.parse().unwrap();
+        let signature = sign_message(&signer, "I own this address").unwrap();
+        assert_eq!(signature.len(), 2 + 130);
+        assert_eq!(
+            recover_signer("I own this address", &signature).unwrap(),
+            signer.address()
+        );
+        assert_ne!(
+            recover_signer("I own another address", &signature).unwrap(),
+            signer.address()
+        );
+    }
+
+    #[test]
+    fn test_recover_rejects_malformed_signatures() {
+        assert!(recover_signer("hello", "0x1234").is_err());
+        assert!(recover_signer("hello", "not hex").is_err());
+    }
+}
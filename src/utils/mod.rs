@@ -1,6 +1,38 @@
+pub mod access_list;
+pub mod activity;
+pub mod address;
 pub mod alchemy;
+pub mod bridge;
+pub mod bulk_journal;
+pub mod calldata;
+pub mod cancel;
+pub mod clock;
 pub mod constants;
+pub mod contacts_csv;
 pub mod eth;
+pub mod file_lock;
+pub mod gas;
+pub mod hash;
+pub mod health;
+pub mod hd;
 pub mod helper;
+pub mod keystore;
+pub mod message;
+pub mod multicall;
+pub mod network_guard;
+pub mod password;
+pub mod payout;
+pub mod pending;
+pub mod qr;
+pub mod rate_limit;
+pub mod receive_log;
+pub mod retry;
+pub mod session;
+pub mod signed_tx;
+pub mod sponsor;
+pub mod status_cache;
 pub mod table;
 pub mod terminal;
+pub mod token_metadata;
+pub mod units;
+pub mod vcard;
@@ -0,0 +1,129 @@
+//! Batched contract reads through Multicall3
+
+use crate::types::network::Network;
+use alloy::primitives::{Address, U256, address};
+use alloy::providers::RootProvider;
+use alloy::sol;
+use alloy::sol_types::SolCall;
+use alloy::transports::http::{Client, Http};
+
+/// Multicall3 is deployed at the same address on Rootstock mainnet and testnet
+pub const MULTICALL3: Address = address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    contract Multicall3 {
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+
+        struct Result {
+            bool success;
+            bytes returnData;
+        }
+
+        function aggregate3(Call3[] calldata calls) external payable returns (Result[] memory returnData);
+    }
+
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    contract TokenBalance {
+        function balanceOf(address account) external view returns (uint256);
+    }
+}
+
+/// Multicall3 address for `network`; a local regtest node has none unless deployed by hand
+pub fn multicall3_address(network: Network) -> Option<Address> {
+    (network != Network::Regtest).then_some(MULTICALL3)
+}
+
+/// Balances of `owner` for each token, in order; `None` where a token could not be read.
+/// All balances come from one Multicall3 call, falling back to one call per token
+/// when Multicall3 is missing or the batch fails.
+pub async fn token_balances(
+    provider: &RootProvider<Http<Client>>,
+    network: Network,
+    owner: Address,
+    tokens: &[Address],
+) -> Vec<Option<U256>> {
+    if tokens.is_empty() {
+        return Vec::new();
+    }
+
+    if let Some(multicall) = multicall3_address(network) {
+        let call_data = TokenBalance::balanceOfCall { account: owner }.abi_encode();
+        let calls: Vec<Multicall3::Call3> = tokens
+            .iter()
+            .map(|token| Multicall3::Call3 {
+                target: *token,
+                allowFailure: true,
+                callData: call_data.clone().into(),
+            })
+            .collect();
+        match Multicall3::new(multicall, provider).aggregate3(calls).call().await {
+            Ok(response) if response.returnData.len() == tokens.len() => {
+                return response
+                    .returnData
+                    .iter()
+                    .map(|result| decode_uint(result.success, &result.returnData))
+                    .collect();
+            }
+            Ok(_) => eprintln!(
+                "⚠️  Multicall3 returned an unexpected result; reading tokens one by one"
+            ),
+            Err(e) => eprintln!("⚠️  Multicall3 unavailable ({}); reading tokens one by one", e),
+        }
+    }
+
+    // One call per token, all in flight at once
+    let mut calls = tokio::task::JoinSet::new();
+    for (index, token) in tokens.iter().copied().enumerate() {
+        let provider = provider.clone();
+        calls.spawn(async move {
+            let balance = TokenBalance::new(token, &provider)
+                .balanceOf(owner)
+                .call()
+                .await
+                .map(|r| r._0)
+                .ok();
+            (index, balance)
+        });
+    }
+    let mut balances = vec![None; tokens.len()];
+    while let Some(result) = calls.join_next().await {
+        if let Ok((index, balance)) = result {
+            balances[index] = balance;
+        }
+    }
+    balances
+}
+
+/// Reads a `uint256` return value; `None` for reverted or malformed results
+fn decode_uint(success: bool, data: &[u8]) -> Option<U256> {
+    (success && data.len() >= 32).then(|| U256::from_be_slice(&data[..32]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_uint() {
+        let mut word = [0u8; 32];
+        word[31] = 42;
+        assert_eq!(decode_uint(true, &word), Some(U256::from(42u64)));
+        assert_eq!(decode_uint(false, &word), None);
+        assert_eq!(decode_uint(true, &word[..16]), None);
+        assert_eq!(decode_uint(true, &[]), None);
+    }
+
+    #[test]
+    fn test_multicall3_address_per_network() {
+        assert_eq!(multicall3_address(Network::Mainnet), Some(MULTICALL3));
+        assert_eq!(multicall3_address(Network::Testnet), Some(MULTICALL3));
+        assert_eq!(multicall3_address(Network::Regtest), None);
+    }
+}
@@ -0,0 +1,65 @@
+//! Making the active network obvious before anything is sent
+
+use crate::types::network::Network;
+use alloy::primitives::U256;
+
+/// Word the user types to acknowledge a large mainnet transfer
+pub const MAINNET_ACKNOWLEDGEMENT: &str = "MAINNET";
+
+/// Line shown in every send confirmation naming the network funds will move on
+pub fn banner(network: Network) -> String {
+    if network.is_mainnet() {
+        format!("⚠  You are on {} — this sends real RBTC", network.to_string().to_uppercase())
+    } else {
+        format!("ℹ  You are on {} (test funds)", network.to_string().to_uppercase())
+    }
+}
+
+/// Whether sending `rbtc_amount` (in wei) on `network` needs the user to type
+/// `MAINNET_ACKNOWLEDGEMENT`: mainnet transfers above the threshold, when one is set
+pub fn requires_acknowledgement(
+    network: Network,
+    rbtc_amount: U256,
+    threshold: Option<U256>,
+) -> bool {
+    network.is_mainnet() && threshold.is_some_and(|threshold| rbtc_amount > threshold)
+}
+
+/// Whether `input` acknowledges a large mainnet transfer; case and whitespace are ignored
+pub fn is_acknowledged(input: &str) -> bool {
+    input.trim().eq_ignore_ascii_case(MAINNET_ACKNOWLEDGEMENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ONE_RBTC: u128 = 1_000_000_000_000_000_000;
+
+    #[test]
+    fn test_requires_acknowledgement_only_above_mainnet_threshold() {
+        let threshold = Some(U256::from(ONE_RBTC));
+        let above = U256::from(ONE_RBTC + 1);
+        assert!(requires_acknowledgement(Network::Mainnet, above, threshold));
+        assert!(requires_acknowledgement(Network::RootStockMainnet, above, threshold));
+        assert!(!requires_acknowledgement(Network::Mainnet, U256::from(ONE_RBTC), threshold));
+        assert!(!requires_acknowledgement(Network::Testnet, above, threshold));
+        assert!(!requires_acknowledgement(Network::Mainnet, above, None));
+    }
+
+    #[test]
+    fn test_banner_emphasizes_mainnet() {
+        assert!(banner(Network::Mainnet).contains("MAINNET"));
+        assert!(banner(Network::Mainnet).contains("real RBTC"));
+        assert!(banner(Network::Testnet).contains("TESTNET"));
+        assert!(!banner(Network::Testnet).contains("real RBTC"));
+    }
+
+    #[test]
+    fn test_is_acknowledged() {
+        assert!(is_acknowledged("MAINNET"));
+        assert!(is_acknowledged(" mainnet\n"));
+        assert!(!is_acknowledged("yes"));
+        assert!(!is_acknowledged(""));
+    }
+}
@@ -0,0 +1,93 @@
+//! Strength policy for the passwords that encrypt wallet keys
+
+use std::fmt;
+
+/// Shortest password accepted for a new or imported wallet
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+/// Passphrases at least this long are strong whatever characters they use
+pub const PASSPHRASE_LENGTH: usize = 20;
+
+/// How hard a password is to guess; only `Weak` ones are rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PasswordStrength {
+    Weak,
+    Fair,
+    Strong,
+}
+
+impl PasswordStrength {
+    pub fn is_acceptable(self) -> bool {
+        self != PasswordStrength::Weak
+    }
+}
+
+impl fmt::Display for PasswordStrength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PasswordStrength::Weak => write!(f, "weak"),
+            PasswordStrength::Fair => write!(f, "fair"),
+            PasswordStrength::Strong => write!(f, "strong"),
+        }
+    }
+}
+
+/// What a password needs to pass the policy
+pub const PASSWORD_POLICY: &str =
+    "Use at least 8 characters mixing lowercase, uppercase, digits or symbols, or a passphrase of 20+ characters";
+
+/// Rates a password by its length and how many character classes (lowercase,
+/// uppercase, digits, symbols) it mixes
+pub fn check_password_strength(password: &str) -> PasswordStrength {
+    let length = password.chars().count();
+    let classes = [
+        password.chars().any(|c| c.is_lowercase()),
+        password.chars().any(|c| c.is_uppercase()),
+        password.chars().any(|c| c.is_ascii_digit()),
+        password.chars().any(|c| !c.is_alphanumeric()),
+    ]
+    .into_iter()
+    .filter(|present| *present)
+    .count();
+
+    if length < MIN_PASSWORD_LENGTH {
+        PasswordStrength::Weak
+    } else if length >= PASSPHRASE_LENGTH || (classes >= 3 && length >= 12) {
+        PasswordStrength::Strong
+    } else if classes >= 2 {
+        PasswordStrength::Fair
+    } else {
+        PasswordStrength::Weak
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_and_single_class_passwords_are_weak() {
+        assert_eq!(check_password_strength(""), PasswordStrength::Weak);
+        assert_eq!(check_password_strength("Ab1!"), PasswordStrength::Weak);
+        assert_eq!(check_password_strength("abcdefgh"), PasswordStrength::Weak);
+        assert_eq!(check_password_strength("12345678901"), PasswordStrength::Weak);
+    }
+
+    #[test]
+    fn test_length_boundary() {
+        assert_eq!(check_password_strength("abcdef1"), PasswordStrength::Weak);
+        assert_eq!(check_password_strength("abcdefg1"), PasswordStrength::Fair);
+        assert_eq!(check_password_strength("Abcdefgh12!x"), PasswordStrength::Strong);
+    }
+
+    #[test]
+    fn test_long_passphrase_is_strong() {
+        assert_eq!(
+            check_password_strength("correct horse battery staple"),
+            PasswordStrength::Strong
+        );
+        assert_eq!(check_password_strength("abcdefghijklmnopqrst"), PasswordStrength::Strong);
+        assert!(!PasswordStrength::Weak.is_acceptable());
+        assert!(PasswordStrength::Fair.is_acceptable());
+    }
+}
@@ -0,0 +1,178 @@
+//! Recipient lists (payouts) read from files or piped stdin
+//!
+//! A list is either a JSON array of `{"to": ..., "value": ...}` objects or
+//! CSV with one `address,amount` pair per line. Addresses and amounts are
+//! returned as written; each flow validates them the same way as typed input.
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::io::{IsTerminal, Read};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayoutEntry {
+    #[serde(alias = "address", alias = "recipient")]
+    pub to: String,
+    #[serde(alias = "amount")]
+    pub value: String,
+}
+
+/// Parses a JSON or CSV recipient list
+pub fn parse_payouts(content: &str) -> Result<Vec<PayoutEntry>> {
+    Ok(parse_payout_rows(content)?
+        .into_iter()
+        .map(|(_, entry)| entry)
+        .collect())
+}
+
+/// Like [`parse_payouts`], with each entry's row number: its line in a CSV file,
+/// its position (from 1) in a JSON array
+pub fn parse_payout_rows(content: &str) -> Result<Vec<(usize, PayoutEntry)>> {
+    if content.trim().starts_with('[') {
+        let entries: Vec<PayoutEntry> = serde_json::from_str(content.trim())
+            .map_err(|e| anyhow!("Failed to parse JSON: {}", e))?;
+        return Ok(entries.into_iter().enumerate().map(|(i, entry)| (i + 1, entry)).collect());
+    }
+
+    // Read line by line so every entry keeps its line number, comments and blank
+    // lines included
+    let mut entries = Vec::new();
+    let mut first = true;
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let record = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .trim(csv::Trim::All)
+            .flexible(true)
+            .from_reader(line.as_bytes())
+            .records()
+            .next()
+            .transpose()
+            .map_err(|e| anyhow!("Failed to parse CSV: {}", e))?;
+        let Some(record) = record else { continue };
+        let (Some(to), Some(value)) = (record.get(0), record.get(1)) else {
+            return Err(anyhow!("Line {}: expected `address,amount`", index + 1));
+        };
+        // Optional header row
+        if std::mem::take(&mut first) && !to.contains(|c: char| c.is_ascii_digit()) {
+            continue;
+        }
+        entries.push((
+            index + 1,
+            PayoutEntry {
+                to: to.to_string(),
+                value: value.to_string(),
+            },
+        ));
+    }
+    Ok(entries)
+}
+
+/// A recipient-list row that failed validation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowError {
+    pub row: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for RowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "row {}: {}", self.row, self.message)
+    }
+}
+
+/// Runs `check` on every row instead of stopping at the first bad one, returning
+/// what the valid rows turned into and an error for each invalid row
+pub fn validate_rows<T>(
+    rows: &[(usize, PayoutEntry)],
+    mut check: impl FnMut(&PayoutEntry) -> Result<T>,
+) -> (Vec<T>, Vec<RowError>) {
+    let mut valid = Vec::new();
+    let mut errors = Vec::new();
+    for (row, entry) in rows {
+        match check(entry) {
+            Ok(value) => valid.push(value),
+            Err(e) => errors.push(RowError {
+                row: *row,
+                message: e.to_string(),
+            }),
+        }
+    }
+    (valid, errors)
+}
+
+/// Everything piped into stdin, or `None` when stdin is a terminal or empty.
+/// Prompts keep working afterwards since they read from the terminal itself.
+pub fn read_piped_stdin() -> Result<Option<String>> {
+    let mut stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        return Ok(None);
+    }
+    let mut content = String::new();
+    stdin.read_to_string(&mut content)?;
+    Ok((!content.trim().is_empty()).then_some(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDR: &str = "0x1234567890123456789012345678901234567890";
+
+    #[test]
+    fn test_parses_json_with_aliases() {
+        let json = format!(r#"[{{"to":"{ADDR}","value":"1.5"}},{{"address":"{ADDR}","amount":"2"}}]"#);
+        let entries = parse_payouts(&json).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].value, "2");
+    }
+
+    #[test]
+    fn test_parses_csv_skipping_header_and_comments() {
+        let csv = format!("address,amount\n# payroll\n{ADDR}, 0.25\n");
+        let entries = parse_payouts(&csv).unwrap();
+        assert_eq!(
+            entries,
+            vec![PayoutEntry {
+                to: ADDR.into(),
+                value: "0.25".into()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_numbers_rows_by_csv_line_and_json_position() {
+        let csv = format!("address,amount\n# payroll\n{ADDR},1\n\n{ADDR},2\n");
+        let rows: Vec<usize> = parse_payout_rows(&csv).unwrap().into_iter().map(|(row, _)| row).collect();
+        assert_eq!(rows, vec![3, 5]);
+
+        let json = format!(r#"[{{"to":"{ADDR}","value":"1"}},{{"to":"{ADDR}","value":"2"}}]"#);
+        let rows: Vec<usize> = parse_payout_rows(&json).unwrap().into_iter().map(|(row, _)| row).collect();
+        assert_eq!(rows, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_validate_rows_reports_every_bad_row() {
+        let csv = format!("{ADDR},1\n0xbad,2\n{ADDR},abc\n{ADDR},3\n");
+        let rows = parse_payout_rows(&csv).unwrap();
+        let (valid, errors) = validate_rows(&rows, |entry| {
+            if entry.to.len() != 42 {
+                return Err(anyhow!("invalid address {}", entry.to));
+            }
+            entry
+                .value
+                .parse::<u32>()
+                .map_err(|_| anyhow!("invalid amount '{}'", entry.value))
+        });
+        assert_eq!(valid, vec![1, 3]);
+        let report: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        assert_eq!(report, vec!["row 2: invalid address 0xbad", "row 3: invalid amount 'abc'"]);
+    }
+
+    #[test]
+    fn test_rejects_csv_without_amount() {
+        assert!(parse_payouts(ADDR).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use crate::utils::{constants, file_lock};
+use alloy::primitives::{Address, B256, U256};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// A transaction sent by this wallet that has not been seen as mined yet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingTransaction {
+    pub hash: B256,
+    pub from: Address,
+    pub network: String,
+    pub nonce: u64,
+    /// `None` for RBTC transfers
+    pub token: Option<Address>,
+    /// Amount sent, in RBTC wei or token base units
+    pub value: U256,
+    /// Upper bound of the fee in wei (gas limit × max fee per gas)
+    pub max_fee: U256,
+    pub sent_at: String,
+}
+
+/// Transactions sent from this machine, stored in `pending_transactions.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PendingStore {
+    pub transactions: Vec<PendingTransaction>,
+}
+
+impl PendingStore {
+    pub fn load() -> Self {
+        fs::read_to_string(constants::pending_transactions_path())
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn record(tx: PendingTransaction) -> Result<()> {
+        file_lock::update_json(
+            &constants::pending_transactions_path(),
+            |store: &mut PendingStore| {
+                store.transactions.retain(|t| t.hash != tx.hash);
+                store.transactions.push(tx);
+                Ok(())
+            },
+        )
+    }
+
+    /// Drops transactions of `from` on `network` whose hash is in `done`
+    /// (mined, replaced or dropped) and returns the ones still pending
+    pub fn prune(network: &str, from: Address, done: &[B256]) -> Result<Vec<PendingTransaction>> {
+        file_lock::update_json(
+            &constants::pending_transactions_path(),
+            |store: &mut PendingStore| {
+                store.transactions.retain(|t| !done.contains(&t.hash));
+                Ok(store
+                    .transactions
+                    .iter()
+                    .filter(|t| t.network == network && t.from == from)
+                    .cloned()
+                    .collect())
+            },
+        )
+    }
+}
+
+/// Nonce for the next transaction from an address: the node's pending count, or
+/// one past the highest nonce still in flight from this machine if that is ahead
+/// (public nodes often lag on transactions sent moments earlier)
+pub fn next_nonce(node_nonce: u64, pending: &[PendingTransaction]) -> u64 {
+    pending
+        .iter()
+        .map(|tx| tx.nonce + 1)
+        .fold(node_nonce, u64::max)
+}
+
+/// How much of the confirmed balance of `token` (`None` for RBTC) is already
+/// committed by pending transactions. RBTC also covers the fees of token transfers.
+pub fn pending_outflow(pending: &[PendingTransaction], token: Option<Address>) -> U256 {
+    pending.iter().fold(U256::ZERO, |total, tx| {
+        let value = if tx.token == token { tx.value } else { U256::ZERO };
+        let fee = if token.is_none() { tx.max_fee } else { U256::ZERO };
+        total.saturating_add(value).saturating_add(fee)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(token: Option<Address>, value: u64, max_fee: u64) -> PendingTransaction {
+        PendingTransaction {
+            hash: B256::repeat_byte(value as u8),
+            from: Address::ZERO,
+            network: "testnet".into(),
+            nonce: value,
+            token,
+            value: U256::from(value),
+            max_fee: U256::from(max_fee),
+            sent_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_pending_outflow_counts_fees_in_rbtc_only() {
+        let token = Address::repeat_byte(0x11);
+        let txs = vec![pending(None, 100, 5), pending(Some(token), 40, 7)];
+
+        assert_eq!(pending_outflow(&txs, None), U256::from(112));
+        assert_eq!(pending_outflow(&txs, Some(token)), U256::from(40));
+        assert_eq!(
+            pending_outflow(&txs, Some(Address::repeat_byte(0x22))),
+            U256::ZERO
+        );
+    }
+
+    #[test]
+    fn test_next_nonce_skips_in_flight_transactions() {
+        assert_eq!(next_nonce(4, &[]), 4);
+        let in_flight = vec![pending(None, 4, 0), pending(None, 5, 0)];
+        assert_eq!(next_nonce(4, &in_flight), 6);
+        assert_eq!(next_nonce(9, &in_flight), 9);
+    }
+}
@@ -1,4 +1,5 @@
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use std::path::Path;
 use qrcode::{QrCode, EcLevel};
 use qrcode::render::unicode::Dense1x2;
 use qrcode::render::unicode::Dense1x2::*;
@@ -18,6 +19,105 @@ pub fn generate_qr_code(text: &str) -> Result<String> {
     Ok(qr_string)
 }
 
+/// Saves a QR code for the given text as a PNG image
+pub fn generate_qr_png(text: &str, path: &Path) -> Result<()> {
+    let code = QrCode::with_error_correction_level(text, EcLevel::M)?;
+    let image = code.render::<image::Luma<u8>>().build();
+    image
+        .save(path)
+        .map_err(|e| anyhow!("Failed to save QR code to {}: {}", path.display(), e))
+}
+
+/// Decodes the first QR code found in an image file and returns its text
+pub fn decode_qr_image(path: &Path) -> Result<String> {
+    let image = image::open(path)
+        .map_err(|e| anyhow!("Failed to open image {}: {}", path.display(), e))?
+        .to_luma8();
+    let mut prepared = rqrr::PreparedImage::prepare(image);
+    let grid = prepared
+        .detect_grids()
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("No QR code found in {}", path.display()))?;
+    let (_, content) = grid
+        .decode()
+        .map_err(|e| anyhow!("Failed to decode QR code: {}", e))?;
+    Ok(content)
+}
+
+/// Prefix of the frames a signed transaction is split into for QR transfer
+pub const TX_FRAME_PREFIX: &str = "RSKTX";
+
+/// Characters of payload per frame; larger QR codes don't fit a terminal
+pub const TX_FRAME_CHARS: usize = 300;
+
+/// Splits `data` into frames of at most `max_chars` payload characters, each
+/// reading `RSKTX:<index>/<count>:<chunk>` with 1-based indices, so they can be
+/// scanned in any order
+pub fn split_frames(data: &str, max_chars: usize) -> Vec<String> {
+    let chars: Vec<char> = data.chars().collect();
+    let chunks: Vec<String> = chars
+        .chunks(max_chars.max(1))
+        .map(|chunk| chunk.iter().collect())
+        .collect();
+    let count = chunks.len();
+    chunks
+        .iter()
+        .enumerate()
+        .map(|(i, chunk)| format!("{}:{}/{}:{}", TX_FRAME_PREFIX, i + 1, count, chunk))
+        .collect()
+}
+
+/// Reassembles frames made by `split_frames`, in any order; frames scanned twice
+/// are ignored. Fails when a frame is malformed or missing.
+pub fn join_frames(frames: &[String]) -> Result<String> {
+    let mut count = None;
+    let mut chunks: Vec<Option<String>> = Vec::new();
+    for frame in frames {
+        let frame = frame.trim();
+        let mut parts = frame.splitn(3, ':');
+        let (Some(TX_FRAME_PREFIX), Some(position), Some(chunk)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(anyhow!("Not a transaction QR frame: {}", frame));
+        };
+        let (index, total) = position
+            .split_once('/')
+            .and_then(|(i, n)| Some((i.parse::<usize>().ok()?, n.parse::<usize>().ok()?)))
+            .filter(|(i, n)| *i >= 1 && i <= n)
+            .ok_or_else(|| anyhow!("Invalid frame position '{}'", position))?;
+        match count {
+            None => {
+                count = Some(total);
+                chunks = vec![None; total];
+            }
+            Some(n) if n != total => {
+                return Err(anyhow!(
+                    "Frames from different transactions ({} and {} frames)",
+                    n,
+                    total
+                ));
+            }
+            Some(_) => {}
+        }
+        chunks[index - 1] = Some(chunk.to_string());
+    }
+
+    let missing: Vec<String> = chunks
+        .iter()
+        .enumerate()
+        .filter(|(_, chunk)| chunk.is_none())
+        .map(|(i, _)| (i + 1).to_string())
+        .collect();
+    if count.is_none() {
+        return Err(anyhow!("No frames were scanned"));
+    }
+    if !missing.is_empty() {
+        return Err(anyhow!("Missing frame(s) {} of {}", missing.join(", "), chunks.len()));
+    }
+    Ok(chunks.into_iter().flatten().collect())
+}
+
 /// Displays a QR code for a wallet address with a label
 pub fn display_address_qr(address: &str, label: &str) -> Result<()> {
     // Create the URI for the QR code (using the standard ethereum: URI scheme)
@@ -57,6 +157,29 @@ mod tests {
         assert!(!qr_code.is_empty());
     }
     
+    #[test]
+    fn test_frames_round_trip_in_any_order() {
+        let raw = format!("0x{}", "ab".repeat(400));
+        let mut frames = split_frames(&raw, TX_FRAME_CHARS);
+        assert_eq!(frames.len(), 3);
+        assert!(frames[0].starts_with("RSKTX:1/3:0x"));
+        frames.reverse();
+        frames.push(frames[0].clone());
+        assert_eq!(join_frames(&frames).unwrap(), raw);
+    }
+
+    #[test]
+    fn test_join_frames_reports_problems() {
+        let frames = split_frames("0x0123456789", 4);
+        assert_eq!(frames.len(), 3);
+        let err = join_frames(&[frames[0].clone(), frames[2].clone()]).unwrap_err();
+        assert_eq!(err.to_string(), "Missing frame(s) 2 of 3");
+        assert!(join_frames(&["hello".to_string()]).is_err());
+        assert!(join_frames(&["RSKTX:4/3:00".to_string()]).is_err());
+        assert!(join_frames(&[frames[0].clone(), "RSKTX:1/2:00".to_string()]).is_err());
+        assert!(join_frames(&[]).is_err());
+    }
+
     #[test]
     fn test_display_address_qr() {
         let address = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
@@ -0,0 +1,93 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Token bucket: holds up to `capacity` tokens and refills `rate` tokens per second
+#[derive(Debug)]
+struct Bucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl Bucket {
+    fn new(requests_per_second: u32, now: Instant) -> Self {
+        let rate = f64::from(requests_per_second.max(1));
+        Self {
+            rate,
+            capacity: rate,
+            tokens: rate,
+            last_refill: now,
+        }
+    }
+
+    /// Takes a token, or returns how long to wait until one is available
+    fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.rate))
+        }
+    }
+}
+
+/// Paces requests to stay under a requests-per-second budget.
+/// Safe to share between concurrent tasks; each `acquire` consumes one slot.
+#[derive(Debug)]
+pub struct RequestScheduler {
+    bucket: Mutex<Bucket>,
+}
+
+impl RequestScheduler {
+    pub fn new(requests_per_second: u32) -> Self {
+        Self {
+            bucket: Mutex::new(Bucket::new(requests_per_second, Instant::now())),
+        }
+    }
+
+    /// Waits until a request may be sent
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+                match bucket.try_take(Instant::now()) {
+                    Ok(()) => return,
+                    Err(wait) => wait,
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_allows_burst_then_waits() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(2, start);
+        assert!(bucket.try_take(start).is_ok());
+        assert!(bucket.try_take(start).is_ok());
+
+        let wait = bucket.try_take(start).unwrap_err();
+        assert!(wait > Duration::from_millis(400) && wait <= Duration::from_millis(500));
+
+        assert!(bucket.try_take(start + Duration::from_millis(500)).is_ok());
+    }
+
+    #[test]
+    fn test_bucket_does_not_refill_past_capacity() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(1, start);
+        let later = start + Duration::from_secs(10);
+        assert!(bucket.try_take(later).is_ok());
+        assert!(bucket.try_take(later).is_err());
+    }
+}
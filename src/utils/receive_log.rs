@@ -0,0 +1,87 @@
+//! Incoming payments seen per address, for the opt-in address reuse warning
+
+use crate::utils::token_metadata::network_key;
+use crate::utils::{constants, file_lock};
+use alloy::primitives::{Address, B256};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+/// An address that received this many payments is reported as reused
+pub const REUSE_WARNING_AFTER: usize = 2;
+
+/// `receive_log.json`: hashes of incoming transactions keyed by "network:0xaddress"
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+struct ReceiveLog(HashMap<String, Vec<String>>);
+
+impl ReceiveLog {
+    /// Adds the hashes not recorded yet; returns the address's receive count
+    fn record(&mut self, key: String, hashes: &[B256]) -> usize {
+        let seen = self.0.entry(key).or_default();
+        for hash in hashes {
+            let hash = format!("{:#x}", hash);
+            if !seen.contains(&hash) {
+                seen.push(hash);
+            }
+        }
+        seen.len()
+    }
+}
+
+fn log_key(network: &str, address: &Address) -> String {
+    format!("{}:0x{:x}", network_key(network), address)
+}
+
+/// Records incoming transactions of `address` and returns how many it has received
+pub fn record(network: &str, address: &Address, hashes: &[B256]) -> Result<usize> {
+    let key = log_key(network, address);
+    file_lock::update_json(&constants::receive_log_path(), |log: &mut ReceiveLog| {
+        Ok(log.record(key, hashes))
+    })
+}
+
+/// Payments `address` is known to have received on `network`
+pub fn count(network: &str, address: &Address) -> usize {
+    fs::read_to_string(constants::receive_log_path())
+        .ok()
+        .and_then(|data| serde_json::from_str::<ReceiveLog>(&data).ok())
+        .and_then(|log| log.0.get(&log_key(network, address)).map(Vec::len))
+        .unwrap_or(0)
+}
+
+/// Privacy nudge for an address that received `count` payments, if it counts as reused
+pub fn reuse_warning(address: &Address, count: usize) -> Option<String> {
+    (count >= REUSE_WARNING_AFTER).then(|| {
+        format!(
+            "0x{:x} has received {} payments; reusing one address lets anyone link them. \
+             Consider a fresh account for each payment you request",
+            address, count
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_ignores_hashes_seen_before() {
+        let mut log = ReceiveLog::default();
+        let key = log_key("Mainnet", &Address::ZERO);
+        let (a, b) = (B256::repeat_byte(1), B256::repeat_byte(2));
+        assert_eq!(log.record(key.clone(), &[a]), 1);
+        assert_eq!(log.record(key.clone(), &[a, b]), 2);
+        assert_eq!(log.record(log_key("RSK Mainnet", &Address::ZERO), &[b]), 2);
+        assert_eq!(log.record(log_key("testnet", &Address::ZERO), &[a]), 1);
+    }
+
+    #[test]
+    fn test_reuse_warning_threshold() {
+        assert_eq!(reuse_warning(&Address::ZERO, 0), None);
+        assert_eq!(reuse_warning(&Address::ZERO, REUSE_WARNING_AFTER - 1), None);
+        let warning = reuse_warning(&Address::ZERO, REUSE_WARNING_AFTER).unwrap();
+        assert!(warning.contains("has received 2 payments"));
+    }
+}
@@ -0,0 +1,194 @@
+//! Retrying node requests that failed on the way to or from the node, with
+//! exponential backoff. Errors the node itself returned are not retried.
+
+use alloy::transports::{RpcError, TransportError, TransportErrorKind};
+use std::future::Future;
+use std::time::Duration;
+
+/// Attempts made for each node request unless configured otherwise
+pub const DEFAULT_RPC_ATTEMPTS: u32 = 3;
+
+/// Pause before the first retry, in milliseconds; doubled after each attempt
+pub const DEFAULT_RPC_RETRY_DELAY_MS: u64 = 500;
+
+/// How often a request is tried and how long to wait in between
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first; 0 is treated as 1
+    pub attempts: u32,
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_RPC_ATTEMPTS, Duration::from_millis(DEFAULT_RPC_RETRY_DELAY_MS))
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(attempts: u32, base_delay: Duration) -> Self {
+        Self {
+            attempts,
+            base_delay,
+        }
+    }
+
+    /// Pause after failed attempt number `attempt` (counting from 1)
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+    }
+}
+
+/// Errors that may go away when the same request is simply sent again
+pub trait Transient {
+    fn is_transient(&self) -> bool;
+}
+
+impl Transient for TransportError {
+    /// Dropped connections, timeouts, empty responses and 5xx / 429 gateway replies.
+    /// JSON-RPC errors such as "insufficient funds" or "nonce too low" are final,
+    /// except rate limiting.
+    fn is_transient(&self) -> bool {
+        match self {
+            RpcError::Transport(TransportErrorKind::HttpError(http)) => {
+                http.status == 429 || http.status >= 500
+            }
+            RpcError::Transport(_) | RpcError::NullResp => true,
+            RpcError::ErrorResp(payload) => payload.code == 429,
+            _ => false,
+        }
+    }
+}
+
+impl Transient for alloy::contract::Error {
+    fn is_transient(&self) -> bool {
+        match self {
+            alloy::contract::Error::TransportError(e) => e.is_transient(),
+            _ => false,
+        }
+    }
+}
+
+/// Whether the node rejected a broadcast because it already has the transaction,
+/// as happens when a resend follows a send whose reply was lost
+pub fn is_already_known(error: &TransportError) -> bool {
+    match error {
+        RpcError::ErrorResp(payload) => {
+            let message = payload.message.to_lowercase();
+            message.contains("already known") || message.contains("known transaction")
+        }
+        _ => false,
+    }
+}
+
+/// Runs `request` until it succeeds, fails with a non-transient error or
+/// `policy.attempts` are used up
+pub async fn retry<T, E, F, Fut>(policy: &RetryPolicy, request: F) -> Result<T, E>
+where
+    E: Transient,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_if(policy, request, E::is_transient).await
+}
+
+/// Like [`retry`], with `should_retry` deciding which errors are worth another attempt
+pub async fn retry_if<T, E, F, Fut, P>(
+    policy: &RetryPolicy,
+    mut request: F,
+    should_retry: P,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    P: Fn(&E) -> bool,
+{
+    let mut attempt = 1;
+    loop {
+        match request().await {
+            Err(e) if attempt < policy.attempts && should_retry(&e) => {
+                tokio::time::sleep(policy.delay(attempt)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::transports::HttpError;
+    use std::cell::Cell;
+
+    fn http(status: u16) -> TransportError {
+        RpcError::Transport(TransportErrorKind::HttpError(HttpError {
+            status,
+            body: String::new(),
+        }))
+    }
+
+    fn rpc(code: i64, message: &'static str) -> TransportError {
+        let payload = serde_json::json!({ "code": code, "message": message });
+        RpcError::ErrorResp(serde_json::from_value(payload).unwrap())
+    }
+
+    #[test]
+    fn test_transient_errors() {
+        assert!(http(502).is_transient());
+        assert!(http(429).is_transient());
+        assert!(!http(400).is_transient());
+        assert!(TransportErrorKind::custom_str("connection reset").is_transient());
+        assert!(rpc(429, "rate limited").is_transient());
+        assert!(!rpc(-32000, "insufficient funds for gas * price + value").is_transient());
+    }
+
+    #[test]
+    fn test_already_known_errors() {
+        assert!(is_already_known(&rpc(-32000, "already known")));
+        assert!(is_already_known(&rpc(-32010, "Known transaction: 0xabc")));
+        assert!(!is_already_known(&rpc(-32000, "nonce too low")));
+        assert!(!is_already_known(&http(502)));
+    }
+
+    #[test]
+    fn test_delay_doubles() {
+        let policy = RetryPolicy::new(4, Duration::from_millis(100));
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(400));
+    }
+
+    #[tokio::test]
+    async fn test_retry_stops_on_success_final_error_or_attempts() {
+        let policy = RetryPolicy::new(3, Duration::ZERO);
+
+        let calls = Cell::new(0);
+        let result = retry(&policy, || async {
+            calls.set(calls.get() + 1);
+            if calls.get() < 3 { Err(http(503)) } else { Ok(7) }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 7);
+        assert_eq!(calls.get(), 3);
+
+        calls.set(0);
+        let result: Result<(), _> = retry(&policy, || async {
+            calls.set(calls.get() + 1);
+            Err(rpc(-32000, "insufficient funds"))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 1);
+
+        calls.set(0);
+        let result: Result<(), _> = retry(&policy, || async {
+            calls.set(calls.get() + 1);
+            Err(http(502))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 3);
+    }
+}
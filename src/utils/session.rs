@@ -0,0 +1,73 @@
+//! Recipients typed into the interactive flows, saved as each one is added so a
+//! closed terminal or dropped SSH connection doesn't lose a half-built batch
+
+use crate::utils::{constants, file_lock, payout::PayoutEntry};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SessionKind {
+    BulkTransfer,
+    SendMany,
+}
+
+impl std::fmt::Display for SessionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BulkTransfer => write!(f, "bulk transfer"),
+            Self::SendMany => write!(f, "send to several recipients"),
+        }
+    }
+}
+
+/// An unfinished recipient list, stored in `session.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub kind: SessionKind,
+    pub network: String,
+    /// Symbol the amounts are in
+    pub token: String,
+    pub saved_at: String,
+    pub recipients: Vec<PayoutEntry>,
+}
+
+impl Session {
+    pub fn new(kind: SessionKind, network: &str, token: &str) -> Self {
+        Self {
+            kind,
+            network: network.to_string(),
+            token: token.to_string(),
+            saved_at: String::new(),
+            recipients: Vec::new(),
+        }
+    }
+
+    pub fn load() -> Option<Self> {
+        fs::read_to_string(constants::session_path())
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+    }
+
+    /// Adds a recipient and saves right away; failing to save only warns
+    pub fn record(&mut self, to: &str, value: &str) {
+        self.recipients.push(PayoutEntry {
+            to: to.to_string(),
+            value: value.trim().to_string(),
+        });
+        self.saved_at = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+        if let Err(e) = self.save() {
+            eprintln!("⚠️  Could not save the session: {}", e);
+        }
+    }
+
+    fn save(&self) -> Result<()> {
+        file_lock::write_atomic(&constants::session_path(), &serde_json::to_string_pretty(self)?)
+    }
+
+    /// Forgets the saved session once its recipients are no longer needed
+    pub fn clear() {
+        let _ = fs::remove_file(constants::session_path());
+    }
+}
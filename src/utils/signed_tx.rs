@@ -0,0 +1,118 @@
+//! Reading the fields of a raw signed transaction before it is broadcast
+
+use alloy::consensus::{Transaction, TxEnvelope};
+use alloy::eips::eip2718::Decodable2718;
+use alloy::primitives::{Address, B256, Bytes, U256};
+use anyhow::{Result, anyhow, bail};
+
+/// What a raw signed transaction will do once broadcast
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedTransaction {
+    /// "Legacy", "EIP-2930", "EIP-1559", ...
+    pub kind: &'static str,
+    pub hash: B256,
+    /// Recovered from the signature
+    pub from: Address,
+    pub nonce: u64,
+    /// `None` for contract deployments
+    pub to: Option<Address>,
+    pub value: U256,
+    pub gas_limit: u64,
+    /// Set on legacy and EIP-2930 transactions
+    pub gas_price: Option<u128>,
+    /// Set on EIP-1559 style transactions
+    pub max_fee_per_gas: Option<u128>,
+    pub max_priority_fee_per_gas: Option<u128>,
+    pub chain_id: Option<u64>,
+    pub input: Bytes,
+}
+
+/// Decodes an RLP / EIP-2718 encoded signed transaction and recovers its sender
+pub fn decode_signed_transaction(raw: &[u8]) -> Result<DecodedTransaction> {
+    let mut buf = raw;
+    let envelope = TxEnvelope::decode_2718(&mut buf)
+        .map_err(|e| anyhow!("Not a signed transaction: {}", e))?;
+    if !buf.is_empty() {
+        return Err(anyhow!("{} unexpected bytes after the transaction", buf.len()));
+    }
+    let from = envelope
+        .recover_signer()
+        .map_err(|e| anyhow!("Could not recover the sender: {}", e))?;
+
+    let kind = match &envelope {
+        TxEnvelope::Legacy(_) => "Legacy",
+        TxEnvelope::Eip2930(_) => "EIP-2930",
+        TxEnvelope::Eip1559(_) => "EIP-1559",
+        TxEnvelope::Eip4844(_) => "EIP-4844",
+        TxEnvelope::Eip7702(_) => "EIP-7702",
+        _ => bail!("Unsupported transaction type"),
+    };
+    let dynamic_fee = !matches!(envelope, TxEnvelope::Legacy(_) | TxEnvelope::Eip2930(_));
+
+    Ok(DecodedTransaction {
+        kind,
+        hash: *envelope.tx_hash(),
+        from,
+        nonce: envelope.nonce(),
+        to: envelope.to(),
+        value: envelope.value(),
+        gas_limit: envelope.gas_limit(),
+        gas_price: envelope.gas_price(),
+        max_fee_per_gas: dynamic_fee.then(|| envelope.max_fee_per_gas()),
+        max_priority_fee_per_gas: envelope.max_priority_fee_per_gas(),
+        chain_id: envelope.chain_id(),
+        input: envelope.input().clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::consensus::{SignableTransaction, TxLegacy};
+    use alloy::eips::eip2718::Encodable2718;
+    use alloy::primitives::{TxKind, address};
+    use alloy::signers::SignerSync;
+    use alloy::signers::local::PrivateKeySigner;
+
+    const KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    fn signed_legacy() -> (Vec<u8>, Address) {
+        let signer: PrivateKeySigner = KEY.parse().unwrap();
+        let tx = TxLegacy {
+            chain_id: Some(31),
+            nonce: 3,
+            gas_price: 65_164_000,
+            gas_limit: 21_000,
+            to: TxKind::Call(address!("742d35cc6634c0532925a3b844bc454e4438f44e")),
+            value: U256::from(1_000_000_000_000_000u64),
+            input: Bytes::new(),
+        };
+        let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+        let envelope = TxEnvelope::from(tx.into_signed(signature));
+        (envelope.encoded_2718(), signer.address())
+    }
+
+    #[test]
+    fn test_decode_legacy_transaction() {
+        let (raw, sender) = signed_legacy();
+        let decoded = decode_signed_transaction(&raw).unwrap();
+        assert_eq!(decoded.kind, "Legacy");
+        assert_eq!(decoded.from, sender);
+        assert_eq!(decoded.nonce, 3);
+        assert_eq!(decoded.chain_id, Some(31));
+        assert_eq!(decoded.gas_price, Some(65_164_000));
+        assert_eq!(decoded.max_fee_per_gas, None);
+        assert_eq!(decoded.gas_limit, 21_000);
+        assert_eq!(decoded.value, U256::from(1_000_000_000_000_000u64));
+        assert!(decoded.input.is_empty());
+    }
+
+    #[test]
+    fn test_decode_rejects_garbage_and_trailing_bytes() {
+        assert!(decode_signed_transaction(&[]).is_err());
+        assert!(decode_signed_transaction(&[0xde, 0xad, 0xbe, 0xef]).is_err());
+        let (mut raw, _) = signed_legacy();
+        raw.push(0);
+        assert!(decode_signed_transaction(&raw).is_err());
+    }
+}
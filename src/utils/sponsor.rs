@@ -0,0 +1,129 @@
+use crate::config::SponsorSettings;
+use alloy::primitives::{Address, B256, Bytes, U256};
+use alloy::providers::{Provider, RootProvider};
+use alloy::signers::SignerSync;
+use alloy::signers::local::PrivateKeySigner;
+use alloy::sol;
+use alloy::sol_types::{SolStruct, eip712_domain};
+use alloy::transports::http::{Client, Http};
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+use std::str::FromStr;
+
+sol! {
+    #[allow(missing_docs)]
+    #[derive(Debug)]
+    struct ForwardRequest {
+        address from;
+        address to;
+        uint256 value;
+        uint256 gas;
+        uint256 nonce;
+        bytes data;
+    }
+
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    contract MinimalForwarder {
+        function getNonce(address from) external view returns (uint256);
+    }
+}
+
+/// Signs an ERC-2771 forward request for `to.call(data)` and posts it to the relayer,
+/// which pays the gas. Returns the hash of the transaction the relayer submitted.
+pub async fn send_sponsored(
+    provider: &RootProvider<Http<Client>>,
+    signer: &PrivateKeySigner,
+    settings: &SponsorSettings,
+    to: Address,
+    data: Bytes,
+    gas: U256,
+) -> Result<B256> {
+    let forwarder = Address::from_str(&settings.forwarder)
+        .map_err(|_| anyhow!("Invalid forwarder address: {}", settings.forwarder))?;
+    let nonce = MinimalForwarder::new(forwarder, provider)
+        .getNonce(signer.address())
+        .call()
+        .await
+        .map_err(|e| anyhow!("Failed to get the forwarder nonce: {}", e))?
+        ._0;
+    let chain_id = provider.get_chain_id().await?;
+
+    let request = ForwardRequest {
+        from: signer.address(),
+        to,
+        value: U256::ZERO,
+        gas,
+        nonce,
+        data,
+    };
+    let domain = eip712_domain! {
+        name: "MinimalForwarder",
+        version: "0.0.1",
+        chain_id: chain_id,
+        verifying_contract: forwarder,
+    };
+    let signature = signer
+        .sign_hash_sync(&request.eip712_signing_hash(&domain))
+        .map_err(|e| anyhow!("Failed to sign the meta-transaction: {}", e))?;
+
+    let body = json!({
+        "forwarder": format!("0x{:x}", forwarder),
+        "chainId": chain_id,
+        "request": {
+            "from": format!("0x{:x}", request.from),
+            "to": format!("0x{:x}", request.to),
+            "value": request.value.to_string(),
+            "gas": request.gas.to_string(),
+            "nonce": request.nonce.to_string(),
+            "data": request.data.to_string(),
+        },
+        "signature": alloy::hex::encode_prefixed(signature.as_bytes()),
+    });
+    let response = reqwest::Client::new()
+        .post(&settings.relayer_url)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| anyhow!("Relayer request failed: {}", e))?;
+    let status = response.status();
+    let reply: Value = response
+        .json()
+        .await
+        .map_err(|e| anyhow!("Relayer returned an unreadable reply ({}): {}", status, e))?;
+    if !status.is_success() {
+        return Err(anyhow!("Relayer rejected the transaction ({}): {}", status, reply));
+    }
+    relayed_hash(&reply)
+}
+
+/// Transaction hash from a relayer reply (`txHash`, `hash` or a JSON-RPC style `result`)
+fn relayed_hash(reply: &Value) -> Result<B256> {
+    ["txHash", "hash", "result"]
+        .iter()
+        .find_map(|key| reply[*key].as_str())
+        .ok_or_else(|| anyhow!("Relayer reply has no transaction hash: {}", reply))
+        .and_then(|hash| {
+            B256::from_str(hash).map_err(|_| anyhow!("Relayer returned an invalid hash: {}", hash))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_the_hash_under_any_known_key() {
+        let hash = format!("0x{}", "ab".repeat(32));
+        for key in ["txHash", "hash", "result"] {
+            let reply = json!({ key: hash });
+            assert_eq!(relayed_hash(&reply).unwrap(), B256::from_str(&hash).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_rejects_replies_without_a_valid_hash() {
+        assert!(relayed_hash(&json!({ "status": "queued" })).is_err());
+        assert!(relayed_hash(&json!({ "txHash": "0x1234" })).is_err());
+    }
+}
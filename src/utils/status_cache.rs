@@ -0,0 +1,64 @@
+//! Short-lived cache of the active wallet's balance for the interactive status line,
+//! so moving between menus doesn't cost an RPC round-trip per screen
+
+use alloy::primitives::U256;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a fetched balance is shown before it is read again
+pub const BALANCE_TTL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+struct CachedBalance {
+    /// "network:0xaddress" the balance belongs to
+    key: String,
+    balance: U256,
+    fetched_at: Instant,
+}
+
+impl CachedBalance {
+    fn fresh(&self, key: &str, now: Instant) -> Option<U256> {
+        (self.key == key && now.duration_since(self.fetched_at) < BALANCE_TTL)
+            .then_some(self.balance)
+    }
+}
+
+static CACHE: Mutex<Option<CachedBalance>> = Mutex::new(None);
+
+/// The balance stored for `key` if it is younger than [`BALANCE_TTL`]
+pub fn balance(key: &str) -> Option<U256> {
+    let cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache.as_ref().and_then(|cached| cached.fresh(key, Instant::now()))
+}
+
+/// Remembers `balance` for `key`, replacing any other entry
+pub fn store_balance(key: &str, balance: U256) {
+    *CACHE.lock().unwrap_or_else(|e| e.into_inner()) = Some(CachedBalance {
+        key: key.to_string(),
+        balance,
+        fetched_at: Instant::now(),
+    });
+}
+
+/// Drops the cached balance, e.g. after a transfer changed it
+pub fn invalidate() {
+    *CACHE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_balance_expires_and_is_keyed() {
+        let fetched_at = Instant::now();
+        let cached = CachedBalance {
+            key: "mainnet:0x01".to_string(),
+            balance: U256::from(5u64),
+            fetched_at,
+        };
+        assert_eq!(cached.fresh("mainnet:0x01", fetched_at), Some(U256::from(5u64)));
+        assert_eq!(cached.fresh("testnet:0x01", fetched_at), None);
+        assert_eq!(cached.fresh("mainnet:0x01", fetched_at + BALANCE_TTL), None);
+    }
+}
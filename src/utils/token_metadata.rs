@@ -0,0 +1,148 @@
+//! Token symbol, decimals and name, read from the chain once and remembered.
+//! Metadata of a deployed ERC-20 doesn't change, so entries never expire; `clear`
+//! forgets them when a token turns out to be misreported.
+
+use crate::utils::{constants, file_lock};
+use alloy::primitives::Address;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Mutex;
+
+/// What `decimals()`, `symbol()` and `name()` returned for a token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenMetadata {
+    pub decimals: u8,
+    pub symbol: String,
+    /// Not every token implements `name()`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// `token_metadata.json`, keyed by "network:0xaddress"
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+struct MetadataCache(HashMap<String, TokenMetadata>);
+
+impl MetadataCache {
+    fn get(&self, network: &str, token: &Address) -> Option<TokenMetadata> {
+        self.0.get(&cache_key(network, token)).cloned()
+    }
+
+    fn insert(&mut self, network: &str, token: &Address, metadata: TokenMetadata) {
+        self.0.insert(cache_key(network, token), metadata);
+    }
+
+    /// Removes the entries of `network`, or all of them; returns how many were removed
+    fn remove(&mut self, network: Option<&str>) -> usize {
+        let before = self.0.len();
+        match network {
+            Some(network) => {
+                let prefix = format!("{}:", network_key(network));
+                self.0.retain(|key, _| !key.starts_with(&prefix));
+            }
+            None => self.0.clear(),
+        }
+        before - self.0.len()
+    }
+}
+
+/// Loaded from disk on first use and kept for the rest of the session
+static SESSION: Mutex<Option<MetadataCache>> = Mutex::new(None);
+
+/// "mainnet", "testnet" or "regtest", whatever name the network was given
+/// ("RSK Mainnet", "Alchemy Testnet", ...)
+pub(crate) fn network_key(network: &str) -> String {
+    let network = network.to_lowercase();
+    ["mainnet", "testnet", "regtest"]
+        .into_iter()
+        .find(|key| network.contains(key))
+        .map(str::to_string)
+        .unwrap_or(network)
+}
+
+fn cache_key(network: &str, token: &Address) -> String {
+    format!("{}:0x{:x}", network_key(network), token)
+}
+
+fn with_session<R>(f: impl FnOnce(&mut MetadataCache) -> R) -> R {
+    let mut session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+    let cache = session.get_or_insert_with(|| {
+        fs::read_to_string(constants::token_metadata_path())
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    });
+    f(cache)
+}
+
+/// Metadata of `token` on `network` if it was read before
+pub fn cached(network: &str, token: &Address) -> Option<TokenMetadata> {
+    with_session(|cache| cache.get(network, token))
+}
+
+/// Remembers `metadata` for this session and saves it for the next ones
+pub fn store(network: &str, token: &Address, metadata: &TokenMetadata) -> Result<()> {
+    with_session(|cache| cache.insert(network, token, metadata.clone()));
+    file_lock::update_json(&constants::token_metadata_path(), |cache: &mut MetadataCache| {
+        cache.insert(network, token, metadata.clone());
+        Ok(())
+    })
+}
+
+/// Forgets the metadata of `network`'s tokens, or of every token, so it is read
+/// from the chain again; returns how many entries were removed
+pub fn clear(network: Option<&str>) -> Result<usize> {
+    with_session(|cache| cache.remove(network));
+    file_lock::update_json(&constants::token_metadata_path(), |cache: &mut MetadataCache| {
+        Ok(cache.remove(network))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+
+    const RIF: Address = address!("2acc95758f8b5f583470ba265eb685a8f45fc9d5");
+
+    fn rif() -> TokenMetadata {
+        TokenMetadata {
+            decimals: 18,
+            symbol: "RIF".to_string(),
+            name: Some("RIF".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_cache_is_keyed_by_network_and_address() {
+        let mut cache = MetadataCache::default();
+        cache.insert("RSK Mainnet", &RIF, rif());
+        assert_eq!(cache.get("mainnet", &RIF), Some(rif()));
+        assert_eq!(cache.get("Alchemy Mainnet", &RIF), Some(rif()));
+        assert_eq!(cache.get("testnet", &RIF), None);
+        assert_eq!(cache.get("mainnet", &Address::ZERO), None);
+    }
+
+    #[test]
+    fn test_remove_by_network() {
+        let mut cache = MetadataCache::default();
+        cache.insert("mainnet", &RIF, rif());
+        cache.insert("testnet", &RIF, rif());
+        assert_eq!(cache.remove(Some("Testnet")), 1);
+        assert_eq!(cache.get("mainnet", &RIF), Some(rif()));
+        assert_eq!(cache.remove(None), 1);
+        assert!(cache.0.is_empty());
+    }
+
+    #[test]
+    fn test_metadata_without_name_round_trips() {
+        let json = format!(r#"{{"mainnet:0x{:x}":{{"decimals":8,"symbol":"X"}}}}"#, RIF);
+        let cache: MetadataCache = serde_json::from_str(&json).unwrap();
+        let metadata = cache.get("mainnet", &RIF).unwrap();
+        assert_eq!(metadata.decimals, 8);
+        assert_eq!(metadata.name, None);
+        assert!(!serde_json::to_string(&metadata).unwrap().contains("name"));
+    }
+}
@@ -0,0 +1,298 @@
+use crate::config::ConfigManager;
+use alloy::primitives::{U256, utils::parse_units};
+use anyhow::{Result, anyhow};
+use std::sync::Mutex;
+
+/// Default number of fractional digits shown for amounts
+pub const DEFAULT_DISPLAY_DECIMALS: u8 = 6;
+
+/// Display settings read from the config file, loaded on first use
+static DISPLAY_SETTINGS: Mutex<Option<(u8, bool)>> = Mutex::new(None);
+
+/// Formats a raw token amount using the display settings from the config file
+pub fn format_amount(value: U256, token_decimals: u8) -> String {
+    let (display_decimals, group_thousands) = display_settings();
+    format_amount_with(value, token_decimals, display_decimals, group_thousands)
+}
+
+fn display_settings() -> (u8, bool) {
+    *DISPLAY_SETTINGS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get_or_insert_with(|| {
+            ConfigManager::new()
+                .and_then(|manager| manager.load())
+                .map(|config| (config.display_decimals, config.group_thousands))
+                .unwrap_or((DEFAULT_DISPLAY_DECIMALS, true))
+        })
+}
+
+/// Makes the next [`format_amount`] re-read the display settings, after they changed
+pub fn forget_display_settings() {
+    *DISPLAY_SETTINGS.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Formats a raw token amount with explicit display settings.
+///
+/// The fractional part is truncated (never rounded up) to `display_decimals`
+/// digits and trailing zeros are dropped, e.g. `1,234.5678`.
+pub fn format_amount_with(
+    value: U256,
+    token_decimals: u8,
+    display_decimals: u8,
+    group_thousands: bool,
+) -> String {
+    let digits = value.to_string();
+    let token_decimals = token_decimals as usize;
+
+    let (whole, fraction) = if digits.len() > token_decimals {
+        let (whole, fraction) = digits.split_at(digits.len() - token_decimals);
+        (whole.to_string(), fraction.to_string())
+    } else {
+        (
+            "0".to_string(),
+            format!("{:0>width$}", digits, width = token_decimals),
+        )
+    };
+
+    let fraction = &fraction[..fraction.len().min(display_decimals as usize)];
+    let fraction = fraction.trim_end_matches('0');
+
+    let whole = if group_thousands {
+        group_digits(&whole)
+    } else {
+        whole
+    };
+
+    if fraction.is_empty() {
+        whole
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+/// Parses an exact integer amount in wei / token base units.
+///
+/// Only plain decimal digits are accepted so a human amount such as `1.5`
+/// is never mistaken for a base-unit value.
+pub fn parse_base_units(input: &str) -> Result<U256> {
+    let input = input.trim();
+    if input.is_empty() || !input.chars().all(|c| c.is_ascii_digit()) {
+        return Err(anyhow!(
+            "Invalid base-unit amount '{}': expected a whole number of wei",
+            input
+        ));
+    }
+    U256::from_str_radix(input, 10).map_err(|_| anyhow!("Amount '{}' is too large", input))
+}
+
+/// Parses a human amount such as `1.5` into base units of a token with
+/// `token_decimals` decimals, without going through floating point
+pub fn parse_amount(input: &str, token_decimals: u8) -> Result<U256> {
+    let input = input.trim();
+    if input.starts_with('-') {
+        return Err(anyhow!("Invalid amount '{}': must not be negative", input));
+    }
+    // parse_units silently drops digits past the token's precision
+    if let Some((_, fraction)) = input.split_once('.')
+        && fraction.trim_end_matches('0').len() > token_decimals as usize
+    {
+        return Err(anyhow!(
+            "Invalid amount '{}': the token has only {} decimals",
+            input,
+            token_decimals
+        ));
+    }
+    alloy::primitives::utils::parse_units(input, token_decimals)
+        .map(Into::into)
+        .map_err(|e| anyhow!("Invalid amount '{}': {}", input, e))
+}
+
+/// Denominations understood by the unit converter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Denomination {
+    Wei,
+    Gwei,
+    Rbtc,
+}
+
+impl Denomination {
+    pub const ALL: [Denomination; 3] = [Denomination::Wei, Denomination::Gwei, Denomination::Rbtc];
+
+    pub fn decimals(self) -> u8 {
+        match self {
+            Denomination::Wei => 0,
+            Denomination::Gwei => 9,
+            Denomination::Rbtc => 18,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Denomination::Wei => "wei",
+            Denomination::Gwei => "gwei",
+            Denomination::Rbtc => "RBTC",
+        }
+    }
+}
+
+/// Converts an amount in `unit` to wei. Fractions smaller than a wei are rejected.
+pub fn to_wei(amount: &str, unit: Denomination) -> Result<U256> {
+    let amount = amount.trim();
+    if amount.starts_with('-') {
+        return Err(anyhow!("Invalid {} amount '{}': must not be negative", unit.name(), amount));
+    }
+    let fraction = amount.split_once('.').map_or("", |(_, f)| f.trim_end_matches('0'));
+    if fraction.len() > unit.decimals() as usize {
+        return Err(anyhow!("'{}' is smaller than one wei", amount));
+    }
+    parse_units(amount, unit.decimals())
+        .map(Into::into)
+        .map_err(|_| anyhow!("Invalid {} amount '{}'", unit.name(), amount))
+}
+
+/// Formats `wei` in `unit` exactly, without truncation or grouping
+pub fn from_wei(wei: U256, unit: Denomination) -> String {
+    format_amount_with(wei, unit.decimals(), unit.decimals(), false)
+}
+
+/// Explains a send larger than the balance, e.g. "you have 1.5 RIF, trying to send 1.500001 RIF".
+/// Both amounts keep every decimal so dust differences are not hidden by display rounding.
+/// Returns `None` when the balance covers the amount.
+pub fn shortfall(balance: U256, amount: U256, token_decimals: u8, symbol: &str) -> Option<String> {
+    (amount > balance).then(|| {
+        format!(
+            "you have {} {}, trying to send {} {}",
+            format_amount_with(balance, token_decimals, token_decimals, true),
+            symbol,
+            format_amount_with(amount, token_decimals, token_decimals, true),
+            symbol
+        )
+    })
+}
+
+/// Converts a `0x`-prefixed (or bare) hex number to decimal
+pub fn hex_to_decimal(input: &str) -> Result<String> {
+    let input = input.trim();
+    let digits = input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+        .unwrap_or(input);
+    U256::from_str_radix(digits, 16)
+        .map(|value| value.to_string())
+        .map_err(|_| anyhow!("Invalid hex number '{}'", input))
+}
+
+/// Converts a decimal number to `0x`-prefixed hex
+pub fn decimal_to_hex(input: &str) -> Result<String> {
+    let value = parse_base_units(input)
+        .map_err(|_| anyhow!("Invalid decimal number '{}'", input.trim()))?;
+    Ok(format!("{:#x}", value))
+}
+
+fn group_digits(digits: &str) -> String {
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_amount_groups_and_truncates() {
+        // 1234.56789 with 18 decimals
+        let value = U256::from(1_234_567_890_000_000_000_000u128);
+        assert_eq!(format_amount_with(value, 18, 4, true), "1,234.5678");
+        assert_eq!(format_amount_with(value, 18, 4, false), "1234.5678");
+    }
+
+    #[test]
+    fn test_format_amount_small_and_whole_values() {
+        assert_eq!(format_amount_with(U256::from(1u64), 18, 6, true), "0");
+        assert_eq!(
+            format_amount_with(U256::from(1_500_000u64), 6, 6, true),
+            "1.5"
+        );
+        assert_eq!(
+            format_amount_with(U256::from(1_000_000_000_000_000_000u128), 18, 6, true),
+            "1"
+        );
+    }
+
+    #[test]
+    fn test_shortfall_keeps_full_precision() {
+        let balance = U256::from(1_500_000_000_000_000_000u128);
+        assert_eq!(shortfall(balance, balance, 18, "RIF"), None);
+        assert_eq!(
+            shortfall(balance, balance + U256::from(1u64), 18, "RIF").as_deref(),
+            Some("you have 1.5 RIF, trying to send 1.500000000000000001 RIF")
+        );
+        assert_eq!(
+            shortfall(U256::from(1_000_000u64), U256::from(2_500_000u64), 6, "USDT").as_deref(),
+            Some("you have 1 USDT, trying to send 2.5 USDT")
+        );
+    }
+
+    #[test]
+    fn test_parse_base_units() {
+        assert_eq!(
+            parse_base_units("1234567890123456789012345").unwrap(),
+            U256::from(1_234_567_890_123_456_789_012_345u128)
+        );
+        assert_eq!(parse_base_units(" 42 ").unwrap(), U256::from(42u64));
+        assert!(parse_base_units("1.5").is_err());
+        assert!(parse_base_units("0x10").is_err());
+        assert!(parse_base_units("-1").is_err());
+        assert!(parse_base_units("").is_err());
+    }
+
+    #[test]
+    fn test_parse_amount_uses_token_decimals() {
+        // A 6-decimal token such as USDT: 1.0 is 1e6 base units, not 1e18
+        let usdt_decimals = 6;
+        assert_eq!(parse_amount("1.0", usdt_decimals).unwrap(), U256::from(1_000_000u64));
+        assert_eq!(parse_amount(" 2.5 ", usdt_decimals).unwrap(), U256::from(2_500_000u64));
+        assert_eq!(
+            format_amount_with(
+                parse_amount("2.5", usdt_decimals).unwrap(),
+                usdt_decimals,
+                DEFAULT_DISPLAY_DECIMALS,
+                true
+            ),
+            "2.5"
+        );
+        assert_eq!(
+            parse_amount("1.0", 18).unwrap(),
+            U256::from(1_000_000_000_000_000_000u128)
+        );
+        assert!(parse_amount("1.0000001", usdt_decimals).is_err());
+        assert!(parse_amount("-1", usdt_decimals).is_err());
+        assert!(parse_amount("abc", usdt_decimals).is_err());
+    }
+
+    #[test]
+    fn test_unit_conversions() {
+        let wei = to_wei("1.5", Denomination::Gwei).unwrap();
+        assert_eq!(wei, U256::from(1_500_000_000u64));
+        assert_eq!(from_wei(wei, Denomination::Rbtc), "0.0000000015");
+        assert_eq!(from_wei(wei, Denomination::Wei), "1500000000");
+        assert!(to_wei("0.5", Denomination::Wei).is_err());
+        assert!(to_wei("-1", Denomination::Rbtc).is_err());
+    }
+
+    #[test]
+    fn test_hex_conversions() {
+        assert_eq!(hex_to_decimal("0xff").unwrap(), "255");
+        assert_eq!(hex_to_decimal("FF").unwrap(), "255");
+        assert_eq!(decimal_to_hex("255").unwrap(), "0xff");
+        assert!(hex_to_decimal("0xzz").is_err());
+        assert!(decimal_to_hex("1.5").is_err());
+    }
+}
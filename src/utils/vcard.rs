@@ -0,0 +1,59 @@
+//! vCard (.vcf) entries for sharing a receive address with phone contacts
+
+/// Builds a vCard 3.0 entry for a receive address. The address goes in the note,
+/// in an `X-RSK-ADDRESS` field and as an `ethereum:` URL so contact apps keep it.
+pub fn address_vcard(name: &str, address: &str, network: &str, note: Option<&str>) -> String {
+    let mut description = format!("Rootstock {} address: {}", network, address);
+    if let Some(note) = note.filter(|n| !n.trim().is_empty()) {
+        description.push('\n');
+        description.push_str(note.trim());
+    }
+
+    [
+        "BEGIN:VCARD".to_string(),
+        "VERSION:3.0".to_string(),
+        format!("FN:{}", escape(name)),
+        format!("N:{};;;;", escape(name)),
+        format!("NOTE:{}", escape(&description)),
+        format!("X-RSK-ADDRESS:{}", escape(address)),
+        format!("URL:ethereum:{}", address),
+        "END:VCARD".to_string(),
+    ]
+    .join("\r\n")
+        + "\r\n"
+}
+
+/// Escapes text values as RFC 6350 requires
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDR: &str = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+
+    #[test]
+    fn test_address_vcard_fields() {
+        let card = address_vcard("Savings", ADDR, "Mainnet", None);
+        assert!(card.starts_with("BEGIN:VCARD\r\nVERSION:3.0\r\n"));
+        assert!(card.contains("FN:Savings\r\n"));
+        assert!(card.contains(&format!("NOTE:Rootstock Mainnet address: {}\r\n", ADDR)));
+        assert!(card.contains(&format!("X-RSK-ADDRESS:{}\r\n", ADDR)));
+        assert!(card.contains(&format!("URL:ethereum:{}\r\n", ADDR)));
+        assert!(card.ends_with("END:VCARD\r\n"));
+    }
+
+    #[test]
+    fn test_address_vcard_escapes_text() {
+        let card = address_vcard("Cold; storage, main", ADDR, "Testnet", Some("do not\nspend"));
+        assert!(card.contains("FN:Cold\\; storage\\, main\r\n"));
+        assert!(card.contains("\\ndo not\\nspend\r\n"));
+    }
+}